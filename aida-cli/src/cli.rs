@@ -1,6 +1,8 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+use crate::output::OutputFormat;
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about = "A simple requirements management system")]
 pub struct Cli {
@@ -12,6 +14,10 @@ pub struct Cli {
     #[clap(long, short = 'p')]
     pub project: Option<String>,
 
+    /// Output format for read commands (table, json, yaml, csv)
+    #[clap(long, value_enum, default_value = "table", global = true)]
+    pub output: OutputFormat,
+
     #[clap(subcommand)]
     pub command: Command,
 }
@@ -154,6 +160,16 @@ pub enum TypeCommand {
         #[clap(long, short = 'y')]
         yes: bool,
     },
+
+    /// Show the description template and default fields for a type
+    Template {
+        /// Requirement type (e.g., bug, story, change-request)
+        name: String,
+
+        /// Copy the template into the shared library file <templates dir>/types/<EXPORT>.yaml
+        #[clap(long)]
+        export: Option<String>,
+    },
 }
 
 /// Commands for managing relationship type definitions
@@ -353,6 +369,571 @@ pub enum CommentCommand {
     },
 }
 
+/// Commands for managing acceptance criteria
+#[derive(Subcommand, Debug)]
+pub enum AcCommand {
+    /// Add an acceptance criterion to a requirement
+    Add {
+        /// Requirement ID (UUID or SPEC-ID)
+        id: String,
+
+        /// Criterion text
+        text: String,
+
+        /// Given clause (requires --when and --then)
+        #[clap(long)]
+        given: Option<String>,
+
+        /// When clause
+        #[clap(long)]
+        when: Option<String>,
+
+        /// Then clause
+        #[clap(long)]
+        then: Option<String>,
+
+        /// Linked test (test name, file path or test case ID)
+        #[clap(long)]
+        test: Option<String>,
+
+        /// Author recorded in the history (defaults to $USER)
+        #[clap(long)]
+        author: Option<String>,
+    },
+
+    /// Set the verification status of a criterion
+    Check {
+        /// Requirement ID (UUID or SPEC-ID)
+        id: String,
+
+        /// Criterion ID (e.g., AC-1 or 1)
+        criterion: String,
+
+        /// Status: passed, failed, waived or pending
+        #[clap(long, default_value = "passed")]
+        status: String,
+
+        /// Author recorded in the history (defaults to $USER)
+        #[clap(long)]
+        author: Option<String>,
+    },
+
+    /// List acceptance criteria for a requirement
+    List {
+        /// Requirement ID (UUID or SPEC-ID)
+        id: String,
+    },
+
+    /// Remove an acceptance criterion
+    Remove {
+        /// Requirement ID (UUID or SPEC-ID)
+        id: String,
+
+        /// Criterion ID (e.g., AC-1 or 1)
+        criterion: String,
+
+        /// Author recorded in the history (defaults to $USER)
+        #[clap(long)]
+        author: Option<String>,
+    },
+}
+
+/// Commands for managing test cases and test runs
+#[derive(Subcommand, Debug)]
+pub enum TestCommand {
+    /// Add a test case
+    Add {
+        /// Test case title
+        title: String,
+
+        /// Requirements verified by this test case (comma-separated UUIDs or SPEC-IDs)
+        #[clap(long)]
+        verifies: Option<String>,
+
+        /// Test case description
+        #[clap(long, short = 'd')]
+        description: Option<String>,
+
+        /// Preconditions for executing the test
+        #[clap(long)]
+        preconditions: Option<String>,
+
+        /// Step as "action => expected result", repeatable
+        #[clap(long = "step")]
+        steps: Vec<String>,
+
+        /// Overall expected result
+        #[clap(long)]
+        expected: Option<String>,
+
+        /// Name of the automated test implementing this case
+        #[clap(long)]
+        automated: Option<String>,
+
+        /// Author (defaults to $USER)
+        #[clap(long)]
+        author: Option<String>,
+    },
+
+    /// List test cases
+    List {
+        /// Only show test cases verifying this requirement (UUID or SPEC-ID)
+        #[clap(long, short = 'r')]
+        requirement: Option<String>,
+
+        /// Include archived test cases
+        #[clap(long)]
+        archived: bool,
+    },
+
+    /// Show a test case with its recent results
+    Show {
+        /// Test case ID (e.g., TC-001 or UUID)
+        id: String,
+    },
+
+    /// Link a test case to a requirement it verifies
+    Link {
+        /// Test case ID (e.g., TC-001 or UUID)
+        id: String,
+
+        /// Requirement ID (UUID or SPEC-ID)
+        requirement: String,
+    },
+
+    /// Unlink a test case from a requirement
+    Unlink {
+        /// Test case ID (e.g., TC-001 or UUID)
+        id: String,
+
+        /// Requirement ID (UUID or SPEC-ID)
+        requirement: String,
+    },
+
+    /// Remove a test case
+    Remove {
+        /// Test case ID (e.g., TC-001 or UUID)
+        id: String,
+    },
+
+    /// Record a test run
+    Run {
+        /// Name of the run (e.g., "Nightly regression")
+        #[clap(long, short = 'n')]
+        name: String,
+
+        /// Environment the tests ran in
+        #[clap(long, short = 'e')]
+        environment: Option<String>,
+
+        /// Build or version under test
+        #[clap(long, short = 'b')]
+        build: Option<String>,
+
+        /// Result as "TC-001=passed" (passed, failed, blocked, skipped), repeatable
+        #[clap(long = "result", required = true)]
+        results: Vec<String>,
+
+        /// Who executed the run (defaults to $USER)
+        #[clap(long)]
+        by: Option<String>,
+    },
+
+    /// List test runs, or show one run in detail
+    Runs {
+        /// Test run ID (e.g., RUN-001 or UUID)
+        id: Option<String>,
+    },
+
+    /// Show test coverage of requirements
+    Coverage {
+        /// Only include requirements of this feature
+        #[clap(long, short = 'f')]
+        feature: Option<String>,
+
+        /// Only show requirements without test cases
+        #[clap(long)]
+        uncovered: bool,
+    },
+}
+
+/// Commands for code traceability
+#[derive(Subcommand, Debug)]
+pub enum TraceCommand {
+    /// Scan source files for `trace:<SPEC-ID>` markers
+    Scan {
+        /// Directory to scan (defaults to the directory containing the requirements file)
+        path: Option<PathBuf>,
+
+        /// Only show markers for this requirement (SPEC-ID)
+        #[clap(long, short = 'r')]
+        requirement: Option<String>,
+
+        /// List every marker location, not just counts per requirement
+        #[clap(long, short = 'v')]
+        verbose: bool,
+    },
+}
+
+/// Commands for linking git commits to requirements
+#[derive(Subcommand, Debug)]
+pub enum GitCommand {
+    /// Link commits whose messages mention spec IDs or requirement UUIDs
+    Sync {
+        /// Repository directory (defaults to the directory containing the requirements file)
+        #[clap(long)]
+        repo: Option<PathBuf>,
+
+        /// Rescan the whole history instead of only commits since the last sync
+        #[clap(long)]
+        full: bool,
+    },
+
+    /// Show linked commits per requirement and Completed requirements without commits
+    Report,
+}
+
+/// Commands for importing requirements from other tools
+#[derive(Subcommand, Debug)]
+pub enum ImportCommand {
+    /// Import rows of a CSV (or .tsv) spreadsheet, updating rows matched by the key column
+    Csv {
+        /// CSV file; files ending in .tsv are read as tab-separated
+        file: PathBuf,
+
+        /// Column mapping, e.g. "key=Issue,title=Summary,description=Details,type=Kind,parent=Parent ID"
+        /// (defaults to mapping each column by its header)
+        #[clap(long, short = 'm')]
+        map: Option<String>,
+
+        /// Type value mapping, e.g. "Defect=Bug,User Story=Story"
+        #[clap(long)]
+        type_map: Option<String>,
+
+        /// Status value mapping, e.g. "Open=Draft,Done=Completed"
+        #[clap(long)]
+        status_map: Option<String>,
+
+        /// Author recorded on created and updated requirements (defaults to $USER)
+        #[clap(long)]
+        user: Option<String>,
+
+        /// Show what would change without saving
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Import a ReqIF document, updating requirements matched by identifier
+    Reqif {
+        /// ReqIF file (.reqif)
+        file: PathBuf,
+
+        /// Author recorded on created and updated requirements (defaults to $USER)
+        #[clap(long)]
+        user: Option<String>,
+
+        /// Show what would change without saving
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+/// Commands for authoring requirements as Markdown files
+#[derive(Subcommand, Debug)]
+pub enum MdCommand {
+    /// Write one Markdown file with front matter per requirement
+    Export {
+        /// Output directory
+        #[clap(default_value = "requirements-md")]
+        dir: PathBuf,
+    },
+
+    /// Apply edited Markdown files, creating and updating requirements
+    Apply {
+        /// Directory of Markdown files
+        #[clap(default_value = "requirements-md")]
+        dir: PathBuf,
+
+        /// Author recorded on created and updated requirements (defaults to $USER)
+        #[clap(long)]
+        user: Option<String>,
+
+        /// Show what would change without saving
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+/// Commands for managing users
+#[derive(Subcommand, Debug)]
+pub enum UserCommand {
+    /// Add a user
+    Add {
+        /// Full name
+        name: String,
+
+        /// Email address
+        #[clap(long)]
+        email: Option<String>,
+
+        /// Handle for @mentions and requirement owners (without the @)
+        #[clap(long)]
+        handle: Option<String>,
+    },
+
+    /// List users
+    List {
+        /// Include archived users
+        #[clap(long)]
+        all: bool,
+    },
+
+    /// Change a user's name, email or handle
+    Edit {
+        /// User spec ID ($USER-001), handle or name
+        user: String,
+
+        /// New full name
+        #[clap(long)]
+        name: Option<String>,
+
+        /// New email address
+        #[clap(long)]
+        email: Option<String>,
+
+        /// New handle
+        #[clap(long)]
+        handle: Option<String>,
+    },
+
+    /// Archive a user so they can no longer own requirements
+    Archive {
+        /// User spec ID ($USER-001), handle or name
+        user: String,
+
+        /// Restore an archived user instead
+        #[clap(long)]
+        restore: bool,
+    },
+}
+
+/// Commands for managing teams
+#[derive(Subcommand, Debug)]
+pub enum TeamCommand {
+    /// Add a team
+    Add {
+        /// Team name
+        name: String,
+
+        /// What the team does
+        #[clap(long, short = 'd')]
+        description: Option<String>,
+
+        /// Parent team spec ID ($TEAM-001) or name
+        #[clap(long)]
+        parent: Option<String>,
+    },
+
+    /// List teams as a tree
+    List {
+        /// Include archived teams
+        #[clap(long)]
+        all: bool,
+    },
+
+    /// Show a team with its members and sub-teams
+    Show {
+        /// Team spec ID ($TEAM-001) or name
+        team: String,
+    },
+
+    /// Add a user to a team
+    AddMember {
+        /// Team spec ID ($TEAM-001) or name
+        team: String,
+
+        /// User spec ID ($USER-001), handle or name
+        user: String,
+    },
+
+    /// Remove a user from a team
+    RemoveMember {
+        /// Team spec ID ($TEAM-001) or name
+        team: String,
+
+        /// User spec ID ($USER-001), handle or name
+        user: String,
+    },
+
+    /// Move a team under another team, or to the top level
+    Move {
+        /// Team spec ID ($TEAM-001) or name
+        team: String,
+
+        /// New parent team
+        #[clap(long, required_unless_present = "root", conflicts_with = "root")]
+        parent: Option<String>,
+
+        /// Make it a top-level team
+        #[clap(long)]
+        root: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AiCommand {
+    /// Evaluate a requirement's quality and store the result
+    Evaluate {
+        /// Requirement ID (SPEC-ID or UUID)
+        id: String,
+    },
+
+    /// Find requirements that may duplicate this one
+    Duplicates {
+        /// Requirement ID (SPEC-ID or UUID)
+        id: String,
+    },
+
+    /// Suggest relationships to other requirements
+    Relate {
+        /// Requirement ID (SPEC-ID or UUID)
+        id: String,
+
+        /// Create the chosen relationships
+        #[clap(long)]
+        apply: bool,
+
+        /// Apply all suggestions without prompting
+        #[clap(long, short = 'y', requires = "apply")]
+        yes: bool,
+    },
+
+    /// Suggest a clearer description
+    Improve {
+        /// Requirement ID (SPEC-ID or UUID)
+        id: String,
+
+        /// Replace the description with the suggestion
+        #[clap(long)]
+        apply: bool,
+
+        /// Apply without prompting
+        #[clap(long, short = 'y', requires = "apply")]
+        yes: bool,
+    },
+
+    /// Suggest child requirements
+    Children {
+        /// Requirement ID (SPEC-ID or UUID)
+        id: String,
+
+        /// Add the chosen children with parent links
+        #[clap(long)]
+        apply: bool,
+
+        /// Add all suggestions without prompting
+        #[clap(long, short = 'y', requires = "apply")]
+        yes: bool,
+    },
+
+    /// Evaluate every requirement that was never evaluated or changed since
+    Scan {
+        /// Evaluate at most this many requirements
+        #[clap(long)]
+        limit: Option<usize>,
+
+        /// Only list the requirements that would be evaluated
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+/// Output of `aida baseline diff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiffFormat {
+    Text,
+    Json,
+    Markdown,
+}
+
+/// Commands for named snapshots of the requirements
+#[derive(Subcommand, Debug)]
+pub enum BaselineCommand {
+    /// Snapshot the current requirements (and tag the commit when in a git repository)
+    Create {
+        /// Baseline name (e.g. "v1.0")
+        name: String,
+
+        /// What this baseline represents
+        #[clap(long, short = 'd')]
+        description: Option<String>,
+
+        /// Author recorded on the baseline (defaults to $USER)
+        #[clap(long)]
+        user: Option<String>,
+
+        /// Lock the baseline so it cannot be deleted
+        #[clap(long)]
+        lock: bool,
+    },
+
+    /// List baselines
+    List,
+
+    /// Show a baseline and the requirements it contains
+    Show {
+        /// Baseline name or UUID
+        name: String,
+    },
+
+    /// Delete a baseline (locked baselines are kept)
+    Delete {
+        /// Baseline name or UUID
+        name: String,
+
+        /// Skip confirmation prompt
+        #[clap(long, short = 'y')]
+        yes: bool,
+    },
+
+    /// Lock a baseline against deletion
+    Lock {
+        /// Baseline name or UUID
+        name: String,
+
+        /// Unlock instead
+        #[clap(long)]
+        unlock: bool,
+    },
+
+    /// Show what changed since a baseline, or between two baselines
+    Diff {
+        /// Older baseline
+        from: String,
+
+        /// Newer baseline (defaults to the working copy)
+        to: Option<String>,
+
+        /// Output format
+        #[clap(long, short = 'f', value_enum, default_value = "text")]
+        format: DiffFormat,
+    },
+
+    /// Export the specification as of a baseline
+    Export {
+        /// Baseline name or UUID
+        name: String,
+
+        /// Document format (md, docx, odt)
+        #[clap(long, short = 'f', default_value = "md")]
+        format: String,
+
+        /// Output file path
+        #[clap(long = "out", short = 'o', id = "out", value_name = "OUT")]
+        output: Option<PathBuf>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Add a new requirement
@@ -377,7 +958,7 @@ pub enum Command {
         #[clap(long)]
         r#type: Option<String>,
 
-        /// Owner of the requirement
+        /// Owner of the requirement (handle, name or $USER-001 of a known user)
         #[clap(long)]
         owner: Option<String>,
 
@@ -447,6 +1028,41 @@ pub enum Command {
         yes: bool,
     },
 
+    /// Apply the same change to many requirements at once
+    Bulk {
+        /// Query selecting requirements (e.g., "status=Draft feature=Auth tag=ui")
+        #[clap(long, short = 'q')]
+        query: Option<String>,
+
+        /// Explicit requirement IDs (comma-separated UUIDs or SPEC-IDs)
+        #[clap(long)]
+        ids: Option<String>,
+
+        /// Field assignment, repeatable (title=..., description=..., status=Approved, priority=High, owner=alice, feature=Auth, <custom-field>=value)
+        #[clap(long = "set")]
+        set: Vec<String>,
+
+        /// Tag to add, repeatable
+        #[clap(long = "add-tag")]
+        add_tag: Vec<String>,
+
+        /// Tag to remove, repeatable
+        #[clap(long = "remove-tag")]
+        remove_tag: Vec<String>,
+
+        /// Author recorded in the history entries (defaults to $USER)
+        #[clap(long)]
+        author: Option<String>,
+
+        /// Show what would change without saving
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[clap(long, short = 'y')]
+        yes: bool,
+    },
+
     /// Feature management commands
     #[clap(subcommand)]
     Feature(FeatureCommand),
@@ -467,6 +1083,22 @@ pub enum Command {
     #[clap(subcommand)]
     Comment(CommentCommand),
 
+    /// Manage acceptance criteria on requirements
+    #[clap(subcommand)]
+    Ac(AcCommand),
+
+    /// Manage test cases and test runs
+    #[clap(subcommand)]
+    Test(TestCommand),
+
+    /// Code traceability commands
+    #[clap(subcommand)]
+    Trace(TraceCommand),
+
+    /// Git commit linking commands
+    #[clap(subcommand)]
+    Git(GitCommand),
+
     /// ID configuration commands
     #[clap(subcommand)]
     Config(ConfigCommand),
@@ -477,13 +1109,121 @@ pub enum Command {
 
     /// Export requirements to different formats
     Export {
-        /// Output format (mapping, json)
+        /// Output format (mapping, json, spec, impl, coverage, reqif, csv, html, docx, odt, dot, mermaid)
         #[clap(long, short = 'f', default_value = "mapping")]
         format: String,
 
-        /// Output file path
-        #[clap(long, short = 'o')]
+        /// Output file path (a directory for html)
+        #[clap(long = "out", short = 'o', id = "out", value_name = "OUT")]
         output: Option<PathBuf>,
+
+        /// Baseline to export instead of the working copy (docx, odt, template)
+        #[clap(long, short = 'b')]
+        baseline: Option<String>,
+
+        /// Render a document template instead of a format: a built-in (spec, impl),
+        /// a template in the project's templates/ or <templates dir>/export/, or a file path
+        #[clap(long, short = 't', conflicts_with = "format")]
+        template: Option<String>,
+
+        /// Requirement to root the diagram at (dot, mermaid; repeatable)
+        #[clap(long = "root", value_name = "SPEC-ID")]
+        roots: Vec<String>,
+
+        /// Root the diagram at the requirements of a feature (dot, mermaid)
+        #[clap(long)]
+        feature: Option<String>,
+
+        /// Maximum number of links to follow from the roots (dot, mermaid)
+        #[clap(long)]
+        depth: Option<usize>,
+
+        /// Relationship types to draw, comma-separated (dot, mermaid)
+        #[clap(long = "rel", value_delimiter = ',')]
+        relationships: Vec<String>,
+
+        /// Group diagram nodes into one cluster per feature (dot, mermaid)
+        #[clap(long)]
+        cluster: bool,
+    },
+
+    /// Import requirements from other tools
+    #[clap(subcommand)]
+    Import(ImportCommand),
+
+    /// Edit requirements as Markdown files and apply the changes back
+    #[clap(subcommand)]
+    Md(MdCommand),
+
+    /// Create, compare and export baselines (named snapshots)
+    #[clap(subcommand)]
+    Baseline(BaselineCommand),
+
+    /// User management commands
+    #[clap(subcommand)]
+    User(UserCommand),
+
+    /// Team management commands
+    #[clap(subcommand)]
+    Team(TeamCommand),
+
+    /// AI-assisted review: evaluate, find duplicates, suggest relationships, descriptions and children
+    #[clap(subcommand)]
+    Ai(AiCommand),
+
+    /// Import JUnit/xUnit XML test results and attach them to verified requirements
+    ImportResults {
+        /// JUnit XML file
+        file: PathBuf,
+
+        /// Name of the recorded test run (defaults to the file name)
+        #[clap(long, short = 'n')]
+        name: Option<String>,
+
+        /// Environment the tests ran in
+        #[clap(long, short = 'e')]
+        environment: Option<String>,
+
+        /// Build or version under test
+        #[clap(long, short = 'b')]
+        build: Option<String>,
+
+        /// Who executed the run (defaults to $USER)
+        #[clap(long)]
+        by: Option<String>,
+
+        /// Show the mapping without saving
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Open a full-screen terminal interface for browsing and editing requirements
+    Tui {
+        /// Name recorded on comments and changes (defaults to $USER)
+        #[clap(long)]
+        user: Option<String>,
+    },
+
+    /// Serve the requirements database as a local JSON/HTTP API
+    Serve {
+        /// Address to listen on
+        #[clap(long, default_value = aida_core::server::DEFAULT_ADDR)]
+        addr: String,
+
+        /// Author for changes made without an X-Aida-User header (defaults to $USER)
+        #[clap(long)]
+        user: Option<String>,
+
+        /// Allow listening on addresses other machines can reach (the API has no authentication)
+        #[clap(long)]
+        allow_remote: bool,
+    },
+
+    /// Run a Model Context Protocol server on stdin/stdout for coding agents
+    Mcp {
+        /// Author recorded on changes (defaults to $USER)
+        #[clap(long)]
+        user: Option<String>,
     },
 
     /// Open the user guide in the default browser
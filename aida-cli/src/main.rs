@@ -1,5 +1,7 @@
 mod cli;
+mod output;
 mod prompts;
+mod tui;
 
 use anyhow::{Context, Result};
 use clap::Parser;
@@ -8,19 +10,37 @@ use std::collections::HashSet;
 use uuid::Uuid;
 
 use aida_core::{
-    determine_requirements_path, export, get_registry_path, Cardinality, Comment, FieldChange,
-    IdFormat, NumberingStrategy, Registry, RelationshipDefinition, RelationshipType, Requirement,
-    RequirementPriority, RequirementStatus, RequirementType, RequirementsStore, Storage,
+    determine_requirements_path, export, get_registry_path, AcceptanceCriterion, AiClient,
+    Baseline, Cardinality, Comment, CriterionStatus, EvaluationResponse, FieldChange,
+    GivenWhenThen, IdFormat, JUnitReport, NumberingStrategy, Registry, RelationshipDefinition,
+    RelationshipType, Requirement, RequirementPatch, RequirementPriority, RequirementQuery,
+    RequirementStatus, RequirementType, RequirementsStore, ScriptEngine, ScriptKind, Storage,
+    StoredAiEvaluation, Team, TemplateContext, TestCase, TestOutcome, TestRun, TraceIndex,
+    TraceMarker, TypeTemplateLibrary, User, VerificationStatus,
 };
 
 use crate::cli::{
-    Cli, Command, CommentCommand, ConfigCommand, DbCommand, FeatureCommand, RelDefCommand,
-    RelationshipCommand, TypeCommand,
+    AcCommand, AiCommand, BaselineCommand, Cli, Command, CommentCommand, ConfigCommand, DbCommand,
+    DiffFormat, FeatureCommand, GitCommand, ImportCommand, MdCommand, RelDefCommand,
+    RelationshipCommand, TeamCommand, TestCommand, TraceCommand, TypeCommand, UserCommand,
+};
+use crate::output::{
+    print_record, print_records, AiEvaluationRecord, BaselineDetail, BaselineRecord, CliError,
+    CommentRecord, ConfigRecord, FeatureRecord, OutputFormat, RelationshipDefinitionRecord,
+    RelationshipRecord, RequirementDetail, RequirementRecord, TeamRecord, TypeRecord, UserRecord,
 };
 
-fn main() -> Result<()> {
+fn main() {
     let cli = Cli::parse();
+    let result = run(&cli);
+    // Let queued hook deliveries finish before the process exits
+    aida_core::events::wait_for_hooks();
+    if let Err(e) = result {
+        std::process::exit(output::report_error(&e, cli.output));
+    }
+}
 
+fn run(cli: &Cli) -> Result<()> {
     // Determine which requirements file to use
     let requirements_path = determine_requirements_path(cli.project.as_deref())?;
     let storage = Storage::new(requirements_path.clone());
@@ -77,10 +97,12 @@ fn main() -> Result<()> {
             feature,
             tags,
         } => {
-            list_requirements(&storage, status, priority, r#type, feature, tags)?;
+            list_requirements(
+                &storage, status, priority, r#type, feature, tags, cli.output,
+            )?;
         }
         Command::Show { id } => {
-            show_requirement(&storage, id)?;
+            show_requirement(&storage, id, cli.output)?;
         }
         Command::Edit { id } => {
             edit_requirement(&storage, id)?;
@@ -88,29 +110,156 @@ fn main() -> Result<()> {
         Command::Del { id, yes } => {
             delete_requirement(&storage, id, *yes)?;
         }
+        Command::Bulk {
+            query,
+            ids,
+            set,
+            add_tag,
+            remove_tag,
+            author,
+            dry_run,
+            yes,
+        } => {
+            bulk_update(
+                &storage,
+                query.as_deref(),
+                ids.as_deref(),
+                set,
+                add_tag,
+                remove_tag,
+                author.as_deref(),
+                *dry_run,
+                *yes,
+            )?;
+        }
         Command::Feature(feature_cmd) => {
-            handle_feature_command(feature_cmd, &storage)?;
+            handle_feature_command(feature_cmd, &storage, cli.output)?;
         }
         Command::Db(db_cmd) => {
             handle_db_command(db_cmd, &requirements_path)?;
         }
         Command::Rel(rel_cmd) => {
-            handle_relationship_command(rel_cmd, &storage)?;
+            handle_relationship_command(rel_cmd, &storage, cli.output)?;
         }
         Command::RelDef(rel_def_cmd) => {
-            handle_rel_def_command(rel_def_cmd, &storage)?;
+            handle_rel_def_command(rel_def_cmd, &storage, cli.output)?;
         }
         Command::Comment(comment_cmd) => {
-            handle_comment_command(comment_cmd, &storage)?;
+            handle_comment_command(comment_cmd, &storage, cli.output)?;
+        }
+        Command::Ac(ac_cmd) => {
+            handle_ac_command(ac_cmd, &storage)?;
+        }
+        Command::Test(test_cmd) => {
+            handle_test_command(test_cmd, &storage)?;
+        }
+        Command::Trace(trace_cmd) => {
+            handle_trace_command(trace_cmd, &storage)?;
+        }
+        Command::Git(git_cmd) => {
+            handle_git_command(git_cmd, &storage)?;
         }
         Command::Config(config_cmd) => {
-            handle_config_command(config_cmd, &storage)?;
+            handle_config_command(config_cmd, &storage, cli.output)?;
         }
         Command::Type(type_cmd) => {
-            handle_type_command(type_cmd, &storage)?;
+            handle_type_command(type_cmd, &storage, cli.output)?;
+        }
+        Command::Export {
+            output,
+            baseline,
+            template: Some(template),
+            ..
+        } => {
+            let store = storage.load()?;
+            let project_dir = storage
+                .path()
+                .parent()
+                .unwrap_or_else(|| std::path::Path::new("."));
+            let template = export::ExportTemplate::load(template, project_dir)?;
+            let output_path = output
+                .clone()
+                .unwrap_or_else(|| template.default_output.clone());
+            export::export_template(&store, &template, &output_path, baseline.as_deref())?;
+        }
+        Command::Export {
+            format,
+            output,
+            baseline,
+            template: None,
+            roots,
+            feature,
+            depth,
+            relationships,
+            cluster,
+        } => {
+            let graph = export::GraphFilter {
+                roots: roots.clone(),
+                feature: feature.clone(),
+                relationships: relationships.clone(),
+                depth: *depth,
+                cluster_by_feature: *cluster,
+            };
+            handle_export_command(
+                &storage,
+                format,
+                output.as_deref(),
+                baseline.as_deref(),
+                &graph,
+            )?;
+        }
+        Command::Import(import_cmd) => {
+            handle_import_command(import_cmd, &storage)?;
+        }
+        Command::Md(md_cmd) => {
+            handle_md_command(md_cmd, &storage)?;
+        }
+        Command::Baseline(baseline_cmd) => {
+            handle_baseline_command(baseline_cmd, &storage, cli.output)?;
+        }
+        Command::User(user_cmd) => {
+            handle_user_command(user_cmd, &storage, cli.output)?;
+        }
+        Command::Team(team_cmd) => {
+            handle_team_command(team_cmd, &storage, cli.output)?;
+        }
+        Command::Ai(ai_cmd) => {
+            handle_ai_command(ai_cmd, &storage, cli.output)?;
+        }
+        Command::ImportResults {
+            file,
+            name,
+            environment,
+            build,
+            by,
+            dry_run,
+        } => {
+            import_test_results(
+                &storage,
+                file,
+                name.as_deref(),
+                environment.as_deref(),
+                build.as_deref(),
+                by.as_deref(),
+                *dry_run,
+            )?;
+        }
+        Command::Tui { user } => {
+            let user = user.clone().unwrap_or_else(|| {
+                std::env::var("USER").unwrap_or_else(|_| String::from("Unknown"))
+            });
+            tui::run(&storage, user)?;
         }
-        Command::Export { format, output } => {
-            handle_export_command(&storage, format, output.as_deref())?;
+        Command::Serve {
+            addr,
+            user,
+            allow_remote,
+        } => {
+            serve_api(&storage, addr, user.as_deref(), *allow_remote)?;
+        }
+        Command::Mcp { user } => {
+            // stdout carries the protocol; nothing else may be printed there
+            aida_core::mcp::serve_stdio(storage.path(), user.as_deref())?;
         }
         Command::UserGuide { dark } => {
             open_user_guide(*dark)?;
@@ -178,11 +327,6 @@ fn add_requirement_cli(
         None => anyhow::bail!("Title is required. Use --title to specify a title."),
     };
 
-    let description = match description {
-        Some(d) => d.clone(),
-        None => String::new(),
-    };
-
     // Validate parent exists if specified
     let parent_uuid = if let Some(parent_id) = parent {
         Some(parse_requirement_id(parent_id, &store)?)
@@ -191,7 +335,7 @@ fn add_requirement_cli(
     };
 
     // Create a requirement with basic data
-    let mut requirement = Requirement::new(title, description);
+    let mut requirement = Requirement::new(title, description.clone().unwrap_or_default());
 
     // Set optional fields
     if let Some(status) = status_str {
@@ -207,13 +351,30 @@ fn add_requirement_cli(
     }
 
     if let Some(owner_val) = owner {
-        requirement.owner = owner_val.clone();
+        requirement.owner = resolve_owner(&store, owner_val)?;
     }
 
     if let Some(feature_val) = feature {
         requirement.feature = feature_val.clone();
     }
 
+    // Instantiate the type template; an explicit --description wins
+    let library = TypeTemplateLibrary::load_default().unwrap_or_else(|e| {
+        eprintln!(
+            "{} could not load shared type templates: {}",
+            "Warning:".yellow(),
+            e
+        );
+        TypeTemplateLibrary::default()
+    });
+    let user = std::env::var("USER").unwrap_or_else(|_| String::from("Unknown"));
+    let ctx = TemplateContext::new(user, &requirement.feature, &requirement.req_type);
+    let template = store.instantiate_type_template(&requirement.req_type, &ctx, &library);
+    if description.is_none() {
+        requirement.description = template.description;
+    }
+    requirement.custom_fields.extend(template.custom_fields);
+
     if let Some(tags) = tags_str {
         let tag_set: HashSet<String> = tags
             .split(',')
@@ -280,10 +441,16 @@ fn list_requirements(
     req_type: &Option<String>,
     feature: &Option<String>,
     tags: &Option<String>,
+    format: OutputFormat,
 ) -> Result<()> {
     // Load requirements
     let store = storage.load()?;
-    let mut requirements = store.requirements;
+    let columns = column_engine(storage, &store);
+    let column_names = columns
+        .as_ref()
+        .map(ScriptEngine::column_names)
+        .unwrap_or_default();
+    let mut requirements = store.requirements.clone();
 
     // Apply filters if provided
     if let Some(status_str) = status {
@@ -310,17 +477,35 @@ fn list_requirements(
         requirements.retain(|r| tag_filters.iter().any(|tag| r.tags.contains(tag)));
     }
 
+    if !format.is_table() {
+        let records: Vec<RequirementRecord> = requirements
+            .iter()
+            .map(|req| {
+                let computed = columns
+                    .as_ref()
+                    .map(|e| e.computed_columns(req))
+                    .unwrap_or_default();
+                RequirementRecord::new(&store, req, computed)
+            })
+            .collect();
+        return print_records(format, &records);
+    }
+
     // Display the requirements
     if requirements.is_empty() {
         println!("{}", "No requirements found.".yellow());
         return Ok(());
     }
 
-    println!(
+    print!(
         "{:<10} | {:<36} | {:<30} | {:<10} | {:<10} | {:<15}",
         "SPEC-ID", "UUID", "Title", "Status", "Priority", "Feature"
     );
-    println!("{}", "-".repeat(120));
+    for name in &column_names {
+        print!(" | {:<15}", name);
+    }
+    println!();
+    println!("{}", "-".repeat(120 + 18 * column_names.len()));
 
     for req in requirements {
         let status_str = match req.status {
@@ -338,7 +523,7 @@ fn list_requirements(
 
         let spec_id_display = req.spec_id.as_ref().map(|s| s.as_str()).unwrap_or("-");
 
-        println!(
+        print!(
             "{:<10} | {:<36} | {:<30} | {:<10} | {:<10} | {:<15}",
             spec_id_display,
             req.id.to_string(),
@@ -347,12 +532,32 @@ fn list_requirements(
             priority_str,
             req.feature
         );
+        if let Some(engine) = &columns {
+            for (_, value) in engine.computed_columns(&req) {
+                print!(" | {:<15}", value);
+            }
+        }
+        println!();
     }
 
     Ok(())
 }
 
-fn show_requirement(storage: &Storage, id_str: &str) -> Result<()> {
+/// Compiles the project scripts when any of them define computed columns
+fn column_engine(storage: &Storage, store: &RequirementsStore) -> Option<ScriptEngine> {
+    if !store.scripts.iter().any(|s| s.kind == ScriptKind::Column) {
+        return None;
+    }
+    match ScriptEngine::new(store, storage.project_dir()) {
+        Ok(engine) => Some(engine),
+        Err(e) => {
+            eprintln!("{} {:#}", "Warning:".yellow(), e);
+            None
+        }
+    }
+}
+
+fn show_requirement(storage: &Storage, id_str: &str, format: OutputFormat) -> Result<()> {
     // Load requirements first (needed for SPEC-ID lookup)
     let store = storage.load()?;
 
@@ -362,7 +567,14 @@ fn show_requirement(storage: &Storage, id_str: &str) -> Result<()> {
     // Find the specified requirement
     let req = store
         .get_requirement_by_id(&id)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(format!("Requirement not found: {}", id_str)))?;
+
+    if !format.is_table() {
+        let computed = column_engine(storage, &store)
+            .map(|e| e.computed_columns(req))
+            .unwrap_or_default();
+        return print_record(format, &RequirementDetail::new(&store, req, computed));
+    }
 
     // Display the requirement details
     println!("{}: {}", "ID".blue(), req.id);
@@ -412,6 +624,12 @@ fn show_requirement(storage: &Storage, id_str: &str) -> Result<()> {
         println!("{}: {}", "Tags".blue(), tags_str);
     }
 
+    if let Some(engine) = column_engine(storage, &store) {
+        for (name, value) in engine.computed_columns(req) {
+            println!("{}: {}", name.blue(), value);
+        }
+    }
+
     if !req.dependencies.is_empty() {
         let deps_str = req
             .dependencies
@@ -457,6 +675,55 @@ fn show_requirement(storage: &Storage, id_str: &str) -> Result<()> {
         }
     }
 
+    if !req.acceptance_criteria.is_empty() {
+        let (satisfied, total) = req.acceptance_progress();
+        println!(
+            "\n{} ({}/{}):",
+            "Acceptance Criteria".green(),
+            satisfied,
+            total
+        );
+        for ac in &req.acceptance_criteria {
+            print_acceptance_criterion(ac);
+        }
+    }
+
+    let test_cases = store.test_cases_for_requirement(&req.id);
+    if !test_cases.is_empty() {
+        let summary = store.verification_summary(&req.id);
+        println!(
+            "\n{} ({}, {}/{} passed):",
+            "Verification".green(),
+            colored_verification_status(summary.status()),
+            summary.passed,
+            summary.total
+        );
+        for case in test_cases {
+            let outcome = store
+                .latest_test_result(&case.id)
+                .map(|(_, result)| result.outcome)
+                .unwrap_or_default();
+            println!(
+                "  {} {} {}",
+                colored_outcome(outcome),
+                case.spec_id.cyan(),
+                case.title
+            );
+        }
+    }
+
+    if !req.commits.is_empty() {
+        println!("\n{}:", "Commits".green());
+        for commit in &req.commits {
+            println!(
+                "  {} {} {}",
+                commit.short_sha().yellow(),
+                commit.subject,
+                format!("({}, {})", commit.author, commit.date.format("%Y-%m-%d")).dimmed()
+            );
+        }
+    }
+
     if !req.comments.is_empty() {
         println!("\n{}:", "Comments".green());
         for comment in &req.comments {
@@ -503,7 +770,7 @@ fn edit_requirement(storage: &Storage, id_str: &str) -> Result<()> {
     // Find the specified requirement
     let req = store
         .get_requirement_by_id_mut(&id)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
 
     // Track changes
     let mut changes: Vec<FieldChange> = Vec::new();
@@ -633,7 +900,7 @@ fn delete_requirement(storage: &Storage, id_str: &str, skip_confirm: bool) -> Re
     // Find the requirement to delete
     let req = store
         .get_requirement_by_id(&id)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
 
     // Display requirement info
     println!("{}", "Requirement to delete:".yellow());
@@ -666,6 +933,122 @@ fn delete_requirement(storage: &Storage, id_str: &str, skip_confirm: bool) -> Re
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+fn bulk_update(
+    storage: &Storage,
+    query: Option<&str>,
+    ids: Option<&str>,
+    assignments: &[String],
+    add_tags: &[String],
+    remove_tags: &[String],
+    author: Option<&str>,
+    dry_run: bool,
+    skip_confirm: bool,
+) -> Result<()> {
+    let store = storage.load()?;
+
+    // Resolve the selection from explicit IDs and/or a query
+    let mut selection: Vec<Uuid> = Vec::new();
+    if let Some(ids_str) = ids {
+        for id_str in ids_str
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+        {
+            let id = parse_requirement_id(id_str, &store)?;
+            if !selection.contains(&id) {
+                selection.push(id);
+            }
+        }
+    }
+    if let Some(query_str) = query {
+        let query = RequirementQuery::parse(query_str)?;
+        for id in store.query_requirements(&query) {
+            if !selection.contains(&id) {
+                selection.push(id);
+            }
+        }
+    }
+    if ids.is_none() && query.is_none() {
+        anyhow::bail!("Specify the requirements to update with --query and/or --ids");
+    }
+    if selection.is_empty() {
+        println!("{}", "No requirements match the selection.".yellow());
+        return Ok(());
+    }
+
+    // Build the patch
+    let mut patch = RequirementPatch::default();
+    for assignment in assignments {
+        patch.set_assignment(assignment)?;
+    }
+    patch.add_tags = add_tags.to_vec();
+    patch.remove_tags = remove_tags.to_vec();
+
+    // Validate everything up front so nothing is half-applied
+    let errors = store.validate_bulk_update(&selection, &patch);
+    if !errors.is_empty() {
+        eprintln!("{}", "Bulk update rejected:".red());
+        for error in &errors {
+            eprintln!("  {}", error);
+        }
+        anyhow::bail!(
+            "{} validation error(s); no requirements were changed",
+            errors.len()
+        );
+    }
+
+    println!(
+        "{} {} requirement(s):",
+        if dry_run { "Would update" } else { "Updating" },
+        selection.len()
+    );
+    for id in &selection {
+        if let Some(req) = store.get_requirement_by_id(id) {
+            println!(
+                "  {} - {}",
+                req.spec_id.as_deref().unwrap_or("N/A").yellow(),
+                req.title
+            );
+        }
+    }
+
+    if dry_run {
+        println!("{}", "Dry run: no changes saved.".cyan());
+        return Ok(());
+    }
+
+    if !skip_confirm {
+        let confirm = inquire::Confirm::new("Apply these changes?")
+            .with_default(false)
+            .prompt()?;
+        if !confirm {
+            println!("{}", "Bulk update cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let author = author
+        .map(|a| a.to_string())
+        .unwrap_or_else(|| std::env::var("USER").unwrap_or_else(|_| String::from("Unknown")));
+
+    // Apply against a fresh copy under the write lock
+    let mut outcome = None;
+    storage.update_atomically(|store| {
+        outcome = Some(store.bulk_update(&selection, &patch, &author));
+    })?;
+
+    let result = outcome.context("Bulk update did not run")??;
+    println!(
+        "{} {} updated, {} already up to date",
+        "Bulk update complete:".green(),
+        result.updated.len(),
+        result.unchanged.len()
+    );
+
+    Ok(())
+}
+
 fn parse_uuid(id_str: &str) -> Result<Uuid> {
     Uuid::parse_str(id_str).with_context(|| format!("Invalid UUID: {}", id_str))
 }
@@ -682,10 +1065,20 @@ fn parse_requirement_id(id_str: &str, store: &RequirementsStore) -> Result<Uuid>
         return Ok(req.id);
     }
 
-    anyhow::bail!(
+    Err(CliError::NotFound(format!(
         "Invalid requirement ID: '{}'. Must be either a UUID or SPEC-ID (e.g., SPEC-001)",
         id_str
-    )
+    ))
+    .into())
+}
+
+/// Checks an owner against the project's users and returns the value to store
+/// (the user's handle, or name if they have none). Projects without users
+/// accept any owner.
+fn resolve_owner(store: &RequirementsStore, owner: &str) -> Result<String> {
+    store
+        .resolve_owner(owner)
+        .map_err(|e| CliError::Validation(e.to_string()).into())
 }
 
 fn parse_status(status_str: &str) -> Result<RequirementStatus> {
@@ -694,7 +1087,7 @@ fn parse_status(status_str: &str) -> Result<RequirementStatus> {
         "approved" => Ok(RequirementStatus::Approved),
         "completed" => Ok(RequirementStatus::Completed),
         "rejected" => Ok(RequirementStatus::Rejected),
-        _ => anyhow::bail!("Invalid status: {}", status_str),
+        _ => Err(CliError::Validation(format!("Invalid status: {}", status_str)).into()),
     }
 }
 
@@ -703,7 +1096,7 @@ fn parse_priority(priority_str: &str) -> Result<RequirementPriority> {
         "high" => Ok(RequirementPriority::High),
         "medium" => Ok(RequirementPriority::Medium),
         "low" => Ok(RequirementPriority::Low),
-        _ => anyhow::bail!("Invalid priority: {}", priority_str),
+        _ => Err(CliError::Validation(format!("Invalid priority: {}", priority_str)).into()),
     }
 }
 
@@ -719,12 +1112,16 @@ fn parse_type(type_str: &str) -> Result<RequirementType> {
         "story" => Ok(RequirementType::Story),
         "task" => Ok(RequirementType::Task),
         "spike" => Ok(RequirementType::Spike),
-        _ => anyhow::bail!("Invalid requirement type: {}", type_str),
+        _ => Err(CliError::Validation(format!("Invalid requirement type: {}", type_str)).into()),
     }
 }
 
 /// Handle feature management subcommands
-fn handle_feature_command(cmd: &FeatureCommand, storage: &Storage) -> Result<()> {
+fn handle_feature_command(
+    cmd: &FeatureCommand,
+    storage: &Storage,
+    format: OutputFormat,
+) -> Result<()> {
     // Load existing requirements
     let mut store = storage.load()?;
 
@@ -766,6 +1163,9 @@ fn handle_feature_command(cmd: &FeatureCommand, storage: &Storage) -> Result<()>
             // Save the updated store
             storage.save(&store)?;
         }
+        FeatureCommand::List if !format.is_table() => {
+            print_records(format, &FeatureRecord::all(&store))?;
+        }
         FeatureCommand::List => {
             // Show both legacy features and new feature definitions
             println!("{}", "Defined Features:".blue().bold());
@@ -851,7 +1251,7 @@ fn handle_feature_command(cmd: &FeatureCommand, storage: &Storage) -> Result<()>
                 }
 
                 if !found {
-                    println!("{} Feature '{}' not found.", "!".yellow(), name);
+                    return Err(CliError::NotFound(format!("Feature '{}' not found", name)).into());
                 }
             }
         }
@@ -926,7 +1326,7 @@ fn handle_feature_command(cmd: &FeatureCommand, storage: &Storage) -> Result<()>
                 }
 
                 if !found {
-                    println!("{} Feature '{}' not found.", "!".yellow(), name);
+                    return Err(CliError::NotFound(format!("Feature '{}' not found", name)).into());
                 }
             }
         }
@@ -936,10 +1336,17 @@ fn handle_feature_command(cmd: &FeatureCommand, storage: &Storage) -> Result<()>
 }
 
 /// Handle ID configuration commands
-fn handle_config_command(cmd: &ConfigCommand, storage: &Storage) -> Result<()> {
+fn handle_config_command(
+    cmd: &ConfigCommand,
+    storage: &Storage,
+    format: OutputFormat,
+) -> Result<()> {
     let mut store = storage.load()?;
 
     match cmd {
+        ConfigCommand::Show if !format.is_table() => {
+            print_record(format, &ConfigRecord::new(&store))?;
+        }
         ConfigCommand::Show => {
             println!("{}", "ID Configuration:".blue().bold());
             println!();
@@ -1039,10 +1446,19 @@ fn handle_config_command(cmd: &ConfigCommand, storage: &Storage) -> Result<()> {
 }
 
 /// Handle requirement type commands
-fn handle_type_command(cmd: &TypeCommand, storage: &Storage) -> Result<()> {
+fn handle_type_command(cmd: &TypeCommand, storage: &Storage, format: OutputFormat) -> Result<()> {
     let mut store = storage.load()?;
 
     match cmd {
+        TypeCommand::List if !format.is_table() => {
+            let records: Vec<TypeRecord> = store
+                .id_config
+                .requirement_types
+                .iter()
+                .map(TypeRecord::from)
+                .collect();
+            print_records(format, &records)?;
+        }
         TypeCommand::List => {
             println!("{}", "Requirement Types:".blue().bold());
             println!("{:<20} | {:<10} | {}", "Name", "Prefix", "Description");
@@ -1101,7 +1517,44 @@ fn handle_type_command(cmd: &TypeCommand, storage: &Storage) -> Result<()> {
                     removed.name
                 );
             } else {
-                println!("{} Type '{}' not found.", "!".yellow(), name);
+                return Err(CliError::NotFound(format!("Type '{}' not found", name)).into());
+            }
+        }
+        TypeCommand::Template { name, export } => {
+            let req_type = parse_type(name)?;
+            let library = TypeTemplateLibrary::load_default()?;
+            let template = store.get_type_template(&req_type, &library);
+            let type_name = store
+                .get_type_definition(&req_type)
+                .map(|td| td.name.clone())
+                .unwrap_or_else(|| req_type.to_string());
+
+            println!("{} {}", "Template for".bold(), type_name.cyan());
+            match &template.description {
+                Some(description) => println!("\n{}", description),
+                None => println!("{}", "(no description template)".dimmed()),
+            }
+            if !template.field_defaults.is_empty() {
+                println!("\n{}", "Default field values:".bold());
+                let mut defaults: Vec<_> = template.field_defaults.iter().collect();
+                defaults.sort();
+                for (field, value) in defaults {
+                    println!("  {} = {}", field, value);
+                }
+            }
+
+            if let Some(library_name) = export {
+                let path =
+                    TypeTemplateLibrary::default_dir()?.join(format!("{}.yaml", library_name));
+                let mut shared = TypeTemplateLibrary::load_file(&path)?;
+                shared.templates.insert(type_name.clone(), template);
+                shared.save(&path)?;
+                println!(
+                    "\n{} Template for '{}' exported to {}",
+                    "✓".green(),
+                    type_name,
+                    path.display()
+                );
             }
         }
     }
@@ -1215,6 +1668,8 @@ fn handle_export_command(
     storage: &Storage,
     format: &str,
     output: Option<&std::path::Path>,
+    baseline: Option<&str>,
+    graph: &export::GraphFilter,
 ) -> Result<()> {
     // Load requirements
     let store = storage.load()?;
@@ -1244,9 +1699,57 @@ fn handle_export_command(
                 .unwrap_or_else(|| std::path::PathBuf::from("IMPLEMENTATION.md"));
             export::export_implementation_records(&store, &output_path)?;
         }
+        "coverage" => {
+            let output_path = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("COVERAGE.md"));
+            export::export_coverage_report(&store, &output_path)?;
+        }
+        "reqif" => {
+            let output_path = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("requirements.reqif"));
+            export::export_reqif(&store, &output_path)?;
+        }
+        "csv" => {
+            let output_path = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("requirements.csv"));
+            export::export_csv(&store, &output_path)?;
+        }
+        "html" | "site" => {
+            let output_dir = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("site"));
+            export::export_site(&store, &output_dir)?;
+        }
+        "docx" | "word" => {
+            let output_path = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("specification.docx"));
+            export::export_docx(&store, &output_path, baseline)?;
+        }
+        "odt" => {
+            let output_path = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("specification.odt"));
+            export::export_odt(&store, &output_path, baseline)?;
+        }
+        "dot" | "graphviz" => {
+            let output_path = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("requirements.dot"));
+            export::export_dot(&store, &output_path, graph)?;
+        }
+        "mermaid" | "mmd" => {
+            let output_path = output
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("requirements.mmd"));
+            export::export_mermaid(&store, &output_path, graph)?;
+        }
         _ => {
             anyhow::bail!(
-                "Unknown export format: {}. Supported formats: mapping, json, spec, impl",
+                "Unknown export format: {}. Supported formats: mapping, json, spec, impl, coverage, reqif, csv, html, docx, odt, dot, mermaid",
                 format
             );
         }
@@ -1255,187 +1758,2280 @@ fn handle_export_command(
     Ok(())
 }
 
-fn handle_relationship_command(cmd: &RelationshipCommand, storage: &Storage) -> Result<()> {
-    match cmd {
-        RelationshipCommand::Add {
-            from,
-            to,
-            r#type,
-            bidirectional,
-        } => {
-            add_relationship(storage, from, to, r#type, *bidirectional)?;
+fn handle_import_command(cmd: &ImportCommand, storage: &Storage) -> Result<()> {
+    let (file, user, dry_run) = match cmd {
+        ImportCommand::Csv {
+            file,
+            user,
+            dry_run,
+            ..
         }
-        RelationshipCommand::Remove {
-            from,
-            to,
-            r#type,
-            bidirectional,
+        | ImportCommand::Reqif {
+            file,
+            user,
+            dry_run,
+        } => (file, user, *dry_run),
+    };
+    let data = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let user = user
+        .clone()
+        .unwrap_or_else(|| std::env::var("USER").unwrap_or_else(|_| String::from("Unknown")));
+    let mut store = storage.load()?;
+
+    let summary = match cmd {
+        ImportCommand::Csv {
+            map,
+            type_map,
+            status_map,
+            ..
         } => {
-            remove_relationship(storage, from, to, r#type, *bidirectional)?;
+            let mut mapping = match map {
+                Some(map) => export::CsvMapping::parse(map)?,
+                None => export::CsvMapping::default(),
+            };
+            if let Some(type_map) = type_map {
+                mapping = mapping.with_type_values(type_map)?;
+            }
+            if let Some(status_map) = status_map {
+                mapping = mapping.with_status_values(status_map)?;
+            }
+            if file
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("tsv"))
+            {
+                mapping = mapping.with_delimiter(b'\t');
+            }
+            export::import_csv(&mut store, &data, &mapping, &user)?
         }
-        RelationshipCommand::List { id } => {
-            list_relationships(storage, id)?;
+        ImportCommand::Reqif { .. } => export::import_reqif(&mut store, &data, &user)?,
+    };
+
+    print_import_summary(&file.display().to_string(), &summary);
+
+    if dry_run {
+        println!("\n{}", "Dry run - nothing saved.".yellow());
+        return Ok(());
+    }
+    storage.save(&store)?;
+    println!("\n{} Import saved", "✓".green());
+    Ok(())
+}
+
+/// Prints what an import created, updated and skipped
+fn print_import_summary(source: &str, summary: &export::ImportSummary) {
+    println!(
+        "Read {}: {} created, {} updated, {} unchanged",
+        source,
+        summary.created.len(),
+        summary.updated.len(),
+        summary.unchanged
+    );
+    if !summary.created.is_empty() {
+        println!("  {} {}", "Created:".green(), summary.created.join(", "));
+    }
+    if !summary.updated.is_empty() {
+        println!("  {} {}", "Updated:".cyan(), summary.updated.join(", "));
+    }
+    if summary.relationships_added > 0 || summary.parents_set > 0 {
+        println!(
+            "  {} relationship(s) added, {} parent link(s) set",
+            summary.relationships_added, summary.parents_set
+        );
+    }
+    if summary.relationships_removed > 0 {
+        println!(
+            "  {} relationship(s) removed",
+            summary.relationships_removed
+        );
+    }
+    for conflict in &summary.conflicts {
+        println!("  {} {}", "Conflict:".red(), conflict);
+    }
+    for warning in &summary.warnings {
+        println!("  {} {}", "Warning:".yellow(), warning);
+    }
+}
+
+fn handle_md_command(cmd: &MdCommand, storage: &Storage) -> Result<()> {
+    match cmd {
+        MdCommand::Export { dir } => {
+            let store = storage.load()?;
+            export::export_markdown(&store, dir)?;
+        }
+        MdCommand::Apply { dir, user, dry_run } => {
+            let user = user.clone().unwrap_or_else(|| {
+                std::env::var("USER").unwrap_or_else(|_| String::from("Unknown"))
+            });
+            let docs = export::read_markdown_dir(dir)?;
+            let mut store = storage.load()?;
+            let result = export::apply_markdown(&mut store, &docs, &user)?;
+            print_import_summary(&dir.display().to_string(), &result.summary);
+            if !result.summary.conflicts.is_empty() {
+                println!(
+                    "  {}",
+                    "Conflicting files were skipped; run 'aida md export' and redo those edits."
+                        .yellow()
+                );
+            }
+
+            if *dry_run {
+                println!("\n{}", "Dry run - nothing saved.".yellow());
+                return Ok(());
+            }
+            storage.save(&store)?;
+            // Reload so the rewritten files carry the saved ids and versions
+            let store = storage.load()?;
+            export::refresh_markdown_files(&store, &result.changed_files)?;
+            println!("\n{} Markdown changes saved", "✓".green());
         }
     }
     Ok(())
 }
 
-fn add_relationship(
+/// Finds a baseline by name or UUID
+fn find_baseline<'a>(store: &'a RequirementsStore, name: &str) -> Result<&'a Baseline> {
+    Uuid::parse_str(name)
+        .ok()
+        .and_then(|id| store.get_baseline(&id))
+        .or_else(|| store.get_baseline_by_name(name))
+        .ok_or_else(|| CliError::NotFound(format!("Baseline '{}' not found", name)).into())
+}
+
+fn handle_baseline_command(
+    cmd: &BaselineCommand,
     storage: &Storage,
-    from_str: &str,
-    to_str: &str,
-    rel_type_str: &str,
-    bidirectional: bool,
+    format: OutputFormat,
 ) -> Result<()> {
-    // Load requirements
-    let mut store = storage.load()?;
+    match cmd {
+        BaselineCommand::Create {
+            name,
+            description,
+            user,
+            lock,
+        } => {
+            let store = storage.load()?;
+            if store.get_baseline_by_name(name).is_some() {
+                return Err(
+                    CliError::Validation(format!("Baseline '{}' already exists", name)).into(),
+                );
+            }
+            let user = user.clone().unwrap_or_else(|| {
+                std::env::var("USER").unwrap_or_else(|_| String::from("Unknown"))
+            });
+            // The backend tags the commit for YAML projects in a git repository
+            let backend = aida_core::db::create_backend(storage.path(), None)?;
+            let baseline = backend.create_baseline(name.clone(), description.clone(), user)?;
+            if *lock {
+                let mut store = storage.load()?;
+                if let Some(b) = store.baselines.iter_mut().find(|b| b.id == baseline.id) {
+                    b.locked = true;
+                }
+                storage.save(&store)?;
+            }
 
-    // Parse source and target IDs
-    let from_id = parse_requirement_id(from_str, &store)?;
-    let to_id = parse_requirement_id(to_str, &store)?;
+            println!(
+                "{} Baseline '{}' created with {} requirements",
+                "✓".green(),
+                baseline.name,
+                baseline.requirements.len()
+            );
+            if let Some(tag) = &baseline.git_tag {
+                println!("  Git tag: {}", tag.cyan());
+            }
+        }
+        BaselineCommand::List => {
+            let store = storage.load()?;
+            let mut baselines: Vec<&Baseline> = store.baselines.iter().collect();
+            baselines.sort_by_key(|b| b.created_at);
+            if !format.is_table() {
+                let records: Vec<BaselineRecord> =
+                    baselines.into_iter().map(BaselineRecord::from).collect();
+                return print_records(format, &records);
+            }
+            if baselines.is_empty() {
+                println!("{}", "No baselines found.".yellow());
+                return Ok(());
+            }
 
-    // Parse relationship type
+            println!(
+                "{:<20} | {:<16} | {:<12} | {:<12} | {:<25} | Locked",
+                "Name", "Created", "By", "Requirements", "Git Tag"
+            );
+            println!("{}", "-".repeat(105));
+            for baseline in baselines {
+                println!(
+                    "{:<20} | {:<16} | {:<12} | {:<12} | {:<25} | {}",
+                    baseline.name,
+                    baseline.created_at.format("%Y-%m-%d %H:%M"),
+                    baseline.created_by,
+                    baseline.requirements.len(),
+                    baseline.git_tag.as_deref().unwrap_or("-"),
+                    if baseline.locked { "🔒" } else { "" }
+                );
+            }
+        }
+        BaselineCommand::Show { name } => {
+            let store = storage.load()?;
+            let baseline = find_baseline(&store, name)?;
+            if !format.is_table() {
+                return print_record(format, &BaselineDetail::from(baseline));
+            }
+
+            println!("{}: {}", "Baseline".blue(), baseline.name);
+            println!("{}: {}", "ID".blue(), baseline.id);
+            if let Some(description) = &baseline.description {
+                println!("{}: {}", "Description".blue(), description);
+            }
+            println!(
+                "{}: {} by {}",
+                "Created".blue(),
+                baseline.created_at.format("%Y-%m-%d %H:%M"),
+                baseline.created_by
+            );
+            if let Some(tag) = &baseline.git_tag {
+                println!("{}: {}", "Git Tag".blue(), tag);
+            }
+            if baseline.locked {
+                println!("{}: yes", "Locked".blue());
+            }
+
+            println!(
+                "\n{} ({}):",
+                "Requirements".green(),
+                baseline.requirements.len()
+            );
+            for snapshot in &baseline.requirements {
+                println!(
+                    "  {:<12} {:<10} {}",
+                    snapshot.spec_id.as_deref().unwrap_or("-"),
+                    snapshot
+                        .custom_status
+                        .clone()
+                        .unwrap_or_else(|| snapshot.status.to_string()),
+                    snapshot.title
+                );
+            }
+        }
+        BaselineCommand::Delete { name, yes } => {
+            let mut store = storage.load()?;
+            let baseline = find_baseline(&store, name)?;
+            let (id, name) = (baseline.id, baseline.name.clone());
+            if baseline.locked {
+                return Err(CliError::Validation(format!(
+                    "Baseline '{}' is locked; unlock it with 'aida baseline lock \"{}\" --unlock'",
+                    name, name
+                ))
+                .into());
+            }
+
+            if !*yes {
+                let confirm = inquire::Confirm::new(&format!(
+                    "Are you sure you want to delete baseline '{}'?",
+                    name
+                ))
+                .with_default(false)
+                .prompt()?;
+                if !confirm {
+                    println!("Deletion cancelled.");
+                    return Ok(());
+                }
+            }
+
+            store.delete_baseline(&id);
+            storage.save(&store)?;
+            println!("{} Baseline '{}' deleted", "✓".green(), name);
+        }
+        BaselineCommand::Lock { name, unlock } => {
+            let mut store = storage.load()?;
+            let id = find_baseline(&store, name)?.id;
+            let baseline = store
+                .baselines
+                .iter_mut()
+                .find(|b| b.id == id)
+                .expect("baseline was just found");
+            baseline.locked = !*unlock;
+            let message = format!(
+                "Baseline '{}' {}",
+                baseline.name,
+                if *unlock { "unlocked" } else { "locked" }
+            );
+            storage.save(&store)?;
+            println!("{} {}", "✓".green(), message);
+        }
+        BaselineCommand::Diff {
+            from,
+            to,
+            format: diff_format,
+        } => {
+            let store = storage.load()?;
+            let from = find_baseline(&store, from)?;
+            let to = to
+                .as_deref()
+                .map(|to| find_baseline(&store, to))
+                .transpose()?;
+            let diff = export::BaselineDiff::new(&store, from, to);
+            match diff_format {
+                DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+                DiffFormat::Markdown => print!("{}", diff.to_markdown()),
+                DiffFormat::Text => print_baseline_diff(&diff),
+            }
+        }
+        BaselineCommand::Export {
+            name,
+            format: doc_format,
+            output,
+        } => {
+            let store = storage.load()?;
+            let baseline = find_baseline(&store, name)?;
+            let extension = match doc_format.as_str() {
+                "md" | "markdown" => "md",
+                "docx" | "word" => "docx",
+                "odt" => "odt",
+                _ => {
+                    return Err(CliError::Validation(format!(
+                        "Unknown document format: {}. Supported formats: md, docx, odt",
+                        doc_format
+                    ))
+                    .into())
+                }
+            };
+            let output_path = output.clone().unwrap_or_else(|| {
+                std::path::PathBuf::from(format!(
+                    "specification-{}.{}",
+                    baseline.name_slug(),
+                    extension
+                ))
+            });
+            match extension {
+                "md" => {
+                    let template = export::ExportTemplate::builtin("spec")?;
+                    export::export_template(&store, &template, &output_path, Some(&baseline.name))?;
+                }
+                "docx" => export::export_docx(&store, &output_path, Some(&baseline.name))?,
+                _ => export::export_odt(&store, &output_path, Some(&baseline.name))?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints a baseline diff with field-level changes
+fn print_baseline_diff(diff: &export::BaselineDiff) {
+    println!(
+        "{} {} {} {}: {} added, {} removed, {} modified, {} unchanged",
+        "Changes from".blue().bold(),
+        diff.from.cyan(),
+        "to".blue().bold(),
+        diff.to_label().cyan(),
+        diff.added.len(),
+        diff.removed.len(),
+        diff.modified.len(),
+        diff.unchanged
+    );
+    if diff.is_empty() {
+        println!("{}", "No changes.".dimmed());
+        return;
+    }
+
+    for (heading, marker, reqs) in [
+        ("Added", "+".green(), &diff.added),
+        ("Removed", "-".red(), &diff.removed),
+    ] {
+        if reqs.is_empty() {
+            continue;
+        }
+        println!("\n{}:", heading.bold());
+        for req in reqs {
+            println!(
+                "  {} {:<12} {}",
+                marker,
+                req.spec_id.as_deref().unwrap_or("-"),
+                req.title
+            );
+        }
+    }
+
+    if !diff.modified.is_empty() {
+        println!("\n{}:", "Modified".bold());
+        for req in &diff.modified {
+            println!(
+                "  {} {:<12} {}",
+                "~".yellow(),
+                req.spec_id.as_deref().unwrap_or("-"),
+                req.title
+            );
+            for change in &req.changes {
+                let (old, new) = (change.old_value.trim(), change.new_value.trim());
+                if old.contains('\n') || new.contains('\n') || old.len() + new.len() > 60 {
+                    println!("      {}:", change.field_name.cyan());
+                    for line in old.lines() {
+                        println!("        {}", format!("- {}", line).red());
+                    }
+                    for line in new.lines() {
+                        println!("        {}", format!("+ {}", line).green());
+                    }
+                } else {
+                    println!(
+                        "      {}: {} → {}",
+                        change.field_name.cyan(),
+                        old.red(),
+                        new.green()
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Finds a user by spec ID, UUID, handle or name
+fn find_user<'a>(store: &'a RequirementsStore, key: &str) -> Result<&'a User> {
+    store
+        .find_user(key)
+        .ok_or_else(|| CliError::NotFound(format!("User '{}' not found", key)).into())
+}
+
+/// Finds a team by spec ID, UUID or name
+fn find_team<'a>(store: &'a RequirementsStore, key: &str) -> Result<&'a Team> {
+    store
+        .find_team(key)
+        .ok_or_else(|| CliError::NotFound(format!("Team '{}' not found", key)).into())
+}
+
+/// Rejects a handle already used by another user
+fn check_handle_free(store: &RequirementsStore, handle: &str, user_id: Option<Uuid>) -> Result<()> {
+    let handle = handle.trim_start_matches('@');
+    if let Some(other) = store
+        .users
+        .iter()
+        .find(|u| !handle.is_empty() && u.handle.eq_ignore_ascii_case(handle))
+    {
+        if Some(other.id) != user_id {
+            return Err(CliError::Validation(format!(
+                "Handle '{}' is already used by {} ({})",
+                handle,
+                other.display_id(),
+                other.name
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn handle_user_command(cmd: &UserCommand, storage: &Storage, format: OutputFormat) -> Result<()> {
+    let mut store = storage.load()?;
+
+    match cmd {
+        UserCommand::Add {
+            name,
+            email,
+            handle,
+        } => {
+            let handle = handle.as_deref().unwrap_or("").trim_start_matches('@');
+            check_handle_free(&store, handle, None)?;
+            let spec_id = store.add_user_with_id(
+                name.clone(),
+                email.clone().unwrap_or_default(),
+                handle.to_string(),
+            );
+            storage.save(&store)?;
+            println!("{} User {} ({}) added", "✓".green(), spec_id, name);
+        }
+        UserCommand::List { all } => {
+            let users: Vec<&User> = store.users.iter().filter(|u| *all || !u.archived).collect();
+            if !format.is_table() {
+                let records: Vec<UserRecord> =
+                    users.iter().map(|u| UserRecord::new(&store, u)).collect();
+                return print_records(format, &records);
+            }
+            if users.is_empty() {
+                println!("{}", "No users found.".yellow());
+                return Ok(());
+            }
+
+            println!(
+                "{:<10} | {:<25} | {:<15} | {:<30} | Teams",
+                "ID", "Name", "Handle", "Email"
+            );
+            println!("{}", "-".repeat(100));
+            for user in users {
+                let teams: Vec<&str> = store
+                    .get_teams_for_user(&user.id)
+                    .iter()
+                    .map(|t| t.name.as_str())
+                    .collect();
+                let name = if user.archived {
+                    format!("{} (archived)", user.name).dimmed()
+                } else {
+                    user.name.normal()
+                };
+                println!(
+                    "{:<10} | {:<25} | {:<15} | {:<30} | {}",
+                    user.display_id(),
+                    name,
+                    user.handle,
+                    user.email,
+                    teams.join(", ")
+                );
+            }
+        }
+        UserCommand::Edit {
+            user,
+            name,
+            email,
+            handle,
+        } => {
+            let id = find_user(&store, user)?.id;
+            if let Some(handle) = handle {
+                check_handle_free(&store, handle, Some(id))?;
+            }
+            let user = store.get_user_by_id_mut(&id).expect("user was just found");
+            if let Some(name) = name {
+                user.name = name.clone();
+            }
+            if let Some(email) = email {
+                user.email = email.clone();
+            }
+            if let Some(handle) = handle {
+                user.handle = handle.trim_start_matches('@').to_string();
+            }
+            let message = format!("User {} updated", user.display_id());
+            storage.save(&store)?;
+            println!("{} {}", "✓".green(), message);
+        }
+        UserCommand::Archive { user, restore } => {
+            let id = find_user(&store, user)?.id;
+            let user = store.get_user_by_id_mut(&id).expect("user was just found");
+            user.archived = !*restore;
+            let message = format!(
+                "User {} {}",
+                user.display_id(),
+                if *restore { "restored" } else { "archived" }
+            );
+            storage.save(&store)?;
+            println!("{} {}", "✓".green(), message);
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_team_command(cmd: &TeamCommand, storage: &Storage, format: OutputFormat) -> Result<()> {
+    let mut store = storage.load()?;
+
+    match cmd {
+        TeamCommand::Add {
+            name,
+            description,
+            parent,
+        } => {
+            let parent_id = match parent {
+                Some(parent) => Some(find_team(&store, parent)?.id),
+                None => None,
+            };
+            let spec_id = store.add_team_with_id(
+                name.clone(),
+                description.clone().unwrap_or_default(),
+                parent_id,
+            );
+            storage.save(&store)?;
+            println!("{} Team {} ({}) added", "✓".green(), spec_id, name);
+        }
+        TeamCommand::List { all } => {
+            if !format.is_table() {
+                let records: Vec<TeamRecord> = store
+                    .teams
+                    .iter()
+                    .filter(|t| *all || !t.archived)
+                    .map(|t| TeamRecord::new(&store, t))
+                    .collect();
+                return print_records(format, &records);
+            }
+            let roots: Vec<&Team> = store
+                .teams
+                .iter()
+                .filter(|t| {
+                    t.parent_team_id
+                        .is_none_or(|id| store.find_team_by_id(&id).is_none())
+                })
+                .collect();
+            if roots.is_empty() {
+                println!("{}", "No teams found.".yellow());
+                return Ok(());
+            }
+            for team in roots {
+                print_team_tree(&store, team, 0, *all);
+            }
+        }
+        TeamCommand::Show { team } => {
+            let team = find_team(&store, team)?;
+            if !format.is_table() {
+                return print_record(format, &TeamRecord::new(&store, team));
+            }
+
+            println!("{}: {}", "Team".blue(), team.name);
+            println!("{}: {}", "ID".blue(), team.display_id());
+            if !team.description.is_empty() {
+                println!("{}: {}", "Description".blue(), team.description);
+            }
+            if let Some(parent) = team
+                .parent_team_id
+                .and_then(|id| store.find_team_by_id(&id))
+            {
+                println!(
+                    "{}: {} ({})",
+                    "Parent".blue(),
+                    parent.name,
+                    parent.display_id()
+                );
+            }
+            if team.archived {
+                println!("{}: yes", "Archived".blue());
+            }
+
+            println!("\n{} ({}):", "Members".green(), team.member_count());
+            for id in &team.member_ids {
+                match store.find_user_by_id(id) {
+                    Some(user) => println!(
+                        "  {:<10} {:<25} {}",
+                        user.display_id(),
+                        user.name,
+                        if user.handle.is_empty() {
+                            String::new()
+                        } else {
+                            format!("@{}", user.handle)
+                        }
+                    ),
+                    None => println!("  {} {}", id, "(unknown user)".dimmed()),
+                }
+            }
+            let subteams = store.get_child_teams(&team.id);
+            if !subteams.is_empty() {
+                println!("\n{} ({}):", "Sub-teams".green(), subteams.len());
+                for sub in subteams {
+                    println!("  {:<10} {}", sub.display_id(), sub.name);
+                }
+            }
+        }
+        TeamCommand::AddMember { team, user } => {
+            let team_id = find_team(&store, team)?.id;
+            let user = find_user(&store, user)?;
+            if user.archived {
+                return Err(CliError::Validation(format!(
+                    "User {} is archived; restore them first",
+                    user.display_id()
+                ))
+                .into());
+            }
+            let (user_id, user_label) = (user.id, user.display_id().to_string());
+            let team = store
+                .get_team_by_id_mut(&team_id)
+                .expect("team was just found");
+            if team.has_member(&user_id) {
+                println!("{} is already a member of {}", user_label, team.name);
+                return Ok(());
+            }
+            team.add_member(user_id);
+            let message = format!("{} added to {}", user_label, team.name);
+            storage.save(&store)?;
+            println!("{} {}", "✓".green(), message);
+        }
+        TeamCommand::RemoveMember { team, user } => {
+            let team_id = find_team(&store, team)?.id;
+            let user = find_user(&store, user)?;
+            let (user_id, user_label) = (user.id, user.display_id().to_string());
+            let team = store
+                .get_team_by_id_mut(&team_id)
+                .expect("team was just found");
+            if !team.remove_member(&user_id) {
+                return Err(CliError::NotFound(format!(
+                    "{} is not a member of {}",
+                    user_label, team.name
+                ))
+                .into());
+            }
+            let message = format!("{} removed from {}", user_label, team.name);
+            storage.save(&store)?;
+            println!("{} {}", "✓".green(), message);
+        }
+        TeamCommand::Move { team, parent, .. } => {
+            let team_id = find_team(&store, team)?.id;
+            let parent = match parent {
+                Some(parent) => Some(find_team(&store, parent)?),
+                None => None,
+            };
+            if let Some(parent) = parent {
+                if store.would_create_team_cycle(&team_id, &parent.id) {
+                    return Err(CliError::Validation(format!(
+                        "Cannot move under {}: it would create a circular team hierarchy",
+                        parent.name
+                    ))
+                    .into());
+                }
+            }
+            let parent = parent.map(|p| (p.id, p.name.clone()));
+
+            let team = store
+                .get_team_by_id_mut(&team_id)
+                .expect("team was just found");
+            team.parent_team_id = parent.as_ref().map(|(id, _)| *id);
+            team.modified_at = Some(chrono::Utc::now());
+            let message = match &parent {
+                Some((_, name)) => format!("{} moved under {}", team.name, name),
+                None => format!("{} is now a top-level team", team.name),
+            };
+            storage.save(&store)?;
+            println!("{} {}", "✓".green(), message);
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints a team and its sub-teams, indented by depth
+fn print_team_tree(store: &RequirementsStore, team: &Team, depth: usize, all: bool) {
+    if team.archived && !all {
+        return;
+    }
+    let members = team.member_count();
+    let label = format!(
+        "{}{:<10} {} ({} member{})",
+        "  ".repeat(depth),
+        team.display_id(),
+        team.name,
+        members,
+        if members == 1 { "" } else { "s" }
+    );
+    if team.archived {
+        println!("{} {}", label.dimmed(), "(archived)".dimmed());
+    } else {
+        println!("{}", label);
+    }
+    for child in store.get_child_teams(&team.id) {
+        print_team_tree(store, child, depth + 1, all);
+    }
+}
+
+/// Returns the AI client, or an error explaining how to enable one
+fn ai_client() -> Result<AiClient> {
+    let client = AiClient::new();
+    if !client.is_available() {
+        anyhow::bail!(
+            "AI integration is not available. Install the Claude CLI or set ANTHROPIC_API_KEY"
+        );
+    }
+    Ok(client)
+}
+
+/// Prints a progress or result line, on stderr when stdout carries
+/// machine-readable output
+fn ai_status(format: OutputFormat, message: impl std::fmt::Display) {
+    if format.is_table() {
+        println!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+/// Lets the user pick which suggestions to apply; all of them with `--yes`
+fn choose_suggestions(labels: Vec<String>, yes: bool) -> Result<Vec<usize>> {
+    if yes {
+        return Ok((0..labels.len()).collect());
+    }
+    let chosen = inquire::MultiSelect::new("Apply which suggestions?", labels)
+        .with_all_selected_by_default()
+        .raw_prompt()?;
+    Ok(chosen.into_iter().map(|option| option.index).collect())
+}
+
+fn handle_ai_command(cmd: &AiCommand, storage: &Storage, format: OutputFormat) -> Result<()> {
+    let store = storage.load()?;
+    let user = std::env::var("USER").unwrap_or_else(|_| String::from("Unknown"));
+
+    if let AiCommand::Scan { limit, dry_run } = cmd {
+        return ai_scan(storage, &store, *limit, *dry_run, format);
+    }
+
+    let client = ai_client()?;
+    let id = match cmd {
+        AiCommand::Evaluate { id }
+        | AiCommand::Duplicates { id }
+        | AiCommand::Relate { id, .. }
+        | AiCommand::Improve { id, .. }
+        | AiCommand::Children { id, .. } => id,
+        AiCommand::Scan { .. } => unreachable!("handled above"),
+    };
+    let req_id = parse_requirement_id(id, &store)?;
+    let req = store
+        .get_requirement_by_id(&req_id)
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
+    let spec_id = req.spec_id.clone().unwrap_or_else(|| req.id.to_string());
+    ai_status(
+        format,
+        format!("Asking {} about {}...", client.mode_description(), spec_id).dimmed(),
+    );
+
+    match cmd {
+        AiCommand::Evaluate { .. } => {
+            let stored = StoredAiEvaluation::new(
+                client.evaluate_requirement(req, &store)?,
+                req.content_hash(),
+            );
+            let saved = stored.clone();
+            storage.update_atomically(|store| {
+                if let Some(req) = store.get_requirement_by_id_mut(&req_id) {
+                    req.ai_evaluation = Some(saved);
+                }
+            })?;
+
+            if !format.is_table() {
+                return print_record(format, &AiEvaluationRecord::new(req, &stored));
+            }
+            print_ai_evaluation(&spec_id, &req.title, &stored.evaluation);
+        }
+        AiCommand::Duplicates { .. } => {
+            let duplicates = client.find_duplicates(req, &store)?.potential_duplicates;
+            if !format.is_table() {
+                return print_records(format, &duplicates);
+            }
+            if duplicates.is_empty() {
+                println!("{}", "No potential duplicates found.".green());
+                return Ok(());
+            }
+
+            println!("{} for {}:", "Potential duplicates".yellow(), spec_id);
+            for dup in &duplicates {
+                let title = store
+                    .get_requirement_by_spec_id(&dup.spec_id)
+                    .map(|r| r.title.as_str())
+                    .unwrap_or("(unknown requirement)");
+                println!(
+                    "  {} {} ({:.0}% similar, {})",
+                    dup.spec_id.yellow(),
+                    title,
+                    dup.similarity * 100.0,
+                    dup.recommendation
+                );
+                println!("    {}", dup.reason);
+            }
+        }
+        AiCommand::Relate { apply, yes, .. } => {
+            let suggestions = client
+                .suggest_relationships(req, &store)?
+                .suggested_relationships;
+            if !format.is_table() {
+                print_records(format, &suggestions)?;
+            } else if suggestions.is_empty() {
+                println!("{}", "No relationship suggestions.".green());
+            } else {
+                println!("{} for {}:", "Suggested relationships".cyan(), spec_id);
+                for s in &suggestions {
+                    println!(
+                        "  {} {} {} ({:.0}% confidence)",
+                        s.rel_type.cyan(),
+                        "->".blue(),
+                        s.target_spec_id.yellow(),
+                        s.confidence * 100.0
+                    );
+                    println!("    {}", s.rationale);
+                }
+            }
+            if !apply || suggestions.is_empty() {
+                return Ok(());
+            }
+
+            let labels = suggestions
+                .iter()
+                .map(|s| format!("{} {} -> {}", spec_id, s.rel_type, s.target_spec_id))
+                .collect();
+            let chosen = choose_suggestions(labels, *yes)?;
+            let mut results = Vec::new();
+            storage.update_atomically(|store| {
+                for &i in &chosen {
+                    let s = &suggestions[i];
+                    let result =
+                        parse_requirement_id(&s.target_spec_id, store).and_then(|target| {
+                            store.add_relationship_with_creator(
+                                &req_id,
+                                RelationshipType::from_str(&s.rel_type),
+                                &target,
+                                false,
+                                Some(user.clone()),
+                            )
+                        });
+                    results.push((s, result));
+                }
+            })?;
+            for (s, result) in results {
+                match result {
+                    Ok(()) => ai_status(
+                        format,
+                        format!(
+                            "{} {} {} -> {}",
+                            "✓".green(),
+                            spec_id,
+                            s.rel_type,
+                            s.target_spec_id
+                        ),
+                    ),
+                    Err(e) => ai_status(
+                        format,
+                        format!(
+                            "{} {} -> {}: {}",
+                            "✗".red(),
+                            s.rel_type,
+                            s.target_spec_id,
+                            e
+                        ),
+                    ),
+                }
+            }
+        }
+        AiCommand::Improve { apply, yes, .. } => {
+            let response = client.improve_description(req, &store)?;
+            if !format.is_table() {
+                print_record(format, &response)?;
+            } else {
+                println!("{} for {}:\n", "Improved description".cyan(), spec_id);
+                println!("{}\n", response.improved_description);
+                if !response.changes_made.is_empty() {
+                    println!("{}:", "Changes".blue());
+                    for change in &response.changes_made {
+                        println!("  • {}", change);
+                    }
+                }
+                println!("{}: {}", "Rationale".blue(), response.rationale);
+            }
+            if !apply {
+                return Ok(());
+            }
+
+            if !yes {
+                let confirm =
+                    inquire::Confirm::new(&format!("Replace the description of {}?", spec_id))
+                        .with_default(false)
+                        .prompt()?;
+                if !confirm {
+                    ai_status(format, "Description not changed.".yellow());
+                    return Ok(());
+                }
+            }
+            let original = req.description.clone();
+            let mut replaced = false;
+            storage.update_atomically(|store| {
+                let Some(req) = store.get_requirement_by_id_mut(&req_id) else {
+                    return;
+                };
+                // Leave descriptions edited while the AI was working alone
+                if req.description != original {
+                    return;
+                }
+                let change = Requirement::field_change(
+                    "description",
+                    original.clone(),
+                    response.improved_description.clone(),
+                );
+                req.description = response.improved_description.clone();
+                req.modified_at = chrono::Utc::now();
+                req.record_change(user.clone(), vec![change]);
+                replaced = true;
+            })?;
+            if !replaced {
+                anyhow::bail!(
+                    "The description of {} changed while waiting for the AI; run the command again",
+                    spec_id
+                );
+            }
+            ai_status(
+                format,
+                format!("{} Description of {} updated", "✓".green(), spec_id),
+            );
+        }
+        AiCommand::Children { apply, yes, .. } => {
+            let children = client.generate_children(req, &store)?.suggested_children;
+            if !format.is_table() {
+                print_records(format, &children)?;
+            } else if children.is_empty() {
+                println!("{}", "No child requirements suggested.".green());
+            } else {
+                println!("{} for {}:", "Suggested children".cyan(), spec_id);
+                for (i, child) in children.iter().enumerate() {
+                    println!("\n  {}. {} [{}]", i + 1, child.title.bold(), child.req_type);
+                    for line in child.description.lines() {
+                        println!("     {}", line);
+                    }
+                    println!("     {}: {}", "Rationale".blue(), child.rationale);
+                }
+            }
+            if !apply || children.is_empty() {
+                return Ok(());
+            }
+
+            let labels = children
+                .iter()
+                .map(|c| format!("{} [{}]", c.title, c.req_type))
+                .collect();
+            let chosen = choose_suggestions(labels, *yes)?;
+            let feature = req.feature.clone();
+            let mut added = Vec::new();
+            let mut unlinked = Vec::new();
+            let store = storage.update_atomically(|store| {
+                for &i in &chosen {
+                    let child = &children[i];
+                    let mut new_req =
+                        Requirement::new(child.title.clone(), child.description.clone());
+                    if let Ok(req_type) = parse_type(&child.req_type) {
+                        new_req.req_type = req_type;
+                    }
+                    new_req.feature = feature.clone();
+                    new_req.created_by = Some(user.clone());
+                    let child_id = new_req.id;
+
+                    let feature_prefix = store
+                        .get_feature_by_name(&new_req.feature)
+                        .map(|f| f.prefix.clone());
+                    let type_prefix = store.get_type_prefix(&new_req.req_type);
+                    store.add_requirement_with_id(
+                        new_req,
+                        feature_prefix.as_deref(),
+                        type_prefix.as_deref(),
+                    );
+                    added.push(child_id);
+                    if let Err(e) = store.add_relationship_with_creator(
+                        &child_id,
+                        RelationshipType::Parent,
+                        &req_id,
+                        true,
+                        Some(user.clone()),
+                    ) {
+                        unlinked.push((child_id, e.to_string()));
+                    }
+                }
+            })?;
+            for r in store.requirements.iter().filter(|r| added.contains(&r.id)) {
+                ai_status(
+                    format,
+                    format!(
+                        "{} {} {} (child of {})",
+                        "✓".green(),
+                        r.spec_id.as_deref().unwrap_or("N/A").green(),
+                        r.title,
+                        spec_id
+                    ),
+                );
+            }
+            for (child_id, error) in &unlinked {
+                let child = store
+                    .get_requirement_by_id(child_id)
+                    .and_then(|r| r.spec_id.as_deref())
+                    .unwrap_or("N/A");
+                eprintln!(
+                    "{} {} was created but could not be linked to {}: {}",
+                    "Warning:".yellow(),
+                    child,
+                    spec_id,
+                    error
+                );
+            }
+        }
+        AiCommand::Scan { .. } => unreachable!("handled above"),
+    }
+
+    Ok(())
+}
+
+/// Evaluates the requirements that were never evaluated or changed since
+fn ai_scan(
+    storage: &Storage,
+    store: &RequirementsStore,
+    limit: Option<usize>,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let pending: Vec<&Requirement> = store
+        .requirements
+        .iter()
+        .filter(|r| !r.archived && r.needs_ai_evaluation())
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    if pending.is_empty() {
+        ai_status(
+            format,
+            "All requirements have up-to-date evaluations.".green(),
+        );
+        return Ok(());
+    }
+    if dry_run {
+        ai_status(
+            format,
+            format!("{} requirement(s) need evaluation:", pending.len()),
+        );
+        for req in &pending {
+            ai_status(
+                format,
+                format!(
+                    "  {} - {}",
+                    req.spec_id.as_deref().unwrap_or("N/A").yellow(),
+                    req.title
+                ),
+            );
+        }
+        return Ok(());
+    }
+
+    let client = ai_client()?;
+    let mut records = Vec::new();
+    let mut failed = 0;
+    for (i, req) in pending.iter().enumerate() {
+        let spec_id = req.spec_id.clone().unwrap_or_else(|| req.id.to_string());
+        ai_status(
+            format,
+            format!("[{}/{}] Evaluating {}...", i + 1, pending.len(), spec_id).dimmed(),
+        );
+        let response = match client.evaluate_requirement(req, store) {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!("{} {}: {}", "✗".red(), spec_id, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        // Save after each one so an interrupted scan keeps its progress
+        let stored = StoredAiEvaluation::new(response, req.content_hash());
+        let saved = stored.clone();
+        storage.update_atomically(|store| {
+            if let Some(req) = store.get_requirement_by_id_mut(&req.id) {
+                req.ai_evaluation = Some(saved);
+            }
+        })?;
+        if format.is_table() {
+            println!(
+                "  {} {}/10 {}",
+                spec_id.yellow(),
+                stored.evaluation.quality_score,
+                req.title
+            );
+        }
+        records.push(AiEvaluationRecord::new(req, &stored));
+    }
+
+    if !format.is_table() {
+        print_records(format, &records)?;
+    }
+    ai_status(
+        format,
+        format!(
+            "{} evaluated, {} failed",
+            records.len().to_string().green(),
+            failed
+        ),
+    );
+    Ok(())
+}
+
+/// Prints an evaluation for people
+fn print_ai_evaluation(spec_id: &str, title: &str, evaluation: &EvaluationResponse) {
+    let score = format!("{}/10", evaluation.quality_score);
+    let score = match evaluation.quality_score {
+        8.. => score.green(),
+        5..=7 => score.yellow(),
+        _ => score.red(),
+    };
+    println!("{} {} - {}", spec_id.yellow(), title, score.bold());
+
+    if !evaluation.strengths.is_empty() {
+        println!("\n{}:", "Strengths".green());
+        for strength in &evaluation.strengths {
+            println!("  • {}", strength);
+        }
+    }
+    if !evaluation.issues.is_empty() {
+        println!("\n{}:", "Issues".red());
+        for issue in &evaluation.issues {
+            println!(
+                "  • [{}] {}: {}",
+                issue.severity, issue.issue_type, issue.text
+            );
+            println!("    {} {}", "→".blue(), issue.suggestion);
+        }
+    }
+    if let Some(improvement) = &evaluation.suggested_improvements {
+        println!("\n{}: {}", "Suggestion".cyan(), improvement.rationale);
+        if let Some(description) = &improvement.description {
+            println!("{}", description);
+        }
+    }
+}
+
+fn handle_relationship_command(
+    cmd: &RelationshipCommand,
+    storage: &Storage,
+    format: OutputFormat,
+) -> Result<()> {
+    match cmd {
+        RelationshipCommand::Add {
+            from,
+            to,
+            r#type,
+            bidirectional,
+        } => {
+            add_relationship(storage, from, to, r#type, *bidirectional)?;
+        }
+        RelationshipCommand::Remove {
+            from,
+            to,
+            r#type,
+            bidirectional,
+        } => {
+            remove_relationship(storage, from, to, r#type, *bidirectional)?;
+        }
+        RelationshipCommand::List { id } => {
+            list_relationships(storage, id, format)?;
+        }
+    }
+    Ok(())
+}
+
+fn add_relationship(
+    storage: &Storage,
+    from_str: &str,
+    to_str: &str,
+    rel_type_str: &str,
+    bidirectional: bool,
+) -> Result<()> {
+    // Load requirements
+    let mut store = storage.load()?;
+
+    // Parse source and target IDs
+    let from_id = parse_requirement_id(from_str, &store)?;
+    let to_id = parse_requirement_id(to_str, &store)?;
+
+    // Parse relationship type
+    let rel_type = RelationshipType::from_str(rel_type_str);
+
+    // Get requirement info for display (clone the data we need)
+    let from_req = store
+        .get_requirement_by_id(&from_id)
+        .ok_or_else(|| CliError::NotFound(String::from("Source requirement not found")))?;
+    let to_req = store
+        .get_requirement_by_id(&to_id)
+        .ok_or_else(|| CliError::NotFound(String::from("Target requirement not found")))?;
+
+    let from_spec = from_req
+        .spec_id
+        .clone()
+        .unwrap_or_else(|| "N/A".to_string());
+    let from_title = from_req.title.clone();
+    let to_spec = to_req.spec_id.clone().unwrap_or_else(|| "N/A".to_string());
+    let to_title = to_req.title.clone();
+
+    // Add the relationship
+    store.add_relationship(&from_id, rel_type.clone(), &to_id, bidirectional)?;
+
+    // Save
+    storage.save(&store)?;
+
+    println!("{}", "Relationship added successfully!".green());
+    println!(
+        "  {} ({}) {} {} ({})",
+        from_spec,
+        from_title,
+        "->".blue(),
+        to_spec,
+        to_title
+    );
+    println!("  Relationship: {}", rel_type.to_string().cyan());
+
+    if bidirectional {
+        if let Some(inverse) = rel_type.inverse() {
+            println!("  {} (bidirectional)", inverse.to_string().cyan());
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_relationship(
+    storage: &Storage,
+    from_str: &str,
+    to_str: &str,
+    rel_type_str: &str,
+    bidirectional: bool,
+) -> Result<()> {
+    // Load requirements
+    let mut store = storage.load()?;
+
+    // Parse source and target IDs
+    let from_id = parse_requirement_id(from_str, &store)?;
+    let to_id = parse_requirement_id(to_str, &store)?;
+
+    // Parse relationship type
     let rel_type = RelationshipType::from_str(rel_type_str);
 
-    // Get requirement info for display (clone the data we need)
-    let from_req = store
-        .get_requirement_by_id(&from_id)
-        .ok_or_else(|| anyhow::anyhow!("Source requirement not found"))?;
-    let to_req = store
-        .get_requirement_by_id(&to_id)
-        .ok_or_else(|| anyhow::anyhow!("Target requirement not found"))?;
+    // Remove the relationship
+    store.remove_relationship(&from_id, &rel_type, &to_id, bidirectional)?;
+
+    // Save
+    storage.save(&store)?;
+
+    println!("{}", "Relationship removed successfully!".green());
+    println!("  Relationship: {}", rel_type.to_string().cyan());
+
+    if bidirectional {
+        if let Some(inverse) = rel_type.inverse() {
+            println!("  {} (bidirectional)", inverse.to_string().cyan());
+        }
+    }
+
+    Ok(())
+}
+
+fn list_relationships(storage: &Storage, id_str: &str, format: OutputFormat) -> Result<()> {
+    // Load requirements
+    let store = storage.load()?;
+
+    // Parse ID
+    let id = parse_requirement_id(id_str, &store)?;
+
+    // Get requirement
+    let req = store
+        .get_requirement_by_id(&id)
+        .ok_or_else(|| CliError::NotFound(format!("Requirement not found: {}", id_str)))?;
+
+    if !format.is_table() {
+        return print_records(format, &RelationshipRecord::for_requirement(&store, req));
+    }
+
+    println!("{}: {}", "Requirement".blue(), req.title);
+    if let Some(spec_id) = &req.spec_id {
+        println!("{}: {}", "SPEC-ID".blue(), spec_id);
+    }
+    println!("{}: {}", "UUID".blue(), req.id);
+    println!();
+
+    if req.relationships.is_empty() {
+        println!("{}", "No relationships found.".yellow());
+        return Ok(());
+    }
+
+    println!("{}:", "Relationships".green());
+    for relationship in &req.relationships {
+        let target = store.get_requirement_by_id(&relationship.target_id);
+        if let Some(target_req) = target {
+            let target_spec = target_req.spec_id.as_deref().unwrap_or("N/A");
+
+            // Format the relationship description based on type
+            let description = match &relationship.rel_type {
+                RelationshipType::Parent => format!("is parent of"),
+                RelationshipType::Child => format!("is child of"),
+                RelationshipType::Duplicate => format!("is duplicate of"),
+                RelationshipType::Verifies => format!("verifies"),
+                RelationshipType::VerifiedBy => format!("is verified by"),
+                RelationshipType::References => format!("references"),
+                RelationshipType::Custom(name) => format!("{}", name),
+            };
+
+            println!(
+                "  {} {} ({}) - {}",
+                description.cyan(),
+                target_spec.yellow(),
+                target_req.id.to_string().dimmed(),
+                target_req.title
+            );
+        } else {
+            println!(
+                "  {} {} {}",
+                relationship.rel_type.to_string().cyan(),
+                relationship.target_id.to_string().yellow(),
+                "(requirement not found)".red()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle test case and test run subcommands
+fn handle_test_command(cmd: &TestCommand, storage: &Storage) -> Result<()> {
+    let mut store = storage.load()?;
+    let default_author = || std::env::var("USER").unwrap_or_else(|_| String::from("Unknown"));
+
+    match cmd {
+        TestCommand::Add {
+            title,
+            verifies,
+            description,
+            preconditions,
+            steps,
+            expected,
+            automated,
+            author,
+        } => {
+            let mut case = TestCase::new(title.clone())
+                .with_created_by(author.clone().unwrap_or_else(default_author));
+            if let Some(description) = description {
+                case = case.with_description(description.clone());
+            }
+            if let Some(preconditions) = preconditions {
+                case = case.with_preconditions(preconditions.clone());
+            }
+            for step in steps {
+                let (action, expected) = step.split_once("=>").unwrap_or((step.as_str(), ""));
+                case = case.with_step(action.trim(), expected.trim());
+            }
+            if let Some(expected) = expected {
+                case = case.with_expected_result(expected.clone());
+            }
+            if let Some(automated) = automated {
+                case = case.with_automated_test(automated.clone());
+            }
+            if let Some(verifies) = verifies {
+                for id in verifies.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                    case = case.with_verifies(parse_requirement_id(id, &store)?);
+                }
+            }
+
+            let spec_id = store.add_test_case(case);
+            storage.save(&store)?;
+            println!("{} Added test case {}", "✓".green(), spec_id.cyan());
+        }
+        TestCommand::List {
+            requirement,
+            archived,
+        } => {
+            let req_filter = requirement
+                .as_deref()
+                .map(|id| parse_requirement_id(id, &store))
+                .transpose()?;
+            let cases: Vec<&TestCase> = store
+                .test_cases
+                .iter()
+                .filter(|tc| *archived || !tc.archived)
+                .filter(|tc| req_filter.is_none_or(|id| tc.verifies.contains(&id)))
+                .collect();
+
+            if cases.is_empty() {
+                println!("{}", "No test cases found.".yellow());
+                return Ok(());
+            }
+
+            println!(
+                "{:<8} | {:<40} | {:<10} | {:<20}",
+                "ID", "Title", "Latest", "Verifies"
+            );
+            println!("{}", "-".repeat(90));
+            for case in cases {
+                let outcome = store
+                    .latest_test_result(&case.id)
+                    .map(|(_, result)| result.outcome)
+                    .unwrap_or_default();
+                let verifies: Vec<String> = case
+                    .verifies
+                    .iter()
+                    .map(|id| requirement_label(&store, id))
+                    .collect();
+                println!(
+                    "{:<8} | {:<40} | {:<10} | {}",
+                    case.spec_id,
+                    case.title,
+                    colored_outcome(outcome),
+                    verifies.join(", ")
+                );
+            }
+        }
+        TestCommand::Show { id } => {
+            let case = find_test_case(&store, id)?;
+            println!("{} - {}", case.spec_id.cyan(), case.title.bold());
+            if !case.description.is_empty() {
+                println!("\n{}", case.description);
+            }
+            if !case.preconditions.is_empty() {
+                println!("\n{}:\n  {}", "Preconditions".green(), case.preconditions);
+            }
+            if !case.steps.is_empty() {
+                println!("\n{}:", "Steps".green());
+                for (i, step) in case.steps.iter().enumerate() {
+                    println!("  {}. {}", i + 1, step.action);
+                    if !step.expected.is_empty() {
+                        println!("     {} {}", "→".dimmed(), step.expected);
+                    }
+                }
+            }
+            if !case.expected_result.is_empty() {
+                println!(
+                    "\n{}:\n  {}",
+                    "Expected Result".green(),
+                    case.expected_result
+                );
+            }
+            if let Some(automated) = &case.automated_test {
+                println!("\n{} {}", "Automated test:".green(), automated);
+            }
+            if !case.verifies.is_empty() {
+                println!("\n{}:", "Verifies".green());
+                for req_id in &case.verifies {
+                    let title = store
+                        .get_requirement_by_id(req_id)
+                        .map(|r| r.title.as_str())
+                        .unwrap_or("(missing)");
+                    println!("  {} {}", requirement_label(&store, req_id), title);
+                }
+            }
+
+            let mut results: Vec<_> = store
+                .test_runs
+                .iter()
+                .filter_map(|run| run.result_for(&case.id).map(|result| (run, result)))
+                .collect();
+            results.sort_by_key(|(run, _)| std::cmp::Reverse(run.executed_at));
+            if !results.is_empty() {
+                println!("\n{}:", "Results".green());
+                for (run, result) in results.iter().take(10) {
+                    println!(
+                        "  {} {} {} {}{}",
+                        run.executed_at.format("%Y-%m-%d %H:%M"),
+                        run.spec_id.cyan(),
+                        colored_outcome(result.outcome),
+                        run_context(run),
+                        result
+                            .notes
+                            .as_deref()
+                            .map(|n| format!(" - {}", n))
+                            .unwrap_or_default()
+                    );
+                }
+            }
+        }
+        TestCommand::Link { id, requirement } => {
+            let req_id = parse_requirement_id(requirement, &store)?;
+            let case_id = find_test_case(&store, id)?.id;
+            let case = store
+                .test_cases
+                .iter_mut()
+                .find(|tc| tc.id == case_id)
+                .ok_or_else(|| CliError::NotFound(String::from("Test case not found")))?;
+            if case.verifies.contains(&req_id) {
+                println!("{} already verifies {}", case.spec_id, requirement);
+                return Ok(());
+            }
+            case.verifies.push(req_id);
+            let spec_id = case.spec_id.clone();
+            storage.save(&store)?;
+            println!(
+                "{} {} now verifies {}",
+                "✓".green(),
+                spec_id.cyan(),
+                requirement
+            );
+        }
+        TestCommand::Unlink { id, requirement } => {
+            let req_id = parse_requirement_id(requirement, &store)?;
+            let case_id = find_test_case(&store, id)?.id;
+            let case = store
+                .test_cases
+                .iter_mut()
+                .find(|tc| tc.id == case_id)
+                .ok_or_else(|| CliError::NotFound(String::from("Test case not found")))?;
+            if !case.verifies.contains(&req_id) {
+                anyhow::bail!("{} does not verify {}", case.spec_id, requirement);
+            }
+            case.verifies.retain(|id| *id != req_id);
+            let spec_id = case.spec_id.clone();
+            storage.save(&store)?;
+            println!(
+                "{} {} no longer verifies {}",
+                "✓".green(),
+                spec_id.cyan(),
+                requirement
+            );
+        }
+        TestCommand::Remove { id } => {
+            let case = find_test_case(&store, id)?;
+            let (case_id, spec_id) = (case.id, case.spec_id.clone());
+            store.remove_test_case(&case_id);
+            storage.save(&store)?;
+            println!("{} Removed test case {}", "✓".green(), spec_id);
+        }
+        TestCommand::Run {
+            name,
+            environment,
+            build,
+            results,
+            by,
+        } => {
+            let mut run = TestRun::new(name.clone())
+                .with_executed_by(by.clone().unwrap_or_else(default_author));
+            if let Some(environment) = environment {
+                run = run.with_environment(environment.clone());
+            }
+            if let Some(build) = build {
+                run = run.with_build(build.clone());
+            }
+            for entry in results {
+                let Some((case_ref, outcome_str)) = entry.split_once('=') else {
+                    anyhow::bail!("Invalid result '{}': expected TC-ID=outcome", entry);
+                };
+                let outcome = TestOutcome::parse(outcome_str).with_context(|| {
+                    format!(
+                        "Invalid outcome '{}'. Use passed, failed, blocked or skipped",
+                        outcome_str
+                    )
+                })?;
+                let case_id = find_test_case(&store, case_ref.trim())?.id;
+                run.record(case_id, outcome);
+            }
+
+            let summary = run.summary();
+            let spec_id = store.add_test_run(run);
+            storage.save(&store)?;
+            println!(
+                "{} Recorded test run {}: {} passed, {} failed, {} blocked, {} skipped",
+                "✓".green(),
+                spec_id.cyan(),
+                summary.passed,
+                summary.failed,
+                summary.blocked,
+                summary.skipped
+            );
+        }
+        TestCommand::Runs { id: Some(id) } => {
+            let run = store
+                .find_test_run(id)
+                .ok_or_else(|| CliError::NotFound(format!("Test run not found: {}", id)))?;
+            println!("{} - {}", run.spec_id.cyan(), run.name.bold());
+            println!(
+                "Executed: {}{}",
+                run.executed_at.format("%Y-%m-%d %H:%M"),
+                run.executed_by
+                    .as_deref()
+                    .map(|by| format!(" by {}", by))
+                    .unwrap_or_default()
+            );
+            if let Some(environment) = &run.environment {
+                println!("Environment: {}", environment);
+            }
+            if let Some(build) = &run.build {
+                println!("Build: {}", build);
+            }
+            println!();
+            for result in &run.results {
+                let case = store.test_cases.iter().find(|tc| tc.id == result.case_id);
+                println!(
+                    "  {} {} {}{}",
+                    colored_outcome(result.outcome),
+                    case.map(|tc| tc.spec_id.as_str())
+                        .unwrap_or("(removed)")
+                        .cyan(),
+                    case.map(|tc| tc.title.as_str()).unwrap_or(""),
+                    result
+                        .notes
+                        .as_deref()
+                        .map(|n| format!(" - {}", n))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        TestCommand::Runs { id: None } => {
+            if store.test_runs.is_empty() {
+                println!("{}", "No test runs recorded.".yellow());
+                return Ok(());
+            }
+            let mut runs: Vec<&TestRun> = store.test_runs.iter().collect();
+            runs.sort_by_key(|run| std::cmp::Reverse(run.executed_at));
+            for run in runs {
+                let summary = run.summary();
+                println!(
+                    "{:<8} {} {:<30} {} ✓{} ✗{} ⊘{}",
+                    run.spec_id.cyan(),
+                    run.executed_at.format("%Y-%m-%d %H:%M"),
+                    run.name,
+                    run_context(run),
+                    summary.passed,
+                    summary.failed,
+                    summary.blocked
+                );
+            }
+        }
+        TestCommand::Coverage { feature, uncovered } => {
+            let mut report = store.coverage_report();
+            if let Some(feature) = feature {
+                report.entries.retain(|e| e.feature == *feature);
+            }
+
+            println!(
+                "{:<10} | {:<40} | {:<10} | {:>5} | {:>6} | {:>6} | {:>7}",
+                "SPEC-ID", "Title", "Status", "Tests", "Passed", "Failed", "Blocked"
+            );
+            println!("{}", "-".repeat(100));
+            for entry in &report.entries {
+                let s = &entry.summary;
+                if *uncovered && s.total > 0 {
+                    continue;
+                }
+                println!(
+                    "{:<10} | {:<40} | {:<10} | {:>5} | {:>6} | {:>6} | {:>7}",
+                    entry.spec_id.as_deref().unwrap_or("N/A"),
+                    entry.title,
+                    colored_verification_status(s.status()),
+                    s.total,
+                    s.passed,
+                    s.failed,
+                    s.blocked
+                );
+            }
+            println!(
+                "\n{} of {} requirements covered by tests ({:.1}%), {} passing, {} failing, {} blocked",
+                report.covered(),
+                report.entries.len(),
+                report.coverage_percent(),
+                report.passing(),
+                report.count(VerificationStatus::Failing),
+                report.count(VerificationStatus::Blocked)
+            );
+        }
+    }
 
-    let from_spec = from_req
-        .spec_id
-        .clone()
-        .unwrap_or_else(|| "N/A".to_string());
-    let from_title = from_req.title.clone();
-    let to_spec = to_req.spec_id.clone().unwrap_or_else(|| "N/A".to_string());
-    let to_title = to_req.title.clone();
+    Ok(())
+}
 
-    // Add the relationship
-    store.add_relationship(&from_id, rel_type.clone(), &to_id, bidirectional)?;
+/// Handle code traceability subcommands
+fn handle_trace_command(cmd: &TraceCommand, storage: &Storage) -> Result<()> {
+    match cmd {
+        TraceCommand::Scan {
+            path,
+            requirement,
+            verbose,
+        } => {
+            let store = storage.load()?;
+            let root = match path {
+                Some(path) => path.clone(),
+                None => storage.project_dir().to_path_buf(),
+            };
+            let index = TraceIndex::scan(&root)?;
+            for warning in &index.warnings {
+                eprintln!("{} skipped {}", "Warning:".yellow(), warning);
+            }
 
-    // Save
-    storage.save(&store)?;
+            if let Some(spec_id) = requirement {
+                let markers = index.markers_for(spec_id);
+                if markers.is_empty() {
+                    println!("{}", format!("No trace markers for {}.", spec_id).yellow());
+                }
+                for marker in markers {
+                    println!("  {}{}", marker.location(), marker_ai_suffix(marker));
+                }
+                return Ok(());
+            }
 
-    println!("{}", "Relationship added successfully!".green());
-    println!(
-        "  {} ({}) {} {} ({})",
-        from_spec,
-        from_title,
-        "->".blue(),
-        to_spec,
-        to_title
-    );
-    println!("  Relationship: {}", rel_type.to_string().cyan());
+            println!(
+                "Scanned {} files in {}: {} markers",
+                index.files_scanned,
+                root.display(),
+                index.markers.len()
+            );
 
-    if bidirectional {
-        if let Some(inverse) = rel_type.inverse() {
-            println!("  {} (bidirectional)", inverse.to_string().cyan());
+            println!("\n{}:", "Implemented requirements".green());
+            for (spec_id, markers) in index.by_requirement() {
+                let Some(req) = store.requirements.iter().find(|r| {
+                    r.spec_id
+                        .as_deref()
+                        .is_some_and(|s| s.eq_ignore_ascii_case(&spec_id))
+                }) else {
+                    continue;
+                };
+                let files: std::collections::BTreeSet<_> =
+                    markers.iter().map(|m| &m.file).collect();
+                println!(
+                    "  {:<12} {} ({} markers in {} files)",
+                    spec_id.cyan(),
+                    req.title,
+                    markers.len(),
+                    files.len()
+                );
+                if *verbose {
+                    for marker in markers {
+                        println!("      {}{}", marker.location(), marker_ai_suffix(marker));
+                    }
+                }
+            }
+
+            let report = index.report(&store);
+            if !report.unknown.is_empty() {
+                println!("\n{}:", "Unknown requirement IDs".yellow());
+                for marker in &report.unknown {
+                    println!("  {:<12} {}", marker.spec_id.red(), marker.location());
+                }
+            }
+            if !report.unimplemented.is_empty() {
+                println!(
+                    "\n{}:",
+                    "Approved requirements with no implementation".yellow()
+                );
+                for req_id in &report.unimplemented {
+                    if let Some(req) = store.get_requirement_by_id(req_id) {
+                        println!(
+                            "  {:<12} {}",
+                            req.spec_id.as_deref().unwrap_or("N/A"),
+                            req.title
+                        );
+                    }
+                }
+            }
+
+            let c = report.confidence;
+            let percent = |n: usize| {
+                if c.total() == 0 {
+                    0.0
+                } else {
+                    n as f64 * 100.0 / c.total() as f64
+                }
+            };
+            println!("\n{}:", "AI vs human".green());
+            println!("  AI (high):    {:>5}", c.ai_high);
+            println!("  AI (med):     {:>5}", c.ai_medium);
+            println!("  AI (low):     {:>5}", c.ai_low);
+            if c.ai_unspecified > 0 {
+                println!("  AI (unrated): {:>5}", c.ai_unspecified);
+            }
+            println!("  Human:        {:>5}", c.human);
+            println!(
+                "  {:.1}% AI-assisted, {:.1}% human",
+                percent(c.ai()),
+                percent(c.human)
+            );
+            println!(
+                "\n{} requirements implemented, {} unknown references, {} approved without code",
+                report.implemented,
+                report.unknown.len(),
+                report.unimplemented.len()
+            );
         }
     }
 
     Ok(())
 }
 
-fn remove_relationship(
+/// " (ai:tool:confidence)" suffix for a marker, empty for human-written code
+fn marker_ai_suffix(marker: &TraceMarker) -> String {
+    match (&marker.ai_tool, marker.confidence) {
+        (Some(tool), Some(confidence)) => format!(" (ai:{}:{})", tool, confidence),
+        (Some(tool), None) => format!(" (ai:{})", tool),
+        (None, _) => String::new(),
+    }
+}
+
+fn handle_git_command(cmd: &GitCommand, storage: &Storage) -> Result<()> {
+    match cmd {
+        GitCommand::Sync { repo, full } => {
+            let mut store = storage.load()?;
+            let repo_dir = match repo {
+                Some(path) => path.clone(),
+                None => storage.project_dir().to_path_buf(),
+            };
+
+            let result = store.sync_git_commits(&repo_dir, *full)?;
+            storage.save(&store)?;
+
+            println!(
+                "Scanned {} new commits in {}",
+                result.commits_scanned,
+                repo_dir.display()
+            );
+            if result.linked.is_empty() {
+                println!("{}", "No new commit links.".yellow());
+            } else {
+                println!("\n{}:", "Linked commits".green());
+                for (req_id, commit) in &result.linked {
+                    println!(
+                        "  {:<12} {} {}",
+                        requirement_label(&store, req_id).cyan(),
+                        commit.short_sha(),
+                        commit.subject
+                    );
+                }
+            }
+            print_completed_without_commits(&store);
+        }
+        GitCommand::Report => {
+            let store = storage.load()?;
+            match &store.git_sync {
+                Some(sync) => println!(
+                    "Last sync: {} at commit {}",
+                    sync.synced_at.format("%Y-%m-%d %H:%M"),
+                    &sync.last_commit[..sync.last_commit.len().min(8)]
+                ),
+                None => println!("{}", "Never synced. Run 'aida git sync'.".yellow()),
+            }
+
+            let mut linked: Vec<_> = store
+                .requirements
+                .iter()
+                .filter(|r| !r.archived && !r.commits.is_empty())
+                .collect();
+            linked.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+            println!("\n{}:", "Requirements with commits".green());
+            for req in linked {
+                println!(
+                    "  {:<12} {} ({} commits)",
+                    req.spec_id.as_deref().unwrap_or("N/A").cyan(),
+                    req.title,
+                    req.commits.len()
+                );
+            }
+            print_completed_without_commits(&store);
+        }
+    }
+
+    Ok(())
+}
+
+/// Flags Completed requirements that have no linked commits
+fn print_completed_without_commits(store: &RequirementsStore) {
+    let missing = store.completed_without_commits();
+    if missing.is_empty() {
+        return;
+    }
+    println!("\n{}:", "Completed requirements with no commits".yellow());
+    for req in missing {
+        println!(
+            "  {:<12} {}",
+            req.spec_id.as_deref().unwrap_or("N/A"),
+            req.title
+        );
+    }
+}
+
+/// Run the local REST API until interrupted
+fn serve_api(storage: &Storage, addr: &str, user: Option<&str>, allow_remote: bool) -> Result<()> {
+    if !aida_core::server::is_loopback_addr(addr) {
+        if !allow_remote {
+            return Err(CliError::Validation(format!(
+                "{} is reachable from other machines and the API has no authentication; pass --allow-remote to serve it anyway",
+                addr
+            ))
+            .into());
+        }
+        println!(
+            "{}",
+            format!(
+                "Warning: {} is reachable from other machines; the API has no authentication.",
+                addr
+            )
+            .yellow()
+        );
+    }
+    println!(
+        "Serving {} at {}",
+        storage.path().display(),
+        format!("http://{}/api", addr).green()
+    );
+    println!("OpenAPI description: http://{}/api/openapi.json", addr);
+    println!("Press Ctrl+C to stop.");
+    aida_core::server::serve(storage.path(), addr, user, allow_remote)
+}
+
+/// Import JUnit XML results as a test run
+fn import_test_results(
     storage: &Storage,
-    from_str: &str,
-    to_str: &str,
-    rel_type_str: &str,
-    bidirectional: bool,
+    file: &std::path::Path,
+    name: Option<&str>,
+    environment: Option<&str>,
+    build: Option<&str>,
+    by: Option<&str>,
+    dry_run: bool,
 ) -> Result<()> {
-    // Load requirements
+    let report = JUnitReport::load(file)?;
     let mut store = storage.load()?;
 
-    // Parse source and target IDs
-    let from_id = parse_requirement_id(from_str, &store)?;
-    let to_id = parse_requirement_id(to_str, &store)?;
-
-    // Parse relationship type
-    let rel_type = RelationshipType::from_str(rel_type_str);
-
-    // Remove the relationship
-    store.remove_relationship(&from_id, &rel_type, &to_id, bidirectional)?;
+    let name = name.map(str::to_string).unwrap_or_else(|| {
+        file.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "JUnit import".to_string())
+    });
+    let by = by
+        .map(str::to_string)
+        .unwrap_or_else(|| std::env::var("USER").unwrap_or_else(|_| String::from("Unknown")));
+    let mut run = TestRun::new(name).with_executed_by(by);
+    if let Some(environment) = environment {
+        run = run.with_environment(environment);
+    }
+    if let Some(build) = build {
+        run = run.with_build(build);
+    }
 
-    // Save
-    storage.save(&store)?;
+    let import = store.import_junit_results(&report, run);
 
-    println!("{}", "Relationship removed successfully!".green());
-    println!("  Relationship: {}", rel_type.to_string().cyan());
+    println!(
+        "Read {} tests from {}: {} mapped, {} unmapped",
+        report.cases.len(),
+        file.display(),
+        import.recorded.len(),
+        import.unmapped_tests.len()
+    );
+    for (spec_id, outcome) in &import.recorded {
+        let case = store.find_test_case(spec_id);
+        let verifies: Vec<String> = case
+            .map(|tc| {
+                tc.verifies
+                    .iter()
+                    .map(|id| requirement_label(&store, id))
+                    .collect()
+            })
+            .unwrap_or_default();
+        println!(
+            "  {} {} {} → {}",
+            colored_outcome(*outcome),
+            spec_id.cyan(),
+            case.and_then(|tc| tc.automated_test.as_deref())
+                .unwrap_or(""),
+            verifies.join(", ")
+        );
+    }
+    if !import.created_cases.is_empty() {
+        if dry_run {
+            println!(
+                "\nWould create {} test case(s): {}",
+                import.created_cases.len(),
+                import.created_cases.join(", ")
+            );
+        } else {
+            println!(
+                "\n{} Created {} test case(s): {}",
+                "✓".green(),
+                import.created_cases.len(),
+                import.created_cases.join(", ")
+            );
+        }
+    }
+    if !import.unmapped_tests.is_empty() {
+        println!("\n{}", "Tests not mapped to any requirement:".yellow());
+        for test in &import.unmapped_tests {
+            println!("  {}", test);
+        }
+    }
+    if !import.unknown_references.is_empty() {
+        println!("\n{}", "Unknown requirement references:".yellow());
+        for (test, reference) in &import.unknown_references {
+            println!("  {} (in {})", reference, test);
+        }
+    }
+    if !import.missing_results.is_empty() {
+        println!(
+            "\n{}",
+            "Verified requirements with no result in this run:".yellow()
+        );
+        for req_id in &import.missing_results {
+            let title = store
+                .get_requirement_by_id(req_id)
+                .map(|r| r.title.as_str())
+                .unwrap_or("");
+            println!("  {} {}", requirement_label(&store, req_id), title);
+        }
+    }
 
-    if bidirectional {
-        if let Some(inverse) = rel_type.inverse() {
-            println!("  {} (bidirectional)", inverse.to_string().cyan());
+    if dry_run {
+        println!("\n{}", "Dry run - nothing saved.".yellow());
+        return Ok(());
+    }
+    match &import.run_spec_id {
+        Some(run_id) => {
+            storage.save(&store)?;
+            println!("\n{} Recorded test run {}", "✓".green(), run_id.cyan());
         }
+        None => println!(
+            "\n{}",
+            "No tests mapped to requirements; nothing recorded.".yellow()
+        ),
     }
 
     Ok(())
 }
 
-fn list_relationships(storage: &Storage, id_str: &str) -> Result<()> {
-    // Load requirements
-    let store = storage.load()?;
+/// Finds a test case by spec_id or UUID
+fn find_test_case<'a>(store: &'a RequirementsStore, id: &str) -> Result<&'a TestCase> {
+    store
+        .find_test_case(id)
+        .ok_or_else(|| CliError::NotFound(format!("Test case not found: {}", id)).into())
+}
 
-    // Parse ID
-    let id = parse_requirement_id(id_str, &store)?;
+/// SPEC-ID of a requirement, falling back to its UUID
+fn requirement_label(store: &RequirementsStore, id: &Uuid) -> String {
+    store
+        .get_requirement_by_id(id)
+        .and_then(|r| r.spec_id.clone())
+        .unwrap_or_else(|| id.to_string())
+}
 
-    // Get requirement
-    let req = store
-        .get_requirement_by_id(&id)
-        .ok_or_else(|| anyhow::anyhow!("Requirement not found"))?;
+/// Build and environment of a run, e.g. "[build 1.2 @ staging]"
+fn run_context(run: &TestRun) -> String {
+    match (&run.build, &run.environment) {
+        (Some(build), Some(env)) => format!("[build {} @ {}]", build, env),
+        (Some(build), None) => format!("[build {}]", build),
+        (None, Some(env)) => format!("[{}]", env),
+        (None, None) => String::new(),
+    }
+}
 
-    println!("{}: {}", "Requirement".blue(), req.title);
-    if let Some(spec_id) = &req.spec_id {
-        println!("{}: {}", "SPEC-ID".blue(), spec_id);
+fn colored_outcome(outcome: TestOutcome) -> colored::ColoredString {
+    let text = format!("{} {}", outcome.symbol(), outcome);
+    match outcome {
+        TestOutcome::Passed => text.green(),
+        TestOutcome::Failed => text.red(),
+        TestOutcome::Blocked => text.yellow(),
+        TestOutcome::Skipped | TestOutcome::NotRun => text.dimmed(),
     }
-    println!("{}: {}", "UUID".blue(), req.id);
-    println!();
+}
 
-    if req.relationships.is_empty() {
-        println!("{}", "No relationships found.".yellow());
-        return Ok(());
+fn colored_verification_status(status: VerificationStatus) -> colored::ColoredString {
+    let text = status.to_string();
+    match status {
+        VerificationStatus::Passing => text.green(),
+        VerificationStatus::Failing => text.red(),
+        VerificationStatus::Blocked | VerificationStatus::Partial => text.yellow(),
+        VerificationStatus::NotRun | VerificationStatus::Unverified => text.dimmed(),
     }
+}
 
-    println!("{}:", "Relationships".green());
-    for relationship in &req.relationships {
-        let target = store.get_requirement_by_id(&relationship.target_id);
-        if let Some(target_req) = target {
-            let target_spec = target_req.spec_id.as_deref().unwrap_or("N/A");
+/// Handle acceptance criteria subcommands
+fn handle_ac_command(cmd: &AcCommand, storage: &Storage) -> Result<()> {
+    let mut store = storage.load()?;
+    let default_author = || std::env::var("USER").unwrap_or_else(|_| String::from("Unknown"));
 
-            // Format the relationship description based on type
-            let description = match &relationship.rel_type {
-                RelationshipType::Parent => format!("is parent of"),
-                RelationshipType::Child => format!("is child of"),
-                RelationshipType::Duplicate => format!("is duplicate of"),
-                RelationshipType::Verifies => format!("verifies"),
-                RelationshipType::VerifiedBy => format!("is verified by"),
-                RelationshipType::References => format!("references"),
-                RelationshipType::Custom(name) => format!("{}", name),
-            };
+    match cmd {
+        AcCommand::Add {
+            id,
+            text,
+            given,
+            when,
+            then,
+            test,
+            author,
+        } => {
+            let req_id = parse_requirement_id(id, &store)?;
+            let mut criterion = AcceptanceCriterion::new("", text.clone());
+            match (given, when, then) {
+                (Some(given), Some(when), Some(then)) => {
+                    criterion = criterion.with_scenario(GivenWhenThen {
+                        given: given.clone(),
+                        when: when.clone(),
+                        then: then.clone(),
+                    });
+                }
+                (None, None, None) => {}
+                _ => anyhow::bail!("--given, --when and --then must be used together"),
+            }
+            if let Some(test) = test {
+                criterion = criterion.with_linked_test(test.clone());
+            }
 
+            let author = author.clone().unwrap_or_else(default_author);
+            let req = store
+                .get_requirement_by_id_mut(&req_id)
+                .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
+            let ac_id = req.add_acceptance_criterion(criterion, &author);
+            storage.save(&store)?;
+            println!("{} Added {} to {}", "✓".green(), ac_id.cyan(), id);
+        }
+        AcCommand::Check {
+            id,
+            criterion,
+            status,
+            author,
+        } => {
+            let status = CriterionStatus::parse(status).with_context(|| {
+                format!(
+                    "Invalid status '{}'. Use passed, failed, waived or pending",
+                    status
+                )
+            })?;
+            let req_id = parse_requirement_id(id, &store)?;
+            let author = author.clone().unwrap_or_else(default_author);
+            let req = store
+                .get_requirement_by_id_mut(&req_id)
+                .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
+            req.set_acceptance_status(criterion, status, &author)?;
+            let ac_id = req
+                .find_acceptance_criterion(criterion)
+                .map(|ac| ac.id.clone())
+                .unwrap_or_else(|| criterion.clone());
+            let (satisfied, total) = req.acceptance_progress();
+            storage.save(&store)?;
             println!(
-                "  {} {} ({}) - {}",
-                description.cyan(),
-                target_spec.yellow(),
-                target_req.id.to_string().dimmed(),
-                target_req.title
+                "{} {} marked {} ({}/{} satisfied)",
+                "✓".green(),
+                ac_id,
+                status,
+                satisfied,
+                total
             );
-        } else {
+        }
+        AcCommand::List { id } => {
+            let req_id = parse_requirement_id(id, &store)?;
+            let req = store
+                .get_requirement_by_id(&req_id)
+                .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
+            if req.acceptance_criteria.is_empty() {
+                println!("{}", "No acceptance criteria.".yellow());
+                return Ok(());
+            }
+            let (satisfied, total) = req.acceptance_progress();
             println!(
-                "  {} {} {}",
-                relationship.rel_type.to_string().cyan(),
-                relationship.target_id.to_string().yellow(),
-                "(requirement not found)".red()
+                "{} - {} ({}/{} satisfied)",
+                req.spec_id.as_deref().unwrap_or("N/A"),
+                req.title,
+                satisfied,
+                total
             );
+            for ac in &req.acceptance_criteria {
+                print_acceptance_criterion(ac);
+            }
+        }
+        AcCommand::Remove {
+            id,
+            criterion,
+            author,
+        } => {
+            let req_id = parse_requirement_id(id, &store)?;
+            let author = author.clone().unwrap_or_else(default_author);
+            let req = store
+                .get_requirement_by_id_mut(&req_id)
+                .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
+            let removed = req.remove_acceptance_criterion(criterion, &author)?;
+            storage.save(&store)?;
+            println!("{} Removed {}", "✓".green(), removed.id);
         }
     }
 
     Ok(())
 }
 
-fn handle_comment_command(cmd: &CommentCommand, storage: &Storage) -> Result<()> {
+/// Print one acceptance criterion as a colored checklist entry
+fn print_acceptance_criterion(ac: &AcceptanceCriterion) {
+    let checkbox = match ac.status {
+        CriterionStatus::Pending => ac.status.checkbox().normal(),
+        CriterionStatus::Passed => ac.status.checkbox().green(),
+        CriterionStatus::Failed => ac.status.checkbox().red(),
+        CriterionStatus::Waived => ac.status.checkbox().dimmed(),
+    };
+    println!("  {} {} {}", checkbox, ac.id.cyan(), ac.text);
+    if let Some(scenario) = &ac.scenario {
+        println!("      Given {}", scenario.given);
+        println!("      When {}", scenario.when);
+        println!("      Then {}", scenario.then);
+    }
+    if let Some(test) = &ac.linked_test {
+        println!("      {} {}", "Test:".dimmed(), test);
+    }
+}
+
+fn handle_comment_command(
+    cmd: &CommentCommand,
+    storage: &Storage,
+    format: OutputFormat,
+) -> Result<()> {
     match cmd {
         CommentCommand::Add {
             id,
@@ -1457,7 +4053,7 @@ fn handle_comment_command(cmd: &CommentCommand, storage: &Storage) -> Result<()>
             }
         }
         CommentCommand::List { id } => {
-            list_comments(storage, id)?;
+            list_comments(storage, id, format)?;
         }
         CommentCommand::Edit {
             req_id,
@@ -1491,7 +4087,7 @@ fn add_comment_interactive(
         .requirements
         .iter_mut()
         .find(|r| r.id == id)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
 
     let author = if let Some(a) = author {
         a.to_string()
@@ -1534,7 +4130,7 @@ fn add_comment_cli(
         .requirements
         .iter_mut()
         .find(|r| r.id == id)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
 
     let author = author.unwrap_or("Unknown").to_string();
 
@@ -1557,7 +4153,7 @@ fn add_comment_cli(
     Ok(())
 }
 
-fn list_comments(storage: &Storage, req_id: &str) -> Result<()> {
+fn list_comments(storage: &Storage, req_id: &str, format: OutputFormat) -> Result<()> {
     let store = storage.load()?;
     let id = parse_requirement_id(req_id, &store)?;
 
@@ -1565,7 +4161,11 @@ fn list_comments(storage: &Storage, req_id: &str) -> Result<()> {
         .requirements
         .iter()
         .find(|r| r.id == id)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(format!("Requirement not found: {}", req_id)))?;
+
+    if !format.is_table() {
+        return print_records(format, &CommentRecord::flatten(&req.comments));
+    }
 
     println!("{}: {}", "Requirement".cyan(), req.title);
     println!();
@@ -1616,11 +4216,11 @@ fn edit_comment_interactive(storage: &Storage, req_id: &str, comment_id: &str) -
         .requirements
         .iter_mut()
         .find(|r| r.id == req_uuid)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
 
     let comment = req
         .find_comment_mut(&comment_uuid)
-        .context("Comment not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Comment not found")))?;
 
     let new_content = inquire::Editor::new("Comment content:")
         .with_predefined_text(&comment.content)
@@ -1648,11 +4248,11 @@ fn edit_comment_cli(
         .requirements
         .iter_mut()
         .find(|r| r.id == req_uuid)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
 
     let comment = req
         .find_comment_mut(&comment_uuid)
-        .context("Comment not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Comment not found")))?;
 
     comment.content = content.to_string();
     comment.touch();
@@ -1671,7 +4271,7 @@ fn delete_comment(storage: &Storage, req_id: &str, comment_id: &str) -> Result<(
         .requirements
         .iter_mut()
         .find(|r| r.id == req_uuid)
-        .context("Requirement not found")?;
+        .ok_or_else(|| CliError::NotFound(String::from("Requirement not found")))?;
 
     req.delete_comment(&comment_uuid)?;
 
@@ -1768,13 +4368,17 @@ fn open_user_guide(dark_mode: bool) -> Result<()> {
 // Relationship Definition Command Handlers
 // ============================================================================
 
-fn handle_rel_def_command(cmd: &RelDefCommand, storage: &Storage) -> Result<()> {
+fn handle_rel_def_command(
+    cmd: &RelDefCommand,
+    storage: &Storage,
+    format: OutputFormat,
+) -> Result<()> {
     match cmd {
         RelDefCommand::List => {
-            list_relationship_definitions(storage)?;
+            list_relationship_definitions(storage, format)?;
         }
         RelDefCommand::Show { name } => {
-            show_relationship_definition(storage, name)?;
+            show_relationship_definition(storage, name, format)?;
         }
         RelDefCommand::Add {
             name,
@@ -1825,9 +4429,18 @@ fn handle_rel_def_command(cmd: &RelDefCommand, storage: &Storage) -> Result<()>
     Ok(())
 }
 
-fn list_relationship_definitions(storage: &Storage) -> Result<()> {
+fn list_relationship_definitions(storage: &Storage, format: OutputFormat) -> Result<()> {
     let store = storage.load()?;
 
+    if !format.is_table() {
+        let records: Vec<RelationshipDefinitionRecord> = store
+            .get_relationship_definitions()
+            .iter()
+            .map(RelationshipDefinitionRecord::from)
+            .collect();
+        return print_records(format, &records);
+    }
+
     println!("{}", "Relationship Definitions".cyan().bold());
     println!("{}", "=".repeat(60));
 
@@ -1887,12 +4500,16 @@ fn list_relationship_definitions(storage: &Storage) -> Result<()> {
     Ok(())
 }
 
-fn show_relationship_definition(storage: &Storage, name: &str) -> Result<()> {
+fn show_relationship_definition(storage: &Storage, name: &str, format: OutputFormat) -> Result<()> {
     let store = storage.load()?;
 
-    let def = store
-        .get_relationship_definition(name)
-        .ok_or_else(|| anyhow::anyhow!("Relationship definition '{}' not found", name))?;
+    let def = store.get_relationship_definition(name).ok_or_else(|| {
+        CliError::NotFound(format!("Relationship definition '{}' not found", name))
+    })?;
+
+    if !format.is_table() {
+        return print_record(format, &RelationshipDefinitionRecord::from(def));
+    }
 
     println!("{}", "Relationship Definition".cyan().bold());
     println!("{}", "=".repeat(40));
@@ -2018,7 +4635,7 @@ fn edit_relationship_definition(
     // Get the existing definition
     let existing = store
         .get_relationship_definition(name)
-        .ok_or_else(|| anyhow::anyhow!("Relationship definition '{}' not found", name))?
+        .ok_or_else(|| CliError::NotFound(format!("Relationship definition '{}' not found", name)))?
         .clone();
 
     // Build updated definition
@@ -2079,9 +4696,9 @@ fn remove_relationship_definition(
     let mut store = storage.load()?;
 
     // Check if it exists and is not built-in
-    let def = store
-        .get_relationship_definition(name)
-        .ok_or_else(|| anyhow::anyhow!("Relationship definition '{}' not found", name))?;
+    let def = store.get_relationship_definition(name).ok_or_else(|| {
+        CliError::NotFound(format!("Relationship definition '{}' not found", name))
+    })?;
 
     if def.built_in {
         anyhow::bail!("Cannot remove built-in relationship definition '{}'", name);
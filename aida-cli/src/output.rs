@@ -0,0 +1,1119 @@
+//! Machine-readable output for read commands (`aida --output json|yaml|csv`)
+//!
+//! The record types below are the documented schemas (see "Scripting
+//! Output" in the user guide). Fields may be added but are never renamed or
+//! removed. JSON and YAML print a list for list commands and a single object
+//! for `show` and `config show`; CSV prints a header row and flattens lists
+//! into `;`-separated values.
+//!
+//! Errors go to stderr, as JSON in the machine formats, and the exit code
+//! tells scripts what went wrong: 1 other errors, 2 invalid arguments,
+//! 3 not found, 4 validation, 5 conflict.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use uuid::Uuid;
+
+use aida_core::ai::responses::ImproveDescriptionResponse;
+use aida_core::ai::{DuplicateResult, GeneratedChild, RelationshipSuggestion};
+use aida_core::{
+    AcceptanceCriterion, Baseline, Comment, CommitLink, FeatureDefinition, HistoryEntry, IdFormat,
+    IssueReport, NumberingStrategy, RelationshipDefinition, RelationshipType, Requirement,
+    RequirementTypeDefinition, RequirementsStore, ScriptRejection, StorageError,
+    StoredAiEvaluation, SuggestedImprovement, Team, User, VerificationSummary,
+};
+
+/// Format of read command output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Colored text for people
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl OutputFormat {
+    /// True for the human-readable format
+    pub fn is_table(self) -> bool {
+        self == OutputFormat::Table
+    }
+}
+
+/// A row of machine-readable output
+pub trait Record: Serialize {
+    /// CSV column names
+    const HEADER: &'static [&'static str];
+
+    /// CSV values, in `HEADER` order followed by any extra columns
+    fn row(&self) -> Vec<String>;
+
+    /// CSV columns beyond `HEADER`, such as script-computed columns
+    fn extra_header(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Prints the records of a list command
+pub fn print_records<R: Record>(format: OutputFormat, records: &[R]) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    match format {
+        OutputFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(records)?)?,
+        OutputFormat::Yaml => write!(out, "{}", serde_yaml::to_string(records)?)?,
+        OutputFormat::Csv | OutputFormat::Table => write_csv(&mut out, records)?,
+    }
+    Ok(())
+}
+
+/// Prints the record of a `show` command
+pub fn print_record<R: Record>(format: OutputFormat, record: &R) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    match format {
+        OutputFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(record)?)?,
+        OutputFormat::Yaml => write!(out, "{}", serde_yaml::to_string(record)?)?,
+        OutputFormat::Csv | OutputFormat::Table => {
+            write_csv(&mut out, std::slice::from_ref(record))?
+        }
+    }
+    Ok(())
+}
+
+fn write_csv<R: Record>(out: &mut impl Write, records: &[R]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    let mut header: Vec<String> = R::HEADER.iter().map(|h| h.to_string()).collect();
+    if let Some(first) = records.first() {
+        header.extend(first.extra_header());
+    }
+    writer.write_record(&header)?;
+    for record in records {
+        writer.write_record(record.row())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn join<T: ToString>(values: impl IntoIterator<Item = T>) -> String {
+    values
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn opt<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+/// Errors with a dedicated exit code
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    /// A requirement, comment, definition or other item does not exist
+    #[error("{0}")]
+    NotFound(String),
+    /// An argument or value was rejected
+    #[error("{0}")]
+    Validation(String),
+}
+
+/// Category and exit code of an error; storage conflicts and locks are
+/// reported as `conflict`
+fn classify(error: &anyhow::Error) -> (&'static str, i32) {
+    for cause in error.chain() {
+        if let Some(e) = cause.downcast_ref::<CliError>() {
+            return match e {
+                CliError::NotFound(_) => ("not_found", 3),
+                CliError::Validation(_) => ("validation", 4),
+            };
+        }
+        if let Some(e) = cause.downcast_ref::<StorageError>() {
+            if matches!(e, StorageError::Conflict(_) | StorageError::FileLocked) {
+                return ("conflict", 5);
+            }
+        }
+        if cause.is::<ScriptRejection>() {
+            return ("validation", 4);
+        }
+    }
+    ("error", 1)
+}
+
+/// Prints an error in the selected format and returns the exit code
+pub fn report_error(error: &anyhow::Error, format: OutputFormat) -> i32 {
+    // The reader went away (e.g. `| head`); nothing useful to report
+    let broken_pipe = error.chain().any(|cause| {
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|e| e.kind() == std::io::ErrorKind::BrokenPipe)
+    });
+    if broken_pipe {
+        return 0;
+    }
+
+    let (kind, code) = classify(error);
+    if format.is_table() {
+        eprintln!("Error: {:?}", error);
+    } else {
+        let body = serde_json::json!({
+            "error": {
+                "kind": kind,
+                "message": format!("{:#}", error),
+                "exit_code": code,
+            }
+        });
+        eprintln!("{}", body);
+    }
+    code
+}
+
+// =============================================================================
+// Schemas
+// =============================================================================
+
+fn spec_or_id(req: &Requirement) -> String {
+    req.spec_id.clone().unwrap_or_else(|| req.id.to_string())
+}
+
+/// A requirement in `aida list`, and the fields shared with `aida show`
+#[derive(Debug, Serialize)]
+pub struct RequirementRecord {
+    pub id: Uuid,
+    pub spec_id: Option<String>,
+    pub title: String,
+    /// Type name, e.g. `Functional`, `NonFunctional`, `Story`
+    #[serde(rename = "type")]
+    pub req_type: String,
+    pub status: String,
+    pub priority: String,
+    pub feature: String,
+    pub owner: String,
+    pub tags: Vec<String>,
+    /// SPEC-ID (or UUID) of the parent requirement
+    pub parent: Option<String>,
+    pub archived: bool,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    /// Columns computed by project scripts, by column name
+    pub columns: BTreeMap<String, String>,
+}
+
+impl RequirementRecord {
+    pub fn new(
+        store: &RequirementsStore,
+        req: &Requirement,
+        columns: Vec<(String, String)>,
+    ) -> Self {
+        let mut tags: Vec<String> = req.tags.iter().cloned().collect();
+        tags.sort();
+        let parent = req
+            .relationships
+            .iter()
+            .find(|rel| rel.rel_type == RelationshipType::Parent)
+            .map(|rel| {
+                store
+                    .get_requirement_by_id(&rel.target_id)
+                    .map(spec_or_id)
+                    .unwrap_or_else(|| rel.target_id.to_string())
+            });
+        Self {
+            id: req.id,
+            spec_id: req.spec_id.clone(),
+            title: req.title.clone(),
+            req_type: format!("{:?}", req.req_type),
+            status: req.effective_status(),
+            priority: req.effective_priority(),
+            feature: req.feature.clone(),
+            owner: req.owner.clone(),
+            tags,
+            parent,
+            archived: req.archived,
+            created_at: req.created_at,
+            modified_at: req.modified_at,
+            columns: columns.into_iter().collect(),
+        }
+    }
+}
+
+impl Record for RequirementRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "spec_id",
+        "title",
+        "type",
+        "status",
+        "priority",
+        "feature",
+        "owner",
+        "tags",
+        "parent",
+        "archived",
+        "created_at",
+        "modified_at",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.id.to_string(),
+            opt(&self.spec_id),
+            self.title.clone(),
+            self.req_type.clone(),
+            self.status.clone(),
+            self.priority.clone(),
+            self.feature.clone(),
+            self.owner.clone(),
+            join(&self.tags),
+            opt(&self.parent),
+            self.archived.to_string(),
+            self.created_at.to_rfc3339(),
+            self.modified_at.to_rfc3339(),
+        ];
+        row.extend(self.columns.values().cloned());
+        row
+    }
+
+    fn extra_header(&self) -> Vec<String> {
+        self.columns.keys().cloned().collect()
+    }
+}
+
+/// Verification rolled up from linked test cases
+#[derive(Debug, Serialize)]
+pub struct VerificationRecord {
+    /// `Unverified`, `NotRun`, `Partial`, `Passing`, `Blocked` or `Failing`
+    pub status: String,
+    /// Linked test case counts by latest outcome
+    #[serde(flatten)]
+    pub summary: VerificationSummary,
+}
+
+/// A requirement in `aida show`
+#[derive(Debug, Serialize)]
+pub struct RequirementDetail {
+    #[serde(flatten)]
+    pub requirement: RequirementRecord,
+    pub description: String,
+    pub relationships: Vec<RelationshipRecord>,
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+    pub verification: VerificationRecord,
+    pub commits: Vec<CommitLink>,
+    pub comments: Vec<CommentRecord>,
+    pub history: Vec<HistoryEntry>,
+}
+
+impl RequirementDetail {
+    pub fn new(
+        store: &RequirementsStore,
+        req: &Requirement,
+        columns: Vec<(String, String)>,
+    ) -> Self {
+        let summary = store.verification_summary(&req.id);
+        Self {
+            requirement: RequirementRecord::new(store, req, columns),
+            description: req.description.clone(),
+            relationships: RelationshipRecord::for_requirement(store, req),
+            acceptance_criteria: req.acceptance_criteria.clone(),
+            verification: VerificationRecord {
+                status: format!("{:?}", summary.status()),
+                summary,
+            },
+            commits: req.commits.clone(),
+            comments: CommentRecord::flatten(&req.comments),
+            history: req.history.clone(),
+        }
+    }
+}
+
+impl Record for RequirementDetail {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "spec_id",
+        "title",
+        "type",
+        "status",
+        "priority",
+        "feature",
+        "owner",
+        "tags",
+        "parent",
+        "archived",
+        "created_at",
+        "modified_at",
+        "description",
+        "relationships",
+        "acceptance_criteria",
+        "verification",
+        "comments",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = self.requirement.row();
+        row.truncate(RequirementRecord::HEADER.len());
+        row.extend([
+            self.description.clone(),
+            join(self.relationships.iter().map(|r| {
+                format!(
+                    "{} {}",
+                    r.rel_type,
+                    r.target_spec_id.as_deref().unwrap_or("")
+                )
+            })),
+            join(self.acceptance_criteria.iter().map(|ac| ac.id.clone())),
+            self.verification.status.clone(),
+            self.comments.len().to_string(),
+        ]);
+        row.extend(self.requirement.columns.values().cloned());
+        row
+    }
+
+    fn extra_header(&self) -> Vec<String> {
+        self.requirement.extra_header()
+    }
+}
+
+/// A relationship in `aida rel list`
+#[derive(Debug, Serialize)]
+pub struct RelationshipRecord {
+    pub source_id: Uuid,
+    pub source_spec_id: Option<String>,
+    /// Relationship name, e.g. `parent`, `verifies` or a custom name
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    pub target_id: Uuid,
+    /// None when the target no longer exists
+    pub target_spec_id: Option<String>,
+    pub target_title: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl RelationshipRecord {
+    pub fn for_requirement(store: &RequirementsStore, req: &Requirement) -> Vec<Self> {
+        req.relationships
+            .iter()
+            .map(|rel| {
+                let target = store.get_requirement_by_id(&rel.target_id);
+                Self {
+                    source_id: req.id,
+                    source_spec_id: req.spec_id.clone(),
+                    rel_type: rel.rel_type.name(),
+                    target_id: rel.target_id,
+                    target_spec_id: target.and_then(|t| t.spec_id.clone()),
+                    target_title: target.map(|t| t.title.clone()),
+                    created_by: rel.created_by.clone(),
+                    created_at: rel.created_at,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Record for RelationshipRecord {
+    const HEADER: &'static [&'static str] = &[
+        "source_id",
+        "source_spec_id",
+        "type",
+        "target_id",
+        "target_spec_id",
+        "target_title",
+        "created_by",
+        "created_at",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.source_id.to_string(),
+            opt(&self.source_spec_id),
+            self.rel_type.clone(),
+            self.target_id.to_string(),
+            opt(&self.target_spec_id),
+            opt(&self.target_title),
+            opt(&self.created_by),
+            self.created_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        ]
+    }
+}
+
+/// A comment in `aida comment list`; replies follow their parent in thread order
+#[derive(Debug, Serialize)]
+pub struct CommentRecord {
+    pub id: Uuid,
+    /// None for top-level comments
+    pub parent_id: Option<Uuid>,
+    /// 0 for top-level comments, 1 for replies to them, and so on
+    pub depth: usize,
+    pub author: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    /// Reaction name to count
+    pub reactions: BTreeMap<String, usize>,
+}
+
+impl CommentRecord {
+    pub fn flatten(comments: &[Comment]) -> Vec<Self> {
+        fn walk(
+            comments: &[Comment],
+            parent_id: Option<Uuid>,
+            depth: usize,
+            out: &mut Vec<CommentRecord>,
+        ) {
+            for comment in comments {
+                out.push(CommentRecord {
+                    id: comment.id,
+                    parent_id,
+                    depth,
+                    author: comment.author.clone(),
+                    content: comment.content.clone(),
+                    created_at: comment.created_at,
+                    modified_at: comment.modified_at,
+                    reactions: comment.reaction_counts().into_iter().collect(),
+                });
+                walk(&comment.replies, Some(comment.id), depth + 1, out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(comments, None, 0, &mut out);
+        out
+    }
+}
+
+impl Record for CommentRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "parent_id",
+        "depth",
+        "author",
+        "content",
+        "created_at",
+        "modified_at",
+        "reactions",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            opt(&self.parent_id),
+            self.depth.to_string(),
+            self.author.clone(),
+            self.content.clone(),
+            self.created_at.to_rfc3339(),
+            self.modified_at.to_rfc3339(),
+            join(
+                self.reactions
+                    .iter()
+                    .map(|(name, count)| format!("{}={}", name, count)),
+            ),
+        ]
+    }
+}
+
+/// A feature in `aida feature list`
+#[derive(Debug, Serialize)]
+pub struct FeatureRecord {
+    /// None for features only named on requirements
+    pub number: Option<u32>,
+    pub name: String,
+    pub prefix: Option<String>,
+    pub description: String,
+    /// Requirements whose feature is this one
+    pub requirement_count: usize,
+}
+
+impl FeatureRecord {
+    /// Defined features, then feature names used on requirements but not defined
+    pub fn all(store: &RequirementsStore) -> Vec<Self> {
+        let count = |name: &str| {
+            store
+                .requirements
+                .iter()
+                .filter(|r| r.feature == name)
+                .count()
+        };
+        let defined = |f: &FeatureDefinition| Self {
+            number: Some(f.number),
+            name: f.name.clone(),
+            prefix: Some(f.prefix.clone()),
+            description: f.description.clone(),
+            requirement_count: count(&f.name),
+        };
+        let mut records: Vec<Self> = store.features.iter().map(defined).collect();
+        for name in store.get_feature_names() {
+            if !records.iter().any(|r| r.name == name) {
+                records.push(Self {
+                    number: None,
+                    requirement_count: count(&name),
+                    name,
+                    prefix: None,
+                    description: String::new(),
+                });
+            }
+        }
+        records
+    }
+}
+
+impl Record for FeatureRecord {
+    const HEADER: &'static [&'static str] = &[
+        "number",
+        "name",
+        "prefix",
+        "description",
+        "requirement_count",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            opt(&self.number),
+            self.name.clone(),
+            opt(&self.prefix),
+            self.description.clone(),
+            self.requirement_count.to_string(),
+        ]
+    }
+}
+
+/// A requirement type in `aida type list`
+#[derive(Debug, Serialize)]
+pub struct TypeRecord {
+    pub name: String,
+    pub prefix: String,
+    pub description: String,
+}
+
+impl From<&RequirementTypeDefinition> for TypeRecord {
+    fn from(def: &RequirementTypeDefinition) -> Self {
+        Self {
+            name: def.name.clone(),
+            prefix: def.prefix.clone(),
+            description: def.description.clone(),
+        }
+    }
+}
+
+impl Record for TypeRecord {
+    const HEADER: &'static [&'static str] = &["name", "prefix", "description"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.prefix.clone(),
+            self.description.clone(),
+        ]
+    }
+}
+
+/// A relationship definition in `aida rel-def list` and `aida rel-def show`
+#[derive(Debug, Serialize)]
+pub struct RelationshipDefinitionRecord {
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    pub inverse: Option<String>,
+    pub symmetric: bool,
+    /// `1:1`, `1:N`, `N:1` or `N:N`
+    pub cardinality: String,
+    /// Allowed source types; empty allows all
+    pub source_types: Vec<String>,
+    /// Allowed target types; empty allows all
+    pub target_types: Vec<String>,
+    pub built_in: bool,
+    pub color: Option<String>,
+}
+
+impl From<&RelationshipDefinition> for RelationshipDefinitionRecord {
+    fn from(def: &RelationshipDefinition) -> Self {
+        Self {
+            name: def.name.clone(),
+            display_name: def.display_name.clone(),
+            description: def.description.clone(),
+            inverse: def.inverse.clone(),
+            symmetric: def.symmetric,
+            cardinality: def.cardinality.to_string(),
+            source_types: def.source_types.clone(),
+            target_types: def.target_types.clone(),
+            built_in: def.built_in,
+            color: def.color.clone(),
+        }
+    }
+}
+
+impl Record for RelationshipDefinitionRecord {
+    const HEADER: &'static [&'static str] = &[
+        "name",
+        "display_name",
+        "description",
+        "inverse",
+        "symmetric",
+        "cardinality",
+        "source_types",
+        "target_types",
+        "built_in",
+        "color",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.display_name.clone(),
+            self.description.clone(),
+            opt(&self.inverse),
+            self.symmetric.to_string(),
+            self.cardinality.clone(),
+            join(&self.source_types),
+            join(&self.target_types),
+            self.built_in.to_string(),
+            opt(&self.color),
+        ]
+    }
+}
+
+/// ID configuration in `aida config show`
+#[derive(Debug, Serialize)]
+pub struct ConfigRecord {
+    /// `single` (PREFIX-NNN) or `two` (FEATURE-TYPE-NNN)
+    pub format: String,
+    /// `global`, `per-prefix` or `per-feature-type`
+    pub numbering: String,
+    pub digits: u8,
+    pub next_spec_number: u32,
+    /// Next number per prefix
+    pub prefix_counters: BTreeMap<String, u32>,
+}
+
+impl ConfigRecord {
+    pub fn new(store: &RequirementsStore) -> Self {
+        Self {
+            format: match store.id_config.format {
+                IdFormat::SingleLevel => "single",
+                IdFormat::TwoLevel => "two",
+            }
+            .to_string(),
+            numbering: match store.id_config.numbering {
+                NumberingStrategy::Global => "global",
+                NumberingStrategy::PerPrefix => "per-prefix",
+                NumberingStrategy::PerFeatureType => "per-feature-type",
+            }
+            .to_string(),
+            digits: store.id_config.digits,
+            next_spec_number: store.next_spec_number,
+            prefix_counters: store.prefix_counters.clone().into_iter().collect(),
+        }
+    }
+}
+
+impl Record for ConfigRecord {
+    const HEADER: &'static [&'static str] = &[
+        "format",
+        "numbering",
+        "digits",
+        "next_spec_number",
+        "prefix_counters",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.format.clone(),
+            self.numbering.clone(),
+            self.digits.to_string(),
+            self.next_spec_number.to_string(),
+            join(
+                self.prefix_counters
+                    .iter()
+                    .map(|(p, n)| format!("{}={}", p, n)),
+            ),
+        ]
+    }
+}
+
+/// A baseline in `aida baseline list`
+#[derive(Debug, Serialize)]
+pub struct BaselineRecord {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: String,
+    /// Git tag created with the baseline, if any
+    pub git_tag: Option<String>,
+    /// Locked baselines cannot be deleted
+    pub locked: bool,
+    pub requirement_count: usize,
+}
+
+impl From<&Baseline> for BaselineRecord {
+    fn from(baseline: &Baseline) -> Self {
+        Self {
+            id: baseline.id,
+            name: baseline.name.clone(),
+            description: baseline.description.clone(),
+            created_at: baseline.created_at,
+            created_by: baseline.created_by.clone(),
+            git_tag: baseline.git_tag.clone(),
+            locked: baseline.locked,
+            requirement_count: baseline.requirements.len(),
+        }
+    }
+}
+
+impl Record for BaselineRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "name",
+        "description",
+        "created_at",
+        "created_by",
+        "git_tag",
+        "locked",
+        "requirement_count",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.name.clone(),
+            opt(&self.description),
+            self.created_at.to_rfc3339(),
+            self.created_by.clone(),
+            opt(&self.git_tag),
+            self.locked.to_string(),
+            self.requirement_count.to_string(),
+        ]
+    }
+}
+
+/// A requirement as recorded in a baseline
+#[derive(Debug, Serialize)]
+pub struct SnapshotRecord {
+    pub id: Uuid,
+    pub spec_id: Option<String>,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub req_type: String,
+    pub status: String,
+    pub priority: String,
+    pub feature: String,
+}
+
+/// A baseline in `aida baseline show`
+#[derive(Debug, Serialize)]
+pub struct BaselineDetail {
+    #[serde(flatten)]
+    pub baseline: BaselineRecord,
+    pub requirements: Vec<SnapshotRecord>,
+}
+
+impl From<&Baseline> for BaselineDetail {
+    fn from(baseline: &Baseline) -> Self {
+        Self {
+            baseline: BaselineRecord::from(baseline),
+            requirements: baseline
+                .requirements
+                .iter()
+                .map(|s| SnapshotRecord {
+                    id: s.original_id,
+                    spec_id: s.spec_id.clone(),
+                    title: s.title.clone(),
+                    req_type: format!("{:?}", s.req_type),
+                    status: s
+                        .custom_status
+                        .clone()
+                        .unwrap_or_else(|| s.status.to_string()),
+                    priority: s
+                        .custom_priority
+                        .clone()
+                        .unwrap_or_else(|| s.priority.to_string()),
+                    feature: s.feature.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Record for BaselineDetail {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "name",
+        "description",
+        "created_at",
+        "created_by",
+        "git_tag",
+        "locked",
+        "requirement_count",
+        "requirements",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = self.baseline.row();
+        row.push(join(
+            self.requirements
+                .iter()
+                .map(|r| r.spec_id.clone().unwrap_or_else(|| r.id.to_string())),
+        ));
+        row
+    }
+}
+
+fn user_ref(store: &RequirementsStore, id: &Uuid) -> String {
+    store
+        .find_user_by_id(id)
+        .map(|u| u.display_id().to_string())
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn team_ref(store: &RequirementsStore, id: &Uuid) -> String {
+    store
+        .find_team_by_id(id)
+        .map(|t| t.display_id().to_string())
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// A user in `aida user list`
+#[derive(Debug, Serialize)]
+pub struct UserRecord {
+    pub id: Uuid,
+    pub spec_id: Option<String>,
+    pub name: String,
+    pub email: String,
+    pub handle: String,
+    pub archived: bool,
+    /// Spec IDs of the teams the user belongs to
+    pub teams: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserRecord {
+    pub fn new(store: &RequirementsStore, user: &User) -> Self {
+        Self {
+            id: user.id,
+            spec_id: user.spec_id.clone(),
+            name: user.name.clone(),
+            email: user.email.clone(),
+            handle: user.handle.clone(),
+            archived: user.archived,
+            teams: store
+                .get_teams_for_user(&user.id)
+                .iter()
+                .map(|t| t.display_id().to_string())
+                .collect(),
+            created_at: user.created_at,
+        }
+    }
+}
+
+impl Record for UserRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "spec_id",
+        "name",
+        "email",
+        "handle",
+        "archived",
+        "teams",
+        "created_at",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            opt(&self.spec_id),
+            self.name.clone(),
+            self.email.clone(),
+            self.handle.clone(),
+            self.archived.to_string(),
+            join(&self.teams),
+            self.created_at.to_rfc3339(),
+        ]
+    }
+}
+
+/// A team in `aida team list` and `aida team show`
+#[derive(Debug, Serialize)]
+pub struct TeamRecord {
+    pub id: Uuid,
+    pub spec_id: Option<String>,
+    pub name: String,
+    pub description: String,
+    /// Spec ID of the parent team
+    pub parent: Option<String>,
+    /// Spec IDs of the members
+    pub members: Vec<String>,
+    /// Spec IDs of the direct sub-teams
+    pub subteams: Vec<String>,
+    pub archived: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TeamRecord {
+    pub fn new(store: &RequirementsStore, team: &Team) -> Self {
+        Self {
+            id: team.id,
+            spec_id: team.spec_id.clone(),
+            name: team.name.clone(),
+            description: team.description.clone(),
+            parent: team.parent_team_id.map(|id| team_ref(store, &id)),
+            members: team
+                .member_ids
+                .iter()
+                .map(|id| user_ref(store, id))
+                .collect(),
+            subteams: store
+                .get_child_teams(&team.id)
+                .iter()
+                .map(|t| t.display_id().to_string())
+                .collect(),
+            archived: team.archived,
+            created_at: team.created_at,
+        }
+    }
+}
+
+impl Record for TeamRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "spec_id",
+        "name",
+        "description",
+        "parent",
+        "members",
+        "subteams",
+        "archived",
+        "created_at",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            opt(&self.spec_id),
+            self.name.clone(),
+            self.description.clone(),
+            opt(&self.parent),
+            join(&self.members),
+            join(&self.subteams),
+            self.archived.to_string(),
+            self.created_at.to_rfc3339(),
+        ]
+    }
+}
+
+/// An evaluation in `aida ai evaluate` and `aida ai scan`
+#[derive(Debug, Serialize)]
+pub struct AiEvaluationRecord {
+    pub id: Uuid,
+    pub spec_id: Option<String>,
+    pub title: String,
+    /// 1 to 10
+    pub quality_score: u8,
+    pub issues: Vec<IssueReport>,
+    pub strengths: Vec<String>,
+    pub suggested_improvements: Option<SuggestedImprovement>,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+impl AiEvaluationRecord {
+    pub fn new(req: &Requirement, stored: &StoredAiEvaluation) -> Self {
+        let evaluation = stored.evaluation.clone();
+        Self {
+            id: req.id,
+            spec_id: req.spec_id.clone(),
+            title: req.title.clone(),
+            quality_score: evaluation.quality_score,
+            issues: evaluation.issues,
+            strengths: evaluation.strengths,
+            suggested_improvements: evaluation.suggested_improvements,
+            evaluated_at: stored.evaluated_at,
+        }
+    }
+}
+
+impl Record for AiEvaluationRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "spec_id",
+        "title",
+        "quality_score",
+        "issues",
+        "strengths",
+        "suggested_description",
+        "evaluated_at",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            opt(&self.spec_id),
+            self.title.clone(),
+            self.quality_score.to_string(),
+            join(
+                self.issues
+                    .iter()
+                    .map(|i| format!("{} {}: {}", i.severity, i.issue_type, i.text)),
+            ),
+            join(&self.strengths),
+            self.suggested_improvements
+                .as_ref()
+                .and_then(|s| s.description.clone())
+                .unwrap_or_default(),
+            self.evaluated_at.to_rfc3339(),
+        ]
+    }
+}
+
+impl Record for DuplicateResult {
+    const HEADER: &'static [&'static str] = &["spec_id", "similarity", "reason", "recommendation"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.spec_id.clone(),
+            self.similarity.to_string(),
+            self.reason.clone(),
+            self.recommendation.clone(),
+        ]
+    }
+}
+
+impl Record for RelationshipSuggestion {
+    const HEADER: &'static [&'static str] =
+        &["rel_type", "target_spec_id", "confidence", "rationale"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.rel_type.clone(),
+            self.target_spec_id.clone(),
+            self.confidence.to_string(),
+            self.rationale.clone(),
+        ]
+    }
+}
+
+impl Record for ImproveDescriptionResponse {
+    const HEADER: &'static [&'static str] = &["improved_description", "changes_made", "rationale"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.improved_description.clone(),
+            join(&self.changes_made),
+            self.rationale.clone(),
+        ]
+    }
+}
+
+impl Record for GeneratedChild {
+    const HEADER: &'static [&'static str] = &["title", "description", "type", "rationale"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.title.clone(),
+            self.description.clone(),
+            self.req_type.clone(),
+            self.rationale.clone(),
+        ]
+    }
+}
@@ -5,18 +5,18 @@ use std::path::PathBuf;
 use uuid::Uuid;
 
 use aida_core::project::list_available_projects;
-use aida_core::{Requirement, RequirementPriority, RequirementStatus, RequirementType};
+use aida_core::{
+    Requirement, RequirementPriority, RequirementStatus, RequirementType, TemplateContext,
+    TypeTemplateLibrary,
+};
 
 /// Prompts the user for a new requirement
 pub fn prompt_new_requirement(store: &mut aida_core::RequirementsStore) -> Result<Requirement> {
     // Get basic information
     let title = Text::new("Title:").prompt()?;
 
-    // Use the Editor type for multiline input
-    let description = inquire::Editor::new("Description:").prompt()?;
-
-    // Create a basic requirement
-    let mut req = Requirement::new(title, description);
+    // Create a basic requirement (description is prompted last, pre-filled from the type template)
+    let mut req = Requirement::new(title, String::new());
 
     // Get additional information with default values
     let status_options = vec![
@@ -96,6 +96,19 @@ pub fn prompt_new_requirement(store: &mut aida_core::RequirementsStore) -> Resul
         req.tags = tags;
     }
 
+    // Use the Editor type for multiline input, starting from the type template
+    let library = TypeTemplateLibrary::load_default().unwrap_or_else(|e| {
+        eprintln!("Warning: could not load shared type templates: {}", e);
+        TypeTemplateLibrary::default()
+    });
+    let user = env::var("USER").unwrap_or_else(|_| String::from("Unknown"));
+    let ctx = TemplateContext::new(user, &req.feature, &req.req_type);
+    let template = store.instantiate_type_template(&req.req_type, &ctx, &library);
+    req.description = inquire::Editor::new("Description:")
+        .with_predefined_text(&template.description)
+        .prompt()?;
+    req.custom_fields.extend(template.custom_fields);
+
     Ok(req)
 }
 
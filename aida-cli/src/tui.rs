@@ -0,0 +1,1210 @@
+//! Full-screen terminal interface (`aida tui`)
+//!
+//! Browses requirements as a filtered list or parent/child tree, renders the
+//! Markdown description, and changes status, priority and comment threads in
+//! place. Each change reloads the project, applies the edit and saves, so it
+//! merges with edits made elsewhere; the session registers in the same lock
+//! file as the GUI so presence shows up on both sides.
+
+use anyhow::Result;
+use chrono::Utc;
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+use ratatui::crossterm::event::{self, Event as TermEvent, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime};
+use uuid::Uuid;
+
+use aida_core::{
+    Comment, EditLock, LockFileInfo, RelationshipType, Requirement, RequirementPatch,
+    RequirementQuery, RequirementsStore, SessionHeartbeat, SessionInfo, Storage,
+    HEARTBEAT_INTERVAL_SECS,
+};
+
+/// Opens the terminal interface until the user quits
+pub fn run(storage: &Storage, user: String) -> Result<()> {
+    let mut app = App::new(storage, user)?;
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Perspective {
+    List,
+    Tree,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    List,
+    Description,
+    Comments,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PickField {
+    Status,
+    Priority,
+}
+
+enum Mode {
+    Browse,
+    Search,
+    Filter(String),
+    Pick {
+        field: PickField,
+        options: Vec<String>,
+        state: ListState,
+    },
+    Comment {
+        reply_to: Option<Uuid>,
+        text: String,
+    },
+    Help,
+}
+
+/// One line of the requirement list
+struct Row {
+    id: Uuid,
+    depth: usize,
+    has_children: bool,
+    /// False for ancestors shown only to place a match in the tree
+    matched: bool,
+}
+
+/// A comment flattened out of its thread
+struct CommentRow {
+    id: Uuid,
+    depth: usize,
+    author: String,
+    created_at: String,
+    content: String,
+    reactions: Vec<(String, usize)>,
+}
+
+struct App {
+    storage: Storage,
+    store: RequirementsStore,
+    user: String,
+    heartbeat: SessionHeartbeat,
+    sessions: LockFileInfo,
+    loaded_at: Option<SystemTime>,
+    last_tick: Instant,
+
+    perspective: Perspective,
+    focus: Focus,
+    mode: Mode,
+    query: RequirementQuery,
+    filter_text: String,
+    search: String,
+    collapsed: HashSet<Uuid>,
+    rows: Vec<Row>,
+    list_state: ListState,
+    description_scroll: u16,
+    comment_state: ListState,
+    message: Option<String>,
+    quit: bool,
+}
+
+impl App {
+    fn new(storage: &Storage, user: String) -> Result<Self> {
+        let storage = Storage::new(storage.path());
+        let store = storage.load()?;
+        let hostname = hostname::get()
+            .map(|h| h.to_string_lossy().to_string())
+            .unwrap_or_else(|_| "unknown".to_string());
+        let session = SessionInfo {
+            session_id: format!("{}-{}", std::process::id(), Uuid::new_v4()),
+            user_name: user.clone(),
+            hostname,
+            pid: std::process::id(),
+            started_at: Utc::now(),
+            last_heartbeat: Utc::now(),
+            editing_requirement: None,
+        };
+        let (heartbeat, sessions) = SessionHeartbeat::start(&storage, session)?;
+
+        let mut app = Self {
+            loaded_at: modified_time(&storage),
+            storage,
+            store,
+            user,
+            heartbeat,
+            sessions,
+            last_tick: Instant::now(),
+            perspective: Perspective::List,
+            focus: Focus::List,
+            mode: Mode::Browse,
+            query: RequirementQuery::default(),
+            filter_text: String::new(),
+            search: String::new(),
+            collapsed: HashSet::new(),
+            rows: Vec::new(),
+            list_state: ListState::default(),
+            description_scroll: 0,
+            comment_state: ListState::default(),
+            message: None,
+            quit: false,
+        };
+        app.refresh_rows();
+        Ok(app)
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(Duration::from_millis(250))? {
+                if let TermEvent::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        self.handle_key(key);
+                    }
+                }
+            }
+            if self.last_tick.elapsed() >= Duration::from_secs(HEARTBEAT_INTERVAL_SECS) {
+                self.tick();
+            }
+        }
+        Ok(())
+    }
+
+    /// Picks up other sessions and edits saved by other processes
+    fn tick(&mut self) {
+        self.last_tick = Instant::now();
+        if let Ok(info) = self.storage.get_active_sessions() {
+            self.sessions = info;
+        }
+        if modified_time(&self.storage) != self.loaded_at {
+            self.reload();
+        }
+    }
+
+    fn reload(&mut self) {
+        match self.storage.load() {
+            Ok(store) => {
+                self.store = store;
+                self.loaded_at = modified_time(&self.storage);
+                self.refresh_rows();
+            }
+            Err(e) => self.message = Some(format!("Reload failed: {}", e)),
+        }
+    }
+
+    fn selected(&self) -> Option<&Requirement> {
+        let row = self.rows.get(self.list_state.selected()?)?;
+        self.store.get_requirement_by_id(&row.id)
+    }
+
+    fn matches(&self, req: &Requirement) -> bool {
+        if !self.query.matches(req) {
+            return false;
+        }
+        let search = self.search.trim().to_lowercase();
+        search.is_empty()
+            || req
+                .spec_id
+                .as_deref()
+                .is_some_and(|s| s.to_lowercase().contains(&search))
+            || req.title.to_lowercase().contains(&search)
+            || req.description.to_lowercase().contains(&search)
+    }
+
+    /// Rebuilds the visible rows, keeping the selected requirement selected
+    fn refresh_rows(&mut self) {
+        let selected = self.selected().map(|r| r.id);
+        let matched: HashSet<Uuid> = self
+            .store
+            .requirements
+            .iter()
+            .filter(|r| self.matches(r))
+            .map(|r| r.id)
+            .collect();
+
+        self.rows = match self.perspective {
+            Perspective::List => self
+                .store
+                .requirements
+                .iter()
+                .filter(|r| matched.contains(&r.id))
+                .map(|r| Row {
+                    id: r.id,
+                    depth: 0,
+                    has_children: false,
+                    matched: true,
+                })
+                .collect(),
+            Perspective::Tree => self.tree_rows(&matched),
+        };
+
+        let index = selected
+            .and_then(|id| self.rows.iter().position(|r| r.id == id))
+            .or_else(|| (!self.rows.is_empty()).then_some(0))
+            .map(|i| i.min(self.rows.len().saturating_sub(1)));
+        if index != self.list_state.selected() {
+            self.description_scroll = 0;
+            self.comment_state.select(None);
+        }
+        self.list_state.select(index);
+    }
+
+    /// Matches nested under their parents, with the ancestors needed to reach them
+    fn tree_rows(&self, matched: &HashSet<Uuid>) -> Vec<Row> {
+        let parents: HashMap<Uuid, Uuid> = self
+            .store
+            .requirements
+            .iter()
+            .filter_map(|r| {
+                r.relationships
+                    .iter()
+                    .find(|rel| rel.rel_type == RelationshipType::Parent)
+                    .map(|rel| (r.id, rel.target_id))
+            })
+            .collect();
+
+        let mut shown: HashSet<Uuid> = HashSet::new();
+        for id in matched {
+            let mut current = Some(*id);
+            while let Some(id) = current {
+                if !shown.insert(id) {
+                    break;
+                }
+                current = parents.get(&id).copied();
+            }
+        }
+
+        let mut children: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        let mut roots = Vec::new();
+        for req in self
+            .store
+            .requirements
+            .iter()
+            .filter(|r| shown.contains(&r.id))
+        {
+            match parents.get(&req.id).filter(|p| shown.contains(p)) {
+                Some(parent) => children.entry(*parent).or_default().push(req.id),
+                None => roots.push(req.id),
+            }
+        }
+
+        let mut rows = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack: Vec<(Uuid, usize)> = roots.into_iter().rev().map(|id| (id, 0)).collect();
+        while let Some((id, depth)) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let kids = children.get(&id).map(Vec::as_slice).unwrap_or_default();
+            rows.push(Row {
+                id,
+                depth,
+                has_children: !kids.is_empty(),
+                matched: matched.contains(&id),
+            });
+            if !self.collapsed.contains(&id) {
+                stack.extend(kids.iter().rev().map(|kid| (*kid, depth + 1)));
+            }
+        }
+        rows
+    }
+
+    fn comment_rows(&self) -> Vec<CommentRow> {
+        fn flatten(comments: &[Comment], depth: usize, rows: &mut Vec<CommentRow>) {
+            for comment in comments {
+                let mut reactions: Vec<(String, usize)> =
+                    comment.reaction_counts().into_iter().collect();
+                reactions.sort();
+                rows.push(CommentRow {
+                    id: comment.id,
+                    depth,
+                    author: comment.author.clone(),
+                    created_at: comment.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                    content: comment.content.clone(),
+                    reactions,
+                });
+                flatten(&comment.replies, depth + 1, rows);
+            }
+        }
+        let mut rows = Vec::new();
+        if let Some(req) = self.selected() {
+            flatten(&req.comments, 0, &mut rows);
+        }
+        rows
+    }
+
+    fn select_row(&mut self, index: usize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let index = index.min(self.rows.len() - 1);
+        if Some(index) != self.list_state.selected() {
+            self.description_scroll = 0;
+            self.comment_state.select(None);
+        }
+        self.list_state.select(Some(index));
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        match self.focus {
+            Focus::List => {
+                let current = self.list_state.selected().unwrap_or(0);
+                self.select_row(current.saturating_add_signed(delta));
+            }
+            Focus::Description => {
+                self.description_scroll =
+                    self.description_scroll.saturating_add_signed(delta as i16);
+            }
+            Focus::Comments => {
+                let count = self.comment_rows().len();
+                if count > 0 {
+                    let current = self.comment_state.selected().unwrap_or(0);
+                    let next = current.saturating_add_signed(delta).min(count - 1);
+                    self.comment_state.select(Some(next));
+                }
+            }
+        }
+    }
+
+    /// Collapses the selected tree node, or moves to its parent
+    fn collapse(&mut self) {
+        let Some(index) = self.list_state.selected() else {
+            return;
+        };
+        let Some(row) = self.rows.get(index) else {
+            return;
+        };
+        if row.has_children && !self.collapsed.contains(&row.id) {
+            self.collapsed.insert(row.id);
+            self.refresh_rows();
+        } else if let Some(parent) = self.rows[..index].iter().rposition(|r| r.depth < row.depth) {
+            self.select_row(parent);
+        }
+    }
+
+    fn expand(&mut self) {
+        if let Some(row) = self.list_state.selected().and_then(|i| self.rows.get(i)) {
+            if self.collapsed.remove(&row.id) {
+                self.refresh_rows();
+            }
+        }
+    }
+
+    /// Reloads the project, applies one change to the selected requirement and saves
+    fn update_selected(
+        &mut self,
+        apply: impl FnOnce(&mut RequirementsStore, Uuid) -> Result<String>,
+    ) {
+        let Some(id) = self.selected().map(|r| r.id) else {
+            return;
+        };
+        let mut outcome = None;
+        let result = self.storage.update_atomically(|store| {
+            let snapshot = store.clone();
+            match apply(store, id) {
+                Ok(message) => outcome = Some(Ok(message)),
+                Err(e) => {
+                    // Leave the file as it was
+                    *store = snapshot;
+                    outcome = Some(Err(e));
+                }
+            }
+        });
+        let result = result.and_then(|store| {
+            let message =
+                outcome.unwrap_or_else(|| Err(anyhow::anyhow!("Update was not applied")))?;
+            Ok((store, message))
+        });
+        match result {
+            Ok((store, message)) => {
+                self.store = store;
+                self.loaded_at = modified_time(&self.storage);
+                self.refresh_rows();
+                self.message = Some(message);
+            }
+            Err(e) => self.message = Some(format!("Not saved: {}", e)),
+        }
+    }
+
+    fn set_field(&mut self, field: PickField, value: String) {
+        let user = self.user.clone();
+        self.update_selected(|store, id| {
+            let patch = match field {
+                PickField::Status => RequirementPatch {
+                    status: Some(value.clone()),
+                    ..Default::default()
+                },
+                PickField::Priority => RequirementPatch {
+                    priority: Some(value.clone()),
+                    ..Default::default()
+                },
+            };
+            store.bulk_update(&[id], &patch, &user)?;
+            let label = store
+                .get_requirement_by_id(&id)
+                .map(|r| r.spec_id.clone().unwrap_or_else(|| r.id.to_string()))
+                .unwrap_or_default();
+            Ok(format!("{} set to {}", label, value))
+        });
+    }
+
+    fn add_comment(&mut self, reply_to: Option<Uuid>, text: String) {
+        let user = self.user.clone();
+        self.update_selected(|store, id| {
+            let req = store
+                .get_requirement_by_id_mut(&id)
+                .ok_or_else(|| anyhow::anyhow!("Requirement was deleted"))?;
+            match reply_to {
+                Some(parent) => req.add_reply(parent, Comment::new_reply(user, text, parent))?,
+                None => req.add_comment(Comment::new(user, text)),
+            }
+            Ok("Comment added".to_string())
+        });
+    }
+
+    /// Announces the requirement being edited to other sessions
+    fn set_editing(&self, editing: bool) {
+        let lock = editing
+            .then(|| self.selected())
+            .flatten()
+            .map(|req| EditLock {
+                requirement_id: req.id,
+                spec_id: req.spec_id.clone().unwrap_or_else(|| req.id.to_string()),
+                started_at: Utc::now(),
+            });
+        self.heartbeat.set_edit_lock(lock);
+    }
+
+    fn open_picker(&mut self, field: PickField) {
+        let Some(req) = self.selected() else {
+            return;
+        };
+        let (options, current) = match field {
+            PickField::Status => (
+                self.store.get_statuses_for_type(&req.req_type),
+                req.effective_status(),
+            ),
+            PickField::Priority => (
+                self.store.get_priorities_for_type(&req.req_type),
+                req.effective_priority(),
+            ),
+        };
+        let mut state = ListState::default();
+        state.select(Some(
+            options.iter().position(|o| *o == current).unwrap_or(0),
+        ));
+        self.set_editing(true);
+        self.mode = Mode::Pick {
+            field,
+            options,
+            state,
+        };
+    }
+
+    fn open_comment(&mut self, reply: bool) {
+        if self.selected().is_none() {
+            return;
+        }
+        let reply_to = if reply {
+            let rows = self.comment_rows();
+            match self.comment_state.selected().and_then(|i| rows.get(i)) {
+                Some(row) => Some(row.id),
+                None => {
+                    self.message = Some("Select a comment to reply to (Tab to Comments)".into());
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        self.set_editing(true);
+        self.mode = Mode::Comment {
+            reply_to,
+            text: String::new(),
+        };
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        self.message = None;
+        match std::mem::replace(&mut self.mode, Mode::Browse) {
+            Mode::Browse => self.handle_browse_key(key),
+            Mode::Help => {}
+            Mode::Search => match key.code {
+                KeyCode::Enter => {}
+                KeyCode::Esc => {
+                    self.search.clear();
+                    self.refresh_rows();
+                }
+                code => {
+                    edit_text(&mut self.search, code);
+                    self.refresh_rows();
+                    self.mode = Mode::Search;
+                }
+            },
+            Mode::Filter(mut text) => match key.code {
+                KeyCode::Enter => match RequirementQuery::parse(&text) {
+                    Ok(query) => {
+                        self.query = query;
+                        self.filter_text = text.trim().to_string();
+                        self.refresh_rows();
+                    }
+                    Err(e) => {
+                        self.message = Some(e.to_string());
+                        self.mode = Mode::Filter(text);
+                    }
+                },
+                KeyCode::Esc => {}
+                code => {
+                    edit_text(&mut text, code);
+                    self.mode = Mode::Filter(text);
+                }
+            },
+            Mode::Pick {
+                field,
+                options,
+                mut state,
+            } => {
+                match key.code {
+                    KeyCode::Enter => {
+                        if let Some(value) = state.selected().and_then(|i| options.get(i)) {
+                            self.set_field(field, value.clone());
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {}
+                    code => {
+                        match code {
+                            KeyCode::Down | KeyCode::Char('j') => state.select_next(),
+                            KeyCode::Up | KeyCode::Char('k') => state.select_previous(),
+                            _ => {}
+                        }
+                        self.mode = Mode::Pick {
+                            field,
+                            options,
+                            state,
+                        };
+                        return;
+                    }
+                }
+                self.set_editing(false);
+            }
+            Mode::Comment { reply_to, mut text } => {
+                match key.code {
+                    KeyCode::Enter if !text.trim().is_empty() => {
+                        self.add_comment(reply_to, text.trim().to_string());
+                    }
+                    KeyCode::Esc => {}
+                    code => {
+                        edit_text(&mut text, code);
+                        self.mode = Mode::Comment { reply_to, text };
+                        return;
+                    }
+                }
+                self.set_editing(false);
+            }
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc if self.focus != Focus::List => self.focus = Focus::List,
+            KeyCode::Esc if !self.search.is_empty() => {
+                self.search.clear();
+                self.refresh_rows();
+            }
+            KeyCode::Char('?') => self.mode = Mode::Help,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Home | KeyCode::Char('g') if self.focus == Focus::List => self.select_row(0),
+            KeyCode::End | KeyCode::Char('G') if self.focus == Focus::List => {
+                self.select_row(usize::MAX)
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.perspective == Perspective::Tree => {
+                self.collapse()
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.perspective == Perspective::Tree => {
+                self.expand()
+            }
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::List => Focus::Description,
+                    Focus::Description => Focus::Comments,
+                    Focus::Comments => Focus::List,
+                };
+                if self.focus == Focus::Comments && self.comment_state.selected().is_none() {
+                    let has_comments = !self.comment_rows().is_empty();
+                    self.comment_state.select(has_comments.then_some(0));
+                }
+            }
+            KeyCode::Char('t') => {
+                self.perspective = match self.perspective {
+                    Perspective::List => Perspective::Tree,
+                    Perspective::Tree => Perspective::List,
+                };
+                self.refresh_rows();
+            }
+            KeyCode::Char('/') => self.mode = Mode::Search,
+            KeyCode::Char('f') => self.mode = Mode::Filter(self.filter_text.clone()),
+            KeyCode::Char('F') => {
+                self.query = RequirementQuery::default();
+                self.filter_text.clear();
+                self.search.clear();
+                self.refresh_rows();
+            }
+            KeyCode::Char('s') => self.open_picker(PickField::Status),
+            KeyCode::Char('p') => self.open_picker(PickField::Priority),
+            KeyCode::Char('c') => self.open_comment(false),
+            KeyCode::Char('r') => self.open_comment(true),
+            KeyCode::Char('R') => {
+                self.reload();
+                self.message = Some("Reloaded".into());
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(body);
+
+        self.draw_header(frame, header);
+        self.draw_list(frame, list_area);
+        self.draw_detail(frame, detail_area);
+        self.draw_footer(frame, footer);
+
+        match &mut self.mode {
+            Mode::Pick {
+                field,
+                options,
+                state,
+            } => {
+                let title = match field {
+                    PickField::Status => " Status ",
+                    PickField::Priority => " Priority ",
+                };
+                let height = options.len() as u16 + 2;
+                let area = centered(frame.area(), 30, height);
+                let items: Vec<ListItem> =
+                    options.iter().map(|o| ListItem::new(o.as_str())).collect();
+                let list = List::new(items)
+                    .block(Block::default().borders(Borders::ALL).title(title))
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+                frame.render_widget(Clear, area);
+                frame.render_stateful_widget(list, area, state);
+            }
+            Mode::Comment { reply_to, text } => {
+                let title = if reply_to.is_some() {
+                    " Reply (Enter to post, Esc to cancel) "
+                } else {
+                    " New comment (Enter to post, Esc to cancel) "
+                };
+                let area = centered(frame.area(), 70, 5);
+                let input = Paragraph::new(format!("{}▏", text))
+                    .wrap(Wrap { trim: false })
+                    .block(Block::default().borders(Borders::ALL).title(title));
+                frame.render_widget(Clear, area);
+                frame.render_widget(input, area);
+            }
+            Mode::Help => {
+                let area = centered(frame.area(), 60, HELP.len() as u16 + 2);
+                let lines: Vec<Line> = HELP
+                    .iter()
+                    .map(|(keys, action)| {
+                        Line::from(vec![
+                            Span::styled(format!("{:<14}", keys), Style::default().fg(Color::Cyan)),
+                            Span::raw(*action),
+                        ])
+                    })
+                    .collect();
+                let help = Paragraph::new(lines)
+                    .block(Block::default().borders(Borders::ALL).title(" Keys "));
+                frame.render_widget(Clear, area);
+                frame.render_widget(help, area);
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_header(&self, frame: &mut Frame, area: Rect) {
+        let perspective = match self.perspective {
+            Perspective::List => "List",
+            Perspective::Tree => "Tree",
+        };
+        let matched = self.rows.iter().filter(|r| r.matched).count();
+        let project = if self.store.name.is_empty() {
+            self.storage
+                .path()
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        } else {
+            self.store.name.clone()
+        };
+        let mut spans = vec![
+            Span::styled(" AIDA ", Style::default().fg(Color::Black).bg(Color::Cyan)),
+            Span::raw(format!(
+                " {} · {} · {} shown",
+                project, perspective, matched
+            )),
+        ];
+        if !self.filter_text.is_empty() {
+            spans.push(Span::styled(
+                format!(" · filter: {}", self.filter_text),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if !self.search.is_empty() || matches!(self.mode, Mode::Search) {
+            spans.push(Span::styled(
+                format!(" · search: {}", self.search),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        frame.render_widget(Line::from(spans), area);
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .filter_map(|row| {
+                let req = self.store.get_requirement_by_id(&row.id)?;
+                let marker = match (self.perspective, row.has_children) {
+                    (Perspective::Tree, true) if self.collapsed.contains(&row.id) => "▸ ",
+                    (Perspective::Tree, true) => "▾ ",
+                    (Perspective::Tree, false) => "  ",
+                    (Perspective::List, _) => "",
+                };
+                let dim = if row.matched {
+                    Style::default()
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Some(ListItem::new(Line::from(vec![
+                    Span::raw(format!("{}{}", "  ".repeat(row.depth), marker)),
+                    Span::styled(
+                        format!("{} ", req.spec_id.as_deref().unwrap_or("-")),
+                        dim.fg(if row.matched {
+                            Color::Cyan
+                        } else {
+                            Color::DarkGray
+                        }),
+                    ),
+                    Span::styled(req.title.clone(), dim),
+                    Span::styled(
+                        format!("  {}", req.effective_status()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(pane(" Requirements ", self.focus == Focus::List))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn draw_detail(&mut self, frame: &mut Frame, area: Rect) {
+        let block = pane(" Details ", self.focus != Focus::List);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let Some(req) = self.selected() else {
+            frame.render_widget(Paragraph::new("No requirements match"), inner);
+            return;
+        };
+
+        let [tabs_area, content] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+        let comment_count = self.comment_rows().len();
+        let tabs = Tabs::new(vec![
+            "Description".to_string(),
+            format!("Comments ({})", comment_count),
+        ])
+        .select(usize::from(self.focus == Focus::Comments))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_widget(tabs, tabs_area);
+
+        if self.focus == Focus::Comments {
+            self.draw_comments(frame, content);
+            return;
+        }
+
+        let mut lines = self.summary_lines(req);
+        lines.push(Line::default());
+        lines.extend(markdown_lines(&req.description));
+        let paragraph = Paragraph::new(Text::from(lines))
+            .wrap(Wrap { trim: false })
+            .scroll((self.description_scroll, 0));
+        frame.render_widget(paragraph, content);
+    }
+
+    /// Title, fields, links and other sessions editing the requirement
+    fn summary_lines(&self, req: &Requirement) -> Vec<Line<'static>> {
+        let label = |id: &Uuid| {
+            self.store
+                .get_requirement_by_id(id)
+                .map(|r| format!("{} {}", r.spec_id.as_deref().unwrap_or("-"), r.title))
+                .unwrap_or_else(|| id.to_string())
+        };
+        let key =
+            |k: &str| Span::styled(format!("{:<10}", k), Style::default().fg(Color::DarkGray));
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                format!("{} ", req.spec_id.as_deref().unwrap_or("-")),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                req.title.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ])];
+        let mut tags: Vec<&String> = req.tags.iter().collect();
+        tags.sort();
+        let fields = [
+            ("Type", format!("{:?}", req.req_type)),
+            ("Status", req.effective_status()),
+            ("Priority", req.effective_priority()),
+            ("Feature", req.feature.clone()),
+            ("Owner", req.owner.clone()),
+            (
+                "Tags",
+                tags.iter()
+                    .map(|t| t.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        ];
+        for (name, value) in fields {
+            if !value.is_empty() {
+                lines.push(Line::from(vec![key(name), Span::raw(value)]));
+            }
+        }
+        for rel in &req.relationships {
+            lines.push(Line::from(vec![
+                key(&rel.rel_type.to_string()),
+                Span::raw(label(&rel.target_id)),
+            ]));
+        }
+        for session in self.sessions.get_editors(req.id) {
+            if session.session_id != self.heartbeat.session_id() {
+                lines.push(Line::styled(
+                    format!(
+                        "⚠ {} is editing this on {}",
+                        session.user_name, session.hostname
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+        lines
+    }
+
+    fn draw_comments(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self.comment_rows();
+        if rows.is_empty() {
+            frame.render_widget(Paragraph::new("No comments yet. Press c to add one."), area);
+            return;
+        }
+        let width = area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| {
+                let indent = "│ ".repeat(row.depth);
+                let mut header = vec![
+                    Span::raw(indent.clone()),
+                    Span::styled(row.author.clone(), Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        format!(" · {}", row.created_at),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ];
+                for (reaction, count) in &row.reactions {
+                    header.push(Span::raw(format!("  {} {}", reaction, count)));
+                }
+                let mut lines = vec![Line::from(header)];
+                for line in row.content.lines() {
+                    for chunk in wrap(line, width.saturating_sub(indent.chars().count()).max(10)) {
+                        lines.push(Line::raw(format!("{}{}", indent, chunk)));
+                    }
+                }
+                lines.push(Line::default());
+                ListItem::new(lines)
+            })
+            .collect();
+        let list = List::new(items).highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(list, area, &mut self.comment_state);
+    }
+
+    fn draw_footer(&self, frame: &mut Frame, area: Rect) {
+        let others: Vec<String> = self
+            .sessions
+            .get_other_sessions(self.heartbeat.session_id())
+            .into_iter()
+            .map(|s| match &s.editing_requirement {
+                Some(lock) => format!("{} ({})", s.user_name, lock.spec_id),
+                None => s.user_name.clone(),
+            })
+            .collect();
+        let presence = if others.is_empty() {
+            String::new()
+        } else {
+            format!("👥 {} ", others.join(", "))
+        };
+
+        let left = match (&self.mode, &self.message) {
+            (Mode::Search, _) => " Type to search · Enter keep · Esc clear".to_string(),
+            (Mode::Filter(text), message) => format!(
+                " Filter: {}▏  {}",
+                text,
+                message
+                    .as_deref()
+                    .unwrap_or("e.g. status=Draft type=story feature=Auth tag=ui")
+            ),
+            (_, Some(message)) => format!(" {}", message),
+            _ => " ? help · / search · f filter · t tree · s status · p priority · c comment · q quit"
+                .to_string(),
+        };
+        let [left_area, right_area] = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(presence.chars().count() as u16),
+        ])
+        .areas(area);
+        frame.render_widget(
+            Paragraph::new(left).style(Style::default().fg(Color::Gray)),
+            left_area,
+        );
+        frame.render_widget(
+            Paragraph::new(presence).style(Style::default().fg(Color::Green)),
+            right_area,
+        );
+    }
+}
+
+const HELP: &[(&str, &str)] = &[
+    ("j/k ↑/↓", "Move, or scroll the focused pane"),
+    ("PgUp/PgDn g/G", "Page, jump to top or bottom"),
+    ("Tab", "Focus list, description, comments"),
+    ("t", "Toggle list and parent/child tree"),
+    ("h/l ←/→", "Collapse or expand a tree node"),
+    ("/", "Search SPEC-ID, title and description"),
+    ("f", "Filter, e.g. status=Draft type=story"),
+    ("F", "Clear filter and search"),
+    ("s / p", "Change status / priority"),
+    ("c / r", "Comment / reply to selected comment"),
+    ("R", "Reload from disk"),
+    ("q", "Quit"),
+];
+
+fn modified_time(storage: &Storage) -> Option<SystemTime> {
+    std::fs::metadata(storage.path())
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+fn edit_text(text: &mut String, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => text.push(c),
+        KeyCode::Backspace => {
+            text.pop();
+        }
+        _ => {}
+    }
+}
+
+fn pane(title: &str, focused: bool) -> Block<'_> {
+    let style = if focused {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(style)
+        .title(title)
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Splits a line into chunks of at most `width` characters at spaces
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut chunks = vec![String::new()];
+    for word in line.split(' ') {
+        let current = chunks.last_mut().expect("never empty");
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            chunks.push(word.to_string());
+        } else {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+    }
+    chunks
+}
+
+/// Renders Markdown as styled terminal lines
+fn markdown_lines(markdown: &str) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut styles = vec![Style::default()];
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut quote_depth = 0;
+    let mut in_code_block = false;
+
+    let flush = |spans: &mut Vec<Span<'static>>, lines: &mut Vec<Line<'static>>, quote: usize| {
+        if spans.is_empty() {
+            return;
+        }
+        let mut line = Vec::new();
+        if quote > 0 {
+            line.push(Span::styled(
+                "│ ".repeat(quote),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        line.append(spans);
+        lines.push(Line::from(line));
+    };
+    let blank = |lines: &mut Vec<Line<'static>>| {
+        if lines.last().is_some_and(|l| !l.spans.is_empty()) {
+            lines.push(Line::default());
+        }
+    };
+
+    for event in Parser::new(markdown) {
+        let style = *styles.last().expect("base style");
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                let heading = Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD);
+                styles.push(if level == HeadingLevel::H1 {
+                    heading.add_modifier(Modifier::UNDERLINED)
+                } else {
+                    heading
+                });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                styles.pop();
+                flush(&mut spans, &mut lines, quote_depth);
+                blank(&mut lines);
+            }
+            Event::End(TagEnd::Paragraph) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                if lists.is_empty() {
+                    blank(&mut lines);
+                }
+            }
+            Event::Start(Tag::Emphasis) => styles.push(style.add_modifier(Modifier::ITALIC)),
+            Event::Start(Tag::Strong) => styles.push(style.add_modifier(Modifier::BOLD)),
+            Event::Start(Tag::Link { .. }) => {
+                styles.push(style.fg(Color::Blue).add_modifier(Modifier::UNDERLINED))
+            }
+            Event::End(TagEnd::Emphasis | TagEnd::Strong | TagEnd::Link) => {
+                styles.pop();
+            }
+            Event::Start(Tag::BlockQuote(_)) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                quote_depth += 1;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                quote_depth -= 1;
+                blank(&mut lines);
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                blank(&mut lines);
+            }
+            Event::Start(Tag::List(start)) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                lists.pop();
+                if lists.is_empty() {
+                    blank(&mut lines);
+                }
+            }
+            Event::Start(Tag::Item) => {
+                flush(&mut spans, &mut lines, quote_depth);
+                let indent = "  ".repeat(lists.len().saturating_sub(1));
+                let bullet = match lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}{}. ", indent, *n - 1)
+                    }
+                    _ => format!("{}• ", indent),
+                };
+                spans.push(Span::styled(bullet, Style::default().fg(Color::DarkGray)));
+            }
+            Event::End(TagEnd::Item) => flush(&mut spans, &mut lines, quote_depth),
+            Event::Text(text) if in_code_block => {
+                for line in text.lines() {
+                    lines.push(Line::styled(
+                        format!("    {}", line),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+            }
+            Event::Text(text) => spans.push(Span::styled(text.to_string(), style)),
+            Event::Code(code) => spans.push(Span::styled(
+                code.to_string(),
+                Style::default().fg(Color::Yellow),
+            )),
+            Event::Html(html) | Event::InlineHtml(html) => spans.push(Span::styled(
+                html.trim_end().to_string(),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Event::SoftBreak => spans.push(Span::raw(" ")),
+            Event::HardBreak => flush(&mut spans, &mut lines, quote_depth),
+            Event::Rule => {
+                flush(&mut spans, &mut lines, quote_depth);
+                lines.push(Line::styled(
+                    "────────────",
+                    Style::default().fg(Color::DarkGray),
+                ));
+                blank(&mut lines);
+            }
+            _ => {}
+        }
+    }
+    flush(&mut spans, &mut lines, quote_depth);
+    while lines.last().is_some_and(|l| l.spans.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
@@ -6,13 +6,24 @@ use crate::models::{Requirement, RequirementsStore};
 use crate::ai::prompts;
 use crate::ai::responses::{
     self, DuplicatesResponse, EvaluationResponse, GenerateChildrenResponse,
-    ImproveDescriptionResponse, SuggestRelationshipsResponse,
+    ImproveDescriptionResponse, ResponseSchema, SuggestRelationshipsResponse,
 };
+use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
 
+/// Messages API endpoint host, overridable with `ANTHROPIC_BASE_URL`
+pub const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+
+/// Model used by the direct API, overridable with `ANTHROPIC_MODEL`
+pub const DEFAULT_MODEL: &str = "claude-sonnet-4-5";
+
+/// `anthropic-version` header sent with every request
+const API_VERSION: &str = "2023-06-01";
+
 /// Errors that can occur during AI operations
 #[derive(Error, Debug)]
 pub enum AiError {
@@ -49,8 +60,8 @@ pub enum AiError {
 pub enum AiMode {
     /// Use Claude CLI with --print flag
     ClaudeCli { path: PathBuf },
-    /// Direct API integration (future)
-    DirectApi { api_key: String },
+    /// Call the Anthropic Messages API over HTTP
+    DirectApi(ApiConfig),
     /// AI features disabled
     Disabled,
 }
@@ -61,6 +72,80 @@ impl Default for AiMode {
     }
 }
 
+/// Settings for [`AiMode::DirectApi`]
+#[derive(Debug, Clone)]
+pub struct ApiConfig {
+    pub api_key: String,
+    /// Scheme and host, without the `/v1/messages` path
+    pub base_url: String,
+    pub model: String,
+    pub max_tokens: u32,
+    /// Timeout for a single request
+    pub timeout: Duration,
+    /// Retries after a rate-limited or overloaded response
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each further attempt
+    pub retry_delay: Duration,
+}
+
+impl ApiConfig {
+    /// Default settings for an API key
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            model: DEFAULT_MODEL.to_string(),
+            max_tokens: 4096,
+            timeout: Duration::from_secs(120),
+            max_retries: 3,
+            retry_delay: Duration::from_secs(2),
+        }
+    }
+
+    /// Default settings with `ANTHROPIC_BASE_URL` and `ANTHROPIC_MODEL` applied
+    pub fn from_env(api_key: impl Into<String>) -> Self {
+        let mut config = Self::new(api_key);
+        let var = |name| std::env::var(name).ok().filter(|v| !v.is_empty());
+        if let Some(url) = var("ANTHROPIC_BASE_URL") {
+            config.base_url = url;
+        }
+        if let Some(model) = var("ANTHROPIC_MODEL") {
+            config.model = model;
+        }
+        config
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_retries(mut self, max_retries: u32, retry_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    fn messages_url(&self) -> String {
+        format!("{}/v1/messages", self.base_url.trim_end_matches('/'))
+    }
+}
+
 /// AI Client for interacting with Claude
 #[derive(Debug, Clone)]
 pub struct AiClient {
@@ -95,7 +180,7 @@ impl AiClient {
         // Could check for API key in environment
         if let Ok(api_key) = std::env::var("ANTHROPIC_API_KEY") {
             if !api_key.is_empty() {
-                return AiMode::DirectApi { api_key };
+                return AiMode::DirectApi(ApiConfig::from_env(api_key));
             }
         }
 
@@ -147,7 +232,7 @@ impl AiClient {
     pub fn is_available(&self) -> bool {
         match &self.mode {
             AiMode::ClaudeCli { path } => path.exists(),
-            AiMode::DirectApi { api_key } => !api_key.is_empty(),
+            AiMode::DirectApi(config) => !config.api_key.is_empty(),
             AiMode::Disabled => false,
         }
     }
@@ -161,7 +246,7 @@ impl AiClient {
     pub fn mode_description(&self) -> String {
         match &self.mode {
             AiMode::ClaudeCli { path } => format!("Claude CLI ({})", path.display()),
-            AiMode::DirectApi { .. } => "Direct API".to_string(),
+            AiMode::DirectApi(config) => format!("Direct API ({})", config.model),
             AiMode::Disabled => "Disabled".to_string(),
         }
     }
@@ -173,7 +258,7 @@ impl AiClient {
         store: &RequirementsStore,
     ) -> Result<EvaluationResponse, AiError> {
         let prompt = prompts::build_evaluation_prompt(req, store);
-        let response = self.send_request(&prompt)?;
+        let response = self.send_request(&prompt, &ResponseSchema::evaluation())?;
         responses::parse_evaluation_response(&response)
     }
 
@@ -184,7 +269,7 @@ impl AiClient {
         store: &RequirementsStore,
     ) -> Result<DuplicatesResponse, AiError> {
         let prompt = prompts::build_duplicates_prompt(req, store);
-        let response = self.send_request(&prompt)?;
+        let response = self.send_request(&prompt, &ResponseSchema::duplicates())?;
         responses::parse_duplicates_response(&response)
     }
 
@@ -195,7 +280,7 @@ impl AiClient {
         store: &RequirementsStore,
     ) -> Result<SuggestRelationshipsResponse, AiError> {
         let prompt = prompts::build_relationships_prompt(req, store);
-        let response = self.send_request(&prompt)?;
+        let response = self.send_request(&prompt, &ResponseSchema::relationships())?;
         responses::parse_relationships_response(&response)
     }
 
@@ -206,7 +291,7 @@ impl AiClient {
         store: &RequirementsStore,
     ) -> Result<ImproveDescriptionResponse, AiError> {
         let prompt = prompts::build_improve_prompt(req, store);
-        let response = self.send_request(&prompt)?;
+        let response = self.send_request(&prompt, &ResponseSchema::improve_description())?;
         responses::parse_improve_response(&response)
     }
 
@@ -217,18 +302,15 @@ impl AiClient {
         store: &RequirementsStore,
     ) -> Result<GenerateChildrenResponse, AiError> {
         let prompt = prompts::build_generate_children_prompt(req, store);
-        let response = self.send_request(&prompt)?;
+        let response = self.send_request(&prompt, &ResponseSchema::generate_children())?;
         responses::parse_generate_children_response(&response)
     }
 
-    /// Send a request to the AI
-    fn send_request(&self, prompt: &str) -> Result<String, AiError> {
+    /// Send a request to the AI; the API mode asks for JSON matching `schema`
+    fn send_request(&self, prompt: &str, schema: &ResponseSchema) -> Result<String, AiError> {
         match &self.mode {
             AiMode::ClaudeCli { path } => self.send_cli_request(path, prompt),
-            AiMode::DirectApi { api_key: _ } => {
-                // Future: implement direct API
-                Err(AiError::NotAvailable)
-            }
+            AiMode::DirectApi(config) => send_api_request(config, prompt, schema),
             AiMode::Disabled => Err(AiError::NotAvailable),
         }
     }
@@ -263,9 +345,149 @@ impl AiClient {
     }
 }
 
+/// Sends a prompt to the Messages API, retrying with backoff while rate
+/// limited
+fn send_api_request(
+    config: &ApiConfig,
+    prompt: &str,
+    schema: &ResponseSchema,
+) -> Result<String, AiError> {
+    if config.api_key.is_empty() {
+        return Err(AiError::ApiKeyMissing);
+    }
+
+    // Forcing a call to a tool whose input schema is the response format
+    // makes the model reply with a JSON object
+    let body = json!({
+        "model": config.model,
+        "max_tokens": config.max_tokens,
+        "messages": [{ "role": "user", "content": prompt }],
+        "tools": [{
+            "name": schema.name,
+            "description": schema.description,
+            "input_schema": schema.schema,
+        }],
+        "tool_choice": { "type": "tool", "name": schema.name },
+    })
+    .to_string();
+    let agent = ureq::AgentBuilder::new().timeout(config.timeout).build();
+
+    let mut attempt = 0;
+    loop {
+        let result = agent
+            .post(&config.messages_url())
+            .set("x-api-key", &config.api_key)
+            .set("anthropic-version", API_VERSION)
+            .set("content-type", "application/json")
+            .send_string(&body);
+
+        let retry_after = match result {
+            Ok(response) => {
+                let message: Value = response
+                    .into_string()
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+                    .map_err(AiError::InvalidResponse)?;
+                return message_output(&message);
+            }
+            Err(ureq::Error::Status(code, response)) => {
+                let retry_after = response
+                    .header("retry-after")
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .map(Duration::from_secs);
+                match status_error(code, response) {
+                    AiError::RateLimited if attempt < config.max_retries => retry_after,
+                    error => return Err(error),
+                }
+            }
+            Err(e) => return Err(AiError::ApiRequestFailed(e.to_string())),
+        };
+
+        std::thread::sleep(retry_after.unwrap_or(config.retry_delay * 2u32.pow(attempt)));
+        attempt += 1;
+    }
+}
+
+/// Maps an HTTP error status to an [`AiError`]
+fn status_error(code: u16, response: ureq::Response) -> AiError {
+    let body: Value = response
+        .into_string()
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or(Value::Null);
+    let message = body["error"]["message"].as_str().unwrap_or_default();
+    match code {
+        // 529 is "overloaded", which clears like a rate limit
+        429 | 529 => AiError::RateLimited,
+        413 => AiError::ContextTooLarge,
+        400 if message.contains("prompt is too long") => AiError::ContextTooLarge,
+        _ => AiError::ApiRequestFailed(format!("HTTP {}: {}", code, message)),
+    }
+}
+
+/// The JSON input of the forced tool call, or the text of the reply if the
+/// model answered in text
+fn message_output(message: &Value) -> Result<String, AiError> {
+    let content = message["content"].as_array().cloned().unwrap_or_default();
+    if let Some(tool_use) = content.iter().find(|block| block["type"] == "tool_use") {
+        return Ok(tool_use["input"].to_string());
+    }
+
+    let text: String = content
+        .iter()
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    if text.is_empty() {
+        return Err(AiError::InvalidResponse(format!(
+            "No content in API response (stop reason: {})",
+            message["stop_reason"].as_str().unwrap_or("unknown")
+        )));
+    }
+    Ok(text)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::Requirement;
+    use std::sync::{Arc, Mutex};
+
+    /// Serves the canned `(status, body)` responses in order, recording the
+    /// request bodies
+    fn mock_server(responses: Vec<(u16, Value)>) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        std::thread::spawn(move || {
+            for (mut request, (status, body)) in server.incoming_requests().zip(responses) {
+                let mut text = String::new();
+                request.as_reader().read_to_string(&mut text).unwrap();
+                let mut entry: Value = serde_json::from_str(&text).unwrap();
+                for header in ["x-api-key", "anthropic-version"] {
+                    entry[header] = request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv(header))
+                        .map(|h| Value::from(h.value.as_str()))
+                        .unwrap_or(Value::Null);
+                }
+                log.lock().unwrap().push(entry);
+                let response =
+                    tiny_http::Response::from_string(body.to_string()).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+        (url, received)
+    }
+
+    fn api_client(url: &str) -> AiClient {
+        let config = ApiConfig::new("test-key")
+            .with_base_url(url)
+            .with_model("test-model")
+            .with_retries(2, Duration::from_millis(10));
+        AiClient::with_mode(AiMode::DirectApi(config))
+    }
 
     #[test]
     fn test_mode_detection() {
@@ -281,4 +503,68 @@ mod tests {
         assert!(!client.is_available());
         assert_eq!(client.mode_description(), "Disabled");
     }
+
+    #[test]
+    fn test_direct_api_structured_output() {
+        let evaluation = json!({
+            "quality_score": 8,
+            "issues": [],
+            "strengths": ["Testable"],
+            "suggested_improvements": null,
+        });
+        let (url, received) = mock_server(vec![(
+            200,
+            json!({
+                "content": [{ "type": "tool_use", "name": "report_evaluation", "input": evaluation }],
+                "stop_reason": "tool_use",
+            }),
+        )]);
+
+        let client = api_client(&url);
+        assert_eq!(client.mode_description(), "Direct API (test-model)");
+        let req = Requirement::new("Login".into(), "Users log in".into());
+        let response = client
+            .evaluate_requirement(&req, &RequirementsStore::new())
+            .unwrap();
+        assert_eq!(response.quality_score, 8);
+
+        let request = &received.lock().unwrap()[0];
+        assert_eq!(request["model"], "test-model");
+        assert_eq!(request["x-api-key"], "test-key");
+        assert_eq!(request["anthropic-version"], API_VERSION);
+        assert_eq!(request["tool_choice"]["name"], "report_evaluation");
+        assert_eq!(request["tools"][0]["input_schema"]["type"], "object");
+    }
+
+    #[test]
+    fn test_direct_api_retries_when_rate_limited() {
+        let limited = json!({ "error": { "type": "rate_limit_error", "message": "slow down" } });
+        let text = json!({
+            "content": [{
+                "type": "text",
+                "text": "```json\n{\"potential_duplicates\": []}\n```",
+            }],
+        });
+        let (url, received) = mock_server(vec![(429, limited.clone()), (200, text)]);
+        let req = Requirement::new("Login".into(), String::new());
+        let store = RequirementsStore::new();
+
+        // Text replies still go through the JSON extraction fallback
+        let response = api_client(&url).find_duplicates(&req, &store).unwrap();
+        assert!(response.potential_duplicates.is_empty());
+        assert_eq!(received.lock().unwrap().len(), 2);
+
+        let (url, _) = mock_server(vec![(429, limited.clone()); 3]);
+        let result = api_client(&url).find_duplicates(&req, &store);
+        assert!(matches!(result, Err(AiError::RateLimited)));
+
+        let (url, _) = mock_server(vec![(
+            401,
+            json!({ "error": { "message": "invalid x-api-key" } }),
+        )]);
+        let result = api_client(&url).find_duplicates(&req, &store);
+        assert!(
+            matches!(result, Err(AiError::ApiRequestFailed(m)) if m == "HTTP 401: invalid x-api-key")
+        );
+    }
 }
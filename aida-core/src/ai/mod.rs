@@ -1,16 +1,16 @@
 //! AI Integration Module for AIDA
 //!
 //! This module provides AI-powered analysis and suggestions for requirements
-//! management using Claude Code CLI integration.
+//! management, through the Claude CLI or the Anthropic Messages API.
 
 pub mod client;
 pub mod evaluator;
 pub mod prompts;
 pub mod responses;
 
-pub use client::{AiClient, AiError, AiMode};
+pub use client::{AiClient, AiError, AiMode, ApiConfig};
 pub use evaluator::{BackgroundEvaluator, EvaluationResult, EvaluatorConfig, EvaluatorStatus};
 pub use responses::{
     DuplicateResult, EvaluationResponse, GeneratedChild, IssueReport, RelationshipSuggestion,
-    StoredAiEvaluation, SuggestedImprovement,
+    ResponseSchema, StoredAiEvaluation, SuggestedImprovement,
 };
@@ -121,6 +121,9 @@ fn requirement_to_context(req: &Requirement) -> String {
 ### Description
 {}
 
+### Acceptance Criteria
+{}
+
 ### Existing Relationships
 {}"#,
         req.spec_id.as_deref().unwrap_or("(not assigned)"),
@@ -144,6 +147,15 @@ fn requirement_to_context(req: &Requirement) -> String {
         } else {
             &req.description
         },
+        if req.acceptance_criteria.is_empty() {
+            "(none)".to_string()
+        } else {
+            req.acceptance_criteria
+                .iter()
+                .map(|ac| format!("- {}", ac.checklist_line()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
         if req.relationships.is_empty() {
             "(none)".to_string()
         } else {
@@ -675,6 +687,19 @@ mod tests {
         assert!(prompt.contains("quality_score"));
     }
 
+    #[test]
+    fn test_evaluation_prompt_includes_acceptance_criteria() {
+        let mut req = create_test_req();
+        req.add_acceptance_criterion(
+            crate::models::AcceptanceCriterion::new("", "Invalid password shows an error"),
+            "tester",
+        );
+        let prompt = build_evaluation_prompt(&req, &create_test_store());
+
+        assert!(prompt.contains("### Acceptance Criteria"));
+        assert!(prompt.contains("[ ] AC-1: Invalid password shows an error"));
+    }
+
     #[test]
     fn test_build_duplicates_prompt() {
         let req = create_test_req();
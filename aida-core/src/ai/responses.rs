@@ -4,7 +4,9 @@
 
 use crate::ai::client::AiError;
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 /// Issue found in a requirement
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,6 +118,150 @@ pub struct GenerateChildrenResponse {
     pub suggested_children: Vec<GeneratedChild>,
 }
 
+/// JSON Schema of an AI response
+///
+/// The direct API sends it as the input schema of a forced tool call, so the
+/// model replies with a JSON object instead of free text.
+#[derive(Debug, Clone)]
+pub struct ResponseSchema {
+    /// Tool name the response is requested under
+    pub name: &'static str,
+    pub description: &'static str,
+    pub schema: Value,
+}
+
+impl ResponseSchema {
+    /// Schema of [`EvaluationResponse`]
+    pub fn evaluation() -> Self {
+        Self {
+            name: "report_evaluation",
+            description: "Report the quality evaluation of the requirement",
+            schema: object(
+                json!({
+                    "quality_score": { "type": "integer", "minimum": 1, "maximum": 10 },
+                    "issues": array(object(
+                        json!({
+                            "type": string(),
+                            "severity": { "type": "string", "enum": ["low", "medium", "high"] },
+                            "text": string(),
+                            "suggestion": string(),
+                        }),
+                        &["type", "severity", "text", "suggestion"],
+                    )),
+                    "strengths": array(string()),
+                    "suggested_improvements": {
+                        "type": ["object", "null"],
+                        "properties": {
+                            "description": { "type": ["string", "null"] },
+                            "rationale": string(),
+                        },
+                        "required": ["rationale"],
+                    },
+                }),
+                &["quality_score", "issues", "strengths"],
+            ),
+        }
+    }
+
+    /// Schema of [`DuplicatesResponse`]
+    pub fn duplicates() -> Self {
+        Self {
+            name: "report_duplicates",
+            description: "Report requirements that may duplicate this one",
+            schema: object(
+                json!({
+                    "potential_duplicates": array(object(
+                        json!({
+                            "spec_id": string(),
+                            "similarity": number(),
+                            "reason": string(),
+                            "recommendation": string(),
+                        }),
+                        &["spec_id", "similarity", "reason", "recommendation"],
+                    )),
+                }),
+                &["potential_duplicates"],
+            ),
+        }
+    }
+
+    /// Schema of [`SuggestRelationshipsResponse`]
+    pub fn relationships() -> Self {
+        Self {
+            name: "suggest_relationships",
+            description: "Suggest relationships to other requirements",
+            schema: object(
+                json!({
+                    "suggested_relationships": array(object(
+                        json!({
+                            "rel_type": string(),
+                            "target_spec_id": string(),
+                            "confidence": number(),
+                            "rationale": string(),
+                        }),
+                        &["rel_type", "target_spec_id", "confidence", "rationale"],
+                    )),
+                }),
+                &["suggested_relationships"],
+            ),
+        }
+    }
+
+    /// Schema of [`ImproveDescriptionResponse`]
+    pub fn improve_description() -> Self {
+        Self {
+            name: "improve_description",
+            description: "Return the improved requirement description",
+            schema: object(
+                json!({
+                    "improved_description": string(),
+                    "changes_made": array(string()),
+                    "rationale": string(),
+                }),
+                &["improved_description", "changes_made", "rationale"],
+            ),
+        }
+    }
+
+    /// Schema of [`GenerateChildrenResponse`]
+    pub fn generate_children() -> Self {
+        Self {
+            name: "suggest_children",
+            description: "Suggest child requirements that together cover the parent",
+            schema: object(
+                json!({
+                    "suggested_children": array(object(
+                        json!({
+                            "title": string(),
+                            "description": string(),
+                            "type": string(),
+                            "rationale": string(),
+                        }),
+                        &["title", "description", "type", "rationale"],
+                    )),
+                }),
+                &["suggested_children"],
+            ),
+        }
+    }
+}
+
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn number() -> Value {
+    json!({ "type": "number" })
+}
+
 /// Extract JSON from a response that may contain markdown code blocks
 fn extract_json(response: &str) -> &str {
     // Look for JSON in markdown code block
@@ -152,67 +298,53 @@ fn extract_json(response: &str) -> &str {
     response.trim()
 }
 
-/// Parse evaluation response from AI
-pub fn parse_evaluation_response(response: &str) -> Result<EvaluationResponse, AiError> {
+/// Parses a response that is plain JSON (structured output), falling back to
+/// extracting JSON from surrounding text
+fn parse_json<T: DeserializeOwned>(response: &str, what: &str) -> Result<T, AiError> {
+    if let Ok(parsed) = serde_json::from_str(response.trim()) {
+        return Ok(parsed);
+    }
     let json_str = extract_json(response);
     serde_json::from_str(json_str).map_err(|e| {
+        let end = json_str
+            .char_indices()
+            .nth(200)
+            .map_or(json_str.len(), |(i, _)| i);
         AiError::InvalidResponse(format!(
-            "Failed to parse evaluation response: {}. JSON: {}",
+            "Failed to parse {} response: {}. JSON: {}",
+            what,
             e,
-            &json_str[..json_str.len().min(200)]
+            &json_str[..end]
         ))
     })
 }
 
+/// Parse evaluation response from AI
+pub fn parse_evaluation_response(response: &str) -> Result<EvaluationResponse, AiError> {
+    parse_json(response, "evaluation")
+}
+
 /// Parse duplicates response from AI
 pub fn parse_duplicates_response(response: &str) -> Result<DuplicatesResponse, AiError> {
-    let json_str = extract_json(response);
-    serde_json::from_str(json_str).map_err(|e| {
-        AiError::InvalidResponse(format!(
-            "Failed to parse duplicates response: {}. JSON: {}",
-            e,
-            &json_str[..json_str.len().min(200)]
-        ))
-    })
+    parse_json(response, "duplicates")
 }
 
 /// Parse relationships response from AI
 pub fn parse_relationships_response(response: &str) -> Result<SuggestRelationshipsResponse, AiError>
 {
-    let json_str = extract_json(response);
-    serde_json::from_str(json_str).map_err(|e| {
-        AiError::InvalidResponse(format!(
-            "Failed to parse relationships response: {}. JSON: {}",
-            e,
-            &json_str[..json_str.len().min(200)]
-        ))
-    })
+    parse_json(response, "relationships")
 }
 
 /// Parse improve description response from AI
 pub fn parse_improve_response(response: &str) -> Result<ImproveDescriptionResponse, AiError> {
-    let json_str = extract_json(response);
-    serde_json::from_str(json_str).map_err(|e| {
-        AiError::InvalidResponse(format!(
-            "Failed to parse improve response: {}. JSON: {}",
-            e,
-            &json_str[..json_str.len().min(200)]
-        ))
-    })
+    parse_json(response, "improve")
 }
 
 /// Parse generate children response from AI
 pub fn parse_generate_children_response(
     response: &str,
 ) -> Result<GenerateChildrenResponse, AiError> {
-    let json_str = extract_json(response);
-    serde_json::from_str(json_str).map_err(|e| {
-        AiError::InvalidResponse(format!(
-            "Failed to parse generate children response: {}. JSON: {}",
-            e,
-            &json_str[..json_str.len().min(200)]
-        ))
-    })
+    parse_json(response, "generate children")
 }
 
 #[cfg(test)]
@@ -324,6 +456,17 @@ That's my evaluation."#;
         assert_eq!(result.changes_made.len(), 2);
     }
 
+    #[test]
+    fn test_plain_json_is_parsed_before_extraction() {
+        // A code fence inside a value would throw off the extraction heuristics
+        let response = r#"{"improved_description": "Run:\n```json\n{}\n```", "changes_made": [], "rationale": "r"}"#;
+        let result = parse_improve_response(response).unwrap();
+        assert!(result.improved_description.contains("```json"));
+
+        let schema = ResponseSchema::improve_description().schema;
+        assert_eq!(schema["required"][0], "improved_description");
+    }
+
     #[test]
     fn test_parse_generate_children_response() {
         let response = r#"```json
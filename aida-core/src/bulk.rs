@@ -0,0 +1,648 @@
+//! Bulk operations on requirements
+//!
+//! Provides a simple query language for selecting requirements and a patch
+//! type describing changes to apply to every requirement in a selection.
+//! Patches are validated against the whole selection before anything is
+//! modified, so a bulk update either applies everywhere or nowhere.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::{FieldChange, Requirement, RequirementsStore};
+
+/// A filter over requirements, parsed from `key=value` terms
+///
+/// Supported keys: `status`, `priority`, `type`, `feature`, `owner`, `tag`,
+/// `prefix` and `text`. Terms are separated by whitespace or commas and are
+/// combined with AND; repeating `tag` matches requirements having any of the tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequirementQuery {
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub req_type: Option<String>,
+    pub feature: Option<String>,
+    pub owner: Option<String>,
+    pub tags: Vec<String>,
+    pub prefix: Option<String>,
+    pub text: Option<String>,
+    pub include_archived: bool,
+}
+
+impl RequirementQuery {
+    /// Parses a query string such as `status=Draft feature=Auth tag=security`
+    pub fn parse(query: &str) -> Result<Self> {
+        let mut parsed = Self::default();
+
+        for term in query
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+        {
+            let Some((key, value)) = term.split_once('=') else {
+                anyhow::bail!("Invalid query term '{}': expected key=value", term);
+            };
+            if value.trim().is_empty() {
+                anyhow::bail!("Query term '{}' has an empty value", term);
+            }
+            parsed.set(key, value)?;
+        }
+
+        Ok(parsed)
+    }
+
+    /// Sets one query term (e.g. key "status", value "Draft")
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let value = value.trim().to_string();
+        match key.trim().to_lowercase().as_str() {
+            "status" => self.status = Some(value),
+            "priority" => self.priority = Some(value),
+            "type" => self.req_type = Some(value),
+            "feature" => self.feature = Some(value),
+            "owner" => self.owner = Some(value),
+            "tag" | "tags" => self.tags.push(value),
+            "prefix" => self.prefix = Some(value.to_uppercase()),
+            "text" => self.text = Some(value.to_lowercase()),
+            "archived" => self.include_archived = value.eq_ignore_ascii_case("true"),
+            other => anyhow::bail!(
+                "Unknown query key '{}'. Supported: status, priority, type, feature, owner, tag, prefix, text, archived",
+                other
+            ),
+        }
+        Ok(())
+    }
+
+    /// Returns true if the requirement satisfies every term of the query
+    pub fn matches(&self, req: &Requirement) -> bool {
+        if req.archived && !self.include_archived {
+            return false;
+        }
+        if let Some(status) = &self.status {
+            if !req.effective_status().eq_ignore_ascii_case(status) {
+                return false;
+            }
+        }
+        if let Some(priority) = &self.priority {
+            if !req.effective_priority().eq_ignore_ascii_case(priority) {
+                return false;
+            }
+        }
+        if let Some(req_type) = &self.req_type {
+            let normalized = req_type.replace(['-', ' '], "");
+            if !format!("{:?}", req.req_type).eq_ignore_ascii_case(&normalized) {
+                return false;
+            }
+        }
+        if let Some(feature) = &self.feature {
+            // Match either the full feature name ("1-Auth") or the name without number
+            let bare = req
+                .feature
+                .split_once('-')
+                .filter(|(n, _)| n.parse::<u32>().is_ok())
+                .map(|(_, name)| name)
+                .unwrap_or(&req.feature);
+            if !req.feature.eq_ignore_ascii_case(feature) && !bare.eq_ignore_ascii_case(feature) {
+                return false;
+            }
+        }
+        if let Some(owner) = &self.owner {
+            if !req.owner.eq_ignore_ascii_case(owner) {
+                return false;
+            }
+        }
+        if !self.tags.is_empty() && !self.tags.iter().any(|t| req.tags.contains(t)) {
+            return false;
+        }
+        if let Some(prefix) = &self.prefix {
+            let spec_prefix = req
+                .spec_id
+                .as_deref()
+                .and_then(|s| s.rsplit_once('-'))
+                .map(|(p, _)| p)
+                .unwrap_or("");
+            if spec_prefix != prefix {
+                return false;
+            }
+        }
+        if let Some(text) = &self.text {
+            if !req.title.to_lowercase().contains(text)
+                && !req.description.to_lowercase().contains(text)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A set of changes to apply to each requirement in a bulk selection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequirementPatch {
+    /// New title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// New description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// New status (validated against each requirement's type)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
+    /// New priority (validated against each requirement's type)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// New owner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// New feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+
+    /// Tags to add
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_tags: Vec<String>,
+
+    /// Tags to remove
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_tags: Vec<String>,
+
+    /// Custom field values to set (key = field name)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_fields: HashMap<String, String>,
+}
+
+impl RequirementPatch {
+    /// Returns true if the patch would not change anything
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.status.is_none()
+            && self.priority.is_none()
+            && self.owner.is_none()
+            && self.feature.is_none()
+            && self.add_tags.is_empty()
+            && self.remove_tags.is_empty()
+            && self.custom_fields.is_empty()
+    }
+
+    /// Sets a field from a `key=value` assignment (e.g., "status=Approved")
+    /// Unknown keys are treated as custom field names.
+    pub fn set_assignment(&mut self, assignment: &str) -> Result<()> {
+        let Some((key, value)) = assignment.split_once('=') else {
+            anyhow::bail!("Invalid assignment '{}': expected field=value", assignment);
+        };
+        let key = key.trim();
+        let value = value.trim().to_string();
+
+        match key.to_lowercase().as_str() {
+            "title" => self.title = Some(value),
+            "description" => self.description = Some(value),
+            "status" => self.status = Some(value),
+            "priority" => self.priority = Some(value),
+            "owner" => self.owner = Some(value),
+            "feature" => self.feature = Some(value),
+            "" => anyhow::bail!("Invalid assignment '{}': missing field name", assignment),
+            _ => {
+                self.custom_fields.insert(key.to_string(), value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a bulk update
+#[derive(Debug, Clone, Default)]
+pub struct BulkUpdateResult {
+    /// Requirements that were changed (one history entry each)
+    pub updated: Vec<Uuid>,
+    /// Requirements already matching the patch
+    pub unchanged: Vec<Uuid>,
+}
+
+impl RequirementsStore {
+    /// Returns the IDs of all requirements matching a query
+    pub fn query_requirements(&self, query: &RequirementQuery) -> Vec<Uuid> {
+        self.requirements
+            .iter()
+            .filter(|r| query.matches(r))
+            .map(|r| r.id)
+            .collect()
+    }
+
+    /// Validates a patch against every requirement in the selection
+    /// Returns all problems found rather than stopping at the first one.
+    pub fn validate_bulk_update(&self, ids: &[Uuid], patch: &RequirementPatch) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if patch.is_empty() {
+            errors.push("Nothing to change: the patch is empty".to_string());
+        }
+        if patch.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            errors.push("Title cannot be empty".to_string());
+        }
+        if patch
+            .feature
+            .as_deref()
+            .is_some_and(|f| f.trim().is_empty())
+        {
+            errors.push("Feature cannot be empty".to_string());
+        }
+        for tag in patch.add_tags.iter().chain(&patch.remove_tags) {
+            if tag.trim().is_empty() {
+                errors.push("Tags cannot be empty".to_string());
+            }
+        }
+        if let Some(owner) = &patch.owner {
+            if let Err(e) = self.resolve_owner(owner) {
+                errors.push(e.to_string());
+            }
+        }
+
+        for id in ids {
+            let Some(req) = self.get_requirement_by_id(id) else {
+                errors.push(format!("Requirement not found: {}", id));
+                continue;
+            };
+            let display_id = req.spec_id.clone().unwrap_or_else(|| id.to_string());
+            let stateless = self.is_type_stateless(&req.req_type);
+
+            if let Some(status) = &patch.status {
+                let statuses = self.get_statuses_for_type(&req.req_type);
+                if stateless {
+                    errors.push(format!(
+                        "{}: type {} has no status",
+                        display_id, req.req_type
+                    ));
+                } else if !statuses.iter().any(|s| s.eq_ignore_ascii_case(status)) {
+                    errors.push(format!(
+                        "{}: invalid status '{}' for type {} (valid: {})",
+                        display_id,
+                        status,
+                        req.req_type,
+                        statuses.join(", ")
+                    ));
+                }
+            }
+
+            if let Some(priority) = &patch.priority {
+                let priorities = self.get_priorities_for_type(&req.req_type);
+                if stateless {
+                    errors.push(format!(
+                        "{}: type {} has no priority",
+                        display_id, req.req_type
+                    ));
+                } else if !priorities.iter().any(|p| p.eq_ignore_ascii_case(priority)) {
+                    errors.push(format!(
+                        "{}: invalid priority '{}' for type {} (valid: {})",
+                        display_id,
+                        priority,
+                        req.req_type,
+                        priorities.join(", ")
+                    ));
+                }
+            }
+
+            let fields = self.get_custom_fields_for_type(&req.req_type);
+            for (name, value) in &patch.custom_fields {
+                match fields.iter().find(|f| &f.name == name) {
+                    None => errors.push(format!(
+                        "{}: type {} has no custom field '{}'",
+                        display_id, req.req_type, name
+                    )),
+                    Some(field) if !field.options.is_empty() && !field.options.contains(value) => {
+                        errors.push(format!(
+                            "{}: invalid value '{}' for field '{}' (valid: {})",
+                            display_id,
+                            value,
+                            name,
+                            field.options.join(", ")
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Applies a patch to every requirement in the selection
+    ///
+    /// The whole selection is validated first; if any requirement would reject
+    /// the patch, nothing is modified. Each changed requirement gets exactly one
+    /// history entry attributed to `author`.
+    pub fn bulk_update(
+        &mut self,
+        ids: &[Uuid],
+        patch: &RequirementPatch,
+        author: &str,
+    ) -> Result<BulkUpdateResult> {
+        let errors = self.validate_bulk_update(ids, patch);
+        if !errors.is_empty() {
+            anyhow::bail!("Bulk update rejected:\n  {}", errors.join("\n  "));
+        }
+
+        // Resolve canonical spellings per type before taking mutable borrows
+        let canonical: HashMap<Uuid, (Option<String>, Option<String>)> = ids
+            .iter()
+            .filter_map(|id| self.get_requirement_by_id(id))
+            .map(|req| {
+                let status = patch.status.as_ref().and_then(|s| {
+                    self.get_statuses_for_type(&req.req_type)
+                        .into_iter()
+                        .find(|c| c.eq_ignore_ascii_case(s))
+                });
+                let priority = patch.priority.as_ref().and_then(|p| {
+                    self.get_priorities_for_type(&req.req_type)
+                        .into_iter()
+                        .find(|c| c.eq_ignore_ascii_case(p))
+                });
+                (req.id, (status, priority))
+            })
+            .collect();
+
+        let owner = match &patch.owner {
+            Some(owner) => Some(self.resolve_owner(owner)?),
+            None => None,
+        };
+
+        let mut result = BulkUpdateResult::default();
+
+        for id in ids {
+            let Some(req) = self.get_requirement_by_id_mut(id) else {
+                continue;
+            };
+            let (status, priority) = canonical.get(id).cloned().unwrap_or_default();
+            let changes = Self::apply_patch(req, patch, status, priority, owner.clone());
+
+            if changes.is_empty() {
+                result.unchanged.push(*id);
+            } else {
+                req.record_change(author.to_string(), changes);
+                result.updated.push(*id);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Resolves an owner to the user's handle (or name)
+    ///
+    /// Projects without registered users accept any owner; otherwise the
+    /// owner must be a known, non-archived user.
+    pub fn resolve_owner(&self, owner: &str) -> Result<String> {
+        if self.users.is_empty() || owner.trim().is_empty() {
+            return Ok(owner.to_string());
+        }
+        let Some(user) = self.find_user(owner) else {
+            anyhow::bail!(
+                "Unknown owner '{}'. Use a handle, name or $USER-ID from 'aida user list'",
+                owner
+            );
+        };
+        if user.archived {
+            anyhow::bail!("Owner '{}' ({}) is archived", owner, user.display_id());
+        }
+        Ok(if user.handle.is_empty() {
+            user.name.clone()
+        } else {
+            user.handle.clone()
+        })
+    }
+
+    /// Applies a validated patch to one requirement, returning the field changes
+    fn apply_patch(
+        req: &mut Requirement,
+        patch: &RequirementPatch,
+        status: Option<String>,
+        priority: Option<String>,
+        owner: Option<String>,
+    ) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+
+        if let Some(title) = &patch.title {
+            if &req.title != title {
+                changes.push(Requirement::field_change(
+                    "title",
+                    req.title.clone(),
+                    title.clone(),
+                ));
+                req.title = title.clone();
+            }
+        }
+
+        if let Some(description) = &patch.description {
+            if &req.description != description {
+                changes.push(Requirement::field_change(
+                    "description",
+                    req.description.clone(),
+                    description.clone(),
+                ));
+                req.description = description.clone();
+            }
+        }
+
+        if let Some(status) = status {
+            let old_status = req.effective_status();
+            if old_status != status {
+                changes.push(Requirement::field_change(
+                    "status",
+                    old_status,
+                    status.clone(),
+                ));
+                req.set_status_from_str(&status);
+            }
+        }
+
+        if let Some(priority) = priority {
+            let old_priority = req.effective_priority();
+            if old_priority != priority {
+                changes.push(Requirement::field_change(
+                    "priority",
+                    old_priority,
+                    priority.clone(),
+                ));
+                req.set_priority_from_str(&priority);
+            }
+        }
+
+        if let Some(owner) = owner {
+            if req.owner != owner {
+                changes.push(Requirement::field_change(
+                    "owner",
+                    req.owner.clone(),
+                    owner.clone(),
+                ));
+                req.owner = owner;
+            }
+        }
+
+        if let Some(feature) = &patch.feature {
+            if &req.feature != feature {
+                changes.push(Requirement::field_change(
+                    "feature",
+                    req.feature.clone(),
+                    feature.clone(),
+                ));
+                req.feature = feature.clone();
+            }
+        }
+
+        let mut new_tags = req.tags.clone();
+        for tag in &patch.add_tags {
+            new_tags.insert(tag.trim().to_string());
+        }
+        for tag in &patch.remove_tags {
+            new_tags.remove(tag.trim());
+        }
+        if new_tags != req.tags {
+            let mut old_tags_vec: Vec<String> = req.tags.iter().cloned().collect();
+            let mut new_tags_vec: Vec<String> = new_tags.iter().cloned().collect();
+            old_tags_vec.sort();
+            new_tags_vec.sort();
+            changes.push(Requirement::field_change(
+                "tags",
+                old_tags_vec.join(", "),
+                new_tags_vec.join(", "),
+            ));
+            req.tags = new_tags;
+        }
+
+        let mut field_names: Vec<&String> = patch.custom_fields.keys().collect();
+        field_names.sort();
+        for name in field_names {
+            let new_value = &patch.custom_fields[name];
+            let old_value = req.custom_fields.get(name).cloned().unwrap_or_default();
+            if &old_value != new_value {
+                changes.push(Requirement::field_change(
+                    name,
+                    old_value,
+                    new_value.clone(),
+                ));
+                req.set_custom_field(name.clone(), new_value.clone());
+            }
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{RequirementType, User};
+
+    fn store_with(reqs: Vec<Requirement>) -> RequirementsStore {
+        let mut store = RequirementsStore::new();
+        for req in reqs {
+            store.add_requirement_with_id(req, None, Some("FR"));
+        }
+        store
+    }
+
+    #[test]
+    fn test_query_parse_and_match() {
+        let mut a = Requirement::new("Login".to_string(), "User login".to_string());
+        a.feature = "1-Auth".to_string();
+        a.tags.insert("security".to_string());
+        let mut b = Requirement::new("Logout".to_string(), String::new());
+        b.feature = "2-Session".to_string();
+        let store = store_with(vec![a, b]);
+
+        let query = RequirementQuery::parse("feature=Auth tag=security").unwrap();
+        let ids = store.query_requirements(&query);
+        assert_eq!(ids.len(), 1);
+        assert_eq!(store.get_requirement_by_id(&ids[0]).unwrap().title, "Login");
+
+        assert!(RequirementQuery::parse("bogus=1").is_err());
+        assert!(RequirementQuery::parse("status").is_err());
+    }
+
+    #[test]
+    fn test_bulk_update_records_one_history_entry_per_item() {
+        let a = Requirement::new("A".to_string(), String::new());
+        let b = Requirement::new("B".to_string(), String::new());
+        let mut store = store_with(vec![a, b]);
+        let ids: Vec<Uuid> = store.requirements.iter().map(|r| r.id).collect();
+
+        let mut patch = RequirementPatch::default();
+        patch.set_assignment("status=approved").unwrap();
+        patch.set_assignment("owner=alice").unwrap();
+        patch.add_tags.push("release-2".to_string());
+
+        let result = store.bulk_update(&ids, &patch, "tester").unwrap();
+        assert_eq!(result.updated.len(), 2);
+
+        for req in &store.requirements {
+            assert_eq!(req.effective_status(), "Approved");
+            assert_eq!(req.owner, "alice");
+            assert!(req.tags.contains("release-2"));
+            assert_eq!(req.history.len(), 1);
+            assert_eq!(req.history[0].changes.len(), 3);
+        }
+
+        // Re-applying the same patch changes nothing
+        let result = store.bulk_update(&ids, &patch, "tester").unwrap();
+        assert!(result.updated.is_empty());
+        assert_eq!(result.unchanged.len(), 2);
+    }
+
+    #[test]
+    fn test_bulk_update_validates_before_applying() {
+        let a = Requirement::new("A".to_string(), String::new());
+        let mut folder = Requirement::new("Folder".to_string(), String::new());
+        folder.req_type = RequirementType::Folder;
+        let mut store = store_with(vec![a, folder]);
+        let ids: Vec<Uuid> = store.requirements.iter().map(|r| r.id).collect();
+
+        let patch = RequirementPatch {
+            status: Some("Approved".to_string()),
+            ..Default::default()
+        };
+
+        let err = store.bulk_update(&ids, &patch, "tester").unwrap_err();
+        assert!(err.to_string().contains("has no status"));
+
+        // Nothing was modified, including the valid requirement
+        assert!(store.requirements.iter().all(|r| r.history.is_empty()));
+        assert_eq!(store.requirements[0].effective_status(), "Draft");
+    }
+
+    #[test]
+    fn test_set_assignment_title_description_and_owner() {
+        let a = Requirement::new("A".to_string(), String::new());
+        let mut store = store_with(vec![a]);
+        store.add_user_with_id(
+            "Alice Smith".to_string(),
+            "alice@example.com".to_string(),
+            "alice".to_string(),
+        );
+        let mut archived = User::new("Bob".to_string(), String::new(), "bob".to_string());
+        archived.archived = true;
+        store.add_user(archived);
+        let ids: Vec<Uuid> = store.requirements.iter().map(|r| r.id).collect();
+
+        let mut patch = RequirementPatch::default();
+        patch.set_assignment("title=Sign in").unwrap();
+        patch.set_assignment("Description=Users sign in").unwrap();
+        assert!(patch.custom_fields.is_empty());
+
+        for owner in ["nobody", "bob"] {
+            patch.owner = Some(owner.to_string());
+            let errors = store.validate_bulk_update(&ids, &patch);
+            assert_eq!(errors.len(), 1, "{:?}", errors);
+            assert!(errors[0].contains(owner));
+        }
+
+        patch.set_assignment("owner=Alice Smith").unwrap();
+        store.bulk_update(&ids, &patch, "tester").unwrap();
+        let req = &store.requirements[0];
+        assert_eq!(req.title, "Sign in");
+        assert_eq!(req.description, "Users sign in");
+        assert_eq!(req.owner, "alice");
+    }
+}
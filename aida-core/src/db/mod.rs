@@ -17,16 +17,18 @@ pub use migration::{migrate_yaml_to_sqlite, migrate_sqlite_to_yaml, export_to_js
 use anyhow::Result;
 use std::path::Path;
 
+/// Infers the backend type from a file extension (YAML by default)
+pub fn backend_type_for_path(path: &Path) -> BackendType {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => BackendType::Yaml,
+        Some("db") | Some("sqlite") | Some("sqlite3") => BackendType::Sqlite,
+        _ => BackendType::Yaml,
+    }
+}
+
 /// Creates a database backend based on the file extension or explicit type
 pub fn create_backend(path: &Path, backend_type: Option<BackendType>) -> Result<Box<dyn DatabaseBackend>> {
-    let bt = backend_type.unwrap_or_else(|| {
-        // Infer from file extension
-        match path.extension().and_then(|e| e.to_str()) {
-            Some("yaml") | Some("yml") => BackendType::Yaml,
-            Some("db") | Some("sqlite") | Some("sqlite3") => BackendType::Sqlite,
-            _ => BackendType::Yaml, // Default to YAML
-        }
-    });
+    let bt = backend_type.unwrap_or_else(|| backend_type_for_path(path));
 
     match bt {
         BackendType::Yaml => Ok(Box::new(YamlBackend::new(path))),
@@ -3,7 +3,7 @@ CREATE TABLE IF NOT EXISTS schema_version (
     version INTEGER NOT NULL
 );
 
-INSERT INTO schema_version (version) VALUES (1);
+INSERT INTO schema_version (version) VALUES (7);
 
 -- Requirements table
 CREATE TABLE IF NOT EXISTS requirements (
@@ -28,7 +28,9 @@ CREATE TABLE IF NOT EXISTS requirements (
     archived INTEGER NOT NULL DEFAULT 0,
     custom_status TEXT,
     custom_fields TEXT NOT NULL DEFAULT '{}',
-    urls TEXT NOT NULL DEFAULT '[]'
+    urls TEXT NOT NULL DEFAULT '[]',
+    acceptance_criteria TEXT NOT NULL DEFAULT '[]',
+    commits TEXT NOT NULL DEFAULT '[]'
 );
 
 -- Index for spec_id lookups
@@ -57,6 +59,34 @@ CREATE TABLE IF NOT EXISTS users (
 -- Index for handle lookups
 CREATE INDEX IF NOT EXISTS idx_users_handle ON users(handle);
 
+-- Test cases table
+CREATE TABLE IF NOT EXISTS test_cases (
+    id TEXT PRIMARY KEY NOT NULL,
+    spec_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    preconditions TEXT NOT NULL DEFAULT '',
+    steps TEXT NOT NULL DEFAULT '[]',
+    expected_result TEXT NOT NULL DEFAULT '',
+    verifies TEXT NOT NULL DEFAULT '[]',
+    automated_test TEXT,
+    created_at TEXT NOT NULL,
+    created_by TEXT,
+    archived INTEGER NOT NULL DEFAULT 0
+);
+
+-- Test runs table
+CREATE TABLE IF NOT EXISTS test_runs (
+    id TEXT PRIMARY KEY NOT NULL,
+    spec_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    environment TEXT,
+    build TEXT,
+    executed_at TEXT NOT NULL,
+    executed_by TEXT,
+    results TEXT NOT NULL DEFAULT '[]'
+);
+
 -- Metadata table (single row with id=1)
 CREATE TABLE IF NOT EXISTS metadata (
     id INTEGER PRIMARY KEY CHECK (id = 1),
@@ -73,7 +103,12 @@ CREATE TABLE IF NOT EXISTS metadata (
     meta_counters TEXT NOT NULL DEFAULT '{}',
     type_definitions TEXT NOT NULL DEFAULT '[]',
     allowed_prefixes TEXT NOT NULL DEFAULT '[]',
-    restrict_prefixes INTEGER NOT NULL DEFAULT 0
+    restrict_prefixes INTEGER NOT NULL DEFAULT 0,
+    git_sync TEXT,
+    hooks TEXT NOT NULL DEFAULT '[]',
+    scripts TEXT NOT NULL DEFAULT '[]',
+    teams TEXT NOT NULL DEFAULT '[]',
+    baselines TEXT NOT NULL DEFAULT '[]'
 );
 
 -- Insert default metadata row
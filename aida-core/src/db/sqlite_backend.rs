@@ -11,16 +11,76 @@ use std::sync::Mutex;
 use uuid::Uuid;
 
 use crate::models::{
-    Comment, CustomTypeDefinition, FeatureDefinition,
+    Baseline, Comment, CustomTypeDefinition, FeatureDefinition,
     HistoryEntry, IdConfiguration, ReactionDefinition, RelationshipDefinition,
     Relationship, Requirement, RequirementPriority, RequirementStatus,
-    RequirementType, RequirementsStore, UrlLink, User,
+    RequirementType, RequirementsStore, Team, UrlLink, User,
 };
+use crate::events::HookConfig;
+use crate::git::GitSyncState;
+use crate::scripting::ScriptConfig;
+use crate::test_management::{TestCase, TestRun};
 
 use super::traits::{BackendType, DatabaseBackend};
 
 /// Current schema version
-const SCHEMA_VERSION: i32 = 1;
+const SCHEMA_VERSION: i32 = 7;
+
+/// Migrations from older schema versions, as (target version, SQL)
+const MIGRATIONS: &[(i32, &str)] = &[
+    // v2: structured acceptance criteria
+    (2, "ALTER TABLE requirements ADD COLUMN acceptance_criteria TEXT NOT NULL DEFAULT '[]';"),
+    // v3: test cases and test runs
+    (
+        3,
+        "CREATE TABLE IF NOT EXISTS test_cases (
+            id TEXT PRIMARY KEY NOT NULL,
+            spec_id TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL DEFAULT '',
+            preconditions TEXT NOT NULL DEFAULT '',
+            steps TEXT NOT NULL DEFAULT '[]',
+            expected_result TEXT NOT NULL DEFAULT '',
+            verifies TEXT NOT NULL DEFAULT '[]',
+            automated_test TEXT,
+            created_at TEXT NOT NULL,
+            created_by TEXT,
+            archived INTEGER NOT NULL DEFAULT 0
+        );
+        CREATE TABLE IF NOT EXISTS test_runs (
+            id TEXT PRIMARY KEY NOT NULL,
+            spec_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            environment TEXT,
+            build TEXT,
+            executed_at TEXT NOT NULL,
+            executed_by TEXT,
+            results TEXT NOT NULL DEFAULT '[]'
+        );",
+    ),
+    // v4: git commit links and sync state
+    (
+        4,
+        "ALTER TABLE requirements ADD COLUMN commits TEXT NOT NULL DEFAULT '[]';
+        ALTER TABLE metadata ADD COLUMN git_sync TEXT;",
+    ),
+    // v5: outbound hooks
+    (5, "ALTER TABLE metadata ADD COLUMN hooks TEXT NOT NULL DEFAULT '[]';"),
+    // v6: project scripts
+    (6, "ALTER TABLE metadata ADD COLUMN scripts TEXT NOT NULL DEFAULT '[]';"),
+    // v7: teams and baselines
+    (
+        7,
+        "ALTER TABLE metadata ADD COLUMN teams TEXT NOT NULL DEFAULT '[]';
+        ALTER TABLE metadata ADD COLUMN baselines TEXT NOT NULL DEFAULT '[]';",
+    ),
+];
+
+/// Columns selected when loading requirements (order matches `requirement_from_row`)
+const REQUIREMENT_COLUMNS: &str = "id, spec_id, prefix_override, title, description, status, priority,
+    owner, feature, created_at, created_by, modified_at, req_type,
+    dependencies, tags, relationships, comments, history, archived,
+    custom_status, custom_fields, urls, acceptance_criteria, commits";
 
 /// SQLite backend implementation
 pub struct SqliteBackend {
@@ -69,10 +129,17 @@ impl SqliteBackend {
             // Create initial schema
             conn.execute_batch(include_str!("schema.sql"))?;
         } else if current_version < SCHEMA_VERSION {
-            // Future: handle migrations
-            // For now, we just fail if the schema is outdated
+            // Apply each pending migration in order
+            conn.execute("BEGIN TRANSACTION", [])?;
+            for (version, sql) in MIGRATIONS.iter().filter(|(v, _)| *v > current_version) {
+                conn.execute_batch(sql)
+                    .with_context(|| format!("Failed to migrate database schema to version {}", version))?;
+            }
+            conn.execute("UPDATE schema_version SET version = ?1", [SCHEMA_VERSION])?;
+            conn.execute("COMMIT", [])?;
+        } else if current_version > SCHEMA_VERSION {
             anyhow::bail!(
-                "Database schema version {} is outdated, expected {}",
+                "Database schema version {} is newer than supported version {}",
                 current_version,
                 SCHEMA_VERSION
             );
@@ -166,102 +233,68 @@ impl SqliteBackend {
         }
     }
 
+    /// Builds a requirement from a row selected with `REQUIREMENT_COLUMNS`
+    fn requirement_from_row(row: &rusqlite::Row) -> rusqlite::Result<Requirement> {
+        let id_str: String = row.get(0)?;
+        let status_str: String = row.get(5)?;
+        let priority_str: String = row.get(6)?;
+        let created_at_str: String = row.get(9)?;
+        let modified_at_str: String = row.get(11)?;
+        let req_type_str: String = row.get(12)?;
+        let dependencies_json: String = row.get(13)?;
+        let tags_json: String = row.get(14)?;
+        let relationships_json: String = row.get(15)?;
+        let comments_json: String = row.get(16)?;
+        let history_json: String = row.get(17)?;
+        let custom_fields_json: String = row.get(20)?;
+        let urls_json: String = row.get(21)?;
+        let acceptance_criteria_json: String = row.get(22)?;
+        let commits_json: String = row.get(23)?;
+
+        Ok(Requirement {
+            id: Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4()),
+            spec_id: row.get(1)?,
+            prefix_override: row.get(2)?,
+            title: row.get(3)?,
+            description: row.get(4)?,
+            status: Self::str_to_status(&status_str),
+            priority: Self::str_to_priority(&priority_str),
+            owner: row.get(7)?,
+            feature: row.get(8)?,
+            created_at: chrono::DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .unwrap_or_else(|_| chrono::Utc::now()),
+            created_by: row.get(10)?,
+            modified_at: chrono::DateTime::parse_from_rfc3339(&modified_at_str)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .unwrap_or_else(|_| chrono::Utc::now()),
+            req_type: Self::str_to_type(&req_type_str),
+            dependencies: Self::from_json(&dependencies_json).unwrap_or_default(),
+            tags: Self::from_json::<HashSet<String>>(&tags_json).unwrap_or_default(),
+            relationships: Self::from_json::<Vec<Relationship>>(&relationships_json).unwrap_or_default(),
+            comments: Self::from_json::<Vec<Comment>>(&comments_json).unwrap_or_default(),
+            history: Self::from_json::<Vec<HistoryEntry>>(&history_json).unwrap_or_default(),
+            archived: row.get(18)?,
+            custom_status: row.get(19)?,
+            custom_priority: None, // TODO: Load from database when column is added
+            custom_fields: Self::from_json::<HashMap<String, String>>(&custom_fields_json).unwrap_or_default(),
+            urls: Self::from_json::<Vec<UrlLink>>(&urls_json).unwrap_or_default(),
+            ai_evaluation: None, // TODO: Load from database when column is added
+            acceptance_criteria: Self::from_json(&acceptance_criteria_json).unwrap_or_default(),
+            commits: Self::from_json(&commits_json).unwrap_or_default(),
+        })
+    }
+
     /// Load requirements from database
     fn load_requirements(&self, conn: &Connection) -> Result<Vec<Requirement>> {
-        let mut stmt = conn.prepare(
-            "SELECT id, spec_id, prefix_override, title, description, status, priority,
-                    owner, feature, created_at, created_by, modified_at, req_type,
-                    dependencies, tags, relationships, comments, history, archived,
-                    custom_status, custom_fields, urls
-             FROM requirements ORDER BY created_at"
-        )?;
-
-        let rows = stmt.query_map([], |row| {
-            let id_str: String = row.get(0)?;
-            let spec_id: Option<String> = row.get(1)?;
-            let prefix_override: Option<String> = row.get(2)?;
-            let title: String = row.get(3)?;
-            let description: String = row.get(4)?;
-            let status_str: String = row.get(5)?;
-            let priority_str: String = row.get(6)?;
-            let owner: String = row.get(7)?;
-            let feature: String = row.get(8)?;
-            let created_at_str: String = row.get(9)?;
-            let created_by: Option<String> = row.get(10)?;
-            let modified_at_str: String = row.get(11)?;
-            let req_type_str: String = row.get(12)?;
-            let dependencies_json: String = row.get(13)?;
-            let tags_json: String = row.get(14)?;
-            let relationships_json: String = row.get(15)?;
-            let comments_json: String = row.get(16)?;
-            let history_json: String = row.get(17)?;
-            let archived: bool = row.get(18)?;
-            let custom_status: Option<String> = row.get(19)?;
-            let custom_fields_json: String = row.get(20)?;
-            let urls_json: String = row.get(21)?;
-
-            Ok((
-                id_str, spec_id, prefix_override, title, description, status_str, priority_str,
-                owner, feature, created_at_str, created_by, modified_at_str, req_type_str,
-                dependencies_json, tags_json, relationships_json, comments_json, history_json,
-                archived, custom_status, custom_fields_json, urls_json
-            ))
-        })?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM requirements ORDER BY created_at",
+            REQUIREMENT_COLUMNS
+        ))?;
 
-        let mut requirements = Vec::new();
-        for row_result in rows {
-            let (
-                id_str, spec_id, prefix_override, title, description, status_str, priority_str,
-                owner, feature, created_at_str, created_by, modified_at_str, req_type_str,
-                dependencies_json, tags_json, relationships_json, comments_json, history_json,
-                archived, custom_status, custom_fields_json, urls_json
-            ) = row_result?;
-
-            let id = Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4());
-            let status = Self::str_to_status(&status_str);
-            let priority = Self::str_to_priority(&priority_str);
-            let req_type = Self::str_to_type(&req_type_str);
-            let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
-                .map(|dt| dt.with_timezone(&chrono::Utc))
-                .unwrap_or_else(|_| chrono::Utc::now());
-            let modified_at = chrono::DateTime::parse_from_rfc3339(&modified_at_str)
-                .map(|dt| dt.with_timezone(&chrono::Utc))
-                .unwrap_or_else(|_| chrono::Utc::now());
-            let dependencies: Vec<Uuid> = Self::from_json(&dependencies_json).unwrap_or_default();
-            let tags: HashSet<String> = Self::from_json(&tags_json).unwrap_or_default();
-            let relationships: Vec<Relationship> = Self::from_json(&relationships_json).unwrap_or_default();
-            let comments: Vec<Comment> = Self::from_json(&comments_json).unwrap_or_default();
-            let history: Vec<HistoryEntry> = Self::from_json(&history_json).unwrap_or_default();
-            let custom_fields: HashMap<String, String> = Self::from_json(&custom_fields_json).unwrap_or_default();
-            let urls: Vec<UrlLink> = Self::from_json(&urls_json).unwrap_or_default();
-
-            requirements.push(Requirement {
-                id,
-                spec_id,
-                prefix_override,
-                title,
-                description,
-                status,
-                priority,
-                owner,
-                feature,
-                created_at,
-                created_by,
-                modified_at,
-                req_type,
-                dependencies,
-                tags,
-                relationships,
-                comments,
-                history,
-                archived,
-                custom_status,
-                custom_priority: None, // TODO: Load from database when column is added
-                custom_fields,
-                urls,
-                ai_evaluation: None, // TODO: Load from database when column is added
-            });
-        }
+        let requirements = stmt
+            .query_map([], Self::requirement_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
 
         Ok(requirements)
     }
@@ -305,6 +338,71 @@ impl SqliteBackend {
         Ok(users)
     }
 
+    /// Parses an RFC 3339 timestamp column, falling back to now
+    fn parse_timestamp(s: &str) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now())
+    }
+
+    /// Load test cases from database
+    fn load_test_cases(&self, conn: &Connection) -> Result<Vec<TestCase>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, spec_id, title, description, preconditions, steps, expected_result,
+                    verifies, automated_test, created_at, created_by, archived
+             FROM test_cases ORDER BY spec_id",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let id_str: String = row.get(0)?;
+            let steps_json: String = row.get(5)?;
+            let verifies_json: String = row.get(7)?;
+            let created_at_str: String = row.get(9)?;
+            Ok(TestCase {
+                id: Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4()),
+                spec_id: row.get(1)?,
+                title: row.get(2)?,
+                description: row.get(3)?,
+                preconditions: row.get(4)?,
+                steps: Self::from_json(&steps_json).unwrap_or_default(),
+                expected_result: row.get(6)?,
+                verifies: Self::from_json(&verifies_json).unwrap_or_default(),
+                automated_test: row.get(8)?,
+                created_at: Self::parse_timestamp(&created_at_str),
+                created_by: row.get(10)?,
+                archived: row.get(11)?,
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| e.into())
+    }
+
+    /// Load test runs from database
+    fn load_test_runs(&self, conn: &Connection) -> Result<Vec<TestRun>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, spec_id, name, environment, build, executed_at, executed_by, results
+             FROM test_runs ORDER BY executed_at",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let id_str: String = row.get(0)?;
+            let executed_at_str: String = row.get(5)?;
+            let results_json: String = row.get(7)?;
+            Ok(TestRun {
+                id: Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4()),
+                spec_id: row.get(1)?,
+                name: row.get(2)?,
+                environment: row.get(3)?,
+                build: row.get(4)?,
+                executed_at: Self::parse_timestamp(&executed_at_str),
+                executed_by: row.get(6)?,
+                results: Self::from_json(&results_json).unwrap_or_default(),
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| e.into())
+    }
+
     /// Load metadata from database
     fn load_metadata(&self, conn: &Connection) -> Result<(String, String, String, IdConfiguration, u32, u32, HashMap<String, u32>, HashMap<String, u32>)> {
         let row = conn.query_row(
@@ -405,14 +503,70 @@ impl SqliteBackend {
         }
     }
 
+    /// Load the git sync state from database
+    fn load_git_sync(&self, conn: &Connection) -> Result<Option<GitSyncState>> {
+        let json: Option<String> = conn
+            .query_row("SELECT git_sync FROM metadata WHERE id = 1", [], |row| row.get(0))
+            .optional()?
+            .flatten();
+        Ok(json.and_then(|j| Self::from_json(&j).ok()))
+    }
+
+    /// Sends the changes between two versions of the store to the configured hooks
+    fn dispatch_events(&self, before: &RequirementsStore, after: &RequirementsStore) {
+        crate::events::dispatch(before, after, self.base_dir());
+    }
+
+    /// Runs the project scripts on the requirements changed by a write
+    fn run_scripts(&self, before: Option<&RequirementsStore>, after: &mut RequirementsStore) -> Result<()> {
+        crate::scripting::apply_scripts(before, after, self.base_dir())
+    }
+
+    /// Directory that relative hook and script paths are resolved against
+    fn base_dir(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new("."))
+    }
+
+    /// Load the configured hooks from database
+    fn load_hooks(&self, conn: &Connection) -> Result<Vec<HookConfig>> {
+        let json: Option<String> = conn
+            .query_row("SELECT hooks FROM metadata WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+        Ok(json.and_then(|j| Self::from_json(&j).ok()).unwrap_or_default())
+    }
+
+    /// Load the project scripts from database
+    fn load_scripts(&self, conn: &Connection) -> Result<Vec<ScriptConfig>> {
+        let json: Option<String> = conn
+            .query_row("SELECT scripts FROM metadata WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+        Ok(json.and_then(|j| Self::from_json(&j).ok()).unwrap_or_default())
+    }
+
+    /// Load the teams from database
+    fn load_teams(&self, conn: &Connection) -> Result<Vec<Team>> {
+        let json: Option<String> = conn
+            .query_row("SELECT teams FROM metadata WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+        Ok(json.and_then(|j| Self::from_json(&j).ok()).unwrap_or_default())
+    }
+
+    /// Load the baselines from database
+    fn load_baselines(&self, conn: &Connection) -> Result<Vec<Baseline>> {
+        let json: Option<String> = conn
+            .query_row("SELECT baselines FROM metadata WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+        Ok(json.and_then(|j| Self::from_json(&j).ok()).unwrap_or_default())
+    }
+
     /// Save a requirement to the database
     fn save_requirement(&self, conn: &Connection, req: &Requirement) -> Result<()> {
         conn.execute(
             "INSERT OR REPLACE INTO requirements
              (id, spec_id, prefix_override, title, description, status, priority, owner, feature,
               created_at, created_by, modified_at, req_type, dependencies, tags, relationships,
-              comments, history, archived, custom_status, custom_fields, urls)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
+              comments, history, archived, custom_status, custom_fields, urls, acceptance_criteria, commits)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
             params![
                 req.id.to_string(),
                 req.spec_id,
@@ -436,6 +590,8 @@ impl SqliteBackend {
                 req.custom_status,
                 Self::to_json(&req.custom_fields)?,
                 Self::to_json(&req.urls)?,
+                Self::to_json(&req.acceptance_criteria)?,
+                Self::to_json(&req.commits)?,
             ],
         )?;
         Ok(())
@@ -459,14 +615,61 @@ impl SqliteBackend {
         Ok(())
     }
 
+    /// Save a test case to the database
+    fn save_test_case(&self, conn: &Connection, case: &TestCase) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO test_cases
+             (id, spec_id, title, description, preconditions, steps, expected_result,
+              verifies, automated_test, created_at, created_by, archived)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                case.id.to_string(),
+                case.spec_id,
+                case.title,
+                case.description,
+                case.preconditions,
+                Self::to_json(&case.steps)?,
+                case.expected_result,
+                Self::to_json(&case.verifies)?,
+                case.automated_test,
+                case.created_at.to_rfc3339(),
+                case.created_by,
+                case.archived,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Save a test run to the database
+    fn save_test_run(&self, conn: &Connection, run: &TestRun) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO test_runs
+             (id, spec_id, name, environment, build, executed_at, executed_by, results)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run.id.to_string(),
+                run.spec_id,
+                run.name,
+                run.environment,
+                run.build,
+                run.executed_at.to_rfc3339(),
+                run.executed_by,
+                Self::to_json(&run.results)?,
+            ],
+        )?;
+        Ok(())
+    }
+
     /// Save metadata to the database
     fn save_metadata(&self, conn: &Connection, store: &RequirementsStore) -> Result<()> {
         conn.execute(
             "INSERT OR REPLACE INTO metadata
              (id, name, title, description, id_config, features, next_feature_number, next_spec_number,
               prefix_counters, relationship_definitions, reaction_definitions, meta_counters,
-              type_definitions, allowed_prefixes, restrict_prefixes)
-             VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+              type_definitions, allowed_prefixes, restrict_prefixes, git_sync, hooks, scripts,
+              teams, baselines)
+             VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                     ?18, ?19)",
             params![
                 store.name,
                 store.title,
@@ -482,6 +685,11 @@ impl SqliteBackend {
                 Self::to_json(&store.type_definitions)?,
                 Self::to_json(&store.allowed_prefixes)?,
                 store.restrict_prefixes,
+                store.git_sync.as_ref().map(Self::to_json).transpose()?,
+                Self::to_json(&store.hooks)?,
+                Self::to_json(&store.scripts)?,
+                Self::to_json(&store.teams)?,
+                Self::to_json(&store.baselines)?,
             ],
         )?;
         Ok(())
@@ -509,6 +717,13 @@ impl DatabaseBackend for SqliteBackend {
         let relationship_definitions = self.load_relationship_definitions(&conn)?;
         let reaction_definitions = self.load_reaction_definitions(&conn)?;
         let (allowed_prefixes, restrict_prefixes) = self.load_allowed_prefixes(&conn)?;
+        let test_cases = self.load_test_cases(&conn)?;
+        let test_runs = self.load_test_runs(&conn)?;
+        let git_sync = self.load_git_sync(&conn)?;
+        let hooks = self.load_hooks(&conn)?;
+        let scripts = self.load_scripts(&conn)?;
+        let teams = self.load_teams(&conn)?;
+        let baselines = self.load_baselines(&conn)?;
 
         Ok(RequirementsStore {
             name,
@@ -516,7 +731,7 @@ impl DatabaseBackend for SqliteBackend {
             description,
             requirements,
             users,
-            teams: Vec::new(), // Teams not yet implemented in SQLite backend
+            teams,
             id_config,
             features,
             next_feature_number,
@@ -529,11 +744,34 @@ impl DatabaseBackend for SqliteBackend {
             allowed_prefixes,
             restrict_prefixes,
             ai_prompts: Default::default(),
-            baselines: Vec::new(), // Baselines not yet implemented in SQLite backend
+            baselines,
+            test_cases,
+            test_runs,
+            git_sync,
+            hooks,
+            scripts,
         })
     }
 
     fn save(&self, store: &RequirementsStore) -> Result<()> {
+        // Keep the previous version for hooks and scripts, if any are configured
+        let before = if store.hooks.is_empty() && store.scripts.is_empty() {
+            None
+        } else {
+            self.load().ok()
+        };
+
+        // Scripts may edit requirements, so they work on a copy
+        let scripted;
+        let store = if store.scripts.is_empty() {
+            store
+        } else {
+            let mut copy = store.clone();
+            self.run_scripts(before.as_ref(), &mut copy)?;
+            scripted = copy;
+            &scripted
+        };
+
         let conn = self.conn.lock().unwrap();
 
         // Use a transaction for atomicity
@@ -542,6 +780,8 @@ impl DatabaseBackend for SqliteBackend {
         // Clear existing data
         conn.execute("DELETE FROM requirements", [])?;
         conn.execute("DELETE FROM users", [])?;
+        conn.execute("DELETE FROM test_cases", [])?;
+        conn.execute("DELETE FROM test_runs", [])?;
 
         // Save all requirements
         for req in &store.requirements {
@@ -553,16 +793,39 @@ impl DatabaseBackend for SqliteBackend {
             self.save_user(&conn, user)?;
         }
 
+        // Save test cases and runs
+        for case in &store.test_cases {
+            self.save_test_case(&conn, case)?;
+        }
+        for run in &store.test_runs {
+            self.save_test_run(&conn, run)?;
+        }
+
         // Save metadata
         self.save_metadata(&conn, store)?;
 
         conn.execute("COMMIT", [])?;
+        drop(conn);
+        if let Some(before) = before {
+            self.dispatch_events(&before, store);
+        }
         Ok(())
     }
 
     fn update_atomically<F>(&self, update_fn: F) -> Result<RequirementsStore>
     where
         F: FnOnce(&mut RequirementsStore),
+    {
+        let saved = self.update_atomically_if(|store| {
+            update_fn(store);
+            true
+        })?;
+        Ok(saved.expect("update is always saved"))
+    }
+
+    fn update_atomically_if<F>(&self, update_fn: F) -> Result<Option<RequirementsStore>>
+    where
+        F: FnOnce(&mut RequirementsStore) -> bool,
     {
         let conn = self.conn.lock().unwrap();
 
@@ -573,7 +836,15 @@ impl DatabaseBackend for SqliteBackend {
         let mut store = self.load()?;
 
         // Apply changes
-        update_fn(&mut store);
+        let before = (!store.hooks.is_empty() || !store.scripts.is_empty()).then(|| store.clone());
+        if !update_fn(&mut store) {
+            self.conn.lock().unwrap().execute("ROLLBACK", [])?;
+            return Ok(None);
+        }
+        if let Err(e) = self.run_scripts(before.as_ref(), &mut store) {
+            self.conn.lock().unwrap().execute("ROLLBACK", [])?;
+            return Err(e);
+        }
 
         // Save within transaction
         let conn = self.conn.lock().unwrap();
@@ -581,6 +852,8 @@ impl DatabaseBackend for SqliteBackend {
         // Clear existing data
         conn.execute("DELETE FROM requirements", [])?;
         conn.execute("DELETE FROM users", [])?;
+        conn.execute("DELETE FROM test_cases", [])?;
+        conn.execute("DELETE FROM test_runs", [])?;
 
         // Save all requirements
         for req in &store.requirements {
@@ -592,11 +865,23 @@ impl DatabaseBackend for SqliteBackend {
             self.save_user(&conn, user)?;
         }
 
+        // Save test cases and runs
+        for case in &store.test_cases {
+            self.save_test_case(&conn, case)?;
+        }
+        for run in &store.test_runs {
+            self.save_test_run(&conn, run)?;
+        }
+
         // Save metadata
         self.save_metadata(&conn, &store)?;
 
         conn.execute("COMMIT", [])?;
-        Ok(store)
+        drop(conn);
+        if let Some(before) = before {
+            self.dispatch_events(&before, &store);
+        }
+        Ok(Some(store))
     }
 
     // Override for more efficient single-requirement operations
@@ -604,100 +889,13 @@ impl DatabaseBackend for SqliteBackend {
     fn get_requirement(&self, id: &Uuid) -> Result<Option<Requirement>> {
         let conn = self.conn.lock().unwrap();
 
-        let result = conn.query_row(
-            "SELECT id, spec_id, prefix_override, title, description, status, priority,
-                    owner, feature, created_at, created_by, modified_at, req_type,
-                    dependencies, tags, relationships, comments, history, archived,
-                    custom_status, custom_fields, urls
-             FROM requirements WHERE id = ?1",
+        conn.query_row(
+            &format!("SELECT {} FROM requirements WHERE id = ?1", REQUIREMENT_COLUMNS),
             [id.to_string()],
-            |row| {
-                let id_str: String = row.get(0)?;
-                let spec_id: Option<String> = row.get(1)?;
-                let prefix_override: Option<String> = row.get(2)?;
-                let title: String = row.get(3)?;
-                let description: String = row.get(4)?;
-                let status_str: String = row.get(5)?;
-                let priority_str: String = row.get(6)?;
-                let owner: String = row.get(7)?;
-                let feature: String = row.get(8)?;
-                let created_at_str: String = row.get(9)?;
-                let created_by: Option<String> = row.get(10)?;
-                let modified_at_str: String = row.get(11)?;
-                let req_type_str: String = row.get(12)?;
-                let dependencies_json: String = row.get(13)?;
-                let tags_json: String = row.get(14)?;
-                let relationships_json: String = row.get(15)?;
-                let comments_json: String = row.get(16)?;
-                let history_json: String = row.get(17)?;
-                let archived: bool = row.get(18)?;
-                let custom_status: Option<String> = row.get(19)?;
-                let custom_fields_json: String = row.get(20)?;
-                let urls_json: String = row.get(21)?;
-
-                Ok((
-                    id_str, spec_id, prefix_override, title, description, status_str, priority_str,
-                    owner, feature, created_at_str, created_by, modified_at_str, req_type_str,
-                    dependencies_json, tags_json, relationships_json, comments_json, history_json,
-                    archived, custom_status, custom_fields_json, urls_json
-                ))
-            }
-        ).optional()?;
-
-        match result {
-            Some((
-                id_str, spec_id, prefix_override, title, description, status_str, priority_str,
-                owner, feature, created_at_str, created_by, modified_at_str, req_type_str,
-                dependencies_json, tags_json, relationships_json, comments_json, history_json,
-                archived, custom_status, custom_fields_json, urls_json
-            )) => {
-                let id = Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4());
-                let status = Self::str_to_status(&status_str);
-                let priority = Self::str_to_priority(&priority_str);
-                let req_type = Self::str_to_type(&req_type_str);
-                let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
-                    .map(|dt| dt.with_timezone(&chrono::Utc))
-                    .unwrap_or_else(|_| chrono::Utc::now());
-                let modified_at = chrono::DateTime::parse_from_rfc3339(&modified_at_str)
-                    .map(|dt| dt.with_timezone(&chrono::Utc))
-                    .unwrap_or_else(|_| chrono::Utc::now());
-                let dependencies: Vec<Uuid> = Self::from_json(&dependencies_json).unwrap_or_default();
-                let tags: HashSet<String> = Self::from_json(&tags_json).unwrap_or_default();
-                let relationships: Vec<Relationship> = Self::from_json(&relationships_json).unwrap_or_default();
-                let comments: Vec<Comment> = Self::from_json(&comments_json).unwrap_or_default();
-                let history: Vec<HistoryEntry> = Self::from_json(&history_json).unwrap_or_default();
-                let custom_fields: HashMap<String, String> = Self::from_json(&custom_fields_json).unwrap_or_default();
-                let urls: Vec<UrlLink> = Self::from_json(&urls_json).unwrap_or_default();
-
-                Ok(Some(Requirement {
-                    id,
-                    spec_id,
-                    prefix_override,
-                    title,
-                    description,
-                    status,
-                    priority,
-                    owner,
-                    feature,
-                    created_at,
-                    created_by,
-                    modified_at,
-                    req_type,
-                    dependencies,
-                    tags,
-                    relationships,
-                    comments,
-                    history,
-                    archived,
-                    custom_status,
-                    custom_priority: None, // TODO: Load from database when column is added
-                    custom_fields,
-                    urls,
-                    ai_evaluation: None, // TODO: Load from database when column is added
-                }))
-            }
-            None => Ok(None),
-        }
+            Self::requirement_from_row,
+        )
+        .optional()
+        .map_err(|e| e.into())
     }
 
     fn update_requirement(&self, requirement: &Requirement) -> Result<()> {
@@ -823,4 +1021,92 @@ mod tests {
         let loaded = backend.get_requirement(&req.id).unwrap();
         assert!(loaded.is_none());
     }
+    #[test]
+    fn test_sqlite_backend_migrates_v1_schema() {
+        let temp_file = NamedTempFile::with_suffix(".db").unwrap();
+
+        // Create a version 1 database (no acceptance_criteria column)
+        {
+            let conn = Connection::open(temp_file.path()).unwrap();
+            let v1_schema = include_str!("schema.sql")
+                .replace("VALUES (7)", "VALUES (1)")
+                .replace(",\n    acceptance_criteria TEXT NOT NULL DEFAULT '[]',\n    commits TEXT NOT NULL DEFAULT '[]'", "")
+                .replace(",\n    git_sync TEXT,\n    hooks TEXT NOT NULL DEFAULT '[]',\n    scripts TEXT NOT NULL DEFAULT '[]',\n    teams TEXT NOT NULL DEFAULT '[]',\n    baselines TEXT NOT NULL DEFAULT '[]'", "");
+            conn.execute_batch(&v1_schema).unwrap();
+        }
+
+        let backend = SqliteBackend::new(temp_file.path()).unwrap();
+        let mut store = RequirementsStore::new();
+        let mut req = Requirement::new("Login".to_string(), String::new());
+        req.add_acceptance_criterion(
+            crate::models::AcceptanceCriterion::new("", "Valid credentials log the user in"),
+            "tester",
+        );
+        store.requirements.push(req);
+        backend.save(&store).unwrap();
+
+        let loaded = backend.load().unwrap();
+        assert_eq!(loaded.requirements[0].acceptance_criteria.len(), 1);
+        assert_eq!(loaded.requirements[0].acceptance_criteria[0].id, "AC-1");
+
+        let conn = Connection::open(temp_file.path()).unwrap();
+        let version: i32 = conn
+            .query_row("SELECT version FROM schema_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_sqlite_backend_persists_test_runs() {
+        use crate::test_management::TestOutcome;
+
+        let temp_file = NamedTempFile::with_suffix(".db").unwrap();
+        let backend = SqliteBackend::new(temp_file.path()).unwrap();
+
+        let mut store = RequirementsStore::new();
+        let req = Requirement::new("Login".to_string(), String::new());
+        let req_id = req.id;
+        store.requirements.push(req);
+        store.add_test_case(
+            TestCase::new("Valid login")
+                .with_step("Submit valid credentials", "Dashboard is shown")
+                .with_verifies(req_id),
+        );
+        let case_id = store.test_cases[0].id;
+        let mut run = TestRun::new("Smoke").with_environment("staging").with_build("42");
+        run.record(case_id, TestOutcome::Failed);
+        store.add_test_run(run);
+        backend.save(&store).unwrap();
+
+        let loaded = backend.load().unwrap();
+        assert_eq!(loaded.test_cases, store.test_cases);
+        assert_eq!(loaded.test_runs[0].spec_id, "RUN-001");
+        assert_eq!(loaded.test_runs[0].environment.as_deref(), Some("staging"));
+        assert_eq!(loaded.verification_summary(&req_id).failed, 1);
+    }
+
+    #[test]
+    fn test_sqlite_backend_persists_commit_links() {
+        use crate::git::GitCommit;
+
+        let temp_file = NamedTempFile::with_suffix(".db").unwrap();
+        let backend = SqliteBackend::new(temp_file.path()).unwrap();
+
+        let mut store = RequirementsStore::new();
+        let mut req = Requirement::new("Login".to_string(), String::new());
+        req.spec_id = Some("FR-001".to_string());
+        store.requirements.push(req);
+        store.link_commits(&[GitCommit {
+            sha: "0123456789abcdef".to_string(),
+            author: "alice".to_string(),
+            date: chrono::Utc::now(),
+            subject: "Implement FR-001".to_string(),
+            body: String::new(),
+        }]);
+        backend.save(&store).unwrap();
+
+        let loaded = backend.load().unwrap();
+        assert_eq!(loaded.requirements[0].commits, store.requirements[0].commits);
+        assert_eq!(loaded.git_sync.unwrap().last_commit, "0123456789abcdef");
+    }
 }
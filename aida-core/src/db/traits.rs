@@ -86,6 +86,21 @@ pub trait DatabaseBackend: Send + Sync {
         Ok(store)
     }
 
+    /// Like `update_atomically`, but nothing is written if `update_fn` returns
+    /// false; returns the saved store, or `None` if the update was dropped
+    fn update_atomically_if<F>(&self, update_fn: F) -> Result<Option<RequirementsStore>>
+    where
+        F: FnOnce(&mut RequirementsStore) -> bool,
+        Self: Sized,
+    {
+        let mut store = self.load()?;
+        if !update_fn(&mut store) {
+            return Ok(None);
+        }
+        self.save(&store)?;
+        Ok(Some(store))
+    }
+
     // =========================================================================
     // Requirement CRUD Operations
     // =========================================================================
@@ -60,6 +60,13 @@ impl DatabaseBackend for YamlBackend {
         self.storage.update_atomically(update_fn)
     }
 
+    fn update_atomically_if<F>(&self, update_fn: F) -> Result<Option<RequirementsStore>>
+    where
+        F: FnOnce(&mut RequirementsStore) -> bool,
+    {
+        self.storage.update_atomically_if(update_fn)
+    }
+
     /// Creates a baseline with git tagging support for YAML backend
     fn create_baseline(
         &self,
@@ -85,13 +92,21 @@ impl DatabaseBackend for YamlBackend {
 }
 
 impl YamlBackend {
+    /// Directory containing the YAML file (`.` for a bare file name)
+    fn dir(&self) -> &Path {
+        match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        }
+    }
+
     /// Attempts to create a git tag for a baseline
     /// Returns the tag name if successful, None if git is not available or fails
     fn create_git_tag_for_baseline(&self, baseline: &crate::models::Baseline) -> Option<String> {
         use std::process::Command;
 
         // Get the directory containing the YAML file
-        let dir = self.path.parent()?;
+        let dir = self.dir();
 
         // Check if we're in a git repository
         let git_check = Command::new("git")
@@ -127,9 +142,7 @@ impl YamlBackend {
     pub fn list_git_baseline_tags(&self) -> Vec<String> {
         use std::process::Command;
 
-        let Some(dir) = self.path.parent() else {
-            return Vec::new();
-        };
+        let dir = self.dir();
 
         let output = Command::new("git")
             .args(["tag", "-l", "baseline-*"])
@@ -0,0 +1,659 @@
+//! Store change events and outbound hooks
+//!
+//! Every write through [`Storage`](crate::storage::Storage) or a database
+//! backend compares the store before and after the write and turns the
+//! differences into [`StoreEvent`]s: new requirements, status transitions,
+//! new comments, @mentions and new baselines. Each event is delivered to the
+//! hooks configured in the project file:
+//!
+//! ```yaml
+//! hooks:
+//!   - type: webhook
+//!     url: https://example.com/aida
+//!     events: [status_changed, mentioned]
+//!   - type: jsonl
+//!     path: events.jsonl
+//!   - type: command
+//!     command: ./scripts/notify.sh
+//! ```
+//!
+//! Hooks run on a background thread, in save order, so a write returns as
+//! soon as the file is on disk. Short-lived processes call
+//! [`wait_for_hooks`] before exiting. Hook failures are reported on stderr
+//! and never fail the write itself.
+//!
+//! Because the project file is shared, `command` hooks are skipped unless the
+//! user opts in: either `AIDA_ALLOW_COMMAND_HOOKS=1` is set, or the project
+//! directory is listed (one path per line) in `~/.config/aida/trusted-hooks`.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::{Comment, Requirement, RequirementsStore};
+
+/// Delay before the first webhook retry; doubled for each further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Timeout for a single webhook request
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Environment variable that allows `command` hooks for every project
+pub const ALLOW_COMMAND_HOOKS_ENV: &str = "AIDA_ALLOW_COMMAND_HOOKS";
+
+/// File in the user's config directory listing projects whose `command` hooks may run
+pub const TRUSTED_HOOKS_FILE: &str = "trusted-hooks";
+
+/// The most recent delivery; each delivery waits for the previous one to keep events in order
+static LAST_DELIVERY: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+/// A change to the requirements store
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StoreEvent {
+    /// Unique event ID (lets receivers drop duplicates after retries)
+    pub id: Uuid,
+
+    /// When the change was saved
+    pub timestamp: DateTime<Utc>,
+
+    /// Project name from the store
+    pub project: String,
+
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+/// What changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EventKind {
+    RequirementCreated {
+        requirement_id: Uuid,
+        spec_id: Option<String>,
+        title: String,
+        created_by: Option<String>,
+    },
+    StatusChanged {
+        requirement_id: Uuid,
+        spec_id: Option<String>,
+        title: String,
+        from: String,
+        to: String,
+        changed_by: Option<String>,
+    },
+    CommentAdded {
+        requirement_id: Uuid,
+        spec_id: Option<String>,
+        comment_id: Uuid,
+        parent_id: Option<Uuid>,
+        author: String,
+        content: String,
+    },
+    Mentioned {
+        requirement_id: Uuid,
+        spec_id: Option<String>,
+        comment_id: Uuid,
+        author: String,
+        /// Mentioned handle, without the @
+        handle: String,
+    },
+    BaselineCreated {
+        baseline_id: Uuid,
+        name: String,
+        created_by: String,
+        requirement_count: usize,
+    },
+}
+
+impl EventKind {
+    /// Event name as used in hook filters and the `event` JSON field
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::RequirementCreated { .. } => "requirement_created",
+            EventKind::StatusChanged { .. } => "status_changed",
+            EventKind::CommentAdded { .. } => "comment_added",
+            EventKind::Mentioned { .. } => "mentioned",
+            EventKind::BaselineCreated { .. } => "baseline_created",
+        }
+    }
+
+    /// SPEC-ID of the requirement the event is about, if any
+    pub fn spec_id(&self) -> Option<&str> {
+        match self {
+            EventKind::RequirementCreated { spec_id, .. }
+            | EventKind::StatusChanged { spec_id, .. }
+            | EventKind::CommentAdded { spec_id, .. }
+            | EventKind::Mentioned { spec_id, .. } => spec_id.as_deref(),
+            EventKind::BaselineCreated { .. } => None,
+        }
+    }
+}
+
+/// A hook configured for the project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HookConfig {
+    #[serde(flatten)]
+    pub sink: HookSink,
+
+    /// Event names to deliver (e.g. "status_changed"); empty means all
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+impl HookConfig {
+    /// Returns true if this hook wants `event`
+    pub fn accepts(&self, event: &StoreEvent) -> bool {
+        self.events.is_empty()
+            || self
+                .events
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(event.kind.name()))
+    }
+}
+
+/// Where a hook delivers events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HookSink {
+    /// POST each event as JSON, retrying on connection errors and 5xx/429 responses
+    Webhook {
+        url: String,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        headers: BTreeMap<String, String>,
+        #[serde(default = "default_retries")]
+        retries: u32,
+    },
+    /// Append each event as one JSON line (relative paths are resolved
+    /// against the requirements file's directory)
+    Jsonl { path: PathBuf },
+    /// Run a shell command with the event JSON on stdin and `AIDA_EVENT` /
+    /// `AIDA_SPEC_ID` in the environment (only if the user allowed command hooks,
+    /// see [`command_hooks_allowed`])
+    Command { command: String },
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+impl HookSink {
+    /// Delivers one event; `base_dir` is the requirements file's directory
+    pub fn deliver(&self, event: &StoreEvent, base_dir: &Path) -> Result<()> {
+        let body = serde_json::to_string(event)?;
+        match self {
+            HookSink::Webhook {
+                url,
+                headers,
+                retries,
+            } => post_with_retry(url, headers, *retries, event, &body),
+            HookSink::Jsonl { path } => {
+                let path = base_dir.join(path);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                writeln!(file, "{}", body)?;
+                Ok(())
+            }
+            HookSink::Command { command } => run_command(command, event, &body, base_dir),
+        }
+    }
+
+    /// Short description for messages
+    pub fn describe(&self) -> String {
+        match self {
+            HookSink::Webhook { url, .. } => format!("webhook {}", url),
+            HookSink::Jsonl { path } => format!("jsonl {}", path.display()),
+            HookSink::Command { command } => format!("command `{}`", command),
+        }
+    }
+}
+
+fn post_with_retry(
+    url: &str,
+    headers: &BTreeMap<String, String>,
+    retries: u32,
+    event: &StoreEvent,
+    body: &str,
+) -> Result<()> {
+    let mut attempt = 0;
+    loop {
+        let mut request = ureq::post(url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .set("Content-Type", "application/json")
+            .set("X-Aida-Event", event.kind.name());
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+
+        let error = match request.send_string(body) {
+            Ok(_) => return Ok(()),
+            Err(ureq::Error::Status(code, _)) if code != 429 && code < 500 => {
+                bail!("{} rejected the event with HTTP {}", url, code)
+            }
+            Err(e) => e,
+        };
+        if attempt >= retries {
+            bail!("{} failed after {} attempts: {}", url, attempt + 1, error);
+        }
+        std::thread::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt));
+        attempt += 1;
+    }
+}
+
+fn run_command(command: &str, event: &StoreEvent, body: &str, base_dir: &Path) -> Result<()> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    let mut child = cmd
+        .arg(command)
+        .current_dir(base_dir)
+        .env("AIDA_EVENT", event.kind.name())
+        .env("AIDA_SPEC_ID", event.kind.spec_id().unwrap_or_default())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run `{}`", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The command may exit without reading its input
+        let _ = stdin.write_all(body.as_bytes());
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("`{}` exited with {}", command, status);
+    }
+    Ok(())
+}
+
+/// Events describing the changes from `before` to `after`
+pub fn diff_events(before: &RequirementsStore, after: &RequirementsStore) -> Vec<StoreEvent> {
+    let mut kinds = Vec::new();
+    let previous: HashMap<Uuid, &Requirement> =
+        before.requirements.iter().map(|r| (r.id, r)).collect();
+
+    for req in &after.requirements {
+        let Some(old) = previous.get(&req.id) else {
+            kinds.push(EventKind::RequirementCreated {
+                requirement_id: req.id,
+                spec_id: req.spec_id.clone(),
+                title: req.title.clone(),
+                created_by: req.created_by.clone(),
+            });
+            continue;
+        };
+
+        let (from, to) = (old.effective_status(), req.effective_status());
+        if from != to {
+            kinds.push(EventKind::StatusChanged {
+                requirement_id: req.id,
+                spec_id: req.spec_id.clone(),
+                title: req.title.clone(),
+                from,
+                to,
+                changed_by: req.history.last().map(|h| h.author.clone()),
+            });
+        }
+
+        let mut seen = HashSet::new();
+        collect_comment_ids(&old.comments, &mut seen);
+        let mut comments = Vec::new();
+        collect_comments(&req.comments, &mut comments);
+        for comment in comments.into_iter().filter(|c| !seen.contains(&c.id)) {
+            push_comment_events(&mut kinds, req, comment);
+        }
+    }
+
+    // Comments on new requirements are reported too
+    for req in after
+        .requirements
+        .iter()
+        .filter(|r| !previous.contains_key(&r.id))
+    {
+        let mut comments = Vec::new();
+        collect_comments(&req.comments, &mut comments);
+        for comment in comments {
+            push_comment_events(&mut kinds, req, comment);
+        }
+    }
+
+    let old_baselines: HashSet<Uuid> = before.baselines.iter().map(|b| b.id).collect();
+    for baseline in after
+        .baselines
+        .iter()
+        .filter(|b| !old_baselines.contains(&b.id))
+    {
+        kinds.push(EventKind::BaselineCreated {
+            baseline_id: baseline.id,
+            name: baseline.name.clone(),
+            created_by: baseline.created_by.clone(),
+            requirement_count: baseline.requirements.len(),
+        });
+    }
+
+    let timestamp = Utc::now();
+    kinds
+        .into_iter()
+        .map(|kind| StoreEvent {
+            id: Uuid::new_v4(),
+            timestamp,
+            project: after.name.clone(),
+            kind,
+        })
+        .collect()
+}
+
+fn collect_comment_ids(comments: &[Comment], ids: &mut HashSet<Uuid>) {
+    for comment in comments {
+        ids.insert(comment.id);
+        collect_comment_ids(&comment.replies, ids);
+    }
+}
+
+fn collect_comments<'a>(comments: &'a [Comment], out: &mut Vec<&'a Comment>) {
+    for comment in comments {
+        out.push(comment);
+        collect_comments(&comment.replies, out);
+    }
+}
+
+fn push_comment_events(kinds: &mut Vec<EventKind>, req: &Requirement, comment: &Comment) {
+    kinds.push(EventKind::CommentAdded {
+        requirement_id: req.id,
+        spec_id: req.spec_id.clone(),
+        comment_id: comment.id,
+        parent_id: comment.parent_id,
+        author: comment.author.clone(),
+        content: comment.content.clone(),
+    });
+    for handle in mentioned_handles(&comment.content) {
+        kinds.push(EventKind::Mentioned {
+            requirement_id: req.id,
+            spec_id: req.spec_id.clone(),
+            comment_id: comment.id,
+            author: comment.author.clone(),
+            handle,
+        });
+    }
+}
+
+/// `@handle` mentions in text (an @ at the start of a word), deduplicated
+pub fn mentioned_handles(text: &str) -> Vec<String> {
+    let mut handles: Vec<String> = Vec::new();
+    let mut previous = ' ';
+    for (i, c) in text.char_indices() {
+        if c == '@' && !(previous.is_alphanumeric() || previous == '.' || previous == '_') {
+            let handle: String = text[i + 1..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                .collect();
+            let handle = handle.trim_end_matches('.').to_string();
+            if !handle.is_empty() && !handles.contains(&handle) {
+                handles.push(handle);
+            }
+        }
+        previous = c;
+    }
+    handles
+}
+
+/// Queues the changes from `before` to `after` for delivery to the hooks
+/// configured in `after`. Failures are reported on stderr.
+pub fn dispatch(before: &RequirementsStore, after: &RequirementsStore, base_dir: &Path) {
+    if after.hooks.is_empty() {
+        return;
+    }
+    let events = diff_events(before, after);
+    if events.is_empty() {
+        return;
+    }
+    // A bare file name has an empty parent
+    let base_dir = if base_dir.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        base_dir.to_path_buf()
+    };
+    let hooks = after.hooks.clone();
+
+    let mut last = LAST_DELIVERY.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = last.take();
+    *last = Some(std::thread::spawn(move || {
+        if let Some(previous) = previous {
+            let _ = previous.join();
+        }
+        deliver(&hooks, &events, &base_dir);
+    }));
+}
+
+/// Blocks until every queued hook delivery has finished
+pub fn wait_for_hooks() {
+    let last = LAST_DELIVERY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    if let Some(handle) = last {
+        let _ = handle.join();
+    }
+}
+
+fn deliver(hooks: &[HookConfig], events: &[StoreEvent], base_dir: &Path) {
+    let commands_allowed = command_hooks_allowed(base_dir);
+    for hook in hooks {
+        if matches!(hook.sink, HookSink::Command { .. }) && !commands_allowed {
+            eprintln!(
+                "Warning: skipped {} hook; set {}=1 or add {} to ~/.config/aida/{} to allow it",
+                hook.sink.describe(),
+                ALLOW_COMMAND_HOOKS_ENV,
+                base_dir.display(),
+                TRUSTED_HOOKS_FILE
+            );
+            continue;
+        }
+        for event in events.iter().filter(|e| hook.accepts(e)) {
+            if let Err(e) = hook.sink.deliver(event, base_dir) {
+                eprintln!(
+                    "Warning: {} hook failed for {}: {:#}",
+                    hook.sink.describe(),
+                    event.kind.name(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Whether the user allowed `command` hooks for the project in `base_dir`
+pub fn command_hooks_allowed(base_dir: &Path) -> bool {
+    if std::env::var(ALLOW_COMMAND_HOOKS_ENV)
+        .is_ok_and(|v| matches!(v.trim(), "1" | "true" | "yes"))
+    {
+        return true;
+    }
+    crate::registry::get_config_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(TRUSTED_HOOKS_FILE)).ok())
+        .is_some_and(|trusted| is_trusted_dir(&trusted, base_dir))
+}
+
+/// Whether `base_dir` is one of the paths listed in `trusted` (`#` starts a comment)
+fn is_trusted_dir(trusted: &str, base_dir: &Path) -> bool {
+    let Ok(base_dir) = base_dir.canonicalize() else {
+        return false;
+    };
+    trusted
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .any(|line| Path::new(line).canonicalize().is_ok_and(|p| p == base_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RequirementStatus;
+    use std::sync::{Arc, Mutex};
+    use tempfile::tempdir;
+
+    fn store_with_requirement() -> (RequirementsStore, Uuid) {
+        let mut store = RequirementsStore::new();
+        store.name = "demo".to_string();
+        let mut req = Requirement::new("Login".into(), String::new());
+        req.spec_id = Some("FR-001".into());
+        let id = req.id;
+        store.requirements.push(req);
+        (store, id)
+    }
+
+    #[test]
+    fn test_diff_events() {
+        let (before, id) = store_with_requirement();
+        let mut after = before.clone();
+        let req = after.get_requirement_by_id_mut(&id).unwrap();
+        req.status = RequirementStatus::Approved;
+        req.add_comment(Comment::new(
+            "alice".into(),
+            "Ready for @bob and @carol.".into(),
+        ));
+        after.create_baseline("v1".into(), None, "alice".into());
+        after
+            .requirements
+            .push(Requirement::new("Logout".into(), String::new()));
+
+        let events = diff_events(&before, &after);
+        let names: Vec<&str> = events.iter().map(|e| e.kind.name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "status_changed",
+                "comment_added",
+                "mentioned",
+                "mentioned",
+                "requirement_created",
+                "baseline_created"
+            ]
+        );
+        assert!(
+            matches!(&events[0].kind, EventKind::StatusChanged { from, to, .. } if from == "Draft" && to == "Approved")
+        );
+        assert!(
+            matches!(&events[3].kind, EventKind::Mentioned { handle, .. } if handle == "carol")
+        );
+        assert!(diff_events(&after, &after).is_empty());
+
+        assert_eq!(
+            mentioned_handles("mail me@example.com, @dave-x: hi @dave-x"),
+            vec!["dave-x"]
+        );
+    }
+
+    #[test]
+    fn test_jsonl_and_webhook_sinks() {
+        let dir = tempdir().unwrap();
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", server.server_addr().to_ip().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        // The listener fails the first delivery so the webhook has to retry
+        let log = received.clone();
+        let listener = std::thread::spawn(move || {
+            for (i, mut request) in server.incoming_requests().take(2).enumerate() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                log.lock().unwrap().push(body);
+                let status = if i == 0 { 503 } else { 200 };
+                request.respond(tiny_http::Response::empty(status)).unwrap();
+            }
+        });
+
+        let (before, id) = store_with_requirement();
+        let mut after = before.clone();
+        after.hooks = vec![
+            HookConfig {
+                sink: HookSink::Jsonl {
+                    path: "events/log.jsonl".into(),
+                },
+                events: Vec::new(),
+            },
+            HookConfig {
+                sink: HookSink::Webhook {
+                    url,
+                    headers: BTreeMap::new(),
+                    retries: 2,
+                },
+                events: vec!["status_changed".into()],
+            },
+        ];
+        let req = after.get_requirement_by_id_mut(&id).unwrap();
+        req.status = RequirementStatus::Completed;
+        req.add_comment(Comment::new("alice".into(), "Done".into()));
+
+        dispatch(&before, &after, dir.path());
+        wait_for_hooks();
+        listener.join().unwrap();
+
+        let lines = std::fs::read_to_string(dir.path().join("events/log.jsonl")).unwrap();
+        assert_eq!(lines.lines().count(), 2);
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        let event: StoreEvent = serde_json::from_str(&received[1]).unwrap();
+        assert_eq!(event.kind.name(), "status_changed");
+        assert_eq!(event.project, "demo");
+    }
+
+    #[test]
+    fn test_storage_update_emits_events() {
+        let dir = tempdir().unwrap();
+        let storage = crate::storage::Storage::new(dir.path().join("requirements.yaml"));
+        let (mut store, id) = store_with_requirement();
+        store.hooks = vec![HookConfig {
+            sink: HookSink::Jsonl {
+                path: "events.jsonl".into(),
+            },
+            events: vec!["comment_added".into()],
+        }];
+        storage.save(&store).unwrap();
+
+        storage
+            .update_atomically(|store| {
+                let req = store.get_requirement_by_id_mut(&id).unwrap();
+                req.add_comment(Comment::new("alice".into(), "Looks good".into()));
+                req.status = RequirementStatus::Approved;
+            })
+            .unwrap();
+        wait_for_hooks();
+
+        let lines = std::fs::read_to_string(dir.path().join("events.jsonl")).unwrap();
+        let event: StoreEvent = serde_json::from_str(lines.trim()).unwrap();
+        assert!(
+            matches!(event.kind, EventKind::CommentAdded { ref content, .. } if content == "Looks good")
+        );
+    }
+
+    #[test]
+    fn test_command_hooks_need_trusted_dir() {
+        let dir = tempdir().unwrap();
+        let other = tempdir().unwrap();
+        let trusted = format!("# projects\n{}\n", dir.path().display());
+        assert!(is_trusted_dir(&trusted, dir.path()));
+        assert!(!is_trusted_dir(&trusted, other.path()));
+        assert!(!is_trusted_dir("", dir.path()));
+    }
+}
@@ -0,0 +1,238 @@
+//! Baseline comparisons resolved for display
+//!
+//! A [`BaselineComparison`](crate::models::BaselineComparison) only holds requirement UUIDs. [`BaselineDiff`]
+//! resolves them to SPEC-IDs and titles (from the baseline snapshots or the
+//! working copy), sorts them, and renders the result as Markdown; it
+//! serializes as the JSON form of `aida baseline diff`.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::{Baseline, FieldChange, RequirementsStore};
+
+/// A requirement that was added or removed
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DiffRequirement {
+    pub id: Uuid,
+    pub spec_id: Option<String>,
+    pub title: String,
+}
+
+/// A requirement whose fields changed
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ModifiedRequirement {
+    pub id: Uuid,
+    pub spec_id: Option<String>,
+    /// Title on the newer side
+    pub title: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Changes from a baseline to the working copy or to another baseline
+#[derive(Debug, Clone, Serialize)]
+pub struct BaselineDiff {
+    /// Name of the older baseline
+    pub from: String,
+    /// Name of the newer baseline; `None` for the working copy
+    pub to: Option<String>,
+    pub added: Vec<DiffRequirement>,
+    pub removed: Vec<DiffRequirement>,
+    pub modified: Vec<ModifiedRequirement>,
+    /// Number of requirements without changes
+    pub unchanged: usize,
+}
+
+impl BaselineDiff {
+    /// Compares `from` with `to`, or with the working copy when `to` is `None`
+    pub fn new(store: &RequirementsStore, from: &Baseline, to: Option<&Baseline>) -> Self {
+        let comparison = match to {
+            Some(to) => store.compare_baselines(&from.id, &to.id),
+            None => store.compare_with_baseline(&from.id),
+        }
+        .unwrap_or_default();
+
+        let old: HashMap<Uuid, (Option<String>, String)> = from
+            .requirements
+            .iter()
+            .map(|s| (s.original_id, (s.spec_id.clone(), s.title.clone())))
+            .collect();
+        let new: HashMap<Uuid, (Option<String>, String)> = match to {
+            Some(to) => to
+                .requirements
+                .iter()
+                .map(|s| (s.original_id, (s.spec_id.clone(), s.title.clone())))
+                .collect(),
+            None => store
+                .requirements
+                .iter()
+                .map(|r| (r.id, (r.spec_id.clone(), r.title.clone())))
+                .collect(),
+        };
+        let resolve = |side: &HashMap<Uuid, (Option<String>, String)>, id: &Uuid| {
+            let (spec_id, title) = side.get(id).cloned().unwrap_or_default();
+            DiffRequirement {
+                id: *id,
+                spec_id,
+                title,
+            }
+        };
+
+        let mut added: Vec<DiffRequirement> = comparison
+            .added
+            .iter()
+            .map(|id| resolve(&new, id))
+            .collect();
+        let mut removed: Vec<DiffRequirement> = comparison
+            .removed
+            .iter()
+            .map(|id| resolve(&old, id))
+            .collect();
+        let mut modified: Vec<ModifiedRequirement> = comparison
+            .modified
+            .into_iter()
+            .map(|diff| {
+                let req = resolve(&new, &diff.id);
+                ModifiedRequirement {
+                    id: diff.id,
+                    spec_id: req.spec_id,
+                    title: req.title,
+                    changes: diff.changes,
+                }
+            })
+            .collect();
+        added.sort_by(|a, b| (&a.spec_id, &a.title).cmp(&(&b.spec_id, &b.title)));
+        removed.sort_by(|a, b| (&a.spec_id, &a.title).cmp(&(&b.spec_id, &b.title)));
+        modified.sort_by(|a, b| (&a.spec_id, &a.title).cmp(&(&b.spec_id, &b.title)));
+
+        Self {
+            from: from.name.clone(),
+            to: to.map(|b| b.name.clone()),
+            added,
+            removed,
+            modified,
+            unchanged: comparison.unchanged.len(),
+        }
+    }
+
+    /// Display name of the newer side
+    pub fn to_label(&self) -> &str {
+        self.to.as_deref().unwrap_or("working copy")
+    }
+
+    /// True when nothing was added, removed or modified
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Renders the changes as a Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Changes from {} to {}\n\n{} added, {} removed, {} modified, {} unchanged.\n",
+            self.from,
+            self.to_label(),
+            self.added.len(),
+            self.removed.len(),
+            self.modified.len(),
+            self.unchanged
+        );
+        for (heading, reqs) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if reqs.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n\n", heading));
+            for req in reqs {
+                out.push_str(&format!(
+                    "- **{}** {}\n",
+                    req.spec_id.as_deref().unwrap_or("-"),
+                    req.title
+                ));
+            }
+        }
+        if !self.modified.is_empty() {
+            out.push_str("\n## Modified\n");
+            for req in &self.modified {
+                out.push_str(&format!(
+                    "\n### {} {}\n\n| Field | {} | {} |\n|-------|------|------|\n",
+                    req.spec_id.as_deref().unwrap_or("-"),
+                    req.title,
+                    table_cell(&self.from),
+                    table_cell(self.to_label())
+                ));
+                for change in &req.changes {
+                    out.push_str(&format!(
+                        "| {} | {} | {} |\n",
+                        change.field_name,
+                        table_cell(&change.old_value),
+                        table_cell(&change.new_value)
+                    ));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Escapes a value for a single Markdown table cell
+fn table_cell(value: &str) -> String {
+    value.trim().replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Requirement, RequirementStatus};
+
+    /// The shared fixture baselined as v1; then FR-001 is approved, FR-003
+    /// removed and FR-004 added
+    fn store() -> RequirementsStore {
+        let mut store = crate::export::sample_store();
+        store.create_baseline("v1".into(), None, "alice".into());
+        store.requirements[0].status = RequirementStatus::Approved;
+        store.requirements.remove(2);
+        let mut added = Requirement::new("Reset | recover password".into(), String::new());
+        added.spec_id = Some("FR-004".into());
+        store.requirements.push(added);
+        store
+    }
+
+    #[test]
+    fn test_diff_against_working_copy() {
+        let store = store();
+        let diff = BaselineDiff::new(&store, &store.baselines[0], None);
+
+        assert_eq!(diff.to_label(), "working copy");
+        assert_eq!(diff.added[0].spec_id.as_deref(), Some("FR-004"));
+        assert_eq!(diff.removed[0].title, "Login test");
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].changes[0].field_name, "status");
+        assert_eq!(diff.unchanged, 1);
+    }
+
+    #[test]
+    fn test_diff_between_baselines() {
+        let mut store = store();
+        store.create_baseline("v2".into(), None, "bob".into());
+        let (v1, v2) = (&store.baselines[0], &store.baselines[1]);
+
+        let diff = BaselineDiff::new(&store, v1, Some(v2));
+        assert_eq!(diff.to.as_deref(), Some("v2"));
+        assert_eq!((diff.added.len(), diff.removed.len()), (1, 1));
+
+        let same = BaselineDiff::new(&store, v2, Some(v2));
+        assert!(same.is_empty());
+        assert_eq!(same.unchanged, 3);
+    }
+
+    #[test]
+    fn test_markdown_lists_changes() {
+        let store = store();
+        let md = BaselineDiff::new(&store, &store.baselines[0], None).to_markdown();
+
+        assert!(md.starts_with("# Changes from v1 to working copy"));
+        assert!(md.contains("1 added, 1 removed, 1 modified, 1 unchanged."));
+        assert!(md.contains("- **FR-004** Reset | recover password"));
+        assert!(md.contains("### FR-001 Login"));
+        assert!(md.contains("| status | Draft | Approved |"));
+    }
+}
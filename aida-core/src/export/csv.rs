@@ -0,0 +1,583 @@
+//! CSV import and export for spreadsheets
+//!
+//! Imports map spreadsheet columns to requirement fields with a
+//! [`CsvMapping`]. A key column identifies rows across imports: rows whose
+//! key matches an existing requirement (by SPEC-ID, or by the key recorded
+//! in the `Import.Key` custom field on a previous import) update it, all
+//! other rows create new requirements. A parent column links rows to their
+//! parent by key or SPEC-ID.
+//!
+//! The export writes the columns the importer understands by default, so
+//! `aida export -f csv` followed by `aida import csv` round-trips.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::{
+    create_requirement, parse_tags, requirement_type, set_parent, update_requirement, ImportSummary,
+};
+use crate::bulk::RequirementPatch;
+use crate::models::{RelationshipType, Requirement, RequirementType, RequirementsStore};
+
+/// Custom field holding the key column value of a row imported from a spreadsheet
+pub const IMPORT_KEY_FIELD: &str = "Import.Key";
+
+/// A requirement field a spreadsheet column can be mapped to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvField {
+    SpecId,
+    Title,
+    Description,
+    Type,
+    Status,
+    Priority,
+    Owner,
+    Feature,
+    Tags,
+    Parent,
+    /// Custom field with the given name
+    Custom(String),
+}
+
+impl CsvField {
+    /// Parses a field name; unknown names are treated as custom fields
+    pub fn parse(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "id" | "spec-id" | "spec_id" | "spec id" => CsvField::SpecId,
+            "title" => CsvField::Title,
+            "description" => CsvField::Description,
+            "type" => CsvField::Type,
+            "status" => CsvField::Status,
+            "priority" => CsvField::Priority,
+            "owner" => CsvField::Owner,
+            "feature" => CsvField::Feature,
+            "tags" | "tag" => CsvField::Tags,
+            "parent" => CsvField::Parent,
+            _ => CsvField::Custom(name.trim().to_string()),
+        }
+    }
+
+    /// Column header used by [`to_csv`]
+    pub fn header(&self) -> &str {
+        match self {
+            CsvField::SpecId => "ID",
+            CsvField::Title => "Title",
+            CsvField::Description => "Description",
+            CsvField::Type => "Type",
+            CsvField::Status => "Status",
+            CsvField::Priority => "Priority",
+            CsvField::Owner => "Owner",
+            CsvField::Feature => "Feature",
+            CsvField::Tags => "Tags",
+            CsvField::Parent => "Parent",
+            CsvField::Custom(name) => name,
+        }
+    }
+}
+
+/// How spreadsheet columns map to requirement fields
+#[derive(Debug, Clone)]
+pub struct CsvMapping {
+    /// Field and the column header it is read from
+    pub columns: Vec<(CsvField, String)>,
+    /// Column identifying rows across imports (defaults to the SPEC-ID column)
+    pub key_column: Option<String>,
+    /// Spreadsheet type values mapped to requirement type names
+    pub type_values: HashMap<String, String>,
+    /// Spreadsheet status values mapped to status names
+    pub status_values: HashMap<String, String>,
+    /// Field delimiter
+    pub delimiter: u8,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            key_column: None,
+            type_values: HashMap::new(),
+            status_values: HashMap::new(),
+            delimiter: b',',
+        }
+    }
+}
+
+impl CsvMapping {
+    /// Parses a mapping such as `title=Summary,description=Details,parent=Parent ID`
+    ///
+    /// `key=<column>` selects the key column; unknown field names map to
+    /// custom fields.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut mapping = Self::default();
+        for (field, column) in parse_pairs(spec, "field=Column")? {
+            if field.eq_ignore_ascii_case("key") {
+                mapping.key_column = Some(column);
+            } else {
+                mapping.columns.push((CsvField::parse(&field), column));
+            }
+        }
+        Ok(mapping)
+    }
+
+    /// Maps every column to the field named by its header
+    pub fn from_headers(headers: &[String]) -> Self {
+        Self {
+            columns: headers
+                .iter()
+                .filter(|h| !h.trim().is_empty())
+                .map(|h| (CsvField::parse(h), h.clone()))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets type value mappings from `Story=Story,Defect=Bug`
+    pub fn with_type_values(mut self, spec: &str) -> Result<Self> {
+        self.type_values = parse_pairs(spec, "Value=Type")?.into_iter().collect();
+        Ok(self)
+    }
+
+    /// Sets status value mappings from `Open=Draft,Done=Completed`
+    pub fn with_status_values(mut self, spec: &str) -> Result<Self> {
+        self.status_values = parse_pairs(spec, "Value=Status")?.into_iter().collect();
+        Ok(self)
+    }
+
+    /// Sets the field delimiter (e.g. `b'\t'` for tab-separated files)
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// The key column, falling back to the column mapped to the SPEC-ID
+    fn key(&self) -> Option<&str> {
+        self.key_column.as_deref().or_else(|| {
+            self.columns
+                .iter()
+                .find(|(field, _)| *field == CsvField::SpecId)
+                .map(|(_, column)| column.as_str())
+        })
+    }
+}
+
+/// Parses comma-separated `left=right` pairs
+fn parse_pairs(spec: &str, expected: &str) -> Result<Vec<(String, String)>> {
+    spec.split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((left, right)) if !left.trim().is_empty() && !right.trim().is_empty() => {
+                Ok((left.trim().to_string(), right.trim().to_string()))
+            }
+            _ => anyhow::bail!("Invalid mapping '{}': expected {}", pair.trim(), expected),
+        })
+        .collect()
+}
+
+/// Imports CSV rows into the store
+///
+/// When the mapping has no columns, every column is mapped by its header.
+/// Empty cells leave the existing value alone.
+pub fn import_csv(
+    store: &mut RequirementsStore,
+    data: &str,
+    mapping: &CsvMapping,
+    author: &str,
+) -> Result<ImportSummary> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(mapping.delimiter)
+        .flexible(true)
+        .from_reader(data.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{feff}').trim().to_string())
+        .collect();
+
+    let default_mapping;
+    let mapping = if mapping.columns.is_empty() {
+        default_mapping = CsvMapping {
+            columns: CsvMapping::from_headers(&headers).columns,
+            ..mapping.clone()
+        };
+        &default_mapping
+    } else {
+        mapping
+    };
+
+    let column_index = |column: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(column));
+    let mut columns = Vec::new();
+    for (field, column) in &mapping.columns {
+        let index = column_index(column).with_context(|| {
+            format!(
+                "Column '{}' not found. Available columns: {}",
+                column,
+                headers.join(", ")
+            )
+        })?;
+        columns.push((field, index));
+    }
+    let key_index = match mapping.key() {
+        Some(column) => Some(column_index(column).with_context(|| {
+            format!(
+                "Key column '{}' not found. Available columns: {}",
+                column,
+                headers.join(", ")
+            )
+        })?),
+        None => None,
+    };
+    let key_is_spec_id = key_index.is_some()
+        && columns
+            .iter()
+            .any(|(field, index)| **field == CsvField::SpecId && Some(*index) == key_index);
+
+    let mut summary = ImportSummary::default();
+    let mut keys: HashMap<String, Uuid> = HashMap::new();
+    let mut parents: Vec<(Uuid, String, usize)> = Vec::new();
+
+    for (row_idx, record) in reader.records().enumerate() {
+        let record = record?;
+        // Header is line 1
+        let line = row_idx + 2;
+        let cell = |index: usize| record.get(index).map(str::trim).unwrap_or("");
+        if record.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+
+        let key = key_index.map(cell).filter(|k| !k.is_empty());
+        let mut patch = RequirementPatch::default();
+        let mut spec_id = None;
+        let mut req_type = None;
+        let mut tags = None;
+        let mut parent = None;
+        for (field, index) in &columns {
+            let value = cell(*index);
+            if value.is_empty() {
+                continue;
+            }
+            let value = value.to_string();
+            match field {
+                CsvField::SpecId => spec_id = Some(value),
+                CsvField::Title => patch.title = Some(value),
+                CsvField::Description => patch.description = Some(value),
+                CsvField::Type => {
+                    let mapped = mapping.type_values.get(&value).unwrap_or(&value);
+                    match requirement_type(store, mapped) {
+                        Some(t) => req_type = Some(t),
+                        None => summary
+                            .warnings
+                            .push(format!("line {}: unknown type '{}'", line, mapped)),
+                    }
+                }
+                CsvField::Status => {
+                    patch.status = Some(mapping.status_values.get(&value).unwrap_or(&value).clone())
+                }
+                CsvField::Priority => patch.priority = Some(value),
+                CsvField::Owner => patch.owner = Some(value),
+                CsvField::Feature => patch.feature = Some(value),
+                CsvField::Tags => tags = Some(parse_tags(&value)),
+                CsvField::Parent => parent = Some(value),
+                CsvField::Custom(name) => {
+                    patch.custom_fields.insert(name.clone(), value);
+                }
+            }
+        }
+
+        let existing = key.and_then(|k| find_by_key(store, k));
+        let id = match existing {
+            Some(id) => {
+                let Some(req) = store.get_requirement_by_id(&id) else {
+                    continue;
+                };
+                if patch.status.as_deref() == Some(req.effective_status().as_str()) {
+                    patch.status = None;
+                }
+                let new_type = req_type.clone().unwrap_or_else(|| req.req_type.clone());
+                normalize_status(store, Some(&new_type), &mut patch, line, &mut summary);
+                update_requirement(store, id, req_type, patch, tags, author, &mut summary);
+                id
+            }
+            None => {
+                let Some(title) = patch.title.clone() else {
+                    summary
+                        .warnings
+                        .push(format!("line {}: no title, row skipped", line));
+                    continue;
+                };
+                let req_type = req_type.unwrap_or(RequirementType::Functional);
+                normalize_status(store, Some(&req_type), &mut patch, line, &mut summary);
+                let mut req =
+                    Requirement::new(title, patch.description.clone().unwrap_or_default());
+                req.req_type = req_type;
+                req.created_by = Some(author.to_string());
+                if let Some(key) = key.filter(|_| !key_is_spec_id) {
+                    req.custom_fields
+                        .insert(IMPORT_KEY_FIELD.to_string(), key.to_string());
+                }
+                create_requirement(store, req, patch, tags, spec_id, &mut summary)
+            }
+        };
+
+        if let Some(key) = key {
+            keys.insert(key.to_string(), id);
+        }
+        if let Some(parent) = parent {
+            parents.push((id, parent, line));
+        }
+    }
+
+    // Parents are linked once every row exists, so rows may precede their parent
+    for (child, parent, line) in parents {
+        let parent_id = keys
+            .get(&parent)
+            .copied()
+            .or_else(|| find_by_key(store, &parent));
+        match parent_id {
+            Some(parent_id) => {
+                if set_parent(store, child, parent_id, author)? {
+                    summary.parents_set += 1;
+                }
+            }
+            None => summary
+                .warnings
+                .push(format!("line {}: parent '{}' not found", line, parent)),
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Finds a requirement by SPEC-ID or by a previously imported key
+fn find_by_key(store: &RequirementsStore, key: &str) -> Option<Uuid> {
+    store
+        .requirements
+        .iter()
+        .find(|r| r.spec_id.as_deref() == Some(key))
+        .or_else(|| {
+            store
+                .requirements
+                .iter()
+                .find(|r| r.custom_fields.get(IMPORT_KEY_FIELD).map(String::as_str) == Some(key))
+        })
+        .map(|r| r.id)
+}
+
+/// Matches the status case-insensitively against the statuses of the type
+fn normalize_status(
+    store: &RequirementsStore,
+    req_type: Option<&RequirementType>,
+    patch: &mut RequirementPatch,
+    line: usize,
+    summary: &mut ImportSummary,
+) {
+    let (Some(status), Some(req_type)) = (patch.status.as_mut(), req_type) else {
+        return;
+    };
+    let statuses = store.get_statuses_for_type(req_type);
+    match statuses.iter().find(|s| s.eq_ignore_ascii_case(status)) {
+        Some(known) => *status = known.clone(),
+        None if !statuses.is_empty() => summary.warnings.push(format!(
+            "line {}: status '{}' is not defined for type {}",
+            line, status, req_type
+        )),
+        None => {}
+    }
+}
+
+/// Export requirements to a CSV file
+pub fn export_csv(store: &RequirementsStore, output_path: &Path) -> Result<()> {
+    fs::write(output_path, to_csv(store)?)?;
+
+    println!("Exported to CSV: {}", output_path.display());
+    println!("  Total requirements: {}", store.requirements.len());
+
+    Ok(())
+}
+
+/// Renders the store as CSV, one row per requirement
+///
+/// Custom fields follow the standard columns, one column per field name.
+pub fn to_csv(store: &RequirementsStore) -> Result<String> {
+    let standard = [
+        CsvField::SpecId,
+        CsvField::Title,
+        CsvField::Description,
+        CsvField::Type,
+        CsvField::Status,
+        CsvField::Priority,
+        CsvField::Owner,
+        CsvField::Feature,
+        CsvField::Tags,
+        CsvField::Parent,
+    ];
+    let custom: BTreeSet<&String> = store
+        .requirements
+        .iter()
+        .flat_map(|r| r.custom_fields.keys())
+        .collect();
+
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    let mut header: Vec<&str> = standard.iter().map(CsvField::header).collect();
+    header.extend(custom.iter().map(|name| name.as_str()));
+    writer.write_record(&header)?;
+
+    for req in &store.requirements {
+        let mut tags: Vec<&str> = req.tags.iter().map(String::as_str).collect();
+        tags.sort_unstable();
+        let parent = req
+            .relationships
+            .iter()
+            .find(|r| r.rel_type == RelationshipType::Parent)
+            .and_then(|r| store.get_requirement_by_id(&r.target_id))
+            .and_then(|p| p.spec_id.clone())
+            .unwrap_or_default();
+        let req_type = store
+            .get_type_definition(&req.req_type)
+            .map(|td| td.display_name.clone())
+            .unwrap_or_else(|| req.req_type.to_string());
+
+        let mut row = vec![
+            req.spec_id.clone().unwrap_or_default(),
+            req.title.clone(),
+            req.description.clone(),
+            req_type,
+            req.effective_status(),
+            req.effective_priority(),
+            req.owner.clone(),
+            req.feature.clone(),
+            tags.join(", "),
+            parent,
+        ];
+        row.extend(
+            custom
+                .iter()
+                .map(|name| req.custom_fields.get(*name).cloned().unwrap_or_default()),
+        );
+        writer.write_record(&row)?;
+    }
+
+    let bytes = writer.into_inner().context("Failed to write CSV")?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RequirementStatus;
+
+    const SHEET: &str = "\
+Issue,Summary,Details,Kind,State,Parent ID,Component
+JIRA-1,Checkout,Customers can pay,Epic,Open,,Shop
+JIRA-2,Card payments,\"Visa, Mastercard\",Story,Done,JIRA-1,Payments
+JIRA-3,,no title,Story,Open,,
+";
+
+    fn mapping() -> CsvMapping {
+        CsvMapping::parse(
+            "key=Issue,title=Summary,description=Details,type=Kind,status=State,parent=Parent ID,component=Component",
+        )
+        .unwrap()
+        .with_status_values("Open=Draft,Done=Completed")
+        .unwrap()
+    }
+
+    #[test]
+    fn test_import_maps_columns_values_and_parents() {
+        let mut store = RequirementsStore::new();
+        let summary = import_csv(&mut store, SHEET, &mapping(), "tester").unwrap();
+
+        assert_eq!(summary.created.len(), 2);
+        assert_eq!(summary.parents_set, 1);
+        assert!(summary.warnings.iter().any(|w| w.contains("line 4")));
+
+        let epic = store
+            .requirements
+            .iter()
+            .find(|r| r.title == "Checkout")
+            .unwrap();
+        let story = store
+            .requirements
+            .iter()
+            .find(|r| r.title == "Card payments")
+            .unwrap();
+        assert_eq!(epic.req_type, RequirementType::Epic);
+        assert_eq!(story.req_type, RequirementType::Story);
+        assert_eq!(story.description, "Visa, Mastercard");
+        assert_eq!(story.status, RequirementStatus::Completed);
+        assert_eq!(story.custom_fields["component"], "Payments");
+        assert_eq!(story.custom_fields[IMPORT_KEY_FIELD], "JIRA-2");
+        assert!(store.children_of(&epic.id).iter().any(|c| c.id == story.id));
+        assert!(story.spec_id.is_some());
+    }
+
+    #[test]
+    fn test_reimport_updates_rows_by_key() {
+        let mut store = RequirementsStore::new();
+        import_csv(&mut store, SHEET, &mapping(), "tester").unwrap();
+
+        let again = import_csv(&mut store, SHEET, &mapping(), "tester").unwrap();
+        assert!(again.created.is_empty());
+        assert_eq!(again.unchanged, 2);
+
+        let changed = SHEET.replace("Customers can pay", "Customers can pay online");
+        let summary = import_csv(&mut store, &changed, &mapping(), "tester").unwrap();
+        assert_eq!(summary.updated.len(), 1);
+        assert_eq!(store.requirements.len(), 2);
+        let epic = store
+            .requirements
+            .iter()
+            .find(|r| r.title == "Checkout")
+            .unwrap();
+        assert_eq!(epic.description, "Customers can pay online");
+        assert!(!epic.history.is_empty());
+
+        let retyped = changed.replace("pay online,Epic", "pay online,Story");
+        let summary = import_csv(&mut store, &retyped, &mapping(), "tester").unwrap();
+        assert_eq!(summary.updated.len(), 1, "{:?}", summary.warnings);
+        let story = store
+            .requirements
+            .iter()
+            .find(|r| r.title == "Checkout")
+            .unwrap();
+        assert_eq!(story.req_type, RequirementType::Story);
+        let change = &story.history.last().unwrap().changes[0];
+        assert_eq!(
+            (change.field_name.as_str(), change.new_value.as_str()),
+            ("type", "Story")
+        );
+    }
+
+    #[test]
+    fn test_export_round_trips_with_default_mapping() {
+        let mut store = RequirementsStore::new();
+        import_csv(&mut store, SHEET, &mapping(), "tester").unwrap();
+        let csv = to_csv(&store).unwrap();
+        assert!(
+            csv.starts_with("ID,Title,Description,Type,Status,Priority,Owner,Feature,Tags,Parent,")
+        );
+
+        let mut copy = RequirementsStore::new();
+        let summary = import_csv(&mut copy, &csv, &CsvMapping::default(), "tester").unwrap();
+        assert!(summary.warnings.is_empty(), "{:?}", summary.warnings);
+        for original in &store.requirements {
+            let imported = copy
+                .requirements
+                .iter()
+                .find(|r| r.spec_id == original.spec_id)
+                .unwrap();
+            assert_eq!(imported.title, original.title);
+            assert_eq!(imported.req_type, original.req_type);
+            assert_eq!(imported.status, original.status);
+            assert_eq!(imported.custom_fields, original.custom_fields);
+        }
+        assert_eq!(summary.parents_set, 1);
+
+        // Re-importing the export into the source store changes nothing
+        let again = import_csv(&mut store, &csv, &CsvMapping::default(), "tester").unwrap();
+        assert_eq!(again.unchanged, 2);
+        assert!(again.updated.is_empty());
+    }
+}
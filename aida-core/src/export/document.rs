@@ -0,0 +1,550 @@
+//! Format-neutral specification document
+//!
+//! [`SpecDocument::build`] lays out the specification once as a list of
+//! [`Block`]s; the DOCX and ODT writers only translate blocks into their
+//! XML vocabularies. The document contains a title page, a revision table
+//! built from the project's baselines, one numbered section per
+//! requirement following the parent/child hierarchy, and a traceability
+//! appendix.
+//!
+//! When a baseline is chosen, requirement content comes from its snapshots
+//! and the revision table stops at that baseline; otherwise the current
+//! requirements are used and the table ends with the unbaselined changes.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Write};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+use crate::models::{
+    Baseline, BaselineComparison, RelationshipType, Requirement, RequirementSnapshot,
+    RequirementsStore,
+};
+
+/// Deepest heading level used for nested requirements
+pub const MAX_HEADING_LEVEL: usize = 9;
+
+/// One structural element of the document
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Title(String),
+    Subtitle(String),
+    /// Heading with its outline number (empty for unnumbered headings)
+    Heading {
+        level: usize,
+        number: String,
+        text: String,
+    },
+    /// Paragraph; `\n` marks a line break
+    Paragraph(String),
+    /// Table whose first row is a repeated header
+    Table {
+        header: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    PageBreak,
+}
+
+/// A specification laid out as blocks, ready for a format writer
+#[derive(Debug, Clone)]
+pub struct SpecDocument {
+    pub title: String,
+    pub author: String,
+    pub created: DateTime<Utc>,
+    pub blocks: Vec<Block>,
+}
+
+impl SpecDocument {
+    /// Lays out the specification, optionally as of the named baseline
+    pub fn build(store: &RequirementsStore, baseline: Option<&str>) -> Result<Self> {
+        let baseline = match baseline {
+            Some(name) => Some(
+                store
+                    .get_baseline_by_name(name)
+                    .with_context(|| format!("Baseline '{}' not found", name))?,
+            ),
+            None => None,
+        };
+        let requirements: Vec<Requirement> = match baseline {
+            Some(b) => b.requirements.iter().map(snapshot_requirement).collect(),
+            None => store
+                .requirements
+                .iter()
+                .filter(|r| !r.archived)
+                .cloned()
+                .collect(),
+        };
+        // IMPL records document the work done, not the specification
+        let requirements: Vec<Requirement> = requirements
+            .into_iter()
+            .filter(|r| !r.spec_id.as_deref().unwrap_or("").starts_with("IMPL-"))
+            .collect();
+
+        let title = if !store.title.is_empty() {
+            store.title.clone()
+        } else if !store.name.is_empty() {
+            store.name.clone()
+        } else {
+            "Requirements Specification".to_string()
+        };
+        let created = Utc::now();
+        let mut doc = Self {
+            title: title.clone(),
+            author: baseline
+                .map(|b| b.created_by.clone())
+                .unwrap_or_else(|| "AIDA".to_string()),
+            created,
+            blocks: Vec::new(),
+        };
+
+        // Title page
+        doc.blocks.push(Block::Title(title));
+        doc.blocks.push(Block::Subtitle(match baseline {
+            Some(b) => format!("Baseline {}", b.name),
+            None => "Working copy".to_string(),
+        }));
+        for paragraph in paragraphs(&store.description) {
+            doc.blocks.push(Block::Paragraph(paragraph));
+        }
+        doc.blocks.push(Block::Paragraph(format!(
+            "Generated on {} by AIDA {}",
+            created.format("%Y-%m-%d"),
+            env!("CARGO_PKG_VERSION")
+        )));
+        doc.blocks.push(Block::PageBreak);
+
+        doc.push_revisions(store, baseline);
+        doc.push_requirements(store, &requirements);
+        doc.push_traceability(store, &requirements);
+        Ok(doc)
+    }
+
+    fn push_revisions(&mut self, store: &RequirementsStore, chosen: Option<&Baseline>) {
+        self.blocks.push(unnumbered_heading("Revision history"));
+        let mut baselines: Vec<&Baseline> = store
+            .baselines
+            .iter()
+            .filter(|b| chosen.is_none_or(|c| b.created_at <= c.created_at))
+            .collect();
+        baselines.sort_by_key(|b| b.created_at);
+
+        let mut rows = Vec::new();
+        let mut previous: Option<&Baseline> = None;
+        for baseline in &baselines {
+            let changes = match previous {
+                Some(prev) => store
+                    .compare_baselines(&prev.id, &baseline.id)
+                    .map(|c| change_summary(&c))
+                    .unwrap_or_default(),
+                None => format!("Initial ({} requirements)", baseline.requirements.len()),
+            };
+            rows.push(vec![
+                baseline.name.clone(),
+                baseline.created_at.format("%Y-%m-%d").to_string(),
+                baseline.created_by.clone(),
+                baseline.description.clone().unwrap_or_default(),
+                changes,
+            ]);
+            previous = Some(baseline);
+        }
+        if chosen.is_none() {
+            let changes = match previous {
+                Some(prev) => store
+                    .compare_with_baseline(&prev.id)
+                    .map(|c| change_summary(&c))
+                    .unwrap_or_default(),
+                None => "No baselines yet".to_string(),
+            };
+            rows.push(vec![
+                "Working copy".to_string(),
+                self.created.format("%Y-%m-%d").to_string(),
+                String::new(),
+                "Changes not yet baselined".to_string(),
+                changes,
+            ]);
+        }
+
+        self.blocks.push(Block::Table {
+            header: ["Revision", "Date", "Author", "Description", "Changes"]
+                .map(String::from)
+                .to_vec(),
+            rows,
+        });
+    }
+
+    fn push_requirements(&mut self, store: &RequirementsStore, requirements: &[Requirement]) {
+        let by_id: HashMap<Uuid, &Requirement> = requirements.iter().map(|r| (r.id, r)).collect();
+        let parent_of = |req: &Requirement| {
+            req.relationships
+                .iter()
+                .find(|r| {
+                    r.rel_type == RelationshipType::Parent && by_id.contains_key(&r.target_id)
+                })
+                .map(|r| r.target_id)
+        };
+        let mut children: HashMap<Uuid, Vec<&Requirement>> = HashMap::new();
+        let mut roots = Vec::new();
+        for req in requirements {
+            match parent_of(req) {
+                Some(parent) => children.entry(parent).or_default().push(req),
+                None => roots.push(req),
+            }
+        }
+        for list in children.values_mut() {
+            list.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+        }
+        roots.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+
+        let mut visited = HashSet::new();
+        let mut number = 0;
+        for root in roots {
+            number += 1;
+            self.push_requirement(store, root, &number.to_string(), 1, &children, &mut visited);
+        }
+        // Requirements caught in a parent cycle have no root; list them at the top level
+        let mut remaining: Vec<&Requirement> = requirements
+            .iter()
+            .filter(|r| !visited.contains(&r.id))
+            .collect();
+        remaining.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+        for req in remaining {
+            if !visited.contains(&req.id) {
+                number += 1;
+                self.push_requirement(store, req, &number.to_string(), 1, &children, &mut visited);
+            }
+        }
+    }
+
+    fn push_requirement(
+        &mut self,
+        store: &RequirementsStore,
+        req: &Requirement,
+        number: &str,
+        level: usize,
+        children: &HashMap<Uuid, Vec<&Requirement>>,
+        visited: &mut HashSet<Uuid>,
+    ) {
+        if !visited.insert(req.id) {
+            return;
+        }
+        self.blocks.push(Block::Heading {
+            level: level.min(MAX_HEADING_LEVEL),
+            number: number.to_string(),
+            text: format!("{} {}", label(req), req.title),
+        });
+
+        let type_name = format!("{:?}", req.req_type);
+        let type_display = store
+            .type_definitions
+            .iter()
+            .find(|td| td.name == type_name)
+            .map(|td| td.display_name.clone())
+            .unwrap_or_else(|| req.req_type.to_string());
+        let mut tags: Vec<&str> = req.tags.iter().map(String::as_str).collect();
+        tags.sort_unstable();
+        let mut rows = vec![
+            vec!["Type".to_string(), type_display],
+            vec!["Status".to_string(), req.effective_status()],
+            vec!["Priority".to_string(), req.effective_priority()],
+            vec!["Feature".to_string(), req.feature.clone()],
+        ];
+        if !req.owner.is_empty() {
+            rows.push(vec!["Owner".to_string(), req.owner.clone()]);
+        }
+        if !tags.is_empty() {
+            rows.push(vec!["Tags".to_string(), tags.join(", ")]);
+        }
+        for (_, label, value) in custom_field_values(store, req) {
+            rows.push(vec![label, value]);
+        }
+        self.blocks.push(Block::Table {
+            header: vec!["Field".to_string(), "Value".to_string()],
+            rows,
+        });
+
+        for paragraph in paragraphs(&req.description) {
+            self.blocks.push(Block::Paragraph(paragraph));
+        }
+        if !req.acceptance_criteria.is_empty() {
+            self.blocks
+                .push(Block::Paragraph("Acceptance criteria:".to_string()));
+            for ac in &req.acceptance_criteria {
+                self.blocks.push(Block::Paragraph(ac.checklist_line()));
+            }
+        }
+
+        if let Some(kids) = children.get(&req.id) {
+            for (idx, child) in kids.iter().enumerate() {
+                let child_number = format!("{}.{}", number, idx + 1);
+                self.push_requirement(store, child, &child_number, level + 1, children, visited);
+            }
+        }
+    }
+
+    fn push_traceability(&mut self, store: &RequirementsStore, requirements: &[Requirement]) {
+        let by_id: HashMap<Uuid, &Requirement> = requirements.iter().map(|r| (r.id, r)).collect();
+        let mut rows = Vec::new();
+        let mut sorted: Vec<&Requirement> = requirements.iter().collect();
+        sorted.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+        for req in sorted {
+            for rel in &req.relationships {
+                if matches!(
+                    rel.rel_type,
+                    RelationshipType::Parent | RelationshipType::Child
+                ) {
+                    continue;
+                }
+                let Some(target) = by_id.get(&rel.target_id) else {
+                    continue;
+                };
+                let relationship = store
+                    .get_definition_for_type(&rel.rel_type)
+                    .map(|d| d.display_name.clone())
+                    .unwrap_or_else(|| rel.rel_type.name());
+                rows.push(vec![
+                    label(req),
+                    relationship,
+                    label(target),
+                    target.title.clone(),
+                ]);
+            }
+        }
+
+        self.blocks.push(Block::PageBreak);
+        self.blocks
+            .push(unnumbered_heading("Appendix A: Traceability matrix"));
+        if rows.is_empty() {
+            self.blocks.push(Block::Paragraph(
+                "No traceability links are recorded.".to_string(),
+            ));
+        } else {
+            self.blocks.push(Block::Table {
+                header: ["Requirement", "Relationship", "Target", "Target title"]
+                    .map(String::from)
+                    .to_vec(),
+                rows,
+            });
+        }
+    }
+}
+
+fn unnumbered_heading(text: &str) -> Block {
+    Block::Heading {
+        level: 1,
+        number: String::new(),
+        text: text.to_string(),
+    }
+}
+
+fn change_summary(comparison: &BaselineComparison) -> String {
+    format!(
+        "{} added, {} modified, {} removed",
+        comparison.added.len(),
+        comparison.modified.len(),
+        comparison.removed.len()
+    )
+}
+
+/// Splits text into paragraphs at blank lines, keeping single line breaks
+fn paragraphs(text: &str) -> Vec<String> {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|p| p.trim_matches('\n').to_string())
+        .filter(|p| !p.trim().is_empty())
+        .collect()
+}
+
+fn label(req: &Requirement) -> String {
+    req.spec_id.clone().unwrap_or_else(|| req.id.to_string())
+}
+
+/// Non-empty custom fields as (name, label, value), in the order the type
+/// defines them, then any others by name
+pub(super) fn custom_field_values(
+    store: &RequirementsStore,
+    req: &Requirement,
+) -> Vec<(String, String, String)> {
+    let defined: Vec<(String, String)> = store
+        .get_type_definition(&req.req_type)
+        .map(|td| {
+            let mut fields = td.custom_fields.clone();
+            fields.sort_by_key(|f| f.order);
+            fields.into_iter().map(|f| (f.name, f.label)).collect()
+        })
+        .unwrap_or_default();
+    let mut values = Vec::new();
+    for (name, label) in &defined {
+        if let Some(value) = req.custom_fields.get(name).filter(|v| !v.is_empty()) {
+            let label = if label.is_empty() || label == name {
+                humanize(name)
+            } else {
+                label.clone()
+            };
+            values.push((name.clone(), label, value.clone()));
+        }
+    }
+    let mut others: Vec<(&String, &String)> = req
+        .custom_fields
+        .iter()
+        .filter(|(name, value)| !value.is_empty() && !defined.iter().any(|(n, _)| n == *name))
+        .collect();
+    others.sort();
+    for (name, value) in others {
+        values.push((name.clone(), humanize(name), value.clone()));
+    }
+    values
+}
+
+/// Turns a field name like `files_changed` into "Files Changed"
+fn humanize(name: &str) -> String {
+    name.split(['_', '-'])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Rebuilds a requirement from its baseline snapshot
+pub(super) fn snapshot_requirement(snapshot: &RequirementSnapshot) -> Requirement {
+    let mut req = Requirement::new(snapshot.title.clone(), snapshot.description.clone());
+    req.id = snapshot.original_id;
+    req.spec_id = snapshot.spec_id.clone();
+    req.status = snapshot.status.clone();
+    req.priority = snapshot.priority.clone();
+    req.custom_status = snapshot.custom_status.clone();
+    req.custom_priority = snapshot.custom_priority.clone();
+    req.owner = snapshot.owner.clone();
+    req.feature = snapshot.feature.clone();
+    req.req_type = snapshot.req_type.clone();
+    req.tags = snapshot.tags.clone();
+    req.relationships = snapshot.relationships.clone();
+    req.custom_fields = snapshot.custom_fields.clone();
+    req.acceptance_criteria = snapshot.acceptance_criteria.clone();
+    req
+}
+
+/// Escapes text for XML, dropping control characters XML 1.0 does not allow
+pub(super) fn xml_text(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    quick_xml::escape::escape(cleaned.as_str()).into_owned()
+}
+
+/// Packs files into a zip archive; entries marked stored are not compressed
+pub(super) fn zip_package(files: &[(&str, String, bool)]) -> Result<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content, stored) in files {
+        let method = if *stored {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        };
+        zip.start_file(
+            *name,
+            SimpleFileOptions::default().compression_method(method),
+        )?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The shared fixture with a project description and a two-paragraph
+    /// FR-001
+    fn sample_store() -> RequirementsStore {
+        let mut store = crate::export::sample_store();
+        store.title = "Authentication".into();
+        store.description = "Specification of the sign-in flow.".into();
+        let login = &mut store.requirements[0];
+        login.description = "Users sign in.\n\nSecond paragraph".into();
+        login.custom_fields.insert("risk".into(), "high".into());
+        store
+    }
+
+    fn headings(doc: &SpecDocument) -> Vec<(usize, String, String)> {
+        doc.blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Heading {
+                    level,
+                    number,
+                    text,
+                } => Some((*level, number.clone(), text.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_headings_follow_hierarchy() {
+        let doc = SpecDocument::build(&sample_store(), None).unwrap();
+        let headings = headings(&doc);
+
+        assert_eq!(headings[0].2, "Revision history");
+        assert!(headings.contains(&(1, "1".into(), "FR-001 Login".into())));
+        assert!(headings.contains(&(2, "1.1".into(), "FR-002 Lockout".into())));
+        assert!(headings.contains(&(1, "2".into(), "FR-003 Login test".into())));
+        assert!(doc.blocks.contains(&Block::Table {
+            header: vec![
+                "Requirement".into(),
+                "Relationship".into(),
+                "Target".into(),
+                "Target title".into()
+            ],
+            rows: vec![vec![
+                "FR-003".into(),
+                "Verifies".into(),
+                "FR-001".into(),
+                "Login".into()
+            ]],
+        }));
+        assert!(doc
+            .blocks
+            .contains(&Block::Paragraph("Second paragraph".into())));
+    }
+
+    #[test]
+    fn test_baseline_selects_snapshot_and_revisions() {
+        let mut store = sample_store();
+        store.create_baseline("v1".into(), Some("First release".into()), "alice".into());
+        store.requirements[0].title = "Login v2".into();
+        store.create_baseline("v2".into(), None, "bob".into());
+
+        let v1 = SpecDocument::build(&store, Some("v1")).unwrap();
+        assert!(headings(&v1).iter().any(|h| h.2 == "FR-001 Login"));
+        let revisions = v1.blocks.iter().find_map(|b| match b {
+            Block::Table { header, rows } if header[0] == "Revision" => Some(rows.clone()),
+            _ => None,
+        });
+        assert_eq!(revisions.unwrap().len(), 1);
+
+        let current = SpecDocument::build(&store, None).unwrap();
+        assert!(headings(&current).iter().any(|h| h.2 == "FR-001 Login v2"));
+        let revisions = current
+            .blocks
+            .iter()
+            .find_map(|b| match b {
+                Block::Table { header, rows } if header[0] == "Revision" => Some(rows.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(revisions.len(), 3);
+        assert_eq!(revisions[1][4], "0 added, 1 modified, 0 removed");
+
+        assert!(SpecDocument::build(&store, Some("missing")).is_err());
+    }
+}
@@ -0,0 +1,552 @@
+//! Relationship diagrams as Graphviz DOT and Mermaid flowcharts
+//!
+//! A [`GraphFilter`] picks the part of the relationship graph to draw:
+//! starting from requirements (by SPEC-ID) and/or a feature, the subgraph
+//! grows along the selected relationship types, in either direction, up to
+//! the given depth. Without a starting point every requirement is drawn.
+//! Nodes are filled with their type's color and edges use the relationship
+//! color; nodes can be grouped into one cluster per feature.
+
+use anyhow::{bail, Result};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::site::{contrast_color, css_color};
+use crate::models::{RelationshipType, Requirement, RequirementsStore};
+
+/// Longest title shown in a node before it is shortened
+const MAX_TITLE_CHARS: usize = 48;
+
+/// Which part of the relationship graph to draw
+#[derive(Debug, Clone, Default)]
+pub struct GraphFilter {
+    /// SPEC-IDs to start from
+    pub roots: Vec<String>,
+    /// Feature whose requirements are starting points
+    pub feature: Option<String>,
+    /// Relationship types to follow (empty means all)
+    pub relationships: Vec<String>,
+    /// Maximum number of hops from the starting points (`None` for no limit)
+    pub depth: Option<usize>,
+    /// Group nodes into one cluster per feature
+    pub cluster_by_feature: bool,
+}
+
+impl GraphFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_root(mut self, spec_id: impl Into<String>) -> Self {
+        self.roots.push(spec_id.into());
+        self
+    }
+
+    pub fn with_feature(mut self, feature: impl Into<String>) -> Self {
+        self.feature = Some(feature.into());
+        self
+    }
+
+    pub fn with_relationship(mut self, name: impl Into<String>) -> Self {
+        self.relationships.push(name.into());
+        self
+    }
+
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn with_feature_clusters(mut self, cluster: bool) -> Self {
+        self.cluster_by_feature = cluster;
+        self
+    }
+
+    fn follows(&self, rel_type: &RelationshipType) -> bool {
+        self.relationships.is_empty()
+            || self
+                .relationships
+                .iter()
+                .any(|name| RelationshipType::from_str(name) == *rel_type)
+    }
+}
+
+/// An edge between two nodes (indexes into [`Subgraph::nodes`])
+struct Edge {
+    from: usize,
+    to: usize,
+    label: String,
+    color: Option<String>,
+    /// Symmetric relationships are drawn without an arrow head
+    undirected: bool,
+}
+
+/// The requirements and links selected by a filter
+struct Subgraph<'a> {
+    nodes: Vec<&'a Requirement>,
+    edges: Vec<Edge>,
+}
+
+impl<'a> Subgraph<'a> {
+    fn select(store: &'a RequirementsStore, filter: &GraphFilter) -> Result<Self> {
+        let active: Vec<&Requirement> = store.requirements.iter().filter(|r| !r.archived).collect();
+        let by_id: HashMap<Uuid, &Requirement> = active.iter().map(|r| (r.id, *r)).collect();
+
+        // Links in both directions, restricted to the followed types
+        let mut neighbours: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for req in &active {
+            for rel in &req.relationships {
+                if filter.follows(&rel.rel_type) && by_id.contains_key(&rel.target_id) {
+                    neighbours.entry(req.id).or_default().push(rel.target_id);
+                    neighbours.entry(rel.target_id).or_default().push(req.id);
+                }
+            }
+        }
+
+        let mut start = Vec::new();
+        for spec_id in &filter.roots {
+            match active
+                .iter()
+                .find(|r| r.spec_id.as_deref() == Some(spec_id.as_str()))
+            {
+                Some(req) => start.push(req.id),
+                None => bail!("Requirement '{}' not found", spec_id),
+            }
+        }
+        if let Some(feature) = &filter.feature {
+            let members: Vec<Uuid> = active
+                .iter()
+                .filter(|r| r.feature.eq_ignore_ascii_case(feature))
+                .map(|r| r.id)
+                .collect();
+            if members.is_empty() {
+                bail!("No requirements found in feature '{}'", feature);
+            }
+            start.extend(members);
+        }
+
+        let included: HashSet<Uuid> = if start.is_empty() {
+            active
+                .iter()
+                .filter(|r| filter.relationships.is_empty() || neighbours.contains_key(&r.id))
+                .map(|r| r.id)
+                .collect()
+        } else {
+            // Breadth-first walk from the starting points
+            let mut seen: HashSet<Uuid> = start.iter().copied().collect();
+            let mut queue: VecDeque<(Uuid, usize)> = start.iter().map(|id| (*id, 0)).collect();
+            while let Some((id, hops)) = queue.pop_front() {
+                if filter.depth.is_some_and(|max| hops >= max) {
+                    continue;
+                }
+                for next in neighbours.get(&id).into_iter().flatten() {
+                    if seen.insert(*next) {
+                        queue.push_back((*next, hops + 1));
+                    }
+                }
+            }
+            seen
+        };
+
+        let mut nodes: Vec<&Requirement> = active
+            .iter()
+            .filter(|r| included.contains(&r.id))
+            .copied()
+            .collect();
+        nodes.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+        let index: HashMap<Uuid, usize> =
+            nodes.iter().enumerate().map(|(i, r)| (r.id, i)).collect();
+
+        let has_link = |from: &Requirement, rel_type: &RelationshipType, to: Uuid| {
+            from.relationships
+                .iter()
+                .any(|r| r.rel_type == *rel_type && r.target_id == to)
+        };
+        let mut edges = Vec::new();
+        for (from, req) in nodes.iter().enumerate() {
+            for rel in &req.relationships {
+                let Some(&to) = index.get(&rel.target_id) else {
+                    continue;
+                };
+                if !filter.follows(&rel.rel_type) {
+                    continue;
+                }
+                let definition = store.get_definition_for_type(&rel.rel_type);
+                let inverse = rel.rel_type.inverse();
+                let symmetric = definition.is_some_and(|d| d.symmetric)
+                    || inverse.as_ref() == Some(&rel.rel_type);
+                // Draw each link once: symmetric links from the lower node, and
+                // child/verified-by links only when their inverse is not drawn
+                if symmetric && from > to && has_link(nodes[to], &rel.rel_type, req.id) {
+                    continue;
+                }
+                if matches!(
+                    rel.rel_type,
+                    RelationshipType::Child | RelationshipType::VerifiedBy
+                ) {
+                    if let Some(inverse) = &inverse {
+                        if filter.follows(inverse) && has_link(nodes[to], inverse, req.id) {
+                            continue;
+                        }
+                    }
+                }
+                edges.push(Edge {
+                    from,
+                    to,
+                    label: definition
+                        .map(|d| d.display_name.clone())
+                        .unwrap_or_else(|| rel.rel_type.to_string()),
+                    color: definition
+                        .and_then(|d| d.color.as_deref())
+                        .and_then(css_color),
+                    undirected: symmetric,
+                });
+            }
+        }
+
+        Ok(Self { nodes, edges })
+    }
+
+    /// Node indexes grouped by feature, in feature name order
+    fn clusters(&self) -> BTreeMap<&str, Vec<usize>> {
+        let mut clusters: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, req) in self.nodes.iter().enumerate() {
+            clusters.entry(req.feature.as_str()).or_default().push(i);
+        }
+        clusters
+    }
+}
+
+/// Fill color of a requirement's type, if one is configured
+fn type_color(store: &RequirementsStore, req: &Requirement) -> Option<String> {
+    store
+        .get_type_definition(&req.req_type)
+        .and_then(|td| td.color.as_deref())
+        .and_then(css_color)
+}
+
+fn node_title(req: &Requirement) -> (String, String) {
+    let label = req.spec_id.clone().unwrap_or_else(|| req.id.to_string());
+    let title = if req.title.chars().count() > MAX_TITLE_CHARS {
+        let short: String = req.title.chars().take(MAX_TITLE_CHARS - 1).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        req.title.clone()
+    };
+    (label, title)
+}
+
+/// Renders the selected subgraph as a Graphviz DOT digraph
+pub fn to_dot(store: &RequirementsStore, filter: &GraphFilter) -> Result<String> {
+    let graph = Subgraph::select(store, filter)?;
+    Ok(dot(store, &graph, filter))
+}
+
+/// Renders the selected subgraph as a Mermaid flowchart
+pub fn to_mermaid(store: &RequirementsStore, filter: &GraphFilter) -> Result<String> {
+    let graph = Subgraph::select(store, filter)?;
+    Ok(mermaid(store, &graph, filter))
+}
+
+fn dot(store: &RequirementsStore, graph: &Subgraph, filter: &GraphFilter) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+
+    let mut out = String::from("digraph requirements {\n");
+    out.push_str("    rankdir=LR;\n");
+    out.push_str(
+        "    node [shape=box, style=\"rounded,filled\", fillcolor=\"#f3f4f6\", fontname=\"Helvetica\"];\n",
+    );
+    out.push_str("    edge [fontname=\"Helvetica\", fontsize=10];\n");
+
+    let node = |i: usize, indent: &str| {
+        let req = graph.nodes[i];
+        let (label, title) = node_title(req);
+        let mut attrs = format!("label=\"{}\\n{}\"", escape(&label), escape(&title));
+        if let Some(color) = type_color(store, req) {
+            attrs.push_str(&format!(
+                ", fillcolor=\"{}\", fontcolor=\"{}\"",
+                color,
+                contrast_color(&color)
+            ));
+        }
+        format!("{}n{} [{}];\n", indent, i, attrs)
+    };
+    if filter.cluster_by_feature {
+        for (number, (feature, members)) in graph.clusters().into_iter().enumerate() {
+            out.push_str(&format!("    subgraph \"cluster_{}\" {{\n", number));
+            let name = if feature.is_empty() {
+                "No feature"
+            } else {
+                feature
+            };
+            out.push_str(&format!("        label=\"{}\";\n", escape(name)));
+            out.push_str("        style=\"rounded,dashed\";\n");
+            for i in members {
+                out.push_str(&node(i, "        "));
+            }
+            out.push_str("    }\n");
+        }
+    } else {
+        for i in 0..graph.nodes.len() {
+            out.push_str(&node(i, "    "));
+        }
+    }
+
+    for edge in &graph.edges {
+        let mut attrs = format!("label=\"{}\"", escape(&edge.label));
+        if let Some(color) = &edge.color {
+            attrs.push_str(&format!(", color=\"{}\", fontcolor=\"{}\"", color, color));
+        }
+        if edge.undirected {
+            attrs.push_str(", dir=none");
+        }
+        out.push_str(&format!(
+            "    n{} -> n{} [{}];\n",
+            edge.from, edge.to, attrs
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid(store: &RequirementsStore, graph: &Subgraph, filter: &GraphFilter) -> String {
+    // Mermaid labels are quoted; quotes and pipes use entity codes
+    let escape = |s: &str| {
+        s.replace('"', "#quot;")
+            .replace('|', "#124;")
+            .replace('<', "#lt;")
+            .replace('>', "#gt;")
+    };
+
+    let mut out = String::from("flowchart LR\n");
+    let node = |i: usize, indent: &str| {
+        let (label, title) = node_title(graph.nodes[i]);
+        format!(
+            "{}n{}[\"{}<br/>{}\"]\n",
+            indent,
+            i,
+            escape(&label),
+            escape(&title)
+        )
+    };
+    if filter.cluster_by_feature {
+        for (number, (feature, members)) in graph.clusters().into_iter().enumerate() {
+            let name = if feature.is_empty() {
+                "No feature"
+            } else {
+                feature
+            };
+            out.push_str(&format!("    subgraph f{}[\"{}\"]\n", number, escape(name)));
+            for i in members {
+                out.push_str(&node(i, "        "));
+            }
+            out.push_str("    end\n");
+        }
+    } else {
+        for i in 0..graph.nodes.len() {
+            out.push_str(&node(i, "    "));
+        }
+    }
+
+    for edge in &graph.edges {
+        let arrow = if edge.undirected { "---" } else { "-->" };
+        out.push_str(&format!(
+            "    n{} {}|{}| n{}\n",
+            edge.from,
+            arrow,
+            escape(&edge.label),
+            edge.to
+        ));
+    }
+
+    // One class per colored type, one link style per colored edge
+    let mut classes: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, req) in graph.nodes.iter().enumerate() {
+        if let Some(color) = type_color(store, req) {
+            classes.entry(color).or_default().push(i);
+        }
+    }
+    for (number, (color, members)) in classes.iter().enumerate() {
+        out.push_str(&format!(
+            "    classDef type{} fill:{},color:{},stroke:{}\n",
+            number,
+            color,
+            contrast_color(color),
+            color
+        ));
+        let ids: Vec<String> = members.iter().map(|i| format!("n{}", i)).collect();
+        out.push_str(&format!("    class {} type{}\n", ids.join(","), number));
+    }
+    for (number, edge) in graph.edges.iter().enumerate() {
+        if let Some(color) = &edge.color {
+            out.push_str(&format!(
+                "    linkStyle {} stroke:{},color:{}\n",
+                number, color, color
+            ));
+        }
+    }
+    out
+}
+
+/// Export a relationship diagram as Graphviz DOT
+pub fn export_dot(
+    store: &RequirementsStore,
+    output_path: &Path,
+    filter: &GraphFilter,
+) -> Result<()> {
+    let graph = Subgraph::select(store, filter)?;
+    fs::write(output_path, dot(store, &graph, filter))?;
+
+    println!("Exported to DOT: {}", output_path.display());
+    println!("  Requirements in diagram: {}", graph.nodes.len());
+    println!("  Relationships in diagram: {}", graph.edges.len());
+
+    Ok(())
+}
+
+/// Export a relationship diagram as a Mermaid flowchart
+pub fn export_mermaid(
+    store: &RequirementsStore,
+    output_path: &Path,
+    filter: &GraphFilter,
+) -> Result<()> {
+    let graph = Subgraph::select(store, filter)?;
+    fs::write(output_path, mermaid(store, &graph, filter))?;
+
+    println!("Exported to Mermaid: {}", output_path.display());
+    println!("  Requirements in diagram: {}", graph.nodes.len());
+    println!("  Relationships in diagram: {}", graph.edges.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RequirementType;
+
+    /// The shared fixture below an epic, plus an unrelated FR-004:
+    /// EPIC-001 <- FR-001 <- FR-002, and FR-003 verifies FR-001
+    fn store() -> RequirementsStore {
+        let mut store = crate::export::sample_store();
+        let mut epic = Requirement::new("Accounts \"core\"".into(), String::new());
+        epic.spec_id = Some("EPIC-001".into());
+        epic.req_type = RequirementType::Epic;
+        epic.feature = "Core".into();
+        let mut audit = Requirement::new("Audit log".into(), String::new());
+        audit.spec_id = Some("FR-004".into());
+        let (epic_id, login_id) = (epic.id, store.requirements[0].id);
+        store.requirements.insert(0, epic);
+        store.requirements.push(audit);
+        for req in &mut store.requirements[1..] {
+            req.feature = "Auth".into();
+        }
+        store.requirements[3].feature = "Core".into();
+        store
+            .add_relationship(&login_id, RelationshipType::Parent, &epic_id, true)
+            .unwrap();
+        store
+    }
+
+    fn labels(graph: &Subgraph) -> Vec<String> {
+        graph
+            .nodes
+            .iter()
+            .map(|r| r.spec_id.clone().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_subgraph_follows_depth_and_relationship_types() {
+        let store = store();
+
+        let graph = Subgraph::select(
+            &store,
+            &GraphFilter::new().with_root("EPIC-001").with_depth(1),
+        )
+        .unwrap();
+        assert_eq!(labels(&graph), vec!["EPIC-001", "FR-001"]);
+        // Parent/child pairs are drawn once, from the child
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!((graph.edges[0].from, graph.edges[0].to), (1, 0));
+
+        let graph = Subgraph::select(&store, &GraphFilter::new().with_root("EPIC-001")).unwrap();
+        assert_eq!(
+            labels(&graph),
+            vec!["EPIC-001", "FR-001", "FR-002", "FR-003"]
+        );
+        assert_eq!(graph.edges.len(), 3);
+
+        let filter = GraphFilter::new()
+            .with_root("EPIC-001")
+            .with_relationship("parent");
+        let graph = Subgraph::select(&store, &filter).unwrap();
+        assert_eq!(labels(&graph), vec!["EPIC-001", "FR-001", "FR-002"]);
+
+        let graph = Subgraph::select(
+            &store,
+            &GraphFilter::new().with_feature("auth").with_depth(0),
+        )
+        .unwrap();
+        assert_eq!(labels(&graph), vec!["FR-001", "FR-002", "FR-004"]);
+
+        assert!(Subgraph::select(&store, &GraphFilter::new().with_root("NOPE-1")).is_err());
+    }
+
+    #[test]
+    fn test_dot_uses_colors_and_feature_clusters() {
+        let mut store = store();
+        for td in &mut store.type_definitions {
+            if td.name == "Epic" {
+                td.color = Some("#1F3864".into());
+            }
+        }
+        for def in &mut store.relationship_definitions {
+            def.color = (def.name == "verifies").then(|| "#ff6b6b".to_string());
+        }
+
+        let dot = to_dot(
+            &store,
+            &GraphFilter::new()
+                .with_root("FR-001")
+                .with_feature_clusters(true),
+        )
+        .unwrap();
+        assert!(dot.starts_with("digraph requirements {"));
+        assert!(dot.contains("subgraph \"cluster_1\" {\n        label=\"Core\";"));
+        assert!(dot.contains(
+            "label=\"EPIC-001\\nAccounts \\\"core\\\"\", fillcolor=\"#1f3864\", fontcolor=\"#ffffff\""
+        ));
+        assert!(dot
+            .contains("n3 -> n1 [label=\"Verifies\", color=\"#ff6b6b\", fontcolor=\"#ff6b6b\"];"));
+        assert!(!dot.contains("FR-004"));
+    }
+
+    #[test]
+    fn test_mermaid_flowchart_with_classes_and_link_styles() {
+        let mut store = store();
+        for td in &mut store.type_definitions {
+            if td.name == "Epic" {
+                td.color = Some("#abc".into());
+            }
+        }
+        for def in &mut store.relationship_definitions {
+            def.color = (def.name == "verifies").then(|| "#ff6b6b".to_string());
+        }
+
+        let filter = GraphFilter::new()
+            .with_root("EPIC-001")
+            .with_feature_clusters(true);
+        let chart = to_mermaid(&store, &filter).unwrap();
+        assert!(chart.starts_with("flowchart LR\n"));
+        assert!(chart.contains(
+            "    subgraph f1[\"Core\"]\n        n0[\"EPIC-001<br/>Accounts #quot;core#quot;\"]\n"
+        ));
+        assert!(chart.contains("    n1 -->|Parent| n0\n"));
+        assert!(chart.contains("classDef type0 fill:#abc,color:#111827,stroke:#abc"));
+        assert!(chart.contains("class n0 type0"));
+        assert!(chart.contains("linkStyle 2 stroke:#ff6b6b,color:#ff6b6b"));
+    }
+}
@@ -0,0 +1,636 @@
+//! Markdown authoring format
+//!
+//! [`export_markdown`] writes one file per requirement, `<dir>/<SPEC-ID>.md`,
+//! so requirements can be edited in any text editor and applied back:
+//!
+//! ```text
+//! ---
+//! spec_id: FR-001
+//! type: Functional
+//! status: Draft
+//! priority: High
+//! feature: Payments
+//! owner: alice
+//! tags:
+//! - checkout
+//! relationships:
+//!   parent:
+//!   - EPIC-001
+//! custom_fields: {}
+//! id: 9b1c...
+//! modified_at: 2026-10-18T09:30:00Z
+//! ---
+//! # Checkout
+//!
+//! Customers can pay by card.
+//! ```
+//!
+//! [`apply_markdown`] diffs the files against the store. Files matching a
+//! requirement by `id` (or `spec_id`) update it through the usual history
+//! recording; files without one create requirements. Fields left out of the
+//! front matter are not changed, and deleting a file deletes nothing.
+//!
+//! `modified_at` is the version a file was exported from. When the
+//! requirement has changed in the store since then, the file is reported as
+//! a conflict and skipped; export again and re-apply the edit.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::{create_requirement, requirement_type, update_requirement, ImportSummary};
+use crate::bulk::RequirementPatch;
+use crate::models::{FieldChange, RelationshipType, Requirement, RequirementsStore};
+
+/// Front matter of a requirement file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrontMatter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec_id: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub req_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<BTreeSet<String>>,
+    /// Relationship name -> target SPEC-IDs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relationships: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_fields: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    /// When the requirement was last modified as of the export
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// A requirement file read from disk
+#[derive(Debug, Clone)]
+pub struct MarkdownDoc {
+    pub path: PathBuf,
+    pub front: FrontMatter,
+    pub title: String,
+    pub description: String,
+}
+
+impl MarkdownDoc {
+    /// Parses a requirement file: front matter, `# Title`, then the description
+    pub fn parse(path: &Path, text: &str) -> Result<Self> {
+        let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+        let (front, body) = match text.strip_prefix("---\n") {
+            Some(rest) => {
+                let end = rest
+                    .find("\n---\n")
+                    .map(|i| (i, i + 5))
+                    .or_else(|| rest.strip_suffix("\n---").map(|r| (r.len(), rest.len())))
+                    .context("Front matter is not closed with '---'")?;
+                let front: FrontMatter =
+                    serde_yaml::from_str(&rest[..end.0]).context("Invalid front matter")?;
+                (front, &rest[end.1..])
+            }
+            None => (FrontMatter::default(), text.as_str()),
+        };
+        let body = body.trim_start_matches('\n');
+        let (first, rest) = body.split_once('\n').unwrap_or((body, ""));
+        let Some(title) = first.strip_prefix("# ") else {
+            bail!("Expected a '# Title' line after the front matter");
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            front,
+            title: title.trim().to_string(),
+            description: rest.trim_matches('\n').to_string(),
+        })
+    }
+}
+
+/// Relationship types written on the requirement that owns the link; their
+/// inverses (child, verified-by) follow automatically
+fn is_inverse_side(rel_type: &RelationshipType) -> bool {
+    matches!(
+        rel_type,
+        RelationshipType::Child | RelationshipType::VerifiedBy
+    )
+}
+
+fn label(req: &Requirement) -> String {
+    req.spec_id.clone().unwrap_or_else(|| req.id.to_string())
+}
+
+/// Front matter describing a requirement as it is in the store
+pub fn front_matter(store: &RequirementsStore, req: &Requirement) -> FrontMatter {
+    let by_id: HashMap<Uuid, &Requirement> = store.requirements.iter().map(|r| (r.id, r)).collect();
+    let mut relationships: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for rel in &req.relationships {
+        if is_inverse_side(&rel.rel_type) {
+            continue;
+        }
+        if let Some(target) = by_id.get(&rel.target_id) {
+            relationships
+                .entry(rel.rel_type.name())
+                .or_default()
+                .push(label(target));
+        }
+    }
+    for targets in relationships.values_mut() {
+        targets.sort();
+    }
+    FrontMatter {
+        spec_id: req.spec_id.clone(),
+        req_type: Some(format!("{:?}", req.req_type)),
+        status: Some(req.effective_status()),
+        priority: Some(req.effective_priority()),
+        feature: Some(req.feature.clone()),
+        owner: Some(req.owner.clone()),
+        tags: Some(req.tags.iter().cloned().collect()),
+        relationships: Some(relationships),
+        custom_fields: Some(req.custom_fields.clone().into_iter().collect()),
+        id: Some(req.id),
+        modified_at: Some(req.modified_at),
+    }
+}
+
+/// Renders one requirement file
+pub fn render_markdown(store: &RequirementsStore, req: &Requirement) -> Result<String> {
+    let yaml = serde_yaml::to_string(&front_matter(store, req))?;
+    let mut out = format!("---\n{}---\n# {}\n", yaml, req.title);
+    if !req.description.trim().is_empty() {
+        out.push('\n');
+        out.push_str(req.description.trim_matches('\n'));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn file_name(req: &Requirement) -> String {
+    let name: String = label(req)
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.md", name)
+}
+
+/// Export every active requirement as a Markdown file in `dir`
+pub fn export_markdown(store: &RequirementsStore, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut count = 0;
+    for req in store.requirements.iter().filter(|r| !r.archived) {
+        fs::write(dir.join(file_name(req)), render_markdown(store, req)?)?;
+        count += 1;
+    }
+
+    println!("Exported to Markdown: {}", dir.display());
+    println!("  Total requirements: {}", count);
+
+    Ok(())
+}
+
+/// Reads every `.md` file under `dir`, in path order
+pub fn read_markdown_dir(dir: &Path) -> Result<Vec<MarkdownDoc>> {
+    if !dir.is_dir() {
+        bail!("{} is not a directory", dir.display());
+    }
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+        .into_iter()
+        .map(|path| {
+            let text = fs::read_to_string(&path)?;
+            MarkdownDoc::parse(&path, &text).with_context(|| format!("{}", path.display()))
+        })
+        .collect()
+}
+
+/// Outcome of applying Markdown files to the store
+#[derive(Debug, Default)]
+pub struct MarkdownApply {
+    pub summary: ImportSummary,
+    /// Files whose requirement was created or changed, to refresh once saved
+    pub changed_files: Vec<(PathBuf, Uuid)>,
+}
+
+/// Applies edited Markdown files to the store
+pub fn apply_markdown(
+    store: &mut RequirementsStore,
+    docs: &[MarkdownDoc],
+    author: &str,
+) -> Result<MarkdownApply> {
+    let mut result = MarkdownApply::default();
+    let mut applied: Vec<(&MarkdownDoc, Uuid)> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for doc in docs {
+        let existing = doc
+            .front
+            .id
+            .and_then(|id| store.get_requirement_by_id(&id))
+            .or_else(|| {
+                doc.front
+                    .spec_id
+                    .as_deref()
+                    .and_then(|s| store.get_requirement_by_spec_id(s))
+            })
+            .map(|r| r.id);
+        let name = doc.path.display();
+
+        let id = match existing {
+            Some(id) => {
+                if !seen.insert(id) {
+                    result.summary.warnings.push(format!(
+                        "{}: requirement already applied from another file",
+                        name
+                    ));
+                    continue;
+                }
+                let req = store.get_requirement_by_id(&id).expect("found above");
+                if doc_changes(store, req, doc).is_empty() {
+                    result.summary.unchanged += 1;
+                    applied.push((doc, id));
+                    continue;
+                }
+                if doc.front.modified_at.is_some_and(|at| req.modified_at > at) {
+                    result.summary.conflicts.push(format!(
+                        "{} ({}): changed in the store since it was exported",
+                        label(req),
+                        name
+                    ));
+                    continue;
+                }
+                update_from_doc(store, id, doc, author, &mut result.summary);
+                id
+            }
+            None if doc.front.id.is_some() => {
+                result.summary.warnings.push(format!(
+                    "{}: requirement {} no longer exists; remove the id to create it again",
+                    name,
+                    doc.front.id.unwrap_or_default()
+                ));
+                continue;
+            }
+            None => create_from_doc(store, doc, &mut result.summary),
+        };
+        applied.push((doc, id));
+        result.changed_files.push((doc.path.clone(), id));
+    }
+
+    // Links last, so files can point at requirements created by other files
+    for (doc, id) in applied {
+        let Some(relationships) = &doc.front.relationships else {
+            continue;
+        };
+        if sync_relationships(store, id, relationships, author, &mut result.summary)? {
+            let name = store
+                .get_requirement_by_id(&id)
+                .map(label)
+                .unwrap_or_default();
+            if !result.summary.updated.contains(&name) && !result.summary.created.contains(&name) {
+                result.summary.unchanged = result.summary.unchanged.saturating_sub(1);
+                result.summary.updated.push(name);
+                result.changed_files.push((doc.path.clone(), id));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Rewrites files from the saved store so they carry fresh ids and versions
+pub fn refresh_markdown_files(store: &RequirementsStore, files: &[(PathBuf, Uuid)]) -> Result<()> {
+    for (path, id) in files {
+        if let Some(req) = store.get_requirement_by_id(id) {
+            fs::write(path, render_markdown(store, req)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fields (other than links) where the file differs from the requirement
+fn doc_changes(
+    store: &RequirementsStore,
+    req: &Requirement,
+    doc: &MarkdownDoc,
+) -> Vec<&'static str> {
+    let current = front_matter(store, req);
+    let front = &doc.front;
+    let mut changes = Vec::new();
+    if doc.title != req.title.trim() {
+        changes.push("title");
+    }
+    if doc.description != req.description.trim_matches('\n') {
+        changes.push("description");
+    }
+    let differs = |new: &Option<String>, old: &Option<String>| {
+        new.as_ref()
+            .is_some_and(|n| !old.as_ref().is_some_and(|o| o.eq_ignore_ascii_case(n)))
+    };
+    if differs(&front.req_type, &current.req_type) {
+        changes.push("type");
+    }
+    if differs(&front.status, &current.status) {
+        changes.push("status");
+    }
+    if differs(&front.priority, &current.priority) {
+        changes.push("priority");
+    }
+    if front.feature.is_some() && front.feature != current.feature {
+        changes.push("feature");
+    }
+    if front.owner.is_some() && front.owner != current.owner {
+        changes.push("owner");
+    }
+    if front.tags.is_some() && front.tags != current.tags {
+        changes.push("tags");
+    }
+    if front.custom_fields.is_some() && front.custom_fields != current.custom_fields {
+        changes.push("custom_fields");
+    }
+    if front.relationships.is_some() && front.relationships != current.relationships {
+        changes.push("relationships");
+    }
+    changes
+}
+
+fn patch_from_doc(doc: &MarkdownDoc) -> RequirementPatch {
+    RequirementPatch {
+        title: Some(doc.title.clone()),
+        description: Some(doc.description.clone()),
+        status: doc.front.status.clone(),
+        priority: doc.front.priority.clone(),
+        owner: doc.front.owner.clone(),
+        feature: doc.front.feature.clone(),
+        custom_fields: doc
+            .front
+            .custom_fields
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn tags_from_doc(doc: &MarkdownDoc) -> Option<HashSet<String>> {
+    doc.front.tags.as_ref().map(|t| t.iter().cloned().collect())
+}
+
+fn update_from_doc(
+    store: &mut RequirementsStore,
+    id: Uuid,
+    doc: &MarkdownDoc,
+    author: &str,
+    summary: &mut ImportSummary,
+) {
+    let req_type = doc.front.req_type.as_ref().and_then(|name| {
+        let found = requirement_type(store, name);
+        if found.is_none() {
+            summary
+                .warnings
+                .push(format!("{}: unknown type '{}'", doc.path.display(), name));
+        }
+        found
+    });
+
+    update_requirement(
+        store,
+        id,
+        req_type,
+        patch_from_doc(doc),
+        tags_from_doc(doc),
+        author,
+        summary,
+    );
+}
+
+fn create_from_doc(
+    store: &mut RequirementsStore,
+    doc: &MarkdownDoc,
+    summary: &mut ImportSummary,
+) -> Uuid {
+    let mut req = Requirement::new(doc.title.clone(), doc.description.clone());
+    if let Some(name) = &doc.front.req_type {
+        match requirement_type(store, name) {
+            Some(req_type) => req.req_type = req_type,
+            None => {
+                summary
+                    .warnings
+                    .push(format!("{}: unknown type '{}'", doc.path.display(), name))
+            }
+        }
+    }
+    create_requirement(
+        store,
+        req,
+        patch_from_doc(doc),
+        tags_from_doc(doc),
+        doc.front.spec_id.clone(),
+        summary,
+    )
+}
+
+/// Makes the requirement's links match the file; returns true if any changed
+fn sync_relationships(
+    store: &mut RequirementsStore,
+    id: Uuid,
+    relationships: &BTreeMap<String, Vec<String>>,
+    author: &str,
+    summary: &mut ImportSummary,
+) -> Result<bool> {
+    let Some(req) = store.get_requirement_by_id(&id) else {
+        return Ok(false);
+    };
+    let source = label(req);
+
+    let mut wanted: HashSet<(RelationshipType, Uuid)> = HashSet::new();
+    for (name, targets) in relationships {
+        let rel_type = RelationshipType::from_str(name);
+        for target in targets {
+            match store.get_requirement_by_spec_id(target) {
+                Some(t) => {
+                    wanted.insert((rel_type.clone(), t.id));
+                }
+                None => summary.warnings.push(format!(
+                    "{}: {} target '{}' not found",
+                    source, name, target
+                )),
+            }
+        }
+    }
+    // Links of a type named in the file, or owned by this side, are managed by the file
+    let managed = |rel_type: &RelationshipType| {
+        !is_inverse_side(rel_type) || wanted.iter().any(|(t, _)| t == rel_type)
+    };
+    let current: HashSet<(RelationshipType, Uuid)> = req
+        .relationships
+        .iter()
+        .filter(|r| managed(&r.rel_type))
+        .map(|r| (r.rel_type.clone(), r.target_id))
+        .collect();
+
+    let target_label = |store: &RequirementsStore, target: &Uuid| {
+        store
+            .get_requirement_by_id(target)
+            .map(label)
+            .unwrap_or_else(|| target.to_string())
+    };
+    let mut changes: Vec<FieldChange> = Vec::new();
+    for (rel_type, target) in current.difference(&wanted) {
+        store.remove_relationship(&id, rel_type, target, true)?;
+        summary.relationships_removed += 1;
+        changes.push(Requirement::field_change(
+            "relationship",
+            format!("{} {}", rel_type, target_label(store, target)),
+            String::new(),
+        ));
+    }
+    for (rel_type, target) in wanted.difference(&current) {
+        if *target == id {
+            continue;
+        }
+        store.add_relationship_with_creator(
+            &id,
+            rel_type.clone(),
+            target,
+            true,
+            Some(author.to_string()),
+        )?;
+        summary.relationships_added += 1;
+        changes.push(Requirement::field_change(
+            "relationship",
+            String::new(),
+            format!("{} {}", rel_type, target_label(store, target)),
+        ));
+    }
+
+    let changed = !changes.is_empty();
+    if let Some(req) = store.get_requirement_by_id_mut(&id) {
+        req.record_change(author.to_string(), changes);
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::sample_store;
+
+    fn doc(store: &RequirementsStore, spec_id: &str) -> MarkdownDoc {
+        let req = store.get_requirement_by_spec_id(spec_id).unwrap();
+        let text = render_markdown(store, req).unwrap();
+        MarkdownDoc::parse(Path::new(&format!("{}.md", spec_id)), &text).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_is_unchanged() {
+        let mut store = sample_store();
+        let docs: Vec<MarkdownDoc> = ["FR-001", "FR-002", "FR-003"]
+            .iter()
+            .map(|id| doc(&store, id))
+            .collect();
+        assert_eq!(
+            docs[1].front.relationships.as_ref().unwrap()["parent"],
+            vec!["FR-001"]
+        );
+        // Child links are implied by the parent link and not written
+        assert_eq!(docs[0].front.relationships.as_ref().unwrap().len(), 0);
+
+        let result = apply_markdown(&mut store, &docs, "alice").unwrap();
+        assert_eq!(result.summary.unchanged, 3);
+        assert!(result.changed_files.is_empty());
+        assert!(store.requirements.iter().all(|r| r.history.is_empty()));
+    }
+
+    #[test]
+    fn test_apply_updates_creates_and_records_history() {
+        let mut store = sample_store();
+        let edited = render_markdown(&store, store.get_requirement_by_spec_id("FR-002").unwrap())
+            .unwrap()
+            .replace("status: Draft", "status: approved")
+            .replace("type: Functional", "type: Story")
+            .replace("# Lockout", "# Lockout and unlock")
+            .replace(
+                "\nLock after 5 tries\n",
+                "\nLock after 5 tries, unlock by email\n",
+            )
+            .replace("parent:\n  - FR-001", "references:\n  - FR-004");
+        let new =
+            "---\nspec_id: FR-004\nstatus: Approved\ntags: [email]\n---\n# Unlock email\n\nSend a link\n";
+        let docs = vec![
+            MarkdownDoc::parse(Path::new("FR-002.md"), &edited).unwrap(),
+            MarkdownDoc::parse(Path::new("new.md"), new).unwrap(),
+        ];
+
+        let result = apply_markdown(&mut store, &docs, "alice").unwrap();
+        assert_eq!(result.summary.created, vec!["FR-004"]);
+        assert_eq!(result.summary.updated, vec!["FR-002"]);
+        assert_eq!(result.summary.relationships_added, 1);
+        assert_eq!(result.summary.relationships_removed, 1);
+        assert_eq!(result.changed_files.len(), 2);
+
+        let lockout = store.get_requirement_by_spec_id("FR-002").unwrap();
+        assert_eq!(lockout.title, "Lockout and unlock");
+        assert_eq!(lockout.description, "Lock after 5 tries, unlock by email");
+        assert_eq!(lockout.effective_status(), "Approved");
+        assert_eq!(lockout.req_type, crate::models::RequirementType::Story);
+        let fields: Vec<&str> = lockout
+            .history
+            .iter()
+            .flat_map(|h| h.changes.iter().map(|c| c.field_name.as_str()))
+            .collect();
+        for field in ["type", "title", "description", "status", "relationship"] {
+            assert!(fields.contains(&field), "missing {} in {:?}", field, fields);
+        }
+        assert!(lockout.history.iter().all(|h| h.author == "alice"));
+        // The parent lost its child along with the parent link
+        let login = store.get_requirement_by_spec_id("FR-001").unwrap();
+        assert!(login.relationships.is_empty());
+        let unlock = store.get_requirement_by_spec_id("FR-004").unwrap();
+        assert!(unlock.tags.contains("email"));
+    }
+
+    #[test]
+    fn test_store_edits_since_export_are_conflicts() {
+        let mut store = sample_store();
+        let stale = doc(&store, "FR-002");
+        let mut edited = stale.clone();
+        edited.title = "Lock".into();
+
+        // Someone else edits the requirement after the export
+        let id = store.get_requirement_by_spec_id("FR-002").unwrap().id;
+        let req = store.get_requirement_by_id_mut(&id).unwrap();
+        req.modified_at = stale.front.modified_at.unwrap() + chrono::Duration::seconds(5);
+        req.description = "Lock after 3 tries".into();
+
+        let result = apply_markdown(&mut store, &[edited], "alice").unwrap();
+        assert_eq!(result.summary.conflicts.len(), 1);
+        assert!(result.summary.conflicts[0].starts_with("FR-002 (FR-002.md)"));
+        assert_eq!(store.get_requirement_by_id(&id).unwrap().title, "Lockout");
+    }
+}
@@ -0,0 +1,494 @@
+//! Exporters for requirements documents and interchange formats
+
+pub mod baseline;
+pub mod csv;
+pub mod document;
+pub mod docx;
+pub mod graph;
+pub mod markdown;
+pub mod odt;
+pub mod reqif;
+pub mod site;
+pub mod template;
+
+pub use self::csv::{export_csv, import_csv, to_csv, CsvField, CsvMapping, IMPORT_KEY_FIELD};
+pub use baseline::{BaselineDiff, DiffRequirement, ModifiedRequirement};
+pub use document::{Block, SpecDocument};
+pub use docx::{export_docx, to_docx};
+pub use graph::{export_dot, export_mermaid, to_dot, to_mermaid, GraphFilter};
+pub use markdown::{
+    apply_markdown, export_markdown, read_markdown_dir, refresh_markdown_files, MarkdownApply,
+    MarkdownDoc,
+};
+pub use odt::{export_odt, to_odt};
+pub use reqif::{export_reqif, import_reqif, to_reqif};
+pub use site::{export_site, render_site};
+pub use template::{export_template, ExportTemplate, BUILTIN_TEMPLATES};
+
+use crate::bulk::RequirementPatch;
+use crate::models::{RelationshipType, Requirement, RequirementType, RequirementsStore};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MappingFile {
+    pub mappings: HashMap<String, String>, // UUID -> SPEC-ID
+    pub next_spec_number: u32,
+}
+
+impl MappingFile {
+    /// Load existing mapping file or create new
+    pub fn load_or_create(path: &Path) -> Result<Self> {
+        if path.exists() {
+            let content = fs::read_to_string(path)?;
+            let mapping: MappingFile = serde_yaml::from_str(&content)?;
+            Ok(mapping)
+        } else {
+            Ok(MappingFile {
+                mappings: HashMap::new(),
+                next_spec_number: 1,
+            })
+        }
+    }
+
+    /// Save mapping file to disk
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let yaml = serde_yaml::to_string(self)?;
+        fs::write(path, yaml)?;
+        Ok(())
+    }
+
+    /// Get or create SPEC-ID for a UUID
+    pub fn get_or_create_spec_id(&mut self, uuid: &str) -> String {
+        if let Some(spec_id) = self.mappings.get(uuid) {
+            spec_id.clone()
+        } else {
+            let spec_id = format!("SPEC-{:03}", self.next_spec_number);
+            self.mappings.insert(uuid.to_string(), spec_id.clone());
+            self.next_spec_number += 1;
+            spec_id
+        }
+    }
+
+    /// Get UUID for SPEC-ID (reverse lookup)
+    pub fn get_uuid(&self, spec_id: &str) -> Option<String> {
+        for (uuid, sid) in &self.mappings {
+            if sid == spec_id {
+                return Some(uuid.clone());
+            }
+        }
+        None
+    }
+}
+
+/// Generate mapping file (UUID -> SPEC-ID)
+pub fn generate_mapping_file(store: &RequirementsStore, output_path: &Path) -> Result<()> {
+    // Load existing mapping or create new
+    let mut mapping = MappingFile::load_or_create(output_path)?;
+
+    // Generate SPEC-IDs for all requirements
+    for req in &store.requirements {
+        let uuid = req.id.to_string();
+        mapping.get_or_create_spec_id(&uuid);
+    }
+
+    // Save mapping
+    mapping.save(output_path)?;
+
+    println!("Generated mapping file: {}", output_path.display());
+    println!("  Total mappings: {}", mapping.mappings.len());
+    println!("  Next SPEC number: {}", mapping.next_spec_number);
+
+    Ok(())
+}
+
+/// Export requirements to JSON format
+pub fn export_json(store: &RequirementsStore, output_path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(store)?;
+    fs::write(output_path, json)?;
+
+    println!("Exported to JSON: {}", output_path.display());
+    println!("  Total requirements: {}", store.requirements.len());
+
+    Ok(())
+}
+
+/// Export requirements specification (excludes IMPL tasks and implementation details)
+pub fn export_requirements_spec(store: &RequirementsStore, output_path: &Path) -> Result<()> {
+    let output = ExportTemplate::builtin("spec")?.render(store, None)?;
+    fs::write(output_path, output)?;
+
+    let req_count = store
+        .requirements
+        .iter()
+        .filter(|r| !r.spec_id.as_deref().unwrap_or("").starts_with("IMPL-"))
+        .count();
+
+    println!(
+        "Exported requirements specification: {}",
+        output_path.display()
+    );
+    println!("  Total requirements: {} (excluding IMPL tasks)", req_count);
+
+    Ok(())
+}
+
+/// Export the test coverage report as Markdown
+pub fn export_coverage_report(store: &RequirementsStore, output_path: &Path) -> Result<()> {
+    let report = store.coverage_report();
+    fs::write(output_path, report.to_markdown())?;
+
+    println!("Exported coverage report: {}", output_path.display());
+    println!(
+        "  {} of {} requirements covered by tests ({:.1}%)",
+        report.covered(),
+        report.entries.len(),
+        report.coverage_percent()
+    );
+
+    Ok(())
+}
+
+/// Export implementation records (IMPL tasks only)
+pub fn export_implementation_records(store: &RequirementsStore, output_path: &Path) -> Result<()> {
+    let output = ExportTemplate::builtin("impl")?.render(store, None)?;
+    fs::write(output_path, output)?;
+
+    let impl_count = store
+        .requirements
+        .iter()
+        .filter(|r| r.spec_id.as_deref().unwrap_or("").starts_with("IMPL-"))
+        .count();
+
+    println!("Exported implementation records: {}", output_path.display());
+    println!("  Total IMPL tasks: {}", impl_count);
+
+    Ok(())
+}
+
+// ============================================================================
+// Shared import helpers
+// ============================================================================
+
+/// Outcome of importing requirements from another tool
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// SPEC-IDs of the requirements created
+    pub created: Vec<String>,
+    /// SPEC-IDs of the existing requirements that changed
+    pub updated: Vec<String>,
+    /// Number of existing requirements that were already up to date
+    pub unchanged: usize,
+    /// Relationships added (other than parent links)
+    pub relationships_added: usize,
+    /// Relationships removed
+    pub relationships_removed: usize,
+    /// Parent links added or moved
+    pub parents_set: usize,
+    /// Entries skipped because the store changed since they were exported
+    pub conflicts: Vec<String>,
+    /// Values that could not be applied
+    pub warnings: Vec<String>,
+}
+
+/// Finds a requirement type by name or display name, ignoring case and punctuation
+fn requirement_type(store: &RequirementsStore, name: &str) -> Option<RequirementType> {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let wanted = normalize(name);
+    let type_name = store
+        .type_definitions
+        .iter()
+        .find(|td| normalize(&td.name) == wanted || normalize(&td.display_name) == wanted)
+        .map(|td| td.name.clone())
+        .unwrap_or_else(|| name.to_string());
+    serde_json::from_value(serde_json::Value::String(type_name)).ok()
+}
+
+/// Splits a comma-separated tag list
+fn parse_tags(value: &str) -> HashSet<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Adds a new imported requirement with the values of `patch`
+///
+/// An imported SPEC-ID is kept when it is still free (and reserved so new
+/// requirements do not reuse it); otherwise a new one is generated.
+fn create_requirement(
+    store: &mut RequirementsStore,
+    mut req: Requirement,
+    patch: RequirementPatch,
+    tags: Option<HashSet<String>>,
+    spec_id: Option<String>,
+    summary: &mut ImportSummary,
+) -> Uuid {
+    if let Some(status) = &patch.status {
+        req.set_status_from_str(status);
+    }
+    if let Some(priority) = &patch.priority {
+        req.set_priority_from_str(priority);
+    }
+    if let Some(owner) = patch.owner {
+        req.owner = owner;
+    }
+    if let Some(feature) = patch.feature.filter(|f| !f.is_empty()) {
+        req.feature = feature;
+    }
+    req.tags = tags.unwrap_or_default();
+    req.custom_fields.extend(patch.custom_fields);
+
+    if let Some(spec_id) = spec_id {
+        if store
+            .requirements
+            .iter()
+            .any(|r| r.spec_id.as_deref() == Some(spec_id.as_str()))
+        {
+            summary.warnings.push(format!(
+                "{}: SPEC-ID already in use, a new one was assigned",
+                spec_id
+            ));
+        } else {
+            store.reserve_spec_id(&spec_id);
+            req.spec_id = Some(spec_id);
+        }
+    }
+
+    let id = req.id;
+    let feature_prefix = store
+        .get_feature_by_name(&req.feature)
+        .map(|f| f.prefix.clone());
+    let type_prefix = store.get_type_prefix(&req.req_type);
+    store.add_requirement_with_id(req, feature_prefix.as_deref(), type_prefix.as_deref());
+    let spec_id = store
+        .get_requirement_by_id(&id)
+        .and_then(|r| r.spec_id.clone())
+        .unwrap_or_default();
+    summary.created.push(spec_id);
+    id
+}
+
+/// Applies the imported values that differ from an existing requirement
+///
+/// Changes go through [`RequirementsStore::bulk_update`], so they are
+/// validated and recorded in the history as `author`. The type is not part
+/// of a bulk patch; a `req_type` change is applied first (with its own
+/// history entry) so the new type's statuses apply to the rest of the update.
+fn update_requirement(
+    store: &mut RequirementsStore,
+    id: Uuid,
+    req_type: Option<RequirementType>,
+    mut patch: RequirementPatch,
+    tags: Option<HashSet<String>>,
+    author: &str,
+    summary: &mut ImportSummary,
+) {
+    let mut type_changed = false;
+    if let Some(new_type) = req_type {
+        if let Some(req) = store.get_requirement_by_id_mut(&id) {
+            if req.req_type != new_type {
+                let change = Requirement::field_change(
+                    "type",
+                    format!("{:?}", req.req_type),
+                    format!("{:?}", new_type),
+                );
+                req.req_type = new_type;
+                req.record_change(author.to_string(), vec![change]);
+                type_changed = true;
+            }
+        }
+    }
+
+    let Some(req) = store.get_requirement_by_id(&id) else {
+        return;
+    };
+    let label = req.spec_id.clone().unwrap_or_else(|| id.to_string());
+    if let Some(tags) = tags {
+        patch.add_tags = tags.difference(&req.tags).cloned().collect();
+        patch.remove_tags = req.tags.difference(&tags).cloned().collect();
+    }
+    patch
+        .custom_fields
+        .retain(|key, value| req.custom_fields.get(key) != Some(value));
+    if patch.status.as_deref() == Some(req.effective_status().as_str()) {
+        patch.status = None;
+    }
+    if patch.priority.as_deref() == Some(req.effective_priority().as_str()) {
+        patch.priority = None;
+    }
+
+    match store.bulk_update(&[id], &patch, author) {
+        Ok(result) if result.updated.is_empty() && !type_changed => summary.unchanged += 1,
+        Ok(_) => summary.updated.push(label),
+        Err(e) => summary.warnings.push(format!("{}: {}", label, e)),
+    }
+}
+
+/// Makes `parent` the only parent of `child`; returns false if it already was
+fn set_parent(
+    store: &mut RequirementsStore,
+    child: Uuid,
+    parent: Uuid,
+    author: &str,
+) -> Result<bool> {
+    if child == parent {
+        return Ok(false);
+    }
+    let current: Vec<Uuid> = store
+        .get_requirement_by_id(&child)
+        .map(|req| {
+            req.relationships
+                .iter()
+                .filter(|r| r.rel_type == RelationshipType::Parent)
+                .map(|r| r.target_id)
+                .collect()
+        })
+        .unwrap_or_default();
+    if current.contains(&parent) {
+        return Ok(false);
+    }
+    for old in current {
+        store.remove_relationship(&child, &RelationshipType::Parent, &old, true)?;
+    }
+    store.add_relationship_with_creator(
+        &child,
+        RelationshipType::Parent,
+        &parent,
+        true,
+        Some(author.to_string()),
+    )?;
+    Ok(true)
+}
+
+/// Store shared by the exporter tests: FR-001 "Login", its child FR-002
+/// "Lockout" and FR-003 "Login test", which verifies FR-001
+#[cfg(test)]
+pub(super) fn sample_store() -> RequirementsStore {
+    let mut store = RequirementsStore::new();
+    let mut login = Requirement::new("Login".into(), "Users sign in".into());
+    login.spec_id = Some("FR-001".into());
+    let mut lockout = Requirement::new("Lockout".into(), "Lock after 5 tries".into());
+    lockout.spec_id = Some("FR-002".into());
+    let mut test = Requirement::new("Login test".into(), String::new());
+    test.spec_id = Some("FR-003".into());
+    let (login_id, lockout_id, test_id) = (login.id, lockout.id, test.id);
+    store.requirements.extend([login, lockout, test]);
+    store
+        .add_relationship(&lockout_id, RelationshipType::Parent, &login_id, true)
+        .unwrap();
+    store
+        .add_relationship(&test_id, RelationshipType::Verifies, &login_id, false)
+        .unwrap();
+    store
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_mapping_file_new() {
+        let mapping = MappingFile {
+            mappings: HashMap::new(),
+            next_spec_number: 1,
+        };
+
+        assert_eq!(mapping.mappings.len(), 0);
+        assert_eq!(mapping.next_spec_number, 1);
+    }
+
+    #[test]
+    fn test_get_or_create_spec_id_new() {
+        let mut mapping = MappingFile {
+            mappings: HashMap::new(),
+            next_spec_number: 1,
+        };
+
+        let uuid = "f7d250bf-5b3e-4ec3-8bd5-2bee2c4b7bb9";
+        let spec_id = mapping.get_or_create_spec_id(uuid);
+
+        assert_eq!(spec_id, "SPEC-001");
+        assert_eq!(mapping.next_spec_number, 2);
+        assert_eq!(mapping.mappings.get(uuid), Some(&"SPEC-001".to_string()));
+    }
+
+    #[test]
+    fn test_get_or_create_spec_id_existing() {
+        let mut mappings = HashMap::new();
+        mappings.insert(
+            "f7d250bf-5b3e-4ec3-8bd5-2bee2c4b7bb9".to_string(),
+            "SPEC-001".to_string(),
+        );
+
+        let mut mapping = MappingFile {
+            mappings,
+            next_spec_number: 2,
+        };
+
+        let uuid = "f7d250bf-5b3e-4ec3-8bd5-2bee2c4b7bb9";
+        let spec_id = mapping.get_or_create_spec_id(uuid);
+
+        assert_eq!(spec_id, "SPEC-001");
+        assert_eq!(mapping.next_spec_number, 2); // Should not increment
+    }
+
+    #[test]
+    fn test_get_uuid() {
+        let mut mappings = HashMap::new();
+        mappings.insert(
+            "f7d250bf-5b3e-4ec3-8bd5-2bee2c4b7bb9".to_string(),
+            "SPEC-001".to_string(),
+        );
+
+        let mapping = MappingFile {
+            mappings,
+            next_spec_number: 2,
+        };
+
+        let uuid = mapping.get_uuid("SPEC-001");
+        assert_eq!(
+            uuid,
+            Some("f7d250bf-5b3e-4ec3-8bd5-2bee2c4b7bb9".to_string())
+        );
+
+        let uuid = mapping.get_uuid("SPEC-999");
+        assert_eq!(uuid, None);
+    }
+
+    #[test]
+    fn test_save_and_load() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("test-mapping.yaml");
+
+        // Create and save
+        let mut mapping = MappingFile {
+            mappings: HashMap::new(),
+            next_spec_number: 1,
+        };
+        mapping.get_or_create_spec_id("uuid-1");
+        mapping.get_or_create_spec_id("uuid-2");
+        mapping.save(&path)?;
+
+        // Load and verify
+        let loaded = MappingFile::load_or_create(&path)?;
+        assert_eq!(loaded.mappings.len(), 2);
+        assert_eq!(loaded.next_spec_number, 3);
+        assert_eq!(loaded.mappings.get("uuid-1"), Some(&"SPEC-001".to_string()));
+        assert_eq!(loaded.mappings.get("uuid-2"), Some(&"SPEC-002".to_string()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+//! OpenDocument Text (ODT) specification export
+//!
+//! Writes the [`SpecDocument`] as an ODF 1.2 package. Headings use the
+//! standard "Heading N" styles with outline levels, so LibreOffice shows
+//! them in the navigator and can generate a table of contents from them.
+
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+use super::document::{xml_text, zip_package, Block, SpecDocument, MAX_HEADING_LEVEL};
+use crate::models::RequirementsStore;
+
+const MIMETYPE: &str = "application/vnd.oasis.opendocument.text";
+
+const MANIFEST: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+<manifest:file-entry manifest:full-path="/" manifest:version="1.2" manifest:media-type="application/vnd.oasis.opendocument.text"/>
+<manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+<manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>
+<manifest:file-entry manifest:full-path="meta.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"##;
+
+const NAMESPACES: &str = r##"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/" office:version="1.2""##;
+
+/// Export the specification to an ODT file
+pub fn export_odt(
+    store: &RequirementsStore,
+    output_path: &Path,
+    baseline: Option<&str>,
+) -> Result<()> {
+    let doc = SpecDocument::build(store, baseline)?;
+    fs::write(output_path, to_odt(&doc)?)?;
+
+    println!("Exported to ODT: {}", output_path.display());
+    println!("  Total requirements: {}", store.requirements.len());
+
+    Ok(())
+}
+
+/// Renders a specification document as ODT bytes
+pub fn to_odt(doc: &SpecDocument) -> Result<Vec<u8>> {
+    // The mimetype entry must come first and be stored uncompressed
+    zip_package(&[
+        ("mimetype", MIMETYPE.to_string(), true),
+        ("META-INF/manifest.xml", MANIFEST.to_string(), false),
+        ("meta.xml", meta(doc), false),
+        ("styles.xml", styles(), false),
+        ("content.xml", content(doc), false),
+    ])
+}
+
+fn meta(doc: &SpecDocument) -> String {
+    format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-meta {NAMESPACES}>
+<office:meta>
+<dc:title>{}</dc:title>
+<meta:initial-creator>{}</meta:initial-creator>
+<meta:creation-date>{}</meta:creation-date>
+<meta:generator>AIDA {}</meta:generator>
+</office:meta>
+</office:document-meta>
+"##,
+        xml_text(&doc.title),
+        xml_text(&doc.author),
+        doc.created.format("%Y-%m-%dT%H:%M:%S"),
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+fn styles() -> String {
+    let mut out = format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles {NAMESPACES}>
+<office:styles>
+<style:default-style style:family="paragraph"><style:paragraph-properties fo:margin-bottom="0.2cm"/><style:text-properties fo:font-size="11pt"/></style:default-style>
+<style:style style:name="Standard" style:family="paragraph"/>
+<style:style style:name="Title" style:family="paragraph" style:parent-style-name="Standard"><style:paragraph-properties fo:margin-top="4cm" fo:margin-bottom="0.4cm"/><style:text-properties fo:font-size="28pt"/></style:style>
+<style:style style:name="Subtitle" style:family="paragraph" style:parent-style-name="Standard"><style:paragraph-properties fo:margin-bottom="0.8cm"/><style:text-properties fo:font-size="16pt" fo:color="#595959"/></style:style>
+<style:style style:name="Heading" style:family="paragraph" style:parent-style-name="Standard"><style:paragraph-properties fo:margin-top="0.4cm" fo:margin-bottom="0.2cm" fo:keep-with-next="always"/><style:text-properties fo:font-weight="bold" fo:color="#1f3864"/></style:style>
+"##
+    );
+    for level in 1..=MAX_HEADING_LEVEL {
+        let size = 16usize.saturating_sub((level - 1) * 2).max(11);
+        out.push_str(&format!(
+            r##"<style:style style:name="Heading_20_{level}" style:display-name="Heading {level}" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="{level}"><style:text-properties fo:font-size="{size}pt"/></style:style>
+"##
+        ));
+    }
+    out.push_str(
+        r##"<style:style style:name="Table_20_Contents" style:display-name="Table Contents" style:family="paragraph" style:parent-style-name="Standard"><style:paragraph-properties fo:margin-bottom="0cm"/></style:style>
+<style:style style:name="Table_20_Heading" style:display-name="Table Heading" style:family="paragraph" style:parent-style-name="Table_20_Contents"><style:text-properties fo:font-weight="bold"/></style:style>
+</office:styles>
+</office:document-styles>
+"##,
+    );
+    out
+}
+
+fn content(doc: &SpecDocument) -> String {
+    let mut body = String::new();
+    let mut table_count = 0;
+    for block in &doc.blocks {
+        match block {
+            Block::Title(text) => body.push_str(&paragraph("Title", text)),
+            Block::Subtitle(text) => body.push_str(&paragraph("Subtitle", text)),
+            Block::Heading {
+                level,
+                number,
+                text,
+            } => {
+                let text = if number.is_empty() {
+                    text.clone()
+                } else {
+                    format!("{} {}", number, text)
+                };
+                body.push_str(&format!(
+                    "<text:h text:style-name=\"Heading_20_{}\" text:outline-level=\"{}\">{}</text:h>\n",
+                    level,
+                    level,
+                    lines(&text)
+                ));
+            }
+            Block::Paragraph(text) => body.push_str(&paragraph("Standard", text)),
+            Block::Table { header, rows } => {
+                table_count += 1;
+                body.push_str(&table(table_count, header, rows));
+            }
+            Block::PageBreak => body.push_str("<text:p text:style-name=\"PageBreak\"/>\n"),
+        }
+    }
+    format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content {NAMESPACES}>
+<office:automatic-styles>
+<style:style style:name="PageBreak" style:family="paragraph" style:parent-style-name="Standard"><style:paragraph-properties fo:break-after="page"/></style:style>
+<style:style style:name="Cell" style:family="table-cell"><style:table-cell-properties fo:padding="0.1cm" fo:border="0.5pt solid #a6a6a6"/></style:style>
+</office:automatic-styles>
+<office:body>
+<office:text>
+{body}</office:text>
+</office:body>
+</office:document-content>
+"##
+    )
+}
+
+fn paragraph(style: &str, text: &str) -> String {
+    format!(
+        "<text:p text:style-name=\"{}\">{}</text:p>\n",
+        style,
+        lines(text)
+    )
+}
+
+/// Escapes text, turning `\n` into line breaks and keeping repeated spaces
+fn lines(text: &str) -> String {
+    text.split('\n')
+        .map(|line| xml_text(line).replace("  ", " <text:s/>"))
+        .collect::<Vec<_>>()
+        .join("<text:line-break/>")
+}
+
+fn table(index: usize, header: &[String], rows: &[Vec<String>]) -> String {
+    let mut out = format!(
+        "<table:table table:name=\"Table{}\">\n<table:table-column table:number-columns-repeated=\"{}\"/>\n<table:table-header-rows>\n",
+        index,
+        header.len().max(1)
+    );
+    out.push_str(&table_row(header, "Table_20_Heading"));
+    out.push_str("</table:table-header-rows>\n");
+    for row in rows {
+        out.push_str(&table_row(row, "Table_20_Contents"));
+    }
+    out.push_str("</table:table>\n");
+    out
+}
+
+fn table_row(cells: &[String], style: &str) -> String {
+    let mut out = String::from("<table:table-row>");
+    for cell in cells {
+        out.push_str(&format!(
+            "<table:table-cell table:style-name=\"Cell\" office:value-type=\"string\">{}</table:table-cell>",
+            paragraph(style, cell).trim_end()
+        ));
+    }
+    out.push_str("</table:table-row>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn test_odt_package_layout_and_content() {
+        let doc = SpecDocument {
+            title: "Spec".into(),
+            author: "alice".into(),
+            created: Utc::now(),
+            blocks: vec![
+                Block::Heading {
+                    level: 1,
+                    number: "1".into(),
+                    text: "FR-001 Checkout".into(),
+                },
+                Block::Paragraph("a  b\nc".into()),
+                Block::Table {
+                    header: vec!["Field".into(), "Value".into()],
+                    rows: vec![vec!["Status".into(), "Draft".into()]],
+                },
+                Block::PageBreak,
+            ],
+        };
+        let bytes = to_odt(&doc).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let first = archive.by_index(0).unwrap();
+        assert_eq!(first.name(), "mimetype");
+        assert_eq!(first.compression(), zip::CompressionMethod::Stored);
+        drop(first);
+
+        let mut xml = String::new();
+        archive
+            .by_name("content.xml")
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        assert!(xml.contains("<text:h text:style-name=\"Heading_20_1\" text:outline-level=\"1\">1 FR-001 Checkout</text:h>"));
+        assert!(xml.contains("a <text:s/>b<text:line-break/>c"));
+        assert!(xml.contains("<table:table-header-rows>"));
+        assert!(xml.contains("fo:break-after=\"page\""));
+    }
+}
@@ -0,0 +1,1380 @@
+//! ReqIF (OMG Requirements Interchange Format) import and export
+//!
+//! The exported document maps the project as follows:
+//! - each [`CustomTypeDefinition`] becomes a SPEC-OBJECT-TYPE with the
+//!   standard `ReqIF.ForeignID`, `ReqIF.Name` and `ReqIF.Text` attributes,
+//!   `AIDA.*` attributes for status, priority, owner, feature and tags, and
+//!   one attribute definition per custom field
+//! - each [`RelationshipDefinition`] becomes a SPEC-RELATION-TYPE, and every
+//!   relationship other than parent/child a SPEC-RELATION
+//! - the parent/child tree becomes the SPEC-HIERARCHY of one SPECIFICATION
+//!
+//! Requirements are identified by `_<uuid>` (XML identifiers cannot start
+//! with a digit). Requirements imported from other tools keep their original
+//! identifier in the `ReqIF.Identifier` custom field and are exported under
+//! it again, so both sides can match objects across repeated exchanges.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::{
+    create_requirement, parse_tags, requirement_type, set_parent, update_requirement, ImportSummary,
+};
+use crate::bulk::RequirementPatch;
+use crate::models::{
+    CustomFieldDefinition, CustomFieldType, CustomTypeDefinition, RelationshipDefinition,
+    RelationshipType, Requirement, RequirementType, RequirementsStore,
+};
+
+const REQIF_NAMESPACE: &str = "http://www.omg.org/spec/ReqIF/20110401/reqif.xsd";
+const XHTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+/// Custom field holding the identifier of a requirement imported from another tool
+pub const FOREIGN_IDENTIFIER_FIELD: &str = "ReqIF.Identifier";
+
+const ATTR_FOREIGN_ID: &str = "ReqIF.ForeignID";
+const ATTR_NAME: &str = "ReqIF.Name";
+const ATTR_TEXT: &str = "ReqIF.Text";
+const ATTR_CHAPTER_NAME: &str = "ReqIF.ChapterName";
+const ATTR_STATUS: &str = "AIDA.Status";
+const ATTR_PRIORITY: &str = "AIDA.Priority";
+const ATTR_OWNER: &str = "AIDA.Owner";
+const ATTR_FEATURE: &str = "AIDA.Feature";
+const ATTR_TAGS: &str = "AIDA.Tags";
+
+const DATATYPE_STRING: &str = "_aida_dt_string";
+const DATATYPE_XHTML: &str = "_aida_dt_xhtml";
+const DATATYPE_BOOLEAN: &str = "_aida_dt_boolean";
+const TYPE_ID_PREFIX: &str = "_aida_type_";
+const SPECIFICATION_TYPE_ID: &str = "_aida_specification_type";
+
+/// ReqIF attribute and datatype kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttrKind {
+    String,
+    Xhtml,
+    Boolean,
+    Enumeration,
+    Integer,
+    Real,
+    Date,
+}
+
+impl AttrKind {
+    fn tag(self) -> &'static str {
+        match self {
+            AttrKind::String => "STRING",
+            AttrKind::Xhtml => "XHTML",
+            AttrKind::Boolean => "BOOLEAN",
+            AttrKind::Enumeration => "ENUMERATION",
+            AttrKind::Integer => "INTEGER",
+            AttrKind::Real => "REAL",
+            AttrKind::Date => "DATE",
+        }
+    }
+
+    /// Parses the suffix of `DATATYPE-DEFINITION-*`, `ATTRIBUTE-VALUE-*` and similar tags
+    fn from_tag_suffix(tag: &str, prefix: &str) -> Option<Self> {
+        match tag.strip_prefix(prefix)?.trim_end_matches("-REF") {
+            "STRING" => Some(AttrKind::String),
+            "XHTML" => Some(AttrKind::Xhtml),
+            "BOOLEAN" => Some(AttrKind::Boolean),
+            "ENUMERATION" => Some(AttrKind::Enumeration),
+            "INTEGER" => Some(AttrKind::Integer),
+            "REAL" => Some(AttrKind::Real),
+            "DATE" => Some(AttrKind::Date),
+            _ => None,
+        }
+    }
+
+    /// Custom field type used for an imported attribute of this kind
+    fn field_type(self) -> CustomFieldType {
+        match self {
+            AttrKind::String => CustomFieldType::Text,
+            AttrKind::Xhtml => CustomFieldType::TextArea,
+            AttrKind::Boolean => CustomFieldType::Boolean,
+            AttrKind::Enumeration => CustomFieldType::Select,
+            AttrKind::Integer | AttrKind::Real => CustomFieldType::Number,
+            AttrKind::Date => CustomFieldType::Date,
+        }
+    }
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// An attribute definition of an exported SPEC-OBJECT-TYPE
+struct AttrDef {
+    id: String,
+    long_name: String,
+    kind: AttrKind,
+    datatype: String,
+    options: Vec<String>,
+}
+
+/// Export requirements to a ReqIF file
+pub fn export_reqif(store: &RequirementsStore, output_path: &Path) -> Result<()> {
+    fs::write(output_path, to_reqif(store))?;
+
+    println!("Exported to ReqIF: {}", output_path.display());
+    println!("  Total requirements: {}", store.requirements.len());
+
+    Ok(())
+}
+
+/// Renders the store as a ReqIF document
+pub fn to_reqif(store: &RequirementsStore) -> String {
+    let now = timestamp(&Utc::now());
+    let types = exported_types(store);
+    let attributes: Vec<Vec<AttrDef>> = types.iter().map(|td| attribute_defs(store, td)).collect();
+    let relation_types = exported_relation_types(store);
+    let title = if store.title.is_empty() {
+        &store.name
+    } else {
+        &store.title
+    };
+
+    let mut w = XmlWriter::default();
+    w.line(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    w.open(
+        "REQ-IF",
+        &[("xmlns", REQIF_NAMESPACE), ("xmlns:xhtml", XHTML_NAMESPACE)],
+    );
+
+    w.open("THE-HEADER", &[]);
+    w.open("REQ-IF-HEADER", &[("IDENTIFIER", "_aida_header")]);
+    w.text_element("CREATION-TIME", &now);
+    w.text_element("REQ-IF-TOOL-ID", "AIDA");
+    w.text_element("REQ-IF-VERSION", "1.0");
+    w.text_element(
+        "SOURCE-TOOL-ID",
+        concat!("AIDA ", env!("CARGO_PKG_VERSION")),
+    );
+    w.text_element("TITLE", title);
+    w.close("REQ-IF-HEADER");
+    w.close("THE-HEADER");
+
+    w.open("CORE-CONTENT", &[]);
+    w.open("REQ-IF-CONTENT", &[]);
+
+    // Datatypes: shared string/XHTML/boolean types plus one enumeration per select field
+    w.open("DATATYPES", &[]);
+    w.empty(
+        "DATATYPE-DEFINITION-STRING",
+        &[
+            ("IDENTIFIER", DATATYPE_STRING),
+            ("LONG-NAME", "String"),
+            ("LAST-CHANGE", &now),
+            ("MAX-LENGTH", "1000000"),
+        ],
+    );
+    w.empty(
+        "DATATYPE-DEFINITION-XHTML",
+        &[
+            ("IDENTIFIER", DATATYPE_XHTML),
+            ("LONG-NAME", "XHTML"),
+            ("LAST-CHANGE", &now),
+        ],
+    );
+    w.empty(
+        "DATATYPE-DEFINITION-BOOLEAN",
+        &[
+            ("IDENTIFIER", DATATYPE_BOOLEAN),
+            ("LONG-NAME", "Boolean"),
+            ("LAST-CHANGE", &now),
+        ],
+    );
+    for attr in attributes
+        .iter()
+        .flatten()
+        .filter(|a| a.kind == AttrKind::Enumeration)
+    {
+        w.open(
+            "DATATYPE-DEFINITION-ENUMERATION",
+            &[
+                ("IDENTIFIER", &attr.datatype),
+                ("LONG-NAME", &attr.long_name),
+                ("LAST-CHANGE", &now),
+            ],
+        );
+        w.open("SPECIFIED-VALUES", &[]);
+        for (idx, option) in attr.options.iter().enumerate() {
+            let key = idx.to_string();
+            w.open(
+                "ENUM-VALUE",
+                &[
+                    ("IDENTIFIER", &enum_value_id(&attr.datatype, idx)),
+                    ("LONG-NAME", option),
+                    ("LAST-CHANGE", &now),
+                ],
+            );
+            w.open("PROPERTIES", &[]);
+            w.empty("EMBEDDED-VALUE", &[("KEY", &key), ("OTHER-CONTENT", "")]);
+            w.close("PROPERTIES");
+            w.close("ENUM-VALUE");
+        }
+        w.close("SPECIFIED-VALUES");
+        w.close("DATATYPE-DEFINITION-ENUMERATION");
+    }
+    w.close("DATATYPES");
+
+    // Spec types: one object type per requirement type, one relation type per relationship
+    w.open("SPEC-TYPES", &[]);
+    for (td, attrs) in types.iter().zip(&attributes) {
+        let id = type_id(&td.name);
+        let mut type_attrs = vec![
+            ("IDENTIFIER", id.as_str()),
+            ("LONG-NAME", td.display_name.as_str()),
+        ];
+        if let Some(desc) = &td.description {
+            type_attrs.push(("DESC", desc));
+        }
+        type_attrs.push(("LAST-CHANGE", &now));
+        w.open("SPEC-OBJECT-TYPE", &type_attrs);
+        w.open("SPEC-ATTRIBUTES", &[]);
+        for attr in attrs {
+            let tag = format!("ATTRIBUTE-DEFINITION-{}", attr.kind.tag());
+            let mut def_attrs = vec![
+                ("IDENTIFIER", attr.id.as_str()),
+                ("LONG-NAME", attr.long_name.as_str()),
+                ("LAST-CHANGE", now.as_str()),
+            ];
+            if attr.kind == AttrKind::Enumeration {
+                def_attrs.push(("MULTI-VALUED", "false"));
+            }
+            w.open(&tag, &def_attrs);
+            w.open("TYPE", &[]);
+            w.text_element(
+                &format!("DATATYPE-DEFINITION-{}-REF", attr.kind.tag()),
+                &attr.datatype,
+            );
+            w.close("TYPE");
+            w.close(&tag);
+        }
+        w.close("SPEC-ATTRIBUTES");
+        w.close("SPEC-OBJECT-TYPE");
+    }
+    for def in &relation_types {
+        w.empty(
+            "SPEC-RELATION-TYPE",
+            &[
+                ("IDENTIFIER", &relation_type_id(&def.name)),
+                ("LONG-NAME", &def.name),
+                ("DESC", &def.display_name),
+                ("LAST-CHANGE", &now),
+            ],
+        );
+    }
+    w.empty(
+        "SPECIFICATION-TYPE",
+        &[
+            ("IDENTIFIER", SPECIFICATION_TYPE_ID),
+            ("LONG-NAME", "AIDA Specification"),
+            ("LAST-CHANGE", &now),
+        ],
+    );
+    w.close("SPEC-TYPES");
+
+    // Spec objects
+    w.open("SPEC-OBJECTS", &[]);
+    for req in &store.requirements {
+        let type_idx = types
+            .iter()
+            .position(|td| td.name == type_key(&req.req_type))
+            .unwrap_or_default();
+        write_spec_object(&mut w, req, &types[type_idx].name, &attributes[type_idx]);
+    }
+    w.close("SPEC-OBJECTS");
+
+    // Spec relations: everything except the parent/child tree
+    w.open("SPEC-RELATIONS", &[]);
+    for req in &store.requirements {
+        for rel in &req.relationships {
+            if matches!(
+                rel.rel_type,
+                RelationshipType::Parent | RelationshipType::Child
+            ) {
+                continue;
+            }
+            let Some(target) = store.get_requirement_by_id(&rel.target_id) else {
+                continue;
+            };
+            let (source_id, target_id) = (object_id(req), object_id(target));
+            let name = rel.rel_type.name();
+            let last_change = timestamp(&rel.created_at.unwrap_or(req.modified_at));
+            w.open(
+                "SPEC-RELATION",
+                &[
+                    (
+                        "IDENTIFIER",
+                        &xml_id(&format!("_rel_{}_{}_{}", source_id, name, target_id)),
+                    ),
+                    ("LAST-CHANGE", &last_change),
+                ],
+            );
+            w.open("TYPE", &[]);
+            w.text_element("SPEC-RELATION-TYPE-REF", &relation_type_id(&name));
+            w.close("TYPE");
+            w.open("SOURCE", &[]);
+            w.text_element("SPEC-OBJECT-REF", &source_id);
+            w.close("SOURCE");
+            w.open("TARGET", &[]);
+            w.text_element("SPEC-OBJECT-REF", &target_id);
+            w.close("TARGET");
+            w.close("SPEC-RELATION");
+        }
+    }
+    w.close("SPEC-RELATIONS");
+
+    // One specification following the parent/child tree
+    w.open("SPECIFICATIONS", &[]);
+    w.open(
+        "SPECIFICATION",
+        &[
+            ("IDENTIFIER", "_aida_specification"),
+            ("LONG-NAME", title),
+            ("LAST-CHANGE", &now),
+        ],
+    );
+    w.open("TYPE", &[]);
+    w.text_element("SPECIFICATION-TYPE-REF", SPECIFICATION_TYPE_ID);
+    w.close("TYPE");
+    let roots: Vec<&Requirement> = store
+        .requirements
+        .iter()
+        .filter(|req| parent_ids(store, req).is_empty())
+        .collect();
+    if !roots.is_empty() {
+        let mut visited = HashSet::new();
+        w.open("CHILDREN", &[]);
+        for root in roots {
+            write_hierarchy(&mut w, store, root, &mut visited);
+        }
+        w.close("CHILDREN");
+    }
+    w.close("SPECIFICATION");
+    w.close("SPECIFICATIONS");
+
+    w.close("REQ-IF-CONTENT");
+    w.close("CORE-CONTENT");
+    w.close("REQ-IF");
+    w.out
+}
+
+/// Type definitions to export: the configured ones plus any type in use without a definition
+fn exported_types(store: &RequirementsStore) -> Vec<CustomTypeDefinition> {
+    let mut types = store.type_definitions.clone();
+    for req in &store.requirements {
+        let key = type_key(&req.req_type);
+        if !types.iter().any(|td| td.name == key) {
+            types.push(CustomTypeDefinition::new(key, req.req_type.to_string()));
+        }
+    }
+    types
+}
+
+/// Relationship definitions to export, plus any relationship type in use without one
+fn exported_relation_types(store: &RequirementsStore) -> Vec<RelationshipDefinition> {
+    let mut defs = store.relationship_definitions.clone();
+    for rel in store.requirements.iter().flat_map(|r| &r.relationships) {
+        let name = rel.rel_type.name();
+        if !defs.iter().any(|d| d.name == name) {
+            defs.push(RelationshipDefinition::new(&name, &name));
+        }
+    }
+    defs
+}
+
+/// Attribute definitions of one SPEC-OBJECT-TYPE
+fn attribute_defs(store: &RequirementsStore, td: &CustomTypeDefinition) -> Vec<AttrDef> {
+    let standard = [
+        (ATTR_FOREIGN_ID, AttrKind::String),
+        (ATTR_NAME, AttrKind::String),
+        (ATTR_TEXT, AttrKind::Xhtml),
+        (ATTR_STATUS, AttrKind::String),
+        (ATTR_PRIORITY, AttrKind::String),
+        (ATTR_OWNER, AttrKind::String),
+        (ATTR_FEATURE, AttrKind::String),
+        (ATTR_TAGS, AttrKind::String),
+    ];
+    let mut attrs: Vec<AttrDef> = standard
+        .iter()
+        .map(|(name, kind)| attr_def(&td.name, name, *kind, Vec::new()))
+        .collect();
+
+    for field in &td.custom_fields {
+        let (kind, options) = match field.field_type {
+            CustomFieldType::Boolean => (AttrKind::Boolean, Vec::new()),
+            CustomFieldType::Select if !field.options.is_empty() => {
+                (AttrKind::Enumeration, field.options.clone())
+            }
+            CustomFieldType::TextArea => (AttrKind::Xhtml, Vec::new()),
+            _ => (AttrKind::String, Vec::new()),
+        };
+        attrs.push(attr_def(&td.name, &field.name, kind, options));
+    }
+
+    // Values without a field definition still need an attribute to survive a round trip
+    let mut extra: Vec<&String> = store
+        .requirements
+        .iter()
+        .filter(|req| type_key(&req.req_type) == td.name)
+        .flat_map(|req| req.custom_fields.keys())
+        .filter(|key| key.as_str() != FOREIGN_IDENTIFIER_FIELD)
+        .filter(|key| !td.custom_fields.iter().any(|f| &f.name == *key))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    extra.sort();
+    for key in extra {
+        attrs.push(attr_def(&td.name, key, AttrKind::String, Vec::new()));
+    }
+    attrs
+}
+
+fn attr_def(type_name: &str, long_name: &str, kind: AttrKind, options: Vec<String>) -> AttrDef {
+    let id = xml_id(&format!("_aida_{}_{}", type_name, long_name));
+    let datatype = match kind {
+        AttrKind::Xhtml => DATATYPE_XHTML.to_string(),
+        AttrKind::Boolean => DATATYPE_BOOLEAN.to_string(),
+        AttrKind::Enumeration => format!("{}_dt", id),
+        _ => DATATYPE_STRING.to_string(),
+    };
+    AttrDef {
+        id,
+        long_name: long_name.to_string(),
+        kind,
+        datatype,
+        options,
+    }
+}
+
+fn write_spec_object(w: &mut XmlWriter, req: &Requirement, type_name: &str, attrs: &[AttrDef]) {
+    let last_change = timestamp(&req.modified_at);
+    let mut object_attrs = vec![("IDENTIFIER", object_id(req))];
+    object_attrs.push(("LAST-CHANGE", last_change));
+    let object_attrs: Vec<(&str, &str)> =
+        object_attrs.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    w.open("SPEC-OBJECT", &object_attrs);
+
+    w.open("VALUES", &[]);
+    for attr in attrs {
+        let value = match attr.long_name.as_str() {
+            ATTR_FOREIGN_ID => req.spec_id.clone().unwrap_or_default(),
+            ATTR_NAME => req.title.clone(),
+            ATTR_TEXT => req.description.clone(),
+            ATTR_STATUS => req.effective_status(),
+            ATTR_PRIORITY => req.effective_priority(),
+            ATTR_OWNER => req.owner.clone(),
+            ATTR_FEATURE => req.feature.clone(),
+            ATTR_TAGS => {
+                let mut tags: Vec<&str> = req.tags.iter().map(String::as_str).collect();
+                tags.sort_unstable();
+                tags.join(", ")
+            }
+            field => req.custom_fields.get(field).cloned().unwrap_or_default(),
+        };
+        if value.is_empty() && attr.long_name != ATTR_NAME {
+            continue;
+        }
+        write_value(w, attr, &value);
+    }
+    w.close("VALUES");
+
+    w.open("TYPE", &[]);
+    w.text_element("SPEC-OBJECT-TYPE-REF", &type_id(type_name));
+    w.close("TYPE");
+    w.close("SPEC-OBJECT");
+}
+
+fn write_value(w: &mut XmlWriter, attr: &AttrDef, value: &str) {
+    let tag = format!("ATTRIBUTE-VALUE-{}", attr.kind.tag());
+    let definition_ref = format!("ATTRIBUTE-DEFINITION-{}-REF", attr.kind.tag());
+    match attr.kind {
+        AttrKind::Xhtml => {
+            w.open(&tag, &[]);
+            w.open("DEFINITION", &[]);
+            w.text_element(&definition_ref, &attr.id);
+            w.close("DEFINITION");
+            // Written on one line: whitespace inside the value is significant
+            let lines: Vec<String> = value.split('\n').map(escape_text).collect();
+            w.line(&format!(
+                "<THE-VALUE><xhtml:div>{}</xhtml:div></THE-VALUE>",
+                lines.join("<xhtml:br/>")
+            ));
+            w.close(&tag);
+        }
+        AttrKind::Enumeration => {
+            let Some(idx) = attr.options.iter().position(|o| o == value) else {
+                return;
+            };
+            w.open(&tag, &[]);
+            w.open("DEFINITION", &[]);
+            w.text_element(&definition_ref, &attr.id);
+            w.close("DEFINITION");
+            w.open("VALUES", &[]);
+            w.text_element("ENUM-VALUE-REF", &enum_value_id(&attr.datatype, idx));
+            w.close("VALUES");
+            w.close(&tag);
+        }
+        AttrKind::Boolean => {
+            let value = match value.to_lowercase().as_str() {
+                "true" | "yes" | "1" => "true",
+                "false" | "no" | "0" => "false",
+                _ => return,
+            };
+            w.open(&tag, &[("THE-VALUE", value)]);
+            w.open("DEFINITION", &[]);
+            w.text_element(&definition_ref, &attr.id);
+            w.close("DEFINITION");
+            w.close(&tag);
+        }
+        _ => {
+            w.open(&tag, &[("THE-VALUE", value)]);
+            w.open("DEFINITION", &[]);
+            w.text_element(&definition_ref, &attr.id);
+            w.close("DEFINITION");
+            w.close(&tag);
+        }
+    }
+}
+
+fn write_hierarchy(
+    w: &mut XmlWriter,
+    store: &RequirementsStore,
+    req: &Requirement,
+    visited: &mut HashSet<Uuid>,
+) {
+    if !visited.insert(req.id) {
+        return;
+    }
+    let id = object_id(req);
+    w.open(
+        "SPEC-HIERARCHY",
+        &[
+            ("IDENTIFIER", &xml_id(&format!("_h{}", id))),
+            ("LAST-CHANGE", &timestamp(&req.modified_at)),
+        ],
+    );
+    w.open("OBJECT", &[]);
+    w.text_element("SPEC-OBJECT-REF", &id);
+    w.close("OBJECT");
+    let children: Vec<&Requirement> = store
+        .children_of(&req.id)
+        .into_iter()
+        .filter(|child| !visited.contains(&child.id))
+        .collect();
+    if !children.is_empty() {
+        w.open("CHILDREN", &[]);
+        for child in children {
+            write_hierarchy(w, store, child, visited);
+        }
+        w.close("CHILDREN");
+    }
+    w.close("SPEC-HIERARCHY");
+}
+
+/// Minimal indenting XML writer
+///
+/// Values are escaped so that newlines in attributes survive parsing.
+#[derive(Default)]
+struct XmlWriter {
+    out: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn line(&mut self, content: &str) {
+        for _ in 0..self.depth {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(content);
+        self.out.push('\n');
+    }
+
+    fn start_tag(tag: &str, attrs: &[(&str, &str)], empty: bool) -> String {
+        let mut s = format!("<{}", tag);
+        for (key, value) in attrs {
+            s.push_str(&format!(" {}=\"{}\"", key, escape_text(value)));
+        }
+        s.push_str(if empty { "/>" } else { ">" });
+        s
+    }
+
+    fn open(&mut self, tag: &str, attrs: &[(&str, &str)]) {
+        self.line(&Self::start_tag(tag, attrs, false));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{}>", tag));
+    }
+
+    fn empty(&mut self, tag: &str, attrs: &[(&str, &str)]) {
+        self.line(&Self::start_tag(tag, attrs, true));
+    }
+
+    fn text_element(&mut self, tag: &str, text: &str) {
+        self.line(&format!("<{0}>{1}</{0}>", tag, escape_text(text)));
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    quick_xml::escape::escape(text)
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+        .replace('\t', "&#9;")
+}
+
+/// Identifier of the SPEC-OBJECT for a requirement
+fn object_id(req: &Requirement) -> String {
+    req.custom_fields
+        .get(FOREIGN_IDENTIFIER_FIELD)
+        .cloned()
+        .unwrap_or_else(|| format!("_{}", req.id))
+}
+
+fn type_id(type_name: &str) -> String {
+    xml_id(&format!("{}{}", TYPE_ID_PREFIX, type_name))
+}
+
+fn relation_type_id(name: &str) -> String {
+    xml_id(&format!("_aida_rel_{}", name))
+}
+
+fn enum_value_id(datatype: &str, idx: usize) -> String {
+    format!("{}_{}", datatype, idx)
+}
+
+/// Replaces characters not allowed in an XML identifier
+fn xml_id(raw: &str) -> String {
+    raw.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Type definition name for a requirement type (e.g. "NonFunctional")
+fn type_key(req_type: &RequirementType) -> String {
+    format!("{:?}", req_type)
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Parents of a requirement that exist in the store
+fn parent_ids(store: &RequirementsStore, req: &Requirement) -> Vec<Uuid> {
+    req.relationships
+        .iter()
+        .filter(|rel| rel.rel_type == RelationshipType::Parent)
+        .map(|rel| rel.target_id)
+        .filter(|id| store.get_requirement_by_id(id).is_some())
+        .collect()
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Attribute values of one SPEC-OBJECT, keyed by attribute LONG-NAME
+struct ImportedObject {
+    identifier: String,
+    type_name: Option<String>,
+    values: Vec<(String, AttrKind, Vec<String>, String)>,
+}
+
+struct ImportedAttr {
+    long_name: String,
+    kind: AttrKind,
+    datatype: String,
+}
+
+/// Imports a ReqIF document into the store
+///
+/// SPEC-OBJECTs matching an existing requirement (by UUID or by a
+/// previously imported identifier) update it, recording history as
+/// `author`; the others are created. Relations are added when missing, and
+/// the specification hierarchy sets the parent of every nested object.
+/// Top-level objects keep their current parent, so partial documents can be
+/// imported without flattening the tree.
+pub fn import_reqif(
+    store: &mut RequirementsStore,
+    xml: &str,
+    author: &str,
+) -> Result<ImportSummary> {
+    let document = parse_document(xml)?;
+    let root = document
+        .elements()
+        .find(|e| e.name == "REQ-IF")
+        .context("Not a ReqIF document: missing REQ-IF element")?;
+    let content = root
+        .find(&["CORE-CONTENT", "REQ-IF-CONTENT"])
+        .context("Not a ReqIF document: missing REQ-IF-CONTENT")?;
+
+    // Datatypes: enumeration values resolve to their LONG-NAME
+    let mut enum_values: HashMap<String, String> = HashMap::new();
+    let mut enum_options: HashMap<String, Vec<String>> = HashMap::new();
+    for datatype in content.children_of("DATATYPES") {
+        if AttrKind::from_tag_suffix(&datatype.name, "DATATYPE-DEFINITION-")
+            != Some(AttrKind::Enumeration)
+        {
+            continue;
+        }
+        let id = datatype.attr("IDENTIFIER").unwrap_or_default().to_string();
+        for value in datatype.children_of("SPECIFIED-VALUES") {
+            let name = value.long_name();
+            enum_values.insert(
+                value.attr("IDENTIFIER").unwrap_or_default().to_string(),
+                name.clone(),
+            );
+            enum_options.entry(id.clone()).or_default().push(name);
+        }
+    }
+
+    // Spec types
+    let mut object_types: HashMap<String, String> = HashMap::new();
+    let mut attribute_defs: HashMap<String, ImportedAttr> = HashMap::new();
+    let mut relation_types: HashMap<String, String> = HashMap::new();
+    for spec_type in content.children_of("SPEC-TYPES") {
+        let id = spec_type.attr("IDENTIFIER").unwrap_or_default().to_string();
+        match spec_type.name.as_str() {
+            "SPEC-OBJECT-TYPE" => {
+                for def in spec_type.children_of("SPEC-ATTRIBUTES") {
+                    let Some(kind) = AttrKind::from_tag_suffix(&def.name, "ATTRIBUTE-DEFINITION-")
+                    else {
+                        continue;
+                    };
+                    let datatype = def
+                        .child("TYPE")
+                        .and_then(|t| t.elements().next())
+                        .map(Element::text)
+                        .unwrap_or_default();
+                    attribute_defs.insert(
+                        def.attr("IDENTIFIER").unwrap_or_default().to_string(),
+                        ImportedAttr {
+                            long_name: def.long_name(),
+                            kind,
+                            datatype,
+                        },
+                    );
+                }
+                object_types.insert(id, resolve_type_name(store, spec_type));
+            }
+            "SPEC-RELATION-TYPE" => {
+                relation_types.insert(id, spec_type.long_name());
+            }
+            _ => {}
+        }
+    }
+
+    // Spec objects
+    let mut objects = Vec::new();
+    for object in content.children_of("SPEC-OBJECTS") {
+        if object.name != "SPEC-OBJECT" {
+            continue;
+        }
+        let type_ref = object
+            .child("TYPE")
+            .and_then(|t| t.elements().next())
+            .map(Element::text);
+        let mut values = Vec::new();
+        for value in object.children_of("VALUES") {
+            let Some(kind) = AttrKind::from_tag_suffix(&value.name, "ATTRIBUTE-VALUE-") else {
+                continue;
+            };
+            let definition = value
+                .child("DEFINITION")
+                .and_then(|d| d.elements().next())
+                .map(Element::text)
+                .unwrap_or_default();
+            let Some(def) = attribute_defs.get(&definition) else {
+                continue;
+            };
+            let text = match kind {
+                AttrKind::Xhtml => value.child("THE-VALUE").map(xhtml_text).unwrap_or_default(),
+                AttrKind::Enumeration => value
+                    .children_of("VALUES")
+                    .filter_map(|v| enum_values.get(&v.text()).cloned())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                _ => value.attr("THE-VALUE").unwrap_or_default().to_string(),
+            };
+            let options = enum_options.get(&def.datatype).cloned().unwrap_or_default();
+            values.push((def.long_name.clone(), def.kind, options, text));
+        }
+        objects.push(ImportedObject {
+            identifier: object.attr("IDENTIFIER").unwrap_or_default().to_string(),
+            type_name: type_ref.and_then(|t| object_types.get(&t).cloned()),
+            values,
+        });
+    }
+
+    let mut summary = ImportSummary::default();
+    let mut ids: HashMap<String, Uuid> = HashMap::new();
+    for object in &objects {
+        let id = import_object(store, object, author, &mut summary);
+        ids.insert(object.identifier.clone(), id);
+    }
+
+    // Spec relations
+    for relation in content.children_of("SPEC-RELATIONS") {
+        let object_ref = |name: &str| {
+            relation
+                .child(name)
+                .and_then(|e| e.elements().next())
+                .map(Element::text)
+                .and_then(|identifier| resolve_identifier(store, &ids, &identifier))
+        };
+        let (Some(source), Some(target)) = (object_ref("SOURCE"), object_ref("TARGET")) else {
+            continue;
+        };
+        let Some(type_name) = relation
+            .child("TYPE")
+            .and_then(|t| t.elements().next())
+            .and_then(|t| relation_types.get(&t.text()))
+        else {
+            continue;
+        };
+        let rel_type = RelationshipType::from_str(type_name);
+        let rel_name = rel_type.name();
+        if !store
+            .relationship_definitions
+            .iter()
+            .any(|d| d.name == rel_name.to_lowercase())
+        {
+            store
+                .relationship_definitions
+                .push(RelationshipDefinition::new(&rel_name, type_name));
+        }
+        let exists = store.get_requirement_by_id(&source).is_some_and(|req| {
+            req.relationships
+                .iter()
+                .any(|r| r.rel_type == rel_type && r.target_id == target)
+        });
+        if exists || source == target {
+            continue;
+        }
+        store.add_relationship_with_creator(
+            &source,
+            rel_type,
+            &target,
+            false,
+            Some(author.to_string()),
+        )?;
+        summary.relationships_added += 1;
+    }
+
+    // Specification hierarchy
+    for specification in content.children_of("SPECIFICATIONS") {
+        for node in specification.children_of("CHILDREN") {
+            import_hierarchy(store, &ids, node, None, author, &mut summary)?;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Creates or updates the requirement for one SPEC-OBJECT, returning its UUID
+fn import_object(
+    store: &mut RequirementsStore,
+    object: &ImportedObject,
+    author: &str,
+    summary: &mut ImportSummary,
+) -> Uuid {
+    let req_type = object
+        .type_name
+        .as_deref()
+        .and_then(|name| requirement_type(store, name))
+        .unwrap_or(RequirementType::Functional);
+    let type_name = type_key(&req_type);
+
+    let mut spec_id = None;
+    let mut title = None;
+    let mut chapter_name = None;
+    let mut tags = None;
+    let mut patch = RequirementPatch::default();
+    for (name, kind, options, value) in &object.values {
+        match name.as_str() {
+            ATTR_FOREIGN_ID => spec_id = Some(value.clone()).filter(|v| !v.is_empty()),
+            ATTR_NAME => title = Some(value.clone()),
+            ATTR_CHAPTER_NAME => chapter_name = Some(value.clone()),
+            ATTR_TEXT => patch.description = Some(value.clone()),
+            ATTR_STATUS => patch.status = Some(value.clone()),
+            ATTR_PRIORITY => patch.priority = Some(value.clone()),
+            ATTR_OWNER => patch.owner = Some(value.clone()),
+            ATTR_FEATURE => patch.feature = Some(value.clone()),
+            ATTR_TAGS => tags = Some(parse_tags(value)),
+            field => {
+                ensure_field_definition(store, &type_name, field, *kind, options);
+                patch.custom_fields.insert(field.to_string(), value.clone());
+            }
+        }
+    }
+    patch.title = title.or(chapter_name).filter(|t| !t.is_empty());
+
+    if let Some(id) = resolve_identifier(store, &HashMap::new(), &object.identifier) {
+        update_requirement(store, id, None, patch, tags, author, summary);
+        return id;
+    }
+
+    let mut req = Requirement::new(
+        patch
+            .title
+            .clone()
+            .unwrap_or_else(|| object.identifier.clone()),
+        patch.description.clone().unwrap_or_default(),
+    );
+    match own_uuid(&object.identifier) {
+        Some(id) => req.id = id,
+        None => {
+            req.custom_fields.insert(
+                FOREIGN_IDENTIFIER_FIELD.to_string(),
+                object.identifier.clone(),
+            );
+        }
+    }
+    req.req_type = req_type;
+    req.created_by = Some(author.to_string());
+    create_requirement(store, req, patch, tags, spec_id, summary)
+}
+
+fn import_hierarchy(
+    store: &mut RequirementsStore,
+    ids: &HashMap<String, Uuid>,
+    node: &Element,
+    parent: Option<Uuid>,
+    author: &str,
+    summary: &mut ImportSummary,
+) -> Result<()> {
+    let object = node
+        .child("OBJECT")
+        .and_then(|o| o.elements().next())
+        .and_then(|o| resolve_identifier(store, ids, &o.text()));
+
+    if let (Some(child), Some(parent)) = (object, parent) {
+        if set_parent(store, child, parent, author)? {
+            summary.parents_set += 1;
+        }
+    }
+    for child in node.children_of("CHILDREN") {
+        import_hierarchy(store, ids, child, object.or(parent), author, summary)?;
+    }
+    Ok(())
+}
+
+/// Finds the requirement for a SPEC-OBJECT identifier
+fn resolve_identifier(
+    store: &RequirementsStore,
+    ids: &HashMap<String, Uuid>,
+    identifier: &str,
+) -> Option<Uuid> {
+    if let Some(id) = ids.get(identifier) {
+        return Some(*id);
+    }
+    if let Some(id) = own_uuid(identifier).filter(|id| store.get_requirement_by_id(id).is_some()) {
+        return Some(id);
+    }
+    store
+        .requirements
+        .iter()
+        .find(|r| {
+            r.custom_fields
+                .get(FOREIGN_IDENTIFIER_FIELD)
+                .map(String::as_str)
+                == Some(identifier)
+        })
+        .map(|r| r.id)
+}
+
+/// Parses identifiers written by [`to_reqif`] (`_<uuid>`)
+fn own_uuid(identifier: &str) -> Option<Uuid> {
+    identifier
+        .strip_prefix('_')
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+/// Maps a SPEC-OBJECT-TYPE to a requirement type name
+fn resolve_type_name(store: &RequirementsStore, spec_type: &Element) -> String {
+    let id = spec_type.attr("IDENTIFIER").unwrap_or_default();
+    let long_name = spec_type.long_name();
+    if let Some(name) = id.strip_prefix(TYPE_ID_PREFIX) {
+        return name.to_string();
+    }
+    store
+        .type_definitions
+        .iter()
+        .find(|td| {
+            td.name.eq_ignore_ascii_case(&long_name)
+                || td.display_name.eq_ignore_ascii_case(&long_name)
+        })
+        .map(|td| td.name.clone())
+        .unwrap_or(long_name)
+}
+
+/// Adds a custom field definition for an imported attribute the type does not define yet
+fn ensure_field_definition(
+    store: &mut RequirementsStore,
+    type_name: &str,
+    field: &str,
+    kind: AttrKind,
+    options: &[String],
+) {
+    let Some(td) = store
+        .type_definitions
+        .iter_mut()
+        .find(|td| td.name == type_name)
+    else {
+        return;
+    };
+    if td.custom_fields.iter().any(|f| f.name == field) {
+        return;
+    }
+    let mut definition = CustomFieldDefinition::text(field, field);
+    definition.field_type = kind.field_type();
+    definition.options = options.to_vec();
+    definition.order = td.custom_fields.len() as i32;
+    td.custom_fields.push(definition);
+}
+
+/// Converts an XHTML value to plain text, keeping line breaks
+fn xhtml_text(value: &Element) -> String {
+    fn append(node: &Node, out: &mut String) {
+        match node {
+            // Whitespace-only text spanning lines is formatting from pretty printers
+            Node::Text(text) if text.contains('\n') && text.trim().is_empty() => {}
+            Node::Text(text) => out.push_str(text),
+            Node::Element(e) if e.name == "br" => out.push('\n'),
+            Node::Element(e) => {
+                let block = matches!(
+                    e.name.as_str(),
+                    "p" | "div"
+                        | "li"
+                        | "h1"
+                        | "h2"
+                        | "h3"
+                        | "h4"
+                        | "h5"
+                        | "h6"
+                        | "tr"
+                        | "pre"
+                        | "blockquote"
+                );
+                if block && !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                for child in &e.children {
+                    append(child, out);
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for child in &value.children {
+        append(child, &mut out);
+    }
+    out
+}
+
+// ============================================================================
+// Minimal XML tree
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn long_name(&self) -> String {
+        self.attr("LONG-NAME").unwrap_or_default().to_string()
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(e) => Some(e),
+            Node::Text(_) => None,
+        })
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|e| e.name == name)
+    }
+
+    /// Elements inside the named child, e.g. the SPEC-OBJECTs of SPEC-OBJECTS
+    fn children_of<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a Element> {
+        self.child(name).into_iter().flat_map(|c| c.elements())
+    }
+
+    fn find(&self, path: &[&str]) -> Option<&Element> {
+        path.iter()
+            .try_fold(self, |element, name| element.child(name))
+    }
+
+    /// Text content, trimmed
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.children {
+            if let Node::Text(t) = node {
+                text.push_str(t);
+            }
+        }
+        text.trim().to_string()
+    }
+}
+
+/// Parses a document into a tree; the returned element holds the top-level elements
+fn parse_document(xml: &str) -> Result<Element> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack = vec![Element::default()];
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => stack.push(element_from(&e)?),
+            Event::Empty(e) => {
+                let element = element_from(&e)?;
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Node::Element(element));
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().context("Unbalanced XML")?;
+                stack
+                    .last_mut()
+                    .context("Unbalanced XML")?
+                    .children
+                    .push(Node::Element(element));
+            }
+            Event::Text(text) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent
+                        .children
+                        .push(Node::Text(text.unescape()?.into_owned()));
+                }
+            }
+            Event::CData(data) => {
+                if let Some(parent) = stack.last_mut() {
+                    let text = String::from_utf8_lossy(&data.into_inner()).into_owned();
+                    parent.children.push(Node::Text(text));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if stack.len() != 1 {
+        anyhow::bail!("Unexpected end of XML document");
+    }
+    Ok(stack.pop().unwrap_or_default())
+}
+
+fn element_from(start: &BytesStart) -> Result<Element> {
+    let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+    let mut attrs = Vec::new();
+    for attr in start.attributes() {
+        let attr = attr?;
+        let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+        attrs.push((key, attr.unescape_value()?.into_owned()));
+    }
+    Ok(Element {
+        name,
+        attrs,
+        children: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RequirementStatus;
+
+    /// The shared fixture with a `risk` select field set on an approved,
+    /// tagged FR-001 whose description needs escaping
+    fn sample_store() -> RequirementsStore {
+        let mut store = crate::export::sample_store();
+        if let Some(td) = store
+            .type_definitions
+            .iter_mut()
+            .find(|td| td.name == "Functional")
+        {
+            td.custom_fields.push(CustomFieldDefinition::select(
+                "risk",
+                "Risk",
+                vec!["low".into(), "high".into()],
+            ));
+        }
+        let login = &mut store.requirements[0];
+        login.description = "Users sign in.\n\n  - with <password> & 2FA\n".into();
+        login.status = RequirementStatus::Approved;
+        login.tags.insert("security".into());
+        login.custom_fields.insert("risk".into(), "high".into());
+        store
+    }
+
+    #[test]
+    fn test_export_maps_types_fields_relations_and_hierarchy() {
+        let store = sample_store();
+        let xml = to_reqif(&store);
+
+        assert!(xml.contains(
+            r#"<SPEC-OBJECT-TYPE IDENTIFIER="_aida_type_Functional" LONG-NAME="Functional""#
+        ));
+        assert!(
+            xml.contains(r#"<ATTRIBUTE-DEFINITION-ENUMERATION IDENTIFIER="_aida_Functional_risk""#)
+        );
+        assert!(xml.contains(r#"LONG-NAME="verifies""#));
+        assert!(xml.contains(&format!(
+            "<SPEC-OBJECT-REF>_{}</SPEC-OBJECT-REF>",
+            store.requirements[1].id
+        )));
+        assert!(xml.contains("&lt;password&gt; &amp; 2FA"));
+
+        // The child is nested below its parent in the specification
+        let parent_pos = xml
+            .find(&format!("IDENTIFIER=\"_h_{}\"", store.requirements[0].id))
+            .unwrap();
+        let child_pos = xml
+            .find(&format!("IDENTIFIER=\"_h_{}\"", store.requirements[1].id))
+            .unwrap();
+        assert!(child_pos > parent_pos);
+    }
+
+    #[test]
+    fn test_round_trip_into_empty_store_preserves_identifiers() {
+        let store = sample_store();
+        let xml = to_reqif(&store);
+
+        let mut imported = RequirementsStore::new();
+        let summary = import_reqif(&mut imported, &xml, "tester").unwrap();
+        assert_eq!(summary.created.len(), 3);
+        assert_eq!(summary.relationships_added, 1);
+        assert_eq!(summary.parents_set, 1);
+        assert!(summary.warnings.is_empty());
+
+        for original in &store.requirements {
+            let copy = imported.get_requirement_by_id(&original.id).unwrap();
+            assert_eq!(copy.spec_id, original.spec_id);
+            assert_eq!(copy.title, original.title);
+            assert_eq!(copy.description, original.description);
+            assert_eq!(copy.status, original.status);
+            assert_eq!(copy.tags, original.tags);
+            assert_eq!(copy.custom_fields, original.custom_fields);
+        }
+        assert_eq!(imported.children_of(&store.requirements[0].id).len(), 1);
+        assert!(imported.requirements[0]
+            .relationships
+            .iter()
+            .any(|r| r.rel_type == RelationshipType::Child));
+
+        // Importing the same document again changes nothing
+        let again = import_reqif(&mut imported, &xml, "tester").unwrap();
+        assert!(again.created.is_empty() && again.updated.is_empty());
+        assert_eq!(again.unchanged, 3);
+
+        // New requirements do not reuse imported SPEC-IDs
+        let next = imported.generate_requirement_id(None, Some("FR"));
+        assert!(!["FR-001", "FR-002", "FR-003"].contains(&next.as_str()));
+    }
+
+    #[test]
+    fn test_import_foreign_document_keeps_identifier() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<REQ-IF xmlns="http://www.omg.org/spec/ReqIF/20110401/reqif.xsd" xmlns:xhtml="http://www.w3.org/1999/xhtml">
+  <CORE-CONTENT>
+    <REQ-IF-CONTENT>
+      <DATATYPES>
+        <DATATYPE-DEFINITION-STRING IDENTIFIER="dt-str" MAX-LENGTH="255"/>
+        <DATATYPE-DEFINITION-XHTML IDENTIFIER="dt-xhtml"/>
+      </DATATYPES>
+      <SPEC-TYPES>
+        <SPEC-OBJECT-TYPE IDENTIFIER="sys-req" LONG-NAME="System">
+          <SPEC-ATTRIBUTES>
+            <ATTRIBUTE-DEFINITION-STRING IDENTIFIER="a-name" LONG-NAME="ReqIF.Name">
+              <TYPE><DATATYPE-DEFINITION-STRING-REF>dt-str</DATATYPE-DEFINITION-STRING-REF></TYPE>
+            </ATTRIBUTE-DEFINITION-STRING>
+            <ATTRIBUTE-DEFINITION-XHTML IDENTIFIER="a-text" LONG-NAME="ReqIF.Text">
+              <TYPE><DATATYPE-DEFINITION-XHTML-REF>dt-xhtml</DATATYPE-DEFINITION-XHTML-REF></TYPE>
+            </ATTRIBUTE-DEFINITION-XHTML>
+            <ATTRIBUTE-DEFINITION-STRING IDENTIFIER="a-asil" LONG-NAME="ASIL">
+              <TYPE><DATATYPE-DEFINITION-STRING-REF>dt-str</DATATYPE-DEFINITION-STRING-REF></TYPE>
+            </ATTRIBUTE-DEFINITION-STRING>
+          </SPEC-ATTRIBUTES>
+        </SPEC-OBJECT-TYPE>
+      </SPEC-TYPES>
+      <SPEC-OBJECTS>
+        <SPEC-OBJECT IDENTIFIER="doors-42">
+          <VALUES>
+            <ATTRIBUTE-VALUE-STRING THE-VALUE="Brake light">
+              <DEFINITION><ATTRIBUTE-DEFINITION-STRING-REF>a-name</ATTRIBUTE-DEFINITION-STRING-REF></DEFINITION>
+            </ATTRIBUTE-VALUE-STRING>
+            <ATTRIBUTE-VALUE-XHTML>
+              <DEFINITION><ATTRIBUTE-DEFINITION-XHTML-REF>a-text</ATTRIBUTE-DEFINITION-XHTML-REF></DEFINITION>
+              <THE-VALUE>
+                <xhtml:div><xhtml:p>Turns on</xhtml:p><xhtml:p>within 100 ms</xhtml:p></xhtml:div>
+              </THE-VALUE>
+            </ATTRIBUTE-VALUE-XHTML>
+            <ATTRIBUTE-VALUE-STRING THE-VALUE="B">
+              <DEFINITION><ATTRIBUTE-DEFINITION-STRING-REF>a-asil</ATTRIBUTE-DEFINITION-STRING-REF></DEFINITION>
+            </ATTRIBUTE-VALUE-STRING>
+          </VALUES>
+          <TYPE><SPEC-OBJECT-TYPE-REF>sys-req</SPEC-OBJECT-TYPE-REF></TYPE>
+        </SPEC-OBJECT>
+      </SPEC-OBJECTS>
+    </REQ-IF-CONTENT>
+  </CORE-CONTENT>
+</REQ-IF>"#;
+
+        let mut store = RequirementsStore::new();
+        import_reqif(&mut store, xml, "tester").unwrap();
+        let req = &store.requirements[0];
+        assert_eq!(req.title, "Brake light");
+        assert_eq!(req.description, "Turns on\nwithin 100 ms");
+        assert_eq!(req.req_type, RequirementType::System);
+        assert_eq!(req.custom_fields["ASIL"], "B");
+        assert!(store
+            .get_type_definition(&RequirementType::System)
+            .unwrap()
+            .custom_fields
+            .iter()
+            .any(|f| f.name == "ASIL"));
+
+        // Exported again under the partner's identifier, and matched on re-import
+        let exported = to_reqif(&store);
+        assert!(exported.contains(r#"<SPEC-OBJECT IDENTIFIER="doors-42""#));
+        let summary =
+            import_reqif(&mut store, xml.replace("B\"", "C\"").as_str(), "tester").unwrap();
+        assert_eq!(summary.updated.len(), 1);
+        assert_eq!(store.requirements.len(), 1);
+        assert_eq!(store.requirements[0].custom_fields["ASIL"], "C");
+    }
+}
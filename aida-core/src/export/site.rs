@@ -0,0 +1,835 @@
+//! Static HTML documentation site
+//!
+//! Renders the project as a set of plain HTML pages that can be browsed
+//! offline or published as-is: an overview, a page per requirement with
+//! rendered Markdown, relationships in both directions and comment threads,
+//! feature and type indexes, and one page per baseline listing the changes
+//! since it was taken. Search runs in the browser from a generated index,
+//! loaded as a script so it also works from `file://` URLs.
+//!
+//! Archived requirements are left out.
+
+use anyhow::Result;
+use pulldown_cmark::{html, Event, Options, Parser};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::models::{
+    Baseline, Comment, FieldChange, RelationshipType, Requirement, RequirementsStore,
+};
+
+const STYLE: &str = r#"
+:root { --accent: #3b82f6; --border: #e5e7eb; --muted: #6b7280; --bg-soft: #f9fafb; }
+* { box-sizing: border-box; }
+body { margin: 0; font-family: system-ui, -apple-system, "Segoe UI", sans-serif; color: #111827; line-height: 1.5; }
+a { color: var(--accent); text-decoration: none; }
+a:hover { text-decoration: underline; }
+header { display: flex; gap: 1.5rem; align-items: center; padding: 0.75rem 2rem; border-bottom: 1px solid var(--border); background: var(--bg-soft); }
+header .brand { font-weight: 700; color: inherit; }
+header nav a { margin-right: 1rem; }
+.search { margin-left: auto; position: relative; }
+.search input { width: 18rem; padding: 0.35rem 0.6rem; border: 1px solid var(--border); border-radius: 6px; }
+#search-results { position: absolute; right: 0; width: 28rem; margin: 0.25rem 0 0; padding: 0; list-style: none; background: white; border: 1px solid var(--border); border-radius: 6px; box-shadow: 0 4px 12px rgba(0,0,0,0.08); z-index: 10; }
+#search-results:empty { display: none; }
+#search-results li { padding: 0.4rem 0.75rem; border-bottom: 1px solid var(--border); }
+#search-results li:last-child { border-bottom: none; }
+main { max-width: 72rem; margin: 0 auto; padding: 1.5rem 2rem; }
+footer { padding: 1rem 2rem; color: var(--muted); font-size: 0.85rem; border-top: 1px solid var(--border); }
+table { border-collapse: collapse; width: 100%; margin: 0.5rem 0 1.5rem; }
+th, td { text-align: left; padding: 0.4rem 0.6rem; border-bottom: 1px solid var(--border); vertical-align: top; }
+th { background: var(--bg-soft); font-weight: 600; }
+table.meta th { width: 10rem; }
+.badge { display: inline-block; padding: 0.05rem 0.5rem; border-radius: 999px; font-size: 0.8rem; background: #e5e7eb; color: #111827; white-space: nowrap; }
+.spec-id { font-family: ui-monospace, monospace; white-space: nowrap; }
+.muted { color: var(--muted); }
+.description { padding: 0.25rem 1rem; border-left: 3px solid var(--border); }
+.diff-old { background: #fef2f2; }
+.diff-new { background: #f0fdf4; }
+ul.comments, ul.comments ul { list-style: none; padding-left: 1.25rem; }
+ul.comments { padding-left: 0; }
+.comment { border: 1px solid var(--border); border-radius: 6px; padding: 0.5rem 0.75rem; margin: 0.5rem 0; }
+.comment .author { font-weight: 600; }
+"#;
+
+const SEARCH_SCRIPT: &str = r#"(function () {
+  var input = document.getElementById('search');
+  var results = document.getElementById('search-results');
+  var root = document.body.getAttribute('data-root') || '';
+  var index = window.AIDA_SEARCH_INDEX || [];
+  function escape(text) {
+    var div = document.createElement('div');
+    div.textContent = text;
+    return div.innerHTML;
+  }
+  input.addEventListener('input', function () {
+    var terms = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+    if (terms.length === 0) { results.innerHTML = ''; return; }
+    var matches = index.filter(function (entry) {
+      var haystack = (entry.id + ' ' + entry.title + ' ' + entry.text).toLowerCase();
+      return terms.every(function (term) { return haystack.indexOf(term) !== -1; });
+    }).slice(0, 20);
+    results.innerHTML = matches.map(function (entry) {
+      return '<li><a href="' + root + entry.url + '"><span class="spec-id">' + escape(entry.id) +
+        '</span> ' + escape(entry.title) + '</a></li>';
+    }).join('') || '<li class="muted">No matches</li>';
+  });
+})();
+"#;
+
+/// Writes the site into `output_dir`, creating it if needed
+pub fn export_site(store: &RequirementsStore, output_dir: &Path) -> Result<()> {
+    let pages = render_site(store)?;
+    for (path, content) in &pages {
+        let path = output_dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    println!(
+        "Exported static site: {}",
+        output_dir.join("index.html").display()
+    );
+    println!("  Total requirements: {}", published(store).count());
+    println!("  Files written: {}", pages.len());
+
+    Ok(())
+}
+
+/// Renders every file of the site, keyed by path relative to the site root
+pub fn render_site(store: &RequirementsStore) -> Result<BTreeMap<String, String>> {
+    let site = Site::new(store);
+    let mut pages = BTreeMap::new();
+
+    pages.insert("style.css".to_string(), site.stylesheet());
+    pages.insert("search.js".to_string(), SEARCH_SCRIPT.to_string());
+    pages.insert("search-index.js".to_string(), site.search_index()?);
+    pages.insert("index.html".to_string(), site.index_page());
+
+    for req in published(store) {
+        pages.insert(site.requirement_path(req), site.requirement_page(req));
+    }
+
+    pages.insert("features.html".to_string(), site.features_page());
+    for (feature, reqs) in site.by_feature() {
+        let body = format!(
+            "<h1>{}</h1>\n{}{}",
+            esc(&feature),
+            store
+                .get_feature_by_name(&feature)
+                .filter(|f| !f.description.is_empty())
+                .map(|f| format!(
+                    "<div class=\"description\">{}</div>\n",
+                    markdown(&f.description)
+                ))
+                .unwrap_or_default(),
+            site.requirement_table(&reqs, "../")
+        );
+        pages.insert(
+            format!("features/{}.html", slug(&feature)),
+            site.layout(&feature, "../", &body),
+        );
+    }
+
+    pages.insert("types.html".to_string(), site.types_page());
+    for (type_name, reqs) in site.by_type() {
+        let display = site.type_display(&type_name);
+        let body = format!(
+            "<h1><span class=\"badge type-{}\">{}</span></h1>\n{}",
+            slug(&type_name),
+            esc(&display),
+            site.requirement_table(&reqs, "../")
+        );
+        pages.insert(
+            format!("types/{}.html", slug(&type_name)),
+            site.layout(&display, "../", &body),
+        );
+    }
+
+    pages.insert("baselines.html".to_string(), site.baselines_page());
+    for baseline in &store.baselines {
+        pages.insert(
+            format!("baselines/{}.html", baseline.name_slug()),
+            site.baseline_page(baseline),
+        );
+    }
+
+    Ok(pages)
+}
+
+/// Requirements included in the site
+fn published(store: &RequirementsStore) -> impl Iterator<Item = &Requirement> {
+    store.requirements.iter().filter(|r| !r.archived)
+}
+
+struct Site<'a> {
+    store: &'a RequirementsStore,
+    title: String,
+    /// Page path of every published requirement
+    paths: HashMap<Uuid, String>,
+}
+
+impl<'a> Site<'a> {
+    fn new(store: &'a RequirementsStore) -> Self {
+        let title = if !store.title.is_empty() {
+            store.title.clone()
+        } else if !store.name.is_empty() {
+            store.name.clone()
+        } else {
+            "Requirements".to_string()
+        };
+        let paths = published(store)
+            .map(|r| {
+                let name = r.spec_id.clone().unwrap_or_else(|| r.id.to_string());
+                (r.id, format!("requirements/{}.html", slug(&name)))
+            })
+            .collect();
+        Self {
+            store,
+            title,
+            paths,
+        }
+    }
+
+    fn requirement_path(&self, req: &Requirement) -> String {
+        self.paths[&req.id].clone()
+    }
+
+    /// Wraps a page body in the shared header, navigation and search box
+    fn layout(&self, title: &str, root: &str, body: &str) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} - {project}</title>
+<link rel="stylesheet" href="{root}style.css">
+</head>
+<body data-root="{root}">
+<header>
+<a class="brand" href="{root}index.html">{project}</a>
+<nav><a href="{root}features.html">Features</a><a href="{root}types.html">Types</a><a href="{root}baselines.html">Baselines</a></nav>
+<div class="search"><input id="search" type="search" placeholder="Search requirements" autocomplete="off"><ul id="search-results"></ul></div>
+</header>
+<main>
+{body}
+</main>
+<footer>Generated by AIDA {version}</footer>
+<script src="{root}search-index.js"></script>
+<script src="{root}search.js"></script>
+</body>
+</html>
+"#,
+            title = esc(title),
+            project = esc(&self.title),
+            root = root,
+            body = body,
+            version = env!("CARGO_PKG_VERSION"),
+        )
+    }
+
+    /// Base styles plus one class per type and relationship using their configured colors
+    fn stylesheet(&self) -> String {
+        let mut css = STYLE.trim_start().to_string();
+        for td in &self.store.type_definitions {
+            if let Some(color) = td.color.as_deref().and_then(css_color) {
+                css.push_str(&format!(
+                    ".type-{} {{ background: {}; color: {}; }}\n",
+                    slug(&td.name),
+                    color,
+                    contrast_color(&color)
+                ));
+            }
+        }
+        for def in &self.store.relationship_definitions {
+            if let Some(color) = def.color.as_deref().and_then(css_color) {
+                css.push_str(&format!(
+                    ".rel-{} {{ color: {}; font-weight: 600; }}\n",
+                    slug(&def.name),
+                    color
+                ));
+            }
+        }
+        css
+    }
+
+    /// Search entries as a script defining `window.AIDA_SEARCH_INDEX`
+    fn search_index(&self) -> Result<String> {
+        let entries: Vec<serde_json::Value> = published(self.store)
+            .map(|req| {
+                serde_json::json!({
+                    "id": label(req),
+                    "title": req.title,
+                    "text": format!("{} {} {}", req.description, req.feature, req.tags.iter().cloned().collect::<Vec<_>>().join(" ")),
+                    "url": self.requirement_path(req),
+                })
+            })
+            .collect();
+        Ok(format!(
+            "window.AIDA_SEARCH_INDEX = {};\n",
+            serde_json::to_string(&entries)?
+        ))
+    }
+
+    fn index_page(&self) -> String {
+        let reqs: Vec<&Requirement> = published(self.store).collect();
+        let mut body = format!("<h1>{}</h1>\n", esc(&self.title));
+        if !self.store.description.is_empty() {
+            body.push_str(&format!(
+                "<div class=\"description\">{}</div>\n",
+                markdown(&self.store.description)
+            ));
+        }
+        body.push_str(&format!(
+            "<p class=\"muted\">{} requirements in {} features and {} types; {} baselines.</p>\n",
+            reqs.len(),
+            self.by_feature().len(),
+            self.by_type().len(),
+            self.store.baselines.len()
+        ));
+        body.push_str("<h2>All requirements</h2>\n");
+        body.push_str(&self.requirement_table(&reqs, ""));
+        self.layout("Overview", "", &body)
+    }
+
+    fn requirement_page(&self, req: &Requirement) -> String {
+        let root = "../";
+        let type_name = format!("{:?}", req.req_type);
+        let mut body = format!(
+            "<h1><span class=\"spec-id\">{}</span> {}</h1>\n<p><a class=\"badge type-{}\" href=\"{root}types/{}.html\">{}</a> <span class=\"badge\">{}</span> <span class=\"badge\">{}</span></p>\n",
+            esc(&label(req)),
+            esc(&req.title),
+            slug(&type_name),
+            slug(&type_name),
+            esc(&self.type_display(&type_name)),
+            esc(&req.effective_status()),
+            esc(&req.effective_priority()),
+        );
+
+        // Metadata
+        body.push_str("<table class=\"meta\">\n");
+        body.push_str(&format!(
+            "<tr><th>Feature</th><td><a href=\"{root}features/{}.html\">{}</a></td></tr>\n",
+            slug(&req.feature),
+            esc(&req.feature)
+        ));
+        if !req.owner.is_empty() {
+            body.push_str(&format!(
+                "<tr><th>Owner</th><td>{}</td></tr>\n",
+                esc(&req.owner)
+            ));
+        }
+        if !req.tags.is_empty() {
+            let mut tags: Vec<&String> = req.tags.iter().collect();
+            tags.sort();
+            let tags: Vec<String> = tags
+                .iter()
+                .map(|t| format!("<span class=\"badge\">{}</span>", esc(t)))
+                .collect();
+            body.push_str(&format!(
+                "<tr><th>Tags</th><td>{}</td></tr>\n",
+                tags.join(" ")
+            ));
+        }
+        let mut fields: Vec<(&String, &String)> = req.custom_fields.iter().collect();
+        fields.sort();
+        for (name, value) in fields {
+            body.push_str(&format!(
+                "<tr><th>{}</th><td>{}</td></tr>\n",
+                esc(name),
+                esc(value)
+            ));
+        }
+        body.push_str(&format!(
+            "<tr><th>Created</th><td>{}{}</td></tr>\n<tr><th>Modified</th><td>{}</td></tr>\n",
+            req.created_at.format("%Y-%m-%d %H:%M"),
+            req.created_by
+                .as_deref()
+                .map(|by| format!(" by {}", esc(by)))
+                .unwrap_or_default(),
+            req.modified_at.format("%Y-%m-%d %H:%M")
+        ));
+        body.push_str(&format!(
+            "<tr><th>UUID</th><td class=\"muted\">{}</td></tr>\n</table>\n",
+            req.id
+        ));
+
+        if !req.description.is_empty() {
+            body.push_str(&format!(
+                "<h2>Description</h2>\n<div class=\"description\">{}</div>\n",
+                markdown(&req.description)
+            ));
+        }
+
+        if !req.acceptance_criteria.is_empty() {
+            body.push_str("<h2>Acceptance criteria</h2>\n<ul>\n");
+            for ac in &req.acceptance_criteria {
+                body.push_str(&format!("<li>{}</li>\n", esc(&ac.checklist_line())));
+            }
+            body.push_str("</ul>\n");
+        }
+
+        let relationships = self.relationship_rows(req, root);
+        if !relationships.is_empty() {
+            body.push_str(
+                "<h2>Relationships</h2>\n<table>\n<tr><th>Relationship</th><th></th><th>Requirement</th><th>Status</th></tr>\n",
+            );
+            body.push_str(&relationships);
+            body.push_str("</table>\n");
+        }
+
+        if !req.comments.is_empty() {
+            body.push_str("<h2>Comments</h2>\n<ul class=\"comments\">\n");
+            for comment in &req.comments {
+                push_comment(&mut body, comment);
+            }
+            body.push_str("</ul>\n");
+        }
+
+        self.layout(&format!("{} {}", label(req), req.title), root, &body)
+    }
+
+    /// Outgoing relationships, then incoming ones not already mirrored by an outgoing link
+    fn relationship_rows(&self, req: &Requirement, root: &str) -> String {
+        let mut rows = String::new();
+        for rel in &req.relationships {
+            let Some(target) = self.published(&rel.target_id) else {
+                continue;
+            };
+            rows.push_str(&self.relationship_row(&rel.rel_type, "&rarr;", target, root));
+        }
+        for other in published(self.store) {
+            for rel in other.relationships.iter().filter(|r| r.target_id == req.id) {
+                let mirrored = req.relationships.iter().any(|own| {
+                    own.target_id == other.id
+                        && Some(&own.rel_type)
+                            == self.store.get_inverse_type(&rel.rel_type).as_ref()
+                });
+                if !mirrored {
+                    rows.push_str(&self.relationship_row(&rel.rel_type, "&larr;", other, root));
+                }
+            }
+        }
+        rows
+    }
+
+    fn relationship_row(
+        &self,
+        rel_type: &RelationshipType,
+        arrow: &str,
+        other: &Requirement,
+        root: &str,
+    ) -> String {
+        let name = rel_type.name();
+        let display = self
+            .store
+            .get_definition_for_type(rel_type)
+            .map(|d| d.display_name.clone())
+            .unwrap_or_else(|| name.clone());
+        format!(
+            "<tr><td class=\"rel-{}\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            slug(&name),
+            esc(&display),
+            arrow,
+            self.requirement_link(other, root),
+            esc(&other.effective_status())
+        )
+    }
+
+    fn published(&self, id: &Uuid) -> Option<&'a Requirement> {
+        self.store
+            .get_requirement_by_id(id)
+            .filter(|r| self.paths.contains_key(&r.id))
+    }
+
+    fn requirement_link(&self, req: &Requirement, root: &str) -> String {
+        format!(
+            "<a href=\"{}{}\"><span class=\"spec-id\">{}</span> {}</a>",
+            root,
+            self.requirement_path(req),
+            esc(&label(req)),
+            esc(&req.title)
+        )
+    }
+
+    fn requirement_table(&self, reqs: &[&Requirement], root: &str) -> String {
+        let mut sorted = reqs.to_vec();
+        sorted.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+        let mut table = String::from(
+            "<table>\n<tr><th>ID</th><th>Title</th><th>Type</th><th>Status</th><th>Priority</th><th>Feature</th></tr>\n",
+        );
+        for req in sorted {
+            let type_name = format!("{:?}", req.req_type);
+            table.push_str(&format!(
+                "<tr><td><a class=\"spec-id\" href=\"{root}{}\">{}</a></td><td>{}</td><td><span class=\"badge type-{}\">{}</span></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                self.requirement_path(req),
+                esc(&label(req)),
+                esc(&req.title),
+                slug(&type_name),
+                esc(&self.type_display(&type_name)),
+                esc(&req.effective_status()),
+                esc(&req.effective_priority()),
+                esc(&req.feature),
+            ));
+        }
+        table.push_str("</table>\n");
+        table
+    }
+
+    fn type_display(&self, type_name: &str) -> String {
+        self.store
+            .type_definitions
+            .iter()
+            .find(|td| td.name == type_name)
+            .map(|td| td.display_name.clone())
+            .unwrap_or_else(|| type_name.to_string())
+    }
+
+    fn by_feature(&self) -> BTreeMap<String, Vec<&'a Requirement>> {
+        let mut groups: BTreeMap<String, Vec<&Requirement>> = BTreeMap::new();
+        for req in published(self.store) {
+            groups.entry(req.feature.clone()).or_default().push(req);
+        }
+        groups
+    }
+
+    fn by_type(&self) -> BTreeMap<String, Vec<&'a Requirement>> {
+        let mut groups: BTreeMap<String, Vec<&Requirement>> = BTreeMap::new();
+        for req in published(self.store) {
+            groups
+                .entry(format!("{:?}", req.req_type))
+                .or_default()
+                .push(req);
+        }
+        groups
+    }
+
+    fn features_page(&self) -> String {
+        let mut body = String::from(
+            "<h1>Features</h1>\n<table>\n<tr><th>Feature</th><th>Prefix</th><th>Requirements</th></tr>\n",
+        );
+        for (feature, reqs) in self.by_feature() {
+            let prefix = self
+                .store
+                .get_feature_by_name(&feature)
+                .map(|f| f.prefix.clone())
+                .unwrap_or_default();
+            body.push_str(&format!(
+                "<tr><td><a href=\"features/{}.html\">{}</a></td><td class=\"spec-id\">{}</td><td>{}</td></tr>\n",
+                slug(&feature),
+                esc(&feature),
+                esc(&prefix),
+                reqs.len()
+            ));
+        }
+        body.push_str("</table>\n");
+        self.layout("Features", "", &body)
+    }
+
+    fn types_page(&self) -> String {
+        let mut body = String::from(
+            "<h1>Types</h1>\n<table>\n<tr><th>Type</th><th>Description</th><th>Requirements</th></tr>\n",
+        );
+        for (type_name, reqs) in self.by_type() {
+            let description = self
+                .store
+                .type_definitions
+                .iter()
+                .find(|td| td.name == type_name)
+                .and_then(|td| td.description.clone())
+                .unwrap_or_default();
+            body.push_str(&format!(
+                "<tr><td><a class=\"badge type-{}\" href=\"types/{}.html\">{}</a></td><td>{}</td><td>{}</td></tr>\n",
+                slug(&type_name),
+                slug(&type_name),
+                esc(&self.type_display(&type_name)),
+                esc(&description),
+                reqs.len()
+            ));
+        }
+        body.push_str("</table>\n");
+        self.layout("Types", "", &body)
+    }
+
+    fn baselines_page(&self) -> String {
+        let mut body = String::from("<h1>Baselines</h1>\n");
+        if self.store.baselines.is_empty() {
+            body.push_str("<p class=\"muted\">No baselines have been created.</p>\n");
+            return self.layout("Baselines", "", &body);
+        }
+        body.push_str(
+            "<table>\n<tr><th>Baseline</th><th>Created</th><th>By</th><th>Requirements</th><th>Description</th></tr>\n",
+        );
+        let mut baselines: Vec<&Baseline> = self.store.baselines.iter().collect();
+        baselines.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        for baseline in baselines {
+            body.push_str(&format!(
+                "<tr><td><a href=\"baselines/{}.html\">{}</a>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                baseline.name_slug(),
+                esc(&baseline.name),
+                if baseline.locked { " <span class=\"badge\">locked</span>" } else { "" },
+                baseline.created_at.format("%Y-%m-%d %H:%M"),
+                esc(&baseline.created_by),
+                baseline.requirements.len(),
+                esc(baseline.description.as_deref().unwrap_or(""))
+            ));
+        }
+        body.push_str("</table>\n");
+        self.layout("Baselines", "", &body)
+    }
+
+    /// Changes between a baseline and the current requirements
+    fn baseline_page(&self, baseline: &Baseline) -> String {
+        let root = "../";
+        let mut body = format!(
+            "<h1>Baseline {}</h1>\n<p class=\"muted\">Taken {} by {}. Changes since then:</p>\n",
+            esc(&baseline.name),
+            baseline.created_at.format("%Y-%m-%d %H:%M"),
+            esc(&baseline.created_by)
+        );
+        let Some(comparison) = self.store.compare_with_baseline(&baseline.id) else {
+            return self.layout(&baseline.name, root, &body);
+        };
+        body.push_str(&format!(
+            "<p>{} added, {} removed, {} modified, {} unchanged.</p>\n",
+            comparison.added.len(),
+            comparison.removed.len(),
+            comparison.modified.len(),
+            comparison.unchanged.len()
+        ));
+
+        let links = |ids: &[Uuid]| {
+            let mut reqs: Vec<&Requirement> =
+                ids.iter().filter_map(|id| self.published(id)).collect();
+            reqs.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+            reqs.iter()
+                .map(|r| format!("<li>{}</li>\n", self.requirement_link(r, root)))
+                .collect::<String>()
+        };
+        if !comparison.added.is_empty() {
+            body.push_str(&format!(
+                "<h2>Added</h2>\n<ul>\n{}</ul>\n",
+                links(&comparison.added)
+            ));
+        }
+        if !comparison.removed.is_empty() {
+            body.push_str("<h2>Removed</h2>\n<ul>\n");
+            for snapshot in baseline
+                .requirements
+                .iter()
+                .filter(|s| comparison.removed.contains(&s.original_id))
+            {
+                body.push_str(&format!(
+                    "<li><span class=\"spec-id\">{}</span> {}</li>\n",
+                    esc(snapshot.spec_id.as_deref().unwrap_or("")),
+                    esc(&snapshot.title)
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+        if !comparison.modified.is_empty() {
+            body.push_str("<h2>Modified</h2>\n");
+            let mut modified = comparison.modified.clone();
+            modified.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+            for diff in &modified {
+                if let Some(req) = self.published(&diff.id) {
+                    body.push_str(&format!("<h3>{}</h3>\n", self.requirement_link(req, root)));
+                }
+                push_changes(&mut body, &diff.changes);
+            }
+        }
+        self.layout(&format!("Baseline {}", baseline.name), root, &body)
+    }
+}
+
+fn push_changes(body: &mut String, changes: &[FieldChange]) {
+    body.push_str("<table>\n<tr><th>Field</th><th>Baseline</th><th>Current</th></tr>\n");
+    for change in changes {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td class=\"diff-old\">{}</td><td class=\"diff-new\">{}</td></tr>\n",
+            esc(&change.field_name),
+            esc(&change.old_value),
+            esc(&change.new_value)
+        ));
+    }
+    body.push_str("</table>\n");
+}
+
+fn push_comment(body: &mut String, comment: &Comment) {
+    body.push_str(&format!(
+        "<li><div class=\"comment\"><span class=\"author\">{}</span> <span class=\"muted\">{}</span>{}</div>\n",
+        esc(&comment.author),
+        comment.created_at.format("%Y-%m-%d %H:%M"),
+        markdown(&comment.content)
+    ));
+    if !comment.replies.is_empty() {
+        body.push_str("<ul>\n");
+        for reply in &comment.replies {
+            push_comment(body, reply);
+        }
+        body.push_str("</ul>\n");
+    }
+    body.push_str("</li>\n");
+}
+
+/// Renders Markdown to HTML; raw HTML in the source is shown as text
+fn markdown(source: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(source, options).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        other => other,
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+fn label(req: &Requirement) -> String {
+    req.spec_id.clone().unwrap_or_else(|| req.id.to_string())
+}
+
+/// Escapes text for HTML element content and attribute values
+fn esc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Lowercase file name and CSS class fragment
+fn slug(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_matches('-');
+    if out.is_empty() {
+        "unnamed".to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// Accepts `#rgb` and `#rrggbb` colors only, so configured values cannot inject CSS
+pub(super) fn css_color(color: &str) -> Option<String> {
+    let hex = color.trim().strip_prefix('#')?;
+    (matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| format!("#{}", hex.to_lowercase()))
+}
+
+/// Black or white, whichever reads better on the given background
+pub(super) fn contrast_color(color: &str) -> &'static str {
+    let hex = color.trim_start_matches('#');
+    let hex: String = if hex.len() == 3 {
+        hex.chars().flat_map(|c| [c, c]).collect()
+    } else {
+        hex.to_string()
+    };
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0) as f32;
+    let luminance = 0.299 * channel(0) + 0.587 * channel(2) + 0.114 * channel(4);
+    if luminance > 150.0 {
+        "#111827"
+    } else {
+        "#ffffff"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The shared fixture with a colored type, a feature, Markdown in the
+    /// description and a comment thread on FR-001
+    fn sample_store() -> RequirementsStore {
+        let mut store = crate::export::sample_store();
+        store.title = "Shop".into();
+        if let Some(td) = store
+            .type_definitions
+            .iter_mut()
+            .find(|td| td.name == "Functional")
+        {
+            td.color = Some("#FF6B6B".into());
+        }
+        let login = &mut store.requirements[0];
+        login.description = "Users **sign in**.\n\n<script>x</script>".into();
+        login.feature = "Auth".into();
+        let mut comment = Comment::new("alice".into(), "Needs *2FA*".into());
+        comment
+            .replies
+            .push(Comment::new("bob".into(), "Agreed".into()));
+        login.comments.push(comment);
+        store
+    }
+
+    #[test]
+    fn test_requirement_pages_link_relationships_both_ways() {
+        let pages = render_site(&sample_store()).unwrap();
+        let login = &pages["requirements/fr-001.html"];
+        let test = &pages["requirements/fr-003.html"];
+
+        assert!(login.contains("<strong>sign in</strong>"));
+        assert!(login.contains("&lt;script&gt;"));
+        assert!(!login.contains("<script>x"));
+        assert!(login.contains("href=\"../requirements/fr-003.html\""));
+        assert!(login.contains("&larr;"));
+        assert!(test.contains("href=\"../requirements/fr-001.html\""));
+        assert!(test.contains("&rarr;"));
+        // Threaded comments
+        assert!(login.contains("<em>2FA</em>"));
+        assert!(login.find("alice").unwrap() < login.find("bob").unwrap());
+    }
+
+    #[test]
+    fn test_indexes_search_and_theme() {
+        let pages = render_site(&sample_store()).unwrap();
+
+        assert!(pages["features/auth.html"].contains("FR-001"));
+        assert!(pages["features.html"].contains("href=\"features/auth.html\""));
+        assert!(pages["types/functional.html"].contains("FR-001"));
+        assert!(pages["style.css"].contains(".type-functional { background: #ff6b6b;"));
+        assert!(pages["search-index.js"].contains("\"url\":\"requirements/fr-001.html\""));
+        assert!(pages["index.html"].contains("Login test"));
+    }
+
+    #[test]
+    fn test_baseline_page_lists_changes() {
+        let mut store = sample_store();
+        store.create_baseline("Release 1".into(), None, "alice".into());
+        store.requirements[0].title = "Sign in".into();
+        let mut added = Requirement::new("Logout".into(), String::new());
+        added.spec_id = Some("FR-004".into());
+        store.requirements.push(added);
+
+        let pages = render_site(&store).unwrap();
+        let page = &pages["baselines/release-1.html"];
+        assert!(page.contains("1 added, 0 removed, 1 modified"));
+        assert!(page.contains("<td class=\"diff-old\">Login</td>"));
+        assert!(page.contains("fr-004.html"));
+        assert!(pages["baselines.html"].contains("Release 1"));
+    }
+
+    #[test]
+    fn test_css_color_rejects_non_hex_values() {
+        assert_eq!(css_color("#ABC").as_deref(), Some("#abc"));
+        assert_eq!(css_color("red; } body { display: none"), None);
+        assert_eq!(contrast_color("#ffffff"), "#111827");
+        assert_eq!(contrast_color("#000"), "#ffffff");
+    }
+}
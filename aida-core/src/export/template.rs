@@ -0,0 +1,1387 @@
+//! Template-driven document export
+//!
+//! Export templates are plain text files with `{{ ... }}` tags, rendered
+//! against the project. They are looked up by name in the project's
+//! `templates/` directory, then in `<templates dir>/export/`, then among
+//! the built-in templates (`spec` and `impl`, the Markdown requirements
+//! specification and implementation records).
+//!
+//! ```text
+//! # {{ project.title | default project.name }}
+//!
+//! {{#each requirements | reject spec_id starts_with "IMPL-" | group_by feature as group}}
+//! ## {{ group.key }}
+//!
+//! {{#each group.items | hierarchy as req}}
+//! {{ "#" | repeat req.depth }}## {{ req.number }} {{ req.title }}
+//! {{#if req.status == "Completed"}}Done.{{else if req.parent}}Part of {{ req.parent.label }}.{{/if}}
+//! {{#each req.relationships | where type == "verifies" as rel}}
+//! - Verifies {{ rel.target.label }} ({{ rel.target.title }})
+//! {{/each}}
+//! {{/each}}
+//! {{/each}}
+//! ```
+//!
+//! Tags:
+//! - `{{ expr }}` outputs a value; `{{! ... }}` is a comment
+//! - `{{#each expr as name}} ... {{else}} ... {{/each}}` loops over a list
+//!   (or the entries of a map as `key`/`value`); `loop.index`,
+//!   `loop.first` and `loop.last` describe the position
+//! - `{{#with expr as name}} ... {{else}} ... {{/with}}` binds a value, such
+//!   as a requirement picked with `find`, if it is present
+//! - `{{#if cond}} ... {{else if cond}} ... {{else}} ... {{/if}}`, where
+//!   conditions compare expressions with `==`, `!=`, `<`, `<=`, `>`, `>=`,
+//!   `starts_with`, `ends_with` or `contains` and combine with `not`, `and`
+//!   and `or`
+//!
+//! Filters, applied with `|`:
+//! - lists: `where field [op value]`, `reject field [op value]`,
+//!   `find field [op value]`, `sort field`, `group_by field` (a list of
+//!   `key`/`items`), `hierarchy` (parent/child order, setting `depth` and
+//!   `number`), `map field`, `first`, `last`, `reverse`, `count`, `join [sep]`
+//! - values: `default value`, `upper`, `lower`, `trim`, `date [format]`,
+//!   `repeat n`
+//!
+//! A tag alone on its line removes the whole line, so block tags can be
+//! written on lines of their own without leaving blank lines behind.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::document::{custom_field_values, snapshot_requirement};
+use crate::models::{RelationshipType, Requirement, RequirementsStore};
+
+/// Templates shipped with AIDA: name, default output file and source
+pub const BUILTIN_TEMPLATES: &[(&str, &str, &str)] = &[
+    ("spec", "REQUIREMENTS.md", include_str!("templates/spec.md")),
+    (
+        "impl",
+        "IMPLEMENTATION.md",
+        include_str!("templates/impl.md"),
+    ),
+];
+
+/// Directory holding project templates, relative to the project file
+pub const PROJECT_TEMPLATES_DIR: &str = "templates";
+
+/// A parsed export template
+#[derive(Debug, Clone)]
+pub struct ExportTemplate {
+    pub name: String,
+    /// File the template was read from (`None` for built-in templates)
+    pub path: Option<PathBuf>,
+    /// Output file used when none is given
+    pub default_output: PathBuf,
+    nodes: Vec<Node>,
+}
+
+impl ExportTemplate {
+    /// Parses template source
+    pub fn parse(name: &str, source: &str) -> Result<Self> {
+        let nodes = parse_template(source)
+            .with_context(|| format!("Failed to parse template '{}'", name))?;
+        Ok(Self {
+            name: name.to_string(),
+            path: None,
+            default_output: PathBuf::from(name),
+            nodes,
+        })
+    }
+
+    /// Gets a built-in template by name
+    pub fn builtin(name: &str) -> Result<Self> {
+        let Some((name, output, source)) = BUILTIN_TEMPLATES.iter().find(|(n, _, _)| *n == name)
+        else {
+            bail!("Unknown built-in template '{}'", name);
+        };
+        let mut template = Self::parse(name, source)?;
+        template.default_output = PathBuf::from(output);
+        Ok(template)
+    }
+
+    /// Reads a template file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read template {}", path.display()))?;
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut template = Self::parse(&name, &source)?;
+        template.path = Some(path.to_path_buf());
+        template.default_output = PathBuf::from(path.file_name().unwrap_or_default());
+        Ok(template)
+    }
+
+    /// Directories searched for templates, in order of precedence
+    pub fn search_dirs(project_dir: &Path) -> Vec<PathBuf> {
+        let mut dirs = vec![project_dir.join(PROJECT_TEMPLATES_DIR)];
+        if let Ok(dir) = crate::registry::get_templates_dir() {
+            dirs.push(dir.join("export"));
+        }
+        dirs
+    }
+
+    /// Finds a template by file path, by name in the search directories,
+    /// or among the built-in templates
+    pub fn load(name: &str, project_dir: &Path) -> Result<Self> {
+        let path = Path::new(name);
+        if path.is_file() {
+            return Self::from_file(path);
+        }
+        for dir in Self::search_dirs(project_dir) {
+            if let Some(file) = template_files(&dir)
+                .into_iter()
+                .find(|f| f.file_name().is_some_and(|n| n == name) || stem(f) == name)
+            {
+                return Self::from_file(&file);
+            }
+        }
+        if BUILTIN_TEMPLATES.iter().any(|(n, _, _)| *n == name) {
+            return Self::builtin(name);
+        }
+        bail!(
+            "Template '{}' not found. Available templates: {}",
+            name,
+            Self::available(project_dir).join(", ")
+        )
+    }
+
+    /// Names of all templates that [`ExportTemplate::load`] can find
+    pub fn available(project_dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = Self::search_dirs(project_dir)
+            .iter()
+            .flat_map(|dir| template_files(dir))
+            .map(|f| stem(&f))
+            .chain(BUILTIN_TEMPLATES.iter().map(|(n, _, _)| n.to_string()))
+            .collect();
+        let mut seen = HashSet::new();
+        names.retain(|n| seen.insert(n.clone()));
+        names
+    }
+
+    /// Renders the template, optionally against the named baseline
+    pub fn render(&self, store: &RequirementsStore, baseline: Option<&str>) -> Result<String> {
+        let root = template_context(store, baseline)?;
+        let mut scope = Scope {
+            root: &root,
+            vars: Vec::new(),
+        };
+        let mut out = String::new();
+        render_nodes(&self.nodes, &mut scope, &mut out)
+            .with_context(|| format!("Failed to render template '{}'", self.name))?;
+        Ok(out)
+    }
+}
+
+/// Export the project through a template
+pub fn export_template(
+    store: &RequirementsStore,
+    template: &ExportTemplate,
+    output_path: &Path,
+    baseline: Option<&str>,
+) -> Result<()> {
+    if let Some(path) = &template.path {
+        if fs::canonicalize(path).ok() == fs::canonicalize(output_path).ok() && output_path.exists()
+        {
+            bail!(
+                "Refusing to overwrite template {}; choose another output file",
+                path.display()
+            );
+        }
+    }
+    let output = template.render(store, baseline)?;
+    fs::write(output_path, output)?;
+
+    println!(
+        "Exported with template '{}': {}",
+        template.name,
+        output_path.display()
+    );
+    println!("  Total requirements: {}", store.requirements.len());
+
+    Ok(())
+}
+
+fn template_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Context
+// ============================================================================
+
+/// Builds the data templates are rendered against
+fn template_context(store: &RequirementsStore, baseline: Option<&str>) -> Result<Value> {
+    let baseline = match baseline {
+        Some(name) => Some(
+            store
+                .get_baseline_by_name(name)
+                .with_context(|| format!("Baseline '{}' not found", name))?,
+        ),
+        None => None,
+    };
+    let mut requirements: Vec<Requirement> = match baseline {
+        Some(b) => b.requirements.iter().map(snapshot_requirement).collect(),
+        None => store.requirements.clone(),
+    };
+    requirements.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+
+    let by_id: HashMap<_, _> = requirements.iter().map(|r| (r.id, r)).collect();
+    let parent_of = |req: &Requirement| {
+        req.relationships
+            .iter()
+            .find(|r| r.rel_type == RelationshipType::Parent)
+            .and_then(|r| by_id.get(&r.target_id).copied())
+    };
+    let mut children: HashMap<_, Vec<Value>> = HashMap::new();
+    for req in &requirements {
+        if let Some(parent) = parent_of(req) {
+            children.entry(parent.id).or_default().push(summary(req));
+        }
+    }
+
+    let requirements: Vec<Value> = requirements
+        .iter()
+        .map(|req| {
+            let relationships: Vec<Value> = req
+                .relationships
+                .iter()
+                .filter_map(|rel| {
+                    let target = by_id.get(&rel.target_id)?;
+                    let label = store
+                        .get_definition_for_type(&rel.rel_type)
+                        .map(|d| d.display_name.clone())
+                        .unwrap_or_else(|| rel.rel_type.name());
+                    Some(json!({
+                        "type": rel.rel_type.to_string(),
+                        "label": label,
+                        "target": summary(target),
+                    }))
+                })
+                .collect();
+            let type_name = format!("{:?}", req.req_type);
+            let type_label = store
+                .type_definitions
+                .iter()
+                .find(|td| td.name == type_name)
+                .map(|td| td.display_name.clone())
+                .unwrap_or_else(|| req.req_type.to_string());
+            let mut tags: Vec<&String> = req.tags.iter().collect();
+            tags.sort();
+            let fields: Vec<Value> = custom_field_values(store, req)
+                .into_iter()
+                .map(|(name, label, value)| json!({"name": name, "label": label, "value": value}))
+                .collect();
+            let verification = store.verification_summary(&req.id);
+
+            json!({
+                "id": req.id.to_string(),
+                "spec_id": req.spec_id.clone().unwrap_or_default(),
+                "label": label(req),
+                "title": req.title,
+                "description": req.description,
+                "type": type_name,
+                "type_label": type_label,
+                "status": req.effective_status(),
+                "priority": req.effective_priority(),
+                "feature": req.feature,
+                "owner": req.owner,
+                "created_by": req.created_by.clone().unwrap_or_default(),
+                "created_at": req.created_at.to_rfc3339(),
+                "modified_at": req.modified_at.to_rfc3339(),
+                "archived": req.archived,
+                "tags": tags,
+                "custom": req.custom_fields.iter().collect::<BTreeMap<_, _>>(),
+                "fields": fields,
+                "acceptance_criteria": req
+                    .acceptance_criteria
+                    .iter()
+                    .map(|ac| ac.checklist_line())
+                    .collect::<Vec<_>>(),
+                "relationships": relationships,
+                "parent": parent_of(req).map(summary),
+                "children": children.remove(&req.id).unwrap_or_default(),
+                "verification": {
+                    "status": verification.status().to_string(),
+                    "total": verification.total,
+                    "passed": verification.passed,
+                    "failed": verification.failed,
+                },
+                "commits": req.commits.iter().map(|c| c.short_sha()).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "project": {
+            "name": store.name,
+            "title": store.title,
+            "description": store.description,
+        },
+        "baseline": baseline.map(|b| json!({
+            "name": b.name,
+            "description": b.description.clone().unwrap_or_default(),
+            "created_at": b.created_at.to_rfc3339(),
+            "created_by": b.created_by,
+        })),
+        "features": store
+            .features
+            .iter()
+            .map(|f| json!({"name": f.name, "prefix": f.prefix, "description": f.description}))
+            .collect::<Vec<_>>(),
+        "requirements": requirements,
+        "now": Utc::now().to_rfc3339(),
+        "generator": format!("AIDA {}", env!("CARGO_PKG_VERSION")),
+    }))
+}
+
+fn label(req: &Requirement) -> String {
+    req.spec_id.clone().unwrap_or_else(|| req.id.to_string())
+}
+
+/// Short form of a requirement used for parents, children and link targets
+fn summary(req: &Requirement) -> Value {
+    json!({
+        "id": req.id.to_string(),
+        "spec_id": req.spec_id.clone().unwrap_or_default(),
+        "label": label(req),
+        "title": req.title,
+        "type": format!("{:?}", req.req_type),
+        "status": req.effective_status(),
+    })
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Output(Expr),
+    Each {
+        expr: Expr,
+        var: String,
+        body: Vec<Node>,
+        empty: Vec<Node>,
+    },
+    With {
+        expr: Expr,
+        var: String,
+        body: Vec<Node>,
+        empty: Vec<Node>,
+    },
+    If {
+        branches: Vec<(Cond, Vec<Node>)>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Literal(Value),
+    Path(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cmp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    StartsWith,
+    EndsWith,
+    Contains,
+}
+
+#[derive(Debug, Clone)]
+struct Filter {
+    name: String,
+    args: Vec<Operand>,
+    /// Comparison for `where`, `reject` and `find`
+    cmp: Option<(Cmp, Operand)>,
+}
+
+#[derive(Debug, Clone)]
+struct Expr {
+    operand: Operand,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone)]
+enum Cond {
+    Or(Vec<Cond>),
+    And(Vec<Cond>),
+    Not(Box<Cond>),
+    Test(Expr),
+    Compare(Expr, Cmp, Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Num(f64),
+    Word(String),
+    Op(String),
+    Pipe,
+}
+
+/// Raw piece of a template: text, or tag content with its line number
+enum Segment {
+    Text(String),
+    Tag(String, usize),
+}
+
+fn parse_template(source: &str) -> Result<Vec<Node>> {
+    let segments = split_segments(source)?;
+    let mut pos = 0;
+    let (nodes, end) = parse_nodes(&segments, &mut pos)?;
+    if let Some((tag, line)) = end {
+        bail!("line {}: unexpected {{{{{}}}}}", line, tag);
+    }
+    Ok(nodes)
+}
+
+fn is_block_tag(tag: &str) -> bool {
+    tag.starts_with('#') || tag.starts_with('/') || tag.starts_with('!') || is_else(tag)
+}
+
+fn is_else(tag: &str) -> bool {
+    tag == "else" || tag.starts_with("else ")
+}
+
+/// Splits source into text and tags, dropping the lines that hold only a block tag
+fn split_segments(source: &str) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut rest = source;
+    let mut line = 1;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            bail!(
+                "line {}: unclosed tag",
+                line + rest[..start].matches('\n').count()
+            );
+        };
+        segments.push(Segment::Text(rest[..start].to_string()));
+        line += rest[..start].matches('\n').count();
+        let tag = &rest[start + 2..start + len];
+        segments.push(Segment::Tag(tag.trim().to_string(), line));
+        line += tag.matches('\n').count();
+        rest = &rest[start + len + 2..];
+    }
+    segments.push(Segment::Text(rest.to_string()));
+
+    // Texts alternate with tags, so tag i sits between texts i - 1 and i + 1.
+    // Decide which tags stand alone using the original text, then trim.
+    let mut ranges: Vec<(usize, usize)> = segments
+        .iter()
+        .map(|s| match s {
+            Segment::Text(t) => (0, t.len()),
+            Segment::Tag(..) => (0, 0),
+        })
+        .collect();
+    for i in (1..segments.len()).step_by(2) {
+        let (Segment::Tag(tag, _), Segment::Text(before), Segment::Text(after)) =
+            (&segments[i], &segments[i - 1], &segments[i + 1])
+        else {
+            continue;
+        };
+        if !is_block_tag(tag) {
+            continue;
+        }
+        let line_start = before.rfind('\n').map(|p| p + 1);
+        let line_end = after.find('\n').map(|p| p + 1);
+        let alone_before = match line_start {
+            Some(p) => before[p..].trim().is_empty(),
+            None => i == 1 && before.trim().is_empty(),
+        };
+        let alone_after = match line_end {
+            Some(p) => after[..p].trim().is_empty(),
+            None => i + 2 == segments.len() && after.trim().is_empty(),
+        };
+        if alone_before && alone_after {
+            ranges[i - 1].1 = line_start.unwrap_or(0);
+            ranges[i + 1].0 = line_end.unwrap_or(after.len());
+        }
+    }
+    for (segment, (start, end)) in segments.iter_mut().zip(ranges) {
+        if let Segment::Text(text) = segment {
+            *text = if start < end {
+                text[start..end].to_string()
+            } else {
+                String::new()
+            };
+        }
+    }
+    Ok(segments)
+}
+
+/// Parsed nodes and the closing or `else` tag (with its line) that ended them
+type Parsed = (Vec<Node>, Option<(String, usize)>);
+
+/// Parses nodes until a closing or `else` tag, which is returned unconsumed
+fn parse_nodes(segments: &[Segment], pos: &mut usize) -> Result<Parsed> {
+    let mut nodes = Vec::new();
+    while let Some(segment) = segments.get(*pos) {
+        *pos += 1;
+        let (tag, line) = match segment {
+            Segment::Text(text) => {
+                if !text.is_empty() {
+                    nodes.push(Node::Text(text.clone()));
+                }
+                continue;
+            }
+            Segment::Tag(tag, line) => (tag.as_str(), *line),
+        };
+        let node = if tag.starts_with('!') {
+            continue;
+        } else if tag.starts_with('/') || is_else(tag) {
+            return Ok((nodes, Some((tag.to_string(), line))));
+        } else if let Some(header) = tag.strip_prefix("#each ") {
+            parse_binding("each", header, segments, pos, line)
+        } else if let Some(header) = tag.strip_prefix("#with ") {
+            parse_binding("with", header, segments, pos, line)
+        } else if let Some(cond) = tag.strip_prefix("#if ") {
+            parse_if(cond, segments, pos, line)
+        } else if tag.starts_with('#') {
+            bail!("line {}: unknown block {{{{{}}}}}", line, tag)
+        } else {
+            parse_expr_str(tag).map(Node::Output)
+        };
+        nodes.push(node.with_context(|| format!("line {}", line))?);
+    }
+    Ok((nodes, None))
+}
+
+/// Parses `#each` and `#with`, which bind a value to a name
+fn parse_binding(
+    block: &str,
+    header: &str,
+    segments: &[Segment],
+    pos: &mut usize,
+    line: usize,
+) -> Result<Node> {
+    let mut tokens = tokenize(header)?;
+    let var = match tokens.as_slice() {
+        [.., Token::Word(kw), Token::Word(name)] if kw == "as" => {
+            let name = name.clone();
+            tokens.truncate(tokens.len() - 2);
+            name
+        }
+        _ => "this".to_string(),
+    };
+    let expr = Parser::new(tokens).expr_to_end()?;
+
+    let (body, end) = parse_nodes(segments, pos)?;
+    let (empty, end) = match end {
+        Some((tag, _)) if tag == "else" => parse_nodes(segments, pos)?,
+        other => (Vec::new(), other),
+    };
+    match end {
+        Some((tag, _)) if tag == format!("/{}", block) => Ok(if block == "each" {
+            Node::Each {
+                expr,
+                var,
+                body,
+                empty,
+            }
+        } else {
+            Node::With {
+                expr,
+                var,
+                body,
+                empty,
+            }
+        }),
+        Some((tag, line)) => bail!(
+            "line {}: expected {{{{/{}}}}}, found {{{{{}}}}}",
+            line,
+            block,
+            tag
+        ),
+        None => bail!("line {}: {{{{#{}}}}} is never closed", line, block),
+    }
+}
+
+fn parse_if(cond: &str, segments: &[Segment], pos: &mut usize, line: usize) -> Result<Node> {
+    let mut branches = Vec::new();
+    let mut cond = Parser::new(tokenize(cond)?).cond_to_end()?;
+    loop {
+        let (body, end) = parse_nodes(segments, pos)?;
+        branches.push((cond, body));
+        match end {
+            Some((tag, _)) if tag == "/if" => {
+                return Ok(Node::If {
+                    branches,
+                    otherwise: Vec::new(),
+                })
+            }
+            Some((tag, line)) if tag.starts_with("else if ") => {
+                cond = Parser::new(tokenize(&tag["else if ".len()..])?)
+                    .cond_to_end()
+                    .with_context(|| format!("line {}", line))?;
+            }
+            Some((tag, _)) if tag == "else" => {
+                let (otherwise, end) = parse_nodes(segments, pos)?;
+                return match end {
+                    Some((tag, _)) if tag == "/if" => Ok(Node::If {
+                        branches,
+                        otherwise,
+                    }),
+                    Some((tag, line)) => {
+                        bail!("line {}: expected {{{{/if}}}}, found {{{{{}}}}}", line, tag)
+                    }
+                    None => bail!("line {}: {{{{#if}}}} is never closed", line),
+                };
+            }
+            Some((tag, line)) => {
+                bail!("line {}: expected {{{{/if}}}}, found {{{{{}}}}}", line, tag)
+            }
+            None => bail!("line {}: {{{{#if}}}} is never closed", line),
+        }
+    }
+}
+
+fn parse_expr_str(source: &str) -> Result<Expr> {
+    Parser::new(tokenize(source)?).expr_to_end()
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some(ch) if ch == c => break,
+                    Some('\\') => match chars.next() {
+                        Some('n') => text.push('\n'),
+                        Some('t') => text.push('\t'),
+                        Some(other) => text.push(other),
+                        None => bail!("unterminated string"),
+                    },
+                    Some(ch) => text.push(ch),
+                    None => bail!("unterminated string"),
+                }
+            }
+            tokens.push(Token::Str(text));
+        } else if c == '|' {
+            chars.next();
+            tokens.push(Token::Pipe);
+        } else if matches!(c, '=' | '!' | '<' | '>') {
+            chars.next();
+            let mut op = c.to_string();
+            if chars.peek() == Some(&'=') {
+                chars.next();
+                op.push('=');
+            }
+            if op == "=" || op == "!" {
+                bail!("unknown operator '{}'", op);
+            }
+            tokens.push(Token::Op(op));
+        } else if c.is_ascii_digit() || c == '-' {
+            let mut text = String::new();
+            while let Some(&ch) = chars.peek() {
+                if ch.is_ascii_digit() || ch == '.' || (ch == '-' && text.is_empty()) {
+                    text.push(ch);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let number = text
+                .parse()
+                .with_context(|| format!("invalid number '{}'", text))?;
+            tokens.push(Token::Num(number));
+        } else if c.is_alphanumeric() || c == '_' || c == '@' {
+            let mut word = String::new();
+            while let Some(&ch) = chars.peek() {
+                if ch.is_alphanumeric() || matches!(ch, '_' | '.' | '-' | '@') {
+                    word.push(ch);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Word(word));
+        } else {
+            bail!("unexpected character '{}'", c);
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w == word)
+    }
+
+    fn finish(&self) -> Result<()> {
+        match self.peek() {
+            Some(token) => bail!("unexpected {:?}", token),
+            None => Ok(()),
+        }
+    }
+
+    fn expr_to_end(mut self) -> Result<Expr> {
+        let expr = self.expr()?;
+        self.finish()?;
+        Ok(expr)
+    }
+
+    fn cond_to_end(mut self) -> Result<Cond> {
+        let cond = self.or()?;
+        self.finish()?;
+        Ok(cond)
+    }
+
+    fn or(&mut self) -> Result<Cond> {
+        let mut terms = vec![self.and()?];
+        while self.peek_word("or") {
+            self.pos += 1;
+            terms.push(self.and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Cond::Or(terms)
+        })
+    }
+
+    fn and(&mut self) -> Result<Cond> {
+        let mut terms = vec![self.not()?];
+        while self.peek_word("and") {
+            self.pos += 1;
+            terms.push(self.not()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Cond::And(terms)
+        })
+    }
+
+    fn not(&mut self) -> Result<Cond> {
+        if self.peek_word("not") {
+            self.pos += 1;
+            return Ok(Cond::Not(Box::new(self.not()?)));
+        }
+        let left = self.expr()?;
+        match self.cmp() {
+            Some(cmp) => Ok(Cond::Compare(left, cmp, self.expr()?)),
+            None => Ok(Cond::Test(left)),
+        }
+    }
+
+    /// Consumes a comparison operator if one comes next
+    fn cmp(&mut self) -> Option<Cmp> {
+        let cmp = match self.peek()? {
+            Token::Op(op) => match op.as_str() {
+                "==" => Cmp::Eq,
+                "!=" => Cmp::Ne,
+                "<" => Cmp::Lt,
+                "<=" => Cmp::Le,
+                ">" => Cmp::Gt,
+                ">=" => Cmp::Ge,
+                _ => return None,
+            },
+            Token::Word(w) => match w.as_str() {
+                "starts_with" => Cmp::StartsWith,
+                "ends_with" => Cmp::EndsWith,
+                "contains" => Cmp::Contains,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(cmp)
+    }
+
+    fn operand(&mut self) -> Result<Operand> {
+        let operand = match self.peek() {
+            Some(Token::Str(s)) => Operand::Literal(Value::String(s.clone())),
+            Some(Token::Num(n)) => Operand::Literal(json!(n)),
+            Some(Token::Word(w)) => match w.as_str() {
+                "true" => Operand::Literal(Value::Bool(true)),
+                "false" => Operand::Literal(Value::Bool(false)),
+                "null" => Operand::Literal(Value::Null),
+                "as" | "and" | "or" | "not" | "starts_with" | "ends_with" | "contains" => {
+                    bail!("expected a value, found '{}'", w)
+                }
+                _ => Operand::Path(w.clone()),
+            },
+            Some(token) => bail!("expected a value, found {:?}", token),
+            None => bail!("expected a value"),
+        };
+        self.pos += 1;
+        Ok(operand)
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let operand = self.operand()?;
+        let mut filters = Vec::new();
+        while self.peek() == Some(&Token::Pipe) {
+            self.pos += 1;
+            let name = match self.peek() {
+                Some(Token::Word(w)) => w.clone(),
+                _ => bail!("expected a filter name after '|'"),
+            };
+            self.pos += 1;
+            let mut filter = Filter {
+                name: name.clone(),
+                args: Vec::new(),
+                cmp: None,
+            };
+            match name.as_str() {
+                "where" | "reject" | "find" => {
+                    filter.args.push(self.operand()?);
+                    if let Some(cmp) = self.cmp() {
+                        filter.cmp = Some((cmp, self.operand()?));
+                    }
+                }
+                "sort" | "group_by" | "map" | "default" | "repeat" => {
+                    filter.args.push(self.operand()?)
+                }
+                "date" | "join" => {
+                    if let Some(Token::Str(_)) = self.peek() {
+                        filter.args.push(self.operand()?);
+                    }
+                }
+                "upper" | "lower" | "trim" | "count" | "first" | "last" | "reverse"
+                | "hierarchy" => {}
+                _ => bail!("unknown filter '{}'", name),
+            }
+            filters.push(filter);
+        }
+        Ok(Expr { operand, filters })
+    }
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+struct Scope<'a> {
+    root: &'a Value,
+    vars: Vec<(String, Value)>,
+}
+
+impl Scope<'_> {
+    fn lookup(&self, path: &str) -> Value {
+        let (head, rest) = match path.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (path, None),
+        };
+        let base = self
+            .vars
+            .iter()
+            .rev()
+            .find(|(name, _)| name == head)
+            .map(|(_, value)| value)
+            .or_else(|| self.root.get(head));
+        match (base, rest) {
+            (Some(value), Some(rest)) => get_path(value, rest),
+            (Some(value), None) => value.clone(),
+            (None, _) => Value::Null,
+        }
+    }
+
+    fn operand(&self, operand: &Operand) -> Value {
+        match operand {
+            Operand::Literal(value) => value.clone(),
+            Operand::Path(path) => self.lookup(path),
+        }
+    }
+}
+
+fn get_path(value: &Value, path: &str) -> Value {
+    let mut current = value;
+    for key in path.split('.') {
+        let next = match current {
+            Value::Object(map) => map.get(key),
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => current = next,
+            None => return Value::Null,
+        }
+    }
+    current.clone()
+}
+
+fn render_nodes(nodes: &[Node], scope: &mut Scope, out: &mut String) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Output(expr) => out.push_str(&display(&eval(expr, scope)?)),
+            Node::Each {
+                expr,
+                var,
+                body,
+                empty,
+            } => {
+                let items = match eval(expr, scope)? {
+                    Value::Array(items) => items,
+                    Value::Object(map) => map
+                        .into_iter()
+                        .map(|(key, value)| json!({"key": key, "value": value}))
+                        .collect(),
+                    Value::Null => Vec::new(),
+                    other => bail!("#each expects a list, found '{}'", display(&other)),
+                };
+                if items.is_empty() {
+                    render_nodes(empty, scope, out)?;
+                }
+                let count = items.len();
+                for (index, item) in items.into_iter().enumerate() {
+                    let position = json!({
+                        "index": index + 1,
+                        "index0": index,
+                        "first": index == 0,
+                        "last": index + 1 == count,
+                        "length": count,
+                    });
+                    scope.vars.push(("loop".to_string(), position));
+                    scope.vars.push((var.clone(), item));
+                    let result = render_nodes(body, scope, out);
+                    scope.vars.truncate(scope.vars.len() - 2);
+                    result?;
+                }
+            }
+            Node::With {
+                expr,
+                var,
+                body,
+                empty,
+            } => {
+                let value = eval(expr, scope)?;
+                if truthy(&value) {
+                    scope.vars.push((var.clone(), value));
+                    let result = render_nodes(body, scope, out);
+                    scope.vars.pop();
+                    result?;
+                } else {
+                    render_nodes(empty, scope, out)?;
+                }
+            }
+            Node::If {
+                branches,
+                otherwise,
+            } => {
+                let mut chosen = otherwise;
+                for (cond, body) in branches {
+                    if test(cond, scope)? {
+                        chosen = body;
+                        break;
+                    }
+                }
+                render_nodes(chosen, scope, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn test(cond: &Cond, scope: &Scope) -> Result<bool> {
+    Ok(match cond {
+        Cond::Or(terms) => {
+            for term in terms {
+                if test(term, scope)? {
+                    return Ok(true);
+                }
+            }
+            false
+        }
+        Cond::And(terms) => {
+            for term in terms {
+                if !test(term, scope)? {
+                    return Ok(false);
+                }
+            }
+            true
+        }
+        Cond::Not(inner) => !test(inner, scope)?,
+        Cond::Test(expr) => truthy(&eval(expr, scope)?),
+        Cond::Compare(left, cmp, right) => compare(&eval(left, scope)?, *cmp, &eval(right, scope)?),
+    })
+}
+
+fn eval(expr: &Expr, scope: &Scope) -> Result<Value> {
+    let mut value = scope.operand(&expr.operand);
+    for filter in &expr.filters {
+        value = apply_filter(filter, value, scope)?;
+    }
+    Ok(value)
+}
+
+/// Name of the item field a list filter works on
+fn field_arg(filter: &Filter) -> String {
+    match filter.args.first() {
+        Some(Operand::Path(path)) => path.clone(),
+        Some(Operand::Literal(value)) => display(value),
+        None => String::new(),
+    }
+}
+
+fn apply_filter(filter: &Filter, value: Value, scope: &Scope) -> Result<Value> {
+    let name = filter.name.as_str();
+    let list = || match &value {
+        Value::Array(items) => Ok(items.clone()),
+        Value::Null => Ok(Vec::new()),
+        other => bail!("'{}' expects a list, found '{}'", name, display(other)),
+    };
+    Ok(match name {
+        "where" | "reject" | "find" => {
+            let field = field_arg(filter);
+            let expected = filter
+                .cmp
+                .as_ref()
+                .map(|(cmp, operand)| (*cmp, scope.operand(operand)));
+            let matches = |item: &Value| {
+                let actual = get_path(item, &field);
+                let hit = match &expected {
+                    Some((cmp, expected)) => compare(&actual, *cmp, expected),
+                    None => truthy(&actual),
+                };
+                hit != (name == "reject")
+            };
+            let items = list()?;
+            if name == "find" {
+                items.into_iter().find(matches).unwrap_or(Value::Null)
+            } else {
+                Value::Array(items.into_iter().filter(matches).collect())
+            }
+        }
+        "sort" => {
+            let field = field_arg(filter);
+            let mut items = list()?;
+            items.sort_by(|a, b| order(&get_path(a, &field), &get_path(b, &field)));
+            Value::Array(items)
+        }
+        "group_by" => {
+            let field = field_arg(filter);
+            let mut groups: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+            for item in list()? {
+                groups
+                    .entry(display(&get_path(&item, &field)))
+                    .or_default()
+                    .push(item);
+            }
+            Value::Array(
+                groups
+                    .into_iter()
+                    .map(|(key, items)| json!({"key": key, "items": items}))
+                    .collect(),
+            )
+        }
+        "map" => {
+            let field = field_arg(filter);
+            Value::Array(list()?.iter().map(|item| get_path(item, &field)).collect())
+        }
+        "hierarchy" => Value::Array(hierarchy(list()?)),
+        "first" => list()?.into_iter().next().unwrap_or(Value::Null),
+        "last" => list()?.into_iter().last().unwrap_or(Value::Null),
+        "reverse" => Value::Array(list()?.into_iter().rev().collect()),
+        "count" => json!(match &value {
+            Value::Array(items) => items.len(),
+            Value::Object(map) => map.len(),
+            Value::Null => 0,
+            other => display(other).chars().count(),
+        }),
+        "join" => {
+            let separator = filter
+                .args
+                .first()
+                .map(|arg| display(&scope.operand(arg)))
+                .unwrap_or_else(|| ", ".to_string());
+            let parts: Vec<String> = list()?.iter().map(display).collect();
+            Value::String(parts.join(&separator))
+        }
+        "default" => {
+            if truthy(&value) {
+                value
+            } else {
+                scope.operand(&filter.args[0])
+            }
+        }
+        "upper" => Value::String(display(&value).to_uppercase()),
+        "lower" => Value::String(display(&value).to_lowercase()),
+        "trim" => Value::String(display(&value).trim().to_string()),
+        "date" => {
+            let format = filter
+                .args
+                .first()
+                .map(|arg| display(&scope.operand(arg)))
+                .unwrap_or_else(|| "%Y-%m-%d".to_string());
+            let text = display(&value);
+            match DateTime::parse_from_rfc3339(&text) {
+                Ok(date) => Value::String(date.with_timezone(&Utc).format(&format).to_string()),
+                Err(_) => Value::String(text),
+            }
+        }
+        "repeat" => {
+            let times = scope.operand(&filter.args[0]).as_f64().unwrap_or(0.0);
+            Value::String(display(&value).repeat(times.clamp(0.0, 1000.0) as usize))
+        }
+        _ => bail!("unknown filter '{}'", name),
+    })
+}
+
+/// Orders requirement items depth-first along their parent links,
+/// numbering them `1`, `1.1`, ... and setting their `depth`
+fn hierarchy(items: Vec<Value>) -> Vec<Value> {
+    let id_of = |item: &Value| display(&get_path(item, "id"));
+    let ids: HashSet<String> = items.iter().map(id_of).collect();
+    let mut children: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let parent = display(&get_path(item, "parent.id"));
+        if ids.contains(&parent) {
+            children.entry(parent).or_default().push(index);
+        } else {
+            roots.push(index);
+        }
+    }
+
+    fn visit(
+        index: usize,
+        number: String,
+        depth: usize,
+        items: &[Value],
+        children: &HashMap<String, Vec<usize>>,
+        visited: &mut Vec<bool>,
+        out: &mut Vec<Value>,
+    ) {
+        if visited[index] {
+            return;
+        }
+        visited[index] = true;
+        let mut item = items[index].clone();
+        if let Value::Object(map) = &mut item {
+            map.insert("depth".to_string(), json!(depth));
+            map.insert("number".to_string(), json!(number));
+        }
+        out.push(item);
+        let id = display(&get_path(&items[index], "id"));
+        for (position, &child) in children.get(&id).into_iter().flatten().enumerate() {
+            let child_number = format!("{}.{}", number, position + 1);
+            visit(
+                child,
+                child_number,
+                depth + 1,
+                items,
+                children,
+                visited,
+                out,
+            );
+        }
+    }
+
+    let mut visited = vec![false; items.len()];
+    let mut out = Vec::with_capacity(items.len());
+    let mut number = 0;
+    // Items caught in a parent cycle have no root; list them at the top level
+    for index in roots.into_iter().chain(0..items.len()) {
+        if !visited[index] {
+            number += 1;
+            visit(
+                index,
+                number.to_string(),
+                1,
+                &items,
+                &children,
+                &mut visited,
+                &mut out,
+            );
+        }
+    }
+    out
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if f.fract() == 0.0 && f.abs() < 1e15 => format!("{}", f as i64),
+            _ => n.to_string(),
+        },
+        Value::Bool(b) => b.to_string(),
+        Value::Array(items) => items.iter().map(display).collect::<Vec<_>>().join(", "),
+        Value::Object(map) => Value::Object(Map::clone(map)).to_string(),
+    }
+}
+
+fn order(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        _ => display(a).cmp(&display(b)),
+    }
+}
+
+fn compare(left: &Value, cmp: Cmp, right: &Value) -> bool {
+    let equal = || match (left.as_f64(), right.as_f64()) {
+        (Some(x), Some(y)) => x == y,
+        _ => display(left) == display(right),
+    };
+    match cmp {
+        Cmp::Eq => equal(),
+        Cmp::Ne => !equal(),
+        Cmp::Lt => order(left, right).is_lt(),
+        Cmp::Le => order(left, right).is_le(),
+        Cmp::Gt => order(left, right).is_gt(),
+        Cmp::Ge => order(left, right).is_ge(),
+        Cmp::StartsWith => display(left).starts_with(&display(right)),
+        Cmp::EndsWith => display(left).ends_with(&display(right)),
+        Cmp::Contains => match left {
+            Value::Array(items) => items.iter().any(|item| display(item) == display(right)),
+            other => display(other).contains(&display(right)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RequirementType;
+
+    /// The shared fixture with FR-001 and FR-002 in a feature, and an
+    /// implementation record
+    fn store() -> RequirementsStore {
+        let mut store = crate::export::sample_store();
+        store.title = "Shop".into();
+        for req in &mut store.requirements[..2] {
+            req.feature = "Auth".into();
+        }
+        store.requirements[1]
+            .custom_fields
+            .insert("files_changed".into(), "src/auth.rs".into());
+        let mut record = Requirement::new("Session store".into(), String::new());
+        record.spec_id = Some("IMPL-001".into());
+        record.req_type = RequirementType::Task;
+        record.feature = "Accounts".into();
+        store.requirements.insert(0, record);
+        store
+    }
+
+    fn render(source: &str) -> String {
+        ExportTemplate::parse("test", source)
+            .unwrap()
+            .render(&store(), None)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_groups_filters_and_standalone_lines() {
+        let source = "\
+# {{ project.title }}
+{{#each requirements | reject spec_id starts_with \"IMPL-\" | group_by feature as group}}
+## {{ group.key }} ({{ group.items | count }})
+  {{#each group.items as req}}
+- {{ req.label }}{{#if req.parent}} < {{ req.parent.label }}{{/if}}
+  {{/each}}
+{{/each}}
+";
+        assert_eq!(
+            render(source),
+            "# Shop\n## Auth (2)\n- FR-001\n- FR-002 < FR-001\n## Uncategorized (1)\n- FR-003\n"
+        );
+    }
+
+    #[test]
+    fn test_hierarchy_lookups_and_conditions() {
+        let source = "\
+{{#each requirements | where feature == \"Auth\" | hierarchy as req}}
+{{ \"#\" | repeat req.depth }} {{ req.number }} {{ req.title }}{{#if loop.last}}.{{/if}}
+{{/each}}
+{{#with requirements | find spec_id == \"FR-002\" as req}}
+{{#each req.fields as f}}{{ f.label }}: {{ f.value }}{{/each}}
+{{#if req.type == \"Task\" or not req.children}}leaf{{else if req.status != \"Draft\"}}open{{else}}?{{/if}}
+{{/with}}
+{{#each requirements | where archived as req}}{{else}}none archived{{/each}}
+";
+        assert_eq!(
+            render(source),
+            "# 1 Login\n## 1.1 Lockout.\nFiles Changed: src/auth.rs\nleaf\nnone archived\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let err = ExportTemplate::parse("t", "a\n{{#if x}}\nb\n").unwrap_err();
+        assert!(format!("{:#}", err).contains("line 2: {{#if}} is never closed"));
+        let err = ExportTemplate::parse("t", "{{#each x}}{{/if}}").unwrap_err();
+        assert!(format!("{:#}", err).contains("expected {{/each}}, found {{/if}}"));
+        let err = ExportTemplate::parse("t", "\n\n{{ x | bogus }}").unwrap_err();
+        assert!(format!("{:#}", err).contains("line 3: unknown filter 'bogus'"));
+    }
+
+    #[test]
+    fn test_builtin_spec_matches_layout() {
+        let out = ExportTemplate::builtin("spec")
+            .unwrap()
+            .render(&store(), None)
+            .unwrap();
+        assert!(out.starts_with("# Shop\n\n## Functional Requirements\n\n### FR-001 - Login\n"));
+        assert!(out.contains("**Parent:** FR-001 - Login\n"));
+        assert!(!out.contains("IMPL-001"));
+
+        let out = ExportTemplate::builtin("impl")
+            .unwrap()
+            .render(&store(), None)
+            .unwrap();
+        assert!(out.starts_with("# Shop - Implementation Records\n"));
+        assert!(out.contains("## IMPL-001 - Session store\n"));
+        assert!(!out.contains("FR-002"));
+    }
+}
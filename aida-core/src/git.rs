@@ -0,0 +1,312 @@
+//! Git commit linking
+//!
+//! Scans the local repository log for commit messages that mention known
+//! requirement spec IDs (e.g. `FR-0042`) or requirement UUIDs, and records
+//! those commits on each requirement as implementation links. The last
+//! processed commit is remembered on the store so later syncs only read
+//! new history.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use uuid::Uuid;
+
+use crate::models::{Requirement, RequirementStatus, RequirementsStore};
+
+/// Field and record separators used in the `git log` format string
+const FIELD_SEP: char = '\u{1f}';
+const RECORD_SEP: char = '\u{1e}';
+
+/// A commit linked to a requirement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommitLink {
+    /// Full commit hash
+    pub sha: String,
+
+    /// Commit author name
+    pub author: String,
+
+    /// Author date
+    pub date: DateTime<Utc>,
+
+    /// First line of the commit message
+    pub subject: String,
+}
+
+impl CommitLink {
+    /// Abbreviated hash for display
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(8)]
+    }
+}
+
+/// Where the last `git sync` stopped
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GitSyncState {
+    /// Hash of the last processed commit (HEAD at sync time)
+    pub last_commit: String,
+
+    /// When the sync ran
+    pub synced_at: DateTime<Utc>,
+}
+
+/// A commit read from the repository log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitCommit {
+    pub sha: String,
+    pub author: String,
+    pub date: DateTime<Utc>,
+    pub subject: String,
+    pub body: String,
+}
+
+impl GitCommit {
+    /// Converts to the link stored on a requirement
+    pub fn to_link(&self) -> CommitLink {
+        CommitLink {
+            sha: self.sha.clone(),
+            author: self.author.clone(),
+            date: self.date,
+            subject: self.subject.clone(),
+        }
+    }
+}
+
+/// Outcome of linking commits to requirements
+#[derive(Debug, Clone, Default)]
+pub struct GitSyncResult {
+    /// Number of commits read from the log
+    pub commits_scanned: usize,
+
+    /// Newly created links (requirement, commit)
+    pub linked: Vec<(Uuid, CommitLink)>,
+}
+
+/// Runs git in `repo_dir` and returns stdout
+fn git(repo_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_dir)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns the hash of HEAD, or None if the repository has no commits
+pub fn head_commit(repo_dir: &Path) -> Result<Option<String>> {
+    git(repo_dir, &["rev-parse", "--git-dir"])
+        .with_context(|| format!("{} is not a git repository", repo_dir.display()))?;
+    Ok(git(repo_dir, &["rev-parse", "--verify", "-q", "HEAD"])
+        .ok()
+        .map(|s| s.trim().to_string()))
+}
+
+/// Reads commits reachable from HEAD, oldest first.
+///
+/// If `since` is given and is an ancestor of HEAD, only commits after it are
+/// returned; otherwise (e.g. after a rebase) the full history is read.
+pub fn read_log(repo_dir: &Path, since: Option<&str>) -> Result<Vec<GitCommit>> {
+    if head_commit(repo_dir)?.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let format = format!(
+        "--format=%H{0}%an{0}%aI{0}%s{0}%b{1}",
+        FIELD_SEP, RECORD_SEP
+    );
+    let range = since
+        .filter(|sha| git(repo_dir, &["merge-base", "--is-ancestor", sha, "HEAD"]).is_ok())
+        .map(|sha| format!("{}..HEAD", sha))
+        .unwrap_or_else(|| "HEAD".to_string());
+
+    let log = git(repo_dir, &["log", "--reverse", &format, &range])?;
+    Ok(parse_log(&log))
+}
+
+/// Parses the output of `git log` in the format used by [`read_log`]
+fn parse_log(log: &str) -> Vec<GitCommit> {
+    log.split(RECORD_SEP)
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(5, FIELD_SEP);
+            let sha = fields.next()?.trim().to_string();
+            let author = fields.next()?.to_string();
+            let date = DateTime::parse_from_rfc3339(fields.next()?.trim()).ok()?;
+            let subject = fields.next()?.to_string();
+            let body = fields.next().unwrap_or("").trim().to_string();
+            if sha.is_empty() {
+                return None;
+            }
+            Some(GitCommit {
+                sha,
+                author,
+                date: date.with_timezone(&Utc),
+                subject,
+                body,
+            })
+        })
+        .collect()
+}
+
+impl RequirementsStore {
+    /// Links commits whose messages mention a spec ID or requirement UUID.
+    ///
+    /// Commits already linked to a requirement are skipped. The sync state is
+    /// advanced to the last commit in `commits`.
+    pub fn link_commits(&mut self, commits: &[GitCommit]) -> GitSyncResult {
+        let mut by_spec_id: HashMap<String, Uuid> = HashMap::new();
+        for req in &self.requirements {
+            if let Some(spec_id) = &req.spec_id {
+                by_spec_id.insert(spec_id.to_uppercase(), req.id);
+            }
+        }
+
+        let mut result = GitSyncResult {
+            commits_scanned: commits.len(),
+            ..Default::default()
+        };
+
+        for commit in commits {
+            let message = format!("{}\n{}", commit.subject, commit.body);
+            let mut targets: Vec<Uuid> = Vec::new();
+            for token in message.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-')) {
+                let token = token.trim_matches('-');
+                if token.is_empty() {
+                    continue;
+                }
+                let target = by_spec_id.get(&token.to_uppercase()).copied().or_else(|| {
+                    Uuid::parse_str(token)
+                        .ok()
+                        .filter(|id| self.requirements.iter().any(|r| r.id == *id))
+                });
+                if let Some(id) = target {
+                    if !targets.contains(&id) {
+                        targets.push(id);
+                    }
+                }
+            }
+
+            for id in targets {
+                let Some(req) = self.requirements.iter_mut().find(|r| r.id == id) else {
+                    continue;
+                };
+                if req.commits.iter().any(|c| c.sha == commit.sha) {
+                    continue;
+                }
+                let link = commit.to_link();
+                req.commits.push(link.clone());
+                result.linked.push((id, link));
+            }
+        }
+
+        if let Some(last) = commits.last() {
+            self.git_sync = Some(GitSyncState {
+                last_commit: last.sha.clone(),
+                synced_at: Utc::now(),
+            });
+        }
+
+        result
+    }
+
+    /// Reads new commits from the repository at `repo_dir` and links them.
+    /// With `full`, the whole history is rescanned.
+    pub fn sync_git_commits(&mut self, repo_dir: &Path, full: bool) -> Result<GitSyncResult> {
+        let since = if full {
+            None
+        } else {
+            self.git_sync.as_ref().map(|s| s.last_commit.clone())
+        };
+        let commits = read_log(repo_dir, since.as_deref())?;
+        Ok(self.link_commits(&commits))
+    }
+
+    /// Completed, non-archived requirements that have no linked commits
+    pub fn completed_without_commits(&self) -> Vec<&Requirement> {
+        self.requirements
+            .iter()
+            .filter(|r| {
+                !r.archived && r.status == RequirementStatus::Completed && r.commits.is_empty()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(sha: &str, subject: &str, body: &str) -> GitCommit {
+        GitCommit {
+            sha: sha.to_string(),
+            author: "alice".to_string(),
+            date: Utc::now(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let log = format!(
+            "abc123{0}Alice{0}2024-01-02T10:00:00+01:00{0}Fix FR-001{0}Body line{1}\n\
+             def456{0}Bob{0}2024-01-03T10:00:00Z{0}Second{0}{1}\n",
+            FIELD_SEP, RECORD_SEP
+        );
+        let commits = parse_log(&log);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, "abc123");
+        assert_eq!(commits[0].body, "Body line");
+        assert_eq!(commits[0].date.to_rfc3339(), "2024-01-02T09:00:00+00:00");
+        assert_eq!(commits[1].author, "Bob");
+        assert_eq!(commits[1].body, "");
+    }
+
+    #[test]
+    fn test_link_commits_by_spec_id_and_uuid() {
+        let mut store = RequirementsStore::new();
+        let mut a = Requirement::new("A".into(), String::new());
+        a.spec_id = Some("FR-001".into());
+        let mut b = Requirement::new("B".into(), String::new());
+        b.spec_id = Some("FR-0010".into());
+        b.status = RequirementStatus::Completed;
+        let b_id = b.id;
+        let a_id = a.id;
+        store.requirements.push(a);
+        store.requirements.push(b);
+
+        let commits = vec![
+            commit("1111", "Implement fr-001 login", ""),
+            commit("2222", "Refactor", &format!("Relates to {}.", b_id)),
+            commit("3333", "Unrelated FR-00100 change", ""),
+        ];
+        let result = store.link_commits(&commits);
+        assert_eq!(result.commits_scanned, 3);
+        assert_eq!(result.linked.len(), 2);
+        assert_eq!(
+            store.get_requirement_by_id(&a_id).unwrap().commits[0].sha,
+            "1111"
+        );
+        assert_eq!(
+            store.get_requirement_by_id(&b_id).unwrap().commits[0].sha,
+            "2222"
+        );
+        assert_eq!(store.git_sync.as_ref().unwrap().last_commit, "3333");
+        assert!(store.completed_without_commits().is_empty());
+
+        // Re-linking the same commits creates no duplicates
+        let again = store.link_commits(&commits);
+        assert!(again.linked.is_empty());
+        assert_eq!(store.get_requirement_by_id(&a_id).unwrap().commits.len(), 1);
+    }
+}
@@ -0,0 +1,449 @@
+//! Import of JUnit/xUnit XML test results
+//!
+//! Each `<testcase>` in the report is mapped to requirements using:
+//! - `trace:<SPEC-ID>` markers in the test name, captured output or
+//!   `<property name="trace" value="..."/>` elements
+//! - SPEC-IDs embedded in the test or class name (e.g. `test_fr_001_login`)
+//! - an existing test case whose `automated_test` matches the test name
+//!
+//! Mapped tests are recorded as a test run; a test case is created for each
+//! newly mapped automated test so later runs roll up the same way.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::models::RequirementsStore;
+use crate::test_management::{TestCase, TestOutcome, TestRun};
+
+/// Property names whose values are treated as requirement references
+const TRACE_PROPERTIES: &[&str] = &["trace", "requirement", "requirements", "verifies"];
+
+/// One `<testcase>` from a JUnit report
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JUnitCase {
+    pub name: String,
+    pub classname: String,
+    pub time: Option<f64>,
+    pub outcome: TestOutcome,
+    /// Failure, error or skip message
+    pub message: Option<String>,
+    /// Captured `<system-out>` / `<system-err>` text
+    pub output: String,
+    /// `<property>` name/value pairs
+    pub properties: Vec<(String, String)>,
+}
+
+impl JUnitCase {
+    /// Fully qualified test name (`classname.name`)
+    pub fn full_name(&self) -> String {
+        if self.classname.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.classname, self.name)
+        }
+    }
+}
+
+/// A parsed JUnit/xUnit XML report
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JUnitReport {
+    pub cases: Vec<JUnitCase>,
+    /// Earliest `<testsuite timestamp="...">`, if any
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl JUnitReport {
+    /// Loads and parses a report file
+    pub fn load(path: &Path) -> Result<Self> {
+        let xml = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&xml).with_context(|| format!("Failed to parse JUnit XML {}", path.display()))
+    }
+
+    /// Parses a report from XML text (`<testsuites>` or a single `<testsuite>`)
+    pub fn parse(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut report = Self::default();
+        let mut current: Option<JUnitCase> = None;
+        let mut in_output = false;
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => match e.name().as_ref() {
+                    b"testsuite" => report.note_timestamp(&e)?,
+                    b"testcase" => current = Some(Self::start_case(&e)?),
+                    b"system-out" | b"system-err" => in_output = true,
+                    _ => Self::apply_element(current.as_mut(), &e)?,
+                },
+                Event::Empty(e) => match e.name().as_ref() {
+                    b"testsuite" => report.note_timestamp(&e)?,
+                    b"testcase" => report.cases.push(Self::start_case(&e)?),
+                    _ => Self::apply_element(current.as_mut(), &e)?,
+                },
+                Event::End(e) => match e.name().as_ref() {
+                    b"testcase" => report.cases.extend(current.take()),
+                    b"system-out" | b"system-err" => in_output = false,
+                    _ => {}
+                },
+                Event::Text(text) => {
+                    if let (true, Some(case)) = (in_output, current.as_mut()) {
+                        case.output.push_str(&text.unescape()?);
+                        case.output.push('\n');
+                    }
+                }
+                Event::CData(data) => {
+                    if let (true, Some(case)) = (in_output, current.as_mut()) {
+                        case.output.push_str(&String::from_utf8_lossy(&data));
+                        case.output.push('\n');
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn attribute(e: &BytesStart, name: &str) -> Result<Option<String>> {
+        for attr in e.attributes() {
+            let attr = attr?;
+            if attr.key.as_ref() == name.as_bytes() {
+                return Ok(Some(attr.unescape_value()?.into_owned()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn start_case(e: &BytesStart) -> Result<JUnitCase> {
+        Ok(JUnitCase {
+            name: Self::attribute(e, "name")?.unwrap_or_default(),
+            classname: Self::attribute(e, "classname")?.unwrap_or_default(),
+            time: Self::attribute(e, "time")?.and_then(|t| t.parse().ok()),
+            outcome: TestOutcome::Passed,
+            ..Default::default()
+        })
+    }
+
+    /// Applies a child element of `<testcase>` (failure, error, skipped, property)
+    fn apply_element(case: Option<&mut JUnitCase>, e: &BytesStart) -> Result<()> {
+        let Some(case) = case else {
+            return Ok(());
+        };
+        match e.name().as_ref() {
+            b"failure" | b"error" => {
+                case.outcome = TestOutcome::Failed;
+                case.message = Self::attribute(e, "message")?;
+            }
+            b"skipped" => {
+                case.outcome = TestOutcome::Skipped;
+                case.message = Self::attribute(e, "message")?;
+            }
+            b"property" => {
+                if let (Some(name), Some(value)) =
+                    (Self::attribute(e, "name")?, Self::attribute(e, "value")?)
+                {
+                    case.properties.push((name, value));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn note_timestamp(&mut self, e: &BytesStart) -> Result<()> {
+        if let Some(ts) = Self::attribute(e, "timestamp")?.and_then(|ts| parse_timestamp(&ts)) {
+            self.timestamp = Some(self.timestamp.map_or(ts, |current| current.min(ts)));
+        }
+        Ok(())
+    }
+}
+
+/// Parses RFC 3339 or the zone-less ISO 8601 form JUnit commonly uses (assumed UTC)
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                .ok()
+                .map(|dt| dt.and_utc())
+        })
+}
+
+/// Extracts the IDs following `trace:` markers anywhere in a test name or output
+pub fn trace_markers(text: &str) -> Vec<String> {
+    crate::trace::trace_ids(text, true)
+}
+
+/// Uppercases and replaces separators so `test_fr_001` and `FR-001` compare equal
+fn normalize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Outcome of importing a report
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultsImport {
+    /// Spec ID of the recorded run (None if no test mapped to anything)
+    pub run_spec_id: Option<String>,
+    /// Recorded results as (test case spec ID, outcome)
+    pub recorded: Vec<(String, TestOutcome)>,
+    /// Test cases created for newly mapped automated tests
+    pub created_cases: Vec<String>,
+    /// Tests that map to no requirement or test case
+    pub unmapped_tests: Vec<String>,
+    /// `trace:` references to unknown SPEC-IDs, as (test name, reference)
+    pub unknown_references: Vec<(String, String)>,
+    /// Requirements with test cases that got no result in this run
+    pub missing_results: Vec<Uuid>,
+}
+
+impl RequirementsStore {
+    /// Requirements referenced by a JUnit test, plus unknown `trace:` references
+    fn requirements_for_junit_case(&self, case: &JUnitCase) -> (Vec<Uuid>, Vec<String>) {
+        let mut ids = Vec::new();
+        let mut unknown = Vec::new();
+
+        // Explicit trace markers and properties
+        let mut references = trace_markers(&case.full_name());
+        references.extend(trace_markers(&case.output));
+        for (name, value) in &case.properties {
+            if TRACE_PROPERTIES
+                .iter()
+                .any(|p| name.eq_ignore_ascii_case(p))
+            {
+                references.extend(value.split(',').map(|v| v.trim().to_string()));
+            }
+        }
+        for reference in references.into_iter().filter(|r| !r.is_empty()) {
+            let wanted = normalize(&reference);
+            match self
+                .requirements
+                .iter()
+                .find(|r| r.spec_id.as_deref().is_some_and(|s| normalize(s) == wanted))
+            {
+                Some(req) => ids.push(req.id),
+                None => unknown.push(reference),
+            }
+        }
+
+        // SPEC-IDs embedded in the test name, preferring the longest match
+        // (a name containing AUTH-FR-001 should not also map to FR-001)
+        let haystack = format!("-{}-", normalize(&case.full_name()));
+        let mut embedded: Vec<(String, Uuid)> = self
+            .requirements
+            .iter()
+            .filter_map(|r| {
+                let needle = normalize(r.spec_id.as_deref()?);
+                haystack
+                    .contains(&format!("-{}-", needle))
+                    .then_some((needle, r.id))
+            })
+            .collect();
+        embedded.sort_by_key(|(needle, _)| std::cmp::Reverse(needle.len()));
+        let mut kept: Vec<String> = Vec::new();
+        for (needle, id) in embedded {
+            if kept
+                .iter()
+                .any(|k| format!("-{}-", k).contains(&format!("-{}-", needle)))
+            {
+                continue;
+            }
+            kept.push(needle);
+            ids.push(id);
+        }
+
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+        (ids, unknown)
+    }
+
+    /// Records the results of a JUnit report as a test run
+    pub fn import_junit_results(
+        &mut self,
+        report: &JUnitReport,
+        mut run: TestRun,
+    ) -> ResultsImport {
+        let mut import = ResultsImport::default();
+        if let Some(ts) = report.timestamp {
+            run.executed_at = ts;
+        }
+
+        for junit_case in &report.cases {
+            let full_name = junit_case.full_name();
+            let (req_ids, unknown) = self.requirements_for_junit_case(junit_case);
+            import
+                .unknown_references
+                .extend(unknown.into_iter().map(|r| (full_name.clone(), r)));
+
+            let case_id = match self
+                .find_test_case_by_automated_test(&full_name)
+                .map(|tc| tc.id)
+            {
+                Some(id) => {
+                    if let Some(tc) = self.test_cases.iter_mut().find(|tc| tc.id == id) {
+                        for req_id in &req_ids {
+                            if !tc.verifies.contains(req_id) {
+                                tc.verifies.push(*req_id);
+                            }
+                        }
+                    }
+                    id
+                }
+                None if !req_ids.is_empty() => {
+                    let mut tc = TestCase::new(junit_case.name.clone())
+                        .with_automated_test(full_name.clone());
+                    tc.verifies = req_ids;
+                    tc.created_by = run.executed_by.clone();
+                    let id = tc.id;
+                    import.created_cases.push(self.add_test_case(tc));
+                    id
+                }
+                None => {
+                    import.unmapped_tests.push(full_name);
+                    continue;
+                }
+            };
+
+            let result = run.record(case_id, junit_case.outcome);
+            result.notes = junit_case.message.clone();
+            result.duration_secs = junit_case.time;
+            if let Some(tc) = self.test_cases.iter().find(|tc| tc.id == case_id) {
+                import
+                    .recorded
+                    .push((tc.spec_id.clone(), junit_case.outcome));
+            }
+        }
+
+        // Verified requirements none of whose test cases ran
+        import.missing_results = self
+            .requirements
+            .iter()
+            .filter(|req| !req.archived)
+            .filter(|req| {
+                let cases = self.test_cases_for_requirement(&req.id);
+                !cases.is_empty() && cases.iter().all(|tc| run.result_for(&tc.id).is_none())
+            })
+            .map(|req| req.id)
+            .collect();
+
+        if !run.results.is_empty() {
+            import.run_spec_id = Some(self.add_test_run(run));
+        }
+        import
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Requirement;
+    use crate::test_management::VerificationStatus;
+
+    const REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="auth" timestamp="2024-03-01T12:00:00">
+    <testcase classname="auth::login" name="test_fr_001_valid_login" time="0.12"/>
+    <testcase classname="auth::login" name="rejects_bad_password" time="0.05">
+      <failure message="expected 401, got 500"/>
+      <system-out>trace:FR-001, FR-002</system-out>
+    </testcase>
+    <testcase classname="auth::session" name="expires">
+      <properties><property name="trace" value="FR-999"/></properties>
+      <skipped/>
+    </testcase>
+    <testcase classname="misc" name="unrelated"/>
+  </testsuite>
+</testsuites>"#;
+
+    fn store() -> RequirementsStore {
+        let mut store = RequirementsStore::new();
+        for (spec_id, title) in [
+            ("FR-001", "Login"),
+            ("FR-002", "Lockout"),
+            ("FR-003", "Logout"),
+        ] {
+            let mut req = Requirement::new(title.to_string(), String::new());
+            req.spec_id = Some(spec_id.to_string());
+            store.add_requirement(req);
+        }
+        store
+    }
+
+    #[test]
+    fn test_parse_junit_report() {
+        let report = JUnitReport::parse(REPORT).unwrap();
+        assert_eq!(report.cases.len(), 4);
+        assert_eq!(
+            report.cases[0].full_name(),
+            "auth::login.test_fr_001_valid_login"
+        );
+        assert_eq!(report.cases[0].time, Some(0.12));
+        assert_eq!(report.cases[1].outcome, TestOutcome::Failed);
+        assert_eq!(
+            report.cases[1].message.as_deref(),
+            Some("expected 401, got 500")
+        );
+        assert_eq!(report.cases[2].outcome, TestOutcome::Skipped);
+        assert_eq!(
+            report.timestamp.unwrap().to_rfc3339(),
+            "2024-03-01T12:00:00+00:00"
+        );
+        assert_eq!(
+            trace_markers("// trace:FR-001, FR-002 | ai:claude"),
+            vec!["FR-001", "FR-002"]
+        );
+    }
+
+    #[test]
+    fn test_import_maps_results_to_requirements() {
+        let mut store = store();
+        let fr3 = store.requirements[2].id;
+        store.add_test_case(TestCase::new("Manual logout").with_verifies(fr3));
+
+        let report = JUnitReport::parse(REPORT).unwrap();
+        let import = store.import_junit_results(&report, TestRun::new("CI"));
+
+        assert_eq!(import.run_spec_id.as_deref(), Some("RUN-001"));
+        assert_eq!(import.created_cases, vec!["TC-002", "TC-003"]);
+        assert_eq!(
+            import.unmapped_tests,
+            vec!["auth::session.expires", "misc.unrelated"]
+        );
+        assert_eq!(
+            import.unknown_references,
+            vec![("auth::session.expires".to_string(), "FR-999".to_string())]
+        );
+        assert_eq!(import.missing_results, vec![fr3]);
+
+        let fr1 = store.requirements[0].id;
+        let fr2 = store.requirements[1].id;
+        assert_eq!(
+            store.verification_summary(&fr1).status(),
+            VerificationStatus::Failing
+        );
+        assert_eq!(store.verification_summary(&fr2).failed, 1);
+
+        // Re-importing reuses the created test cases
+        let import = store.import_junit_results(&report, TestRun::new("CI"));
+        assert!(import.created_cases.is_empty());
+        assert_eq!(store.test_cases.len(), 3);
+        assert_eq!(store.test_runs.len(), 2);
+    }
+}
@@ -1,20 +1,33 @@
 pub mod ai;
+pub mod bulk;
 pub mod db;
+pub mod events;
 pub mod export;
+pub mod git;
+pub mod junit;
+pub mod mcp;
 pub mod models;
 pub mod project;
 pub mod registry;
 pub mod scaffolding;
+pub mod scripting;
+pub mod server;
 pub mod storage;
+pub mod templates;
+pub mod test_management;
+pub mod trace;
 
 // Re-export commonly used types
 pub use ai::{
-    AiClient, AiMode, BackgroundEvaluator, EvaluationResponse, EvaluationResult, EvaluatorConfig,
+    AiClient, AiMode, ApiConfig, BackgroundEvaluator, EvaluationResponse, EvaluationResult, EvaluatorConfig,
     EvaluatorStatus, IssueReport, StoredAiEvaluation, SuggestedImprovement,
 };
+pub use bulk::{BulkUpdateResult, RequirementPatch, RequirementQuery};
 pub use models::{
     default_reaction_definitions,
     default_type_definitions,
+    // Acceptance criteria types
+    AcceptanceCriterion,
     // AI prompt configuration types
     AiActionPromptConfig,
     AiPromptConfig,
@@ -27,12 +40,14 @@ pub use models::{
     Comment,
     // Comment reaction types
     CommentReaction,
+    CriterionStatus,
     CustomFieldDefinition,
     // Custom type definition types
     CustomFieldType,
     CustomTypeDefinition,
     FeatureDefinition,
     FieldChange,
+    GivenWhenThen,
     HistoryEntry,
     IdConfigValidation,
     IdConfiguration,
@@ -63,12 +78,26 @@ pub use models::{
     META_PREFIX_VIEW,
     META_PREFIX_TEAM,
 };
+pub use events::{EventKind, HookConfig, HookSink, StoreEvent};
+pub use scripting::{ScriptConfig, ScriptEngine, ScriptKind, ScriptRejection};
+pub use git::{CommitLink, GitCommit, GitSyncResult, GitSyncState};
+pub use junit::{JUnitCase, JUnitReport, ResultsImport};
+pub use mcp::McpServer;
 pub use project::determine_requirements_path;
 pub use registry::{get_config_dir, get_registry_path, get_templates_dir, Registry};
 pub use scaffolding::{
     ProjectType, ScaffoldArtifact, ScaffoldConfig, ScaffoldError, ScaffoldPreview, Scaffolder,
 };
+pub use server::{ApiError, ApiRequest, ApiResponse, ApiServer, NewRequirement};
 pub use storage::{
     AddResult, ConflictInfo, ConflictResolution, EditLock, FieldConflict, LockFileInfo, SaveResult,
-    SessionInfo, Storage, StorageError,
+    SessionHeartbeat, SessionInfo, Storage, StorageError, HEARTBEAT_INTERVAL_SECS,
+};
+pub use templates::{TemplateContext, TemplateInstance, TypeTemplate, TypeTemplateLibrary};
+pub use test_management::{
+    CoverageEntry, CoverageReport, TestCase, TestOutcome, TestResult, TestRun, TestStep,
+    VerificationStatus, VerificationSummary, META_PREFIX_TEST_CASE, META_PREFIX_TEST_RUN,
+};
+pub use trace::{
+    AiConfidence, ConfidenceBreakdown, SpecIdMention, TraceIndex, TraceMarker, TraceReport,
 };
@@ -0,0 +1,750 @@
+//! Model Context Protocol server (`aida mcp`)
+//!
+//! Speaks newline-delimited JSON-RPC 2.0 over stdin/stdout so coding agents
+//! can search, read and update the requirements store while they work. Tools
+//! cover search, reading requirements and their children, creating and
+//! updating requirements, linking, commenting and recording implementation
+//! (IMPL) records; features and type definitions are exposed as resources.
+//!
+//! Every write goes through `update_atomically`, so the server can run
+//! alongside the CLI and GUI on the same file.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::bulk::{RequirementPatch, RequirementQuery};
+use crate::db::{backend_type_for_path, BackendType, DatabaseBackend, SqliteBackend, YamlBackend};
+use crate::models::{
+    Comment, RelationshipType, Requirement, RequirementStatus, RequirementType, RequirementsStore,
+};
+use crate::server::NewRequirement;
+
+/// MCP protocol revision implemented by this server
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Resource URIs
+const FEATURES_URI: &str = "aida://features";
+const TYPES_URI: &str = "aida://types";
+
+/// A JSON-RPC error returned to the client
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Arguments of `search_requirements`
+#[derive(Debug, Default, Deserialize)]
+struct SearchArgs {
+    /// `key=value` terms, as accepted by [`RequirementQuery::parse`]
+    #[serde(default)]
+    query: String,
+    text: Option<String>,
+    #[serde(default)]
+    include_archived: bool,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdArgs {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateArgs {
+    id: String,
+    #[serde(flatten)]
+    patch: RequirementPatch,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinkArgs {
+    from: String,
+    to: String,
+    #[serde(rename = "type")]
+    rel_type: String,
+    #[serde(default)]
+    bidirectional: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentArgs {
+    id: String,
+    content: String,
+    parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChildrenArgs {
+    id: String,
+    #[serde(default)]
+    recursive: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImplementationArgs {
+    id: String,
+    summary: String,
+    #[serde(default)]
+    files_changed: Vec<String>,
+    title: Option<String>,
+}
+
+/// MCP server over any storage backend
+pub struct McpServer<B: DatabaseBackend> {
+    backend: B,
+    author: String,
+}
+
+impl<B: DatabaseBackend> McpServer<B> {
+    /// Creates a server; writes are attributed to `$USER` unless overridden
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            author: std::env::var("USER").unwrap_or_else(|_| String::from("mcp")),
+        }
+    }
+
+    /// Sets the author recorded on writes
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    /// Reads JSON-RPC messages from stdin and writes responses to stdout until EOF
+    pub fn run_stdio(&self) -> Result<()> {
+        let stdin = std::io::stdin();
+        let mut stdout = std::io::stdout().lock();
+        for line in stdin.lock().lines() {
+            let line = line.context("Failed to read from stdin")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle_message(&message),
+                Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+            };
+            if let Some(response) = response {
+                writeln!(stdout, "{}", response)?;
+                stdout.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles one message; returns the response, or None for notifications
+    pub fn handle_message(&self, message: &Value) -> Option<Value> {
+        let id = message.get("id").cloned()?;
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return Some(error_response(id, INVALID_REQUEST, "Missing method"));
+        };
+        let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {}, "resources": {} },
+                "serverInfo": { "name": "aida", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params),
+            "resources/list" => Ok(json!({ "resources": resource_definitions() })),
+            "resources/read" => self.read_resource(&params),
+            other => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Method not found: {}", other),
+            }),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, e.code, &e.message),
+        })
+    }
+
+    fn call_tool(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::invalid_params("Missing tool name"))?;
+        let args = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+
+        let outcome = match name {
+            "search_requirements" => self.search_requirements(args),
+            "get_requirement" => self.get_requirement(args),
+            "create_requirement" => self.create_requirement(args),
+            "update_requirement" => self.update_requirement(args),
+            "link_requirements" => self.link_requirements(args),
+            "add_comment" => self.add_comment(args),
+            "list_children" => self.list_children(args),
+            "record_implementation" => self.record_implementation(args),
+            other => return Err(RpcError::invalid_params(format!("Unknown tool: {}", other))),
+        };
+
+        // Tool failures are reported in the result so the agent can react to them
+        Ok(match outcome {
+            Ok(value) => json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&value).unwrap_or_default(),
+                }],
+            }),
+            Err(e) => json!({
+                "content": [{ "type": "text", "text": format!("{:#}", e) }],
+                "isError": true,
+            }),
+        })
+    }
+
+    fn read_resource(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let uri = params
+            .get("uri")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::invalid_params("Missing resource uri"))?;
+        let store = self.backend.load().map_err(|e| RpcError {
+            code: -32603,
+            message: e.to_string(),
+        })?;
+        let contents = match uri {
+            FEATURES_URI => serde_json::to_string_pretty(&store.features),
+            TYPES_URI => serde_json::to_string_pretty(&store.type_definitions),
+            other => {
+                return Err(RpcError::invalid_params(format!(
+                    "Unknown resource: {}",
+                    other
+                )))
+            }
+        }
+        .unwrap_or_default();
+
+        Ok(json!({
+            "contents": [{ "uri": uri, "mimeType": "application/json", "text": contents }],
+        }))
+    }
+
+    /// Applies `f` inside `update_atomically`; on error the store is left unchanged
+    fn write<T>(&self, f: impl FnOnce(&mut RequirementsStore) -> Result<T>) -> Result<T> {
+        let mut outcome = None;
+        self.backend.update_atomically(|store| {
+            let snapshot = store.clone();
+            let result = f(store);
+            if result.is_err() {
+                *store = snapshot;
+            }
+            outcome = Some(result);
+        })?;
+        outcome.unwrap_or_else(|| Err(anyhow!("Update was not applied")))
+    }
+
+    fn search_requirements(&self, args: Value) -> Result<Value> {
+        let args: SearchArgs = parse_args(args)?;
+        let mut query = RequirementQuery::parse(&args.query)?;
+        if let Some(text) = &args.text {
+            query.set("text", text)?;
+        }
+        query.include_archived |= args.include_archived;
+
+        let store = self.backend.load()?;
+        let matches: Vec<Value> = store
+            .requirements
+            .iter()
+            .filter(|r| query.matches(r))
+            .take(args.limit.unwrap_or(50))
+            .map(summary)
+            .collect();
+        Ok(json!(matches))
+    }
+
+    fn get_requirement(&self, args: Value) -> Result<Value> {
+        let args: IdArgs = parse_args(args)?;
+        let store = self.backend.load()?;
+        let id = resolve(&store, &args.id)?;
+        let requirement = store
+            .get_requirement_by_id(&id)
+            .expect("resolved id exists");
+        let children: Vec<Value> = store.children_of(&id).into_iter().map(summary).collect();
+        Ok(json!({ "requirement": requirement, "children": children }))
+    }
+
+    fn create_requirement(&self, args: Value) -> Result<Value> {
+        let new: NewRequirement = parse_args(args)?;
+        self.write(|store| {
+            let id = new.insert(store, &self.author)?;
+            Ok(json!(store.get_requirement_by_id(&id)))
+        })
+    }
+
+    fn update_requirement(&self, args: Value) -> Result<Value> {
+        let args: UpdateArgs = parse_args(args)?;
+        if args.patch.is_empty() {
+            anyhow::bail!("No fields to update");
+        }
+        self.write(|store| {
+            let id = resolve(store, &args.id)?;
+            let errors = store.validate_bulk_update(&[id], &args.patch);
+            if !errors.is_empty() {
+                anyhow::bail!(errors.join("; "));
+            }
+            store.bulk_update(&[id], &args.patch, &self.author)?;
+            Ok(json!(store.get_requirement_by_id(&id)))
+        })
+    }
+
+    fn link_requirements(&self, args: Value) -> Result<Value> {
+        let args: LinkArgs = parse_args(args)?;
+        self.write(|store| {
+            let from = resolve(store, &args.from)?;
+            let to = resolve(store, &args.to)?;
+            let rel_type = RelationshipType::from_str(&args.rel_type);
+            store.add_relationship_with_creator(
+                &from,
+                rel_type,
+                &to,
+                args.bidirectional,
+                Some(self.author.clone()),
+            )?;
+            Ok(json!(store
+                .get_requirement_by_id(&from)
+                .map(|r| &r.relationships)))
+        })
+    }
+
+    fn add_comment(&self, args: Value) -> Result<Value> {
+        let args: CommentArgs = parse_args(args)?;
+        if args.content.trim().is_empty() {
+            anyhow::bail!("Comment cannot be empty");
+        }
+        self.write(|store| {
+            let id = resolve(store, &args.id)?;
+            let requirement = store
+                .get_requirement_by_id_mut(&id)
+                .expect("resolved id exists");
+            let comment = match args.parent_id {
+                Some(parent_id) => {
+                    let reply = Comment::new_reply(self.author.clone(), args.content, parent_id);
+                    requirement.add_reply(parent_id, reply.clone())?;
+                    reply
+                }
+                None => {
+                    let comment = Comment::new(self.author.clone(), args.content);
+                    requirement.add_comment(comment.clone());
+                    comment
+                }
+            };
+            Ok(json!(comment))
+        })
+    }
+
+    fn list_children(&self, args: Value) -> Result<Value> {
+        let args: ChildrenArgs = parse_args(args)?;
+        let store = self.backend.load()?;
+        let root = resolve(&store, &args.id)?;
+
+        let mut seen = HashSet::from([root]);
+        let mut queue = vec![root];
+        let mut children = Vec::new();
+        while let Some(parent) = queue.pop() {
+            for child in store.children_of(&parent) {
+                if seen.insert(child.id) {
+                    children.push(summary(child));
+                    if args.recursive {
+                        queue.push(child.id);
+                    }
+                }
+            }
+        }
+        Ok(json!(children))
+    }
+
+    fn record_implementation(&self, args: Value) -> Result<Value> {
+        let args: ImplementationArgs = parse_args(args)?;
+        self.write(|store| {
+            let id = resolve(store, &args.id)?;
+            let record_id = store.add_implementation_record(
+                id,
+                &args.summary,
+                &args.files_changed,
+                args.title,
+                &self.author,
+            )?;
+            Ok(json!(store.get_requirement_by_id(&record_id)))
+        })
+    }
+}
+
+impl RequirementsStore {
+    /// Requirements whose Parent relationship points at `parent_id`
+    pub fn children_of(&self, parent_id: &Uuid) -> Vec<&Requirement> {
+        self.requirements
+            .iter()
+            .filter(|r| {
+                r.relationships.iter().any(|rel| {
+                    rel.rel_type == RelationshipType::Parent && rel.target_id == *parent_id
+                })
+            })
+            .collect()
+    }
+
+    /// Records a completed implementation (IMPL) task as a child of `requirement_id`
+    pub fn add_implementation_record(
+        &mut self,
+        requirement_id: Uuid,
+        summary: &str,
+        files_changed: &[String],
+        title: Option<String>,
+        author: &str,
+    ) -> Result<Uuid> {
+        if summary.trim().is_empty() {
+            anyhow::bail!("Implementation summary cannot be empty");
+        }
+        let parent = self
+            .get_requirement_by_id(&requirement_id)
+            .ok_or_else(|| anyhow!("Requirement not found: {}", requirement_id))?;
+        let title = title.unwrap_or_else(|| {
+            format!(
+                "Implement {}",
+                parent.spec_id.as_deref().unwrap_or(&parent.title)
+            )
+        });
+
+        let mut record = Requirement::new(title, String::new());
+        record.req_type = RequirementType::Task;
+        record.status = RequirementStatus::Completed;
+        record.feature = parent.feature.clone();
+        record.created_by = Some(author.to_string());
+        record
+            .set_prefix_override("IMPL")
+            .map_err(anyhow::Error::msg)?;
+        record.custom_fields.insert(
+            "implementation_summary".to_string(),
+            summary.trim().to_string(),
+        );
+        if !files_changed.is_empty() {
+            record
+                .custom_fields
+                .insert("files_changed".to_string(), files_changed.join("\n"));
+        }
+        record.custom_fields.insert(
+            "session_date".to_string(),
+            Utc::now().format("%Y-%m-%d").to_string(),
+        );
+
+        let record_id = record.id;
+        self.add_requirement_with_id(record, None, None);
+        self.add_relationship_with_creator(
+            &record_id,
+            RelationshipType::Parent,
+            &requirement_id,
+            true,
+            Some(author.to_string()),
+        )?;
+        Ok(record_id)
+    }
+}
+
+fn parse_args<T: DeserializeOwned>(args: Value) -> Result<T> {
+    serde_json::from_value(args).context("Invalid tool arguments")
+}
+
+fn resolve(store: &RequirementsStore, id: &str) -> Result<Uuid> {
+    store
+        .resolve_requirement_id(id)
+        .ok_or_else(|| anyhow!("Requirement not found: {}", id))
+}
+
+/// Compact listing entry for a requirement
+fn summary(req: &Requirement) -> Value {
+    json!({
+        "id": req.id,
+        "spec_id": req.spec_id,
+        "title": req.title,
+        "type": req.req_type,
+        "status": req.effective_status(),
+        "priority": req.priority,
+        "feature": req.feature,
+    })
+}
+
+fn resource_definitions() -> Value {
+    json!([
+        {
+            "uri": FEATURES_URI,
+            "name": "Features",
+            "description": "Feature definitions with their ID prefixes",
+            "mimeType": "application/json",
+        },
+        {
+            "uri": TYPES_URI,
+            "name": "Type definitions",
+            "description": "Requirement types with their statuses, priorities and custom fields",
+            "mimeType": "application/json",
+        },
+    ])
+}
+
+fn tool_definitions() -> Value {
+    let id = json!({ "type": "string", "description": "SPEC-ID (e.g. FR-0001) or UUID" });
+    json!([
+        {
+            "name": "search_requirements",
+            "description": "Search requirements. Returns id, spec_id, title, type, status, priority and feature for each match.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Space-separated key=value terms: status, priority, type, feature, owner, tag, prefix, text, archived" },
+                    "text": { "type": "string", "description": "Free text matched against title and description" },
+                    "include_archived": { "type": "boolean" },
+                    "limit": { "type": "integer", "description": "Maximum results (default 50)" },
+                },
+            },
+        },
+        {
+            "name": "get_requirement",
+            "description": "Get a requirement with its description, relationships, comments and children.",
+            "inputSchema": { "type": "object", "properties": { "id": id }, "required": ["id"] },
+        },
+        {
+            "name": "create_requirement",
+            "description": "Create a requirement. Returns it with its assigned SPEC-ID.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "description": { "type": "string" },
+                    "type": { "type": "string", "description": "e.g. Functional, NonFunctional, Story, Task, Bug" },
+                    "status": { "type": "string" },
+                    "priority": { "type": "string" },
+                    "feature": { "type": "string" },
+                    "owner": { "type": "string" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "prefix": { "type": "string", "description": "ID prefix override (A-Z)" },
+                    "parent": { "type": "string", "description": "Parent requirement SPEC-ID or UUID" },
+                },
+                "required": ["title"],
+            },
+        },
+        {
+            "name": "update_requirement",
+            "description": "Update fields of a requirement. Only the given fields change.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": id,
+                    "title": { "type": "string" },
+                    "description": { "type": "string" },
+                    "status": { "type": "string" },
+                    "priority": { "type": "string" },
+                    "owner": { "type": "string" },
+                    "feature": { "type": "string" },
+                    "add_tags": { "type": "array", "items": { "type": "string" } },
+                    "remove_tags": { "type": "array", "items": { "type": "string" } },
+                },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "link_requirements",
+            "description": "Add a relationship from one requirement to another.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "from": id,
+                    "to": id,
+                    "type": { "type": "string", "description": "e.g. parent, child, verifies, references, duplicate" },
+                    "bidirectional": { "type": "boolean", "description": "Also add the inverse relationship" },
+                },
+                "required": ["from", "to", "type"],
+            },
+        },
+        {
+            "name": "add_comment",
+            "description": "Comment on a requirement, or reply to an existing comment.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": id,
+                    "content": { "type": "string" },
+                    "parent_id": { "type": "string", "description": "UUID of the comment to reply to" },
+                },
+                "required": ["id", "content"],
+            },
+        },
+        {
+            "name": "list_children",
+            "description": "List the child requirements of a requirement.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": id,
+                    "recursive": { "type": "boolean", "description": "Include all descendants" },
+                },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "record_implementation",
+            "description": "Record the work done for a requirement as a completed IMPL record linked to it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": id,
+                    "summary": { "type": "string", "description": "What was implemented and how" },
+                    "files_changed": { "type": "array", "items": { "type": "string" } },
+                    "title": { "type": "string", "description": "Defaults to \"Implement <SPEC-ID>\"" },
+                },
+                "required": ["id", "summary"],
+            },
+        },
+    ])
+}
+
+/// Runs the MCP server over stdio on the requirements file at `path`
+pub fn serve_stdio(path: &Path, author: Option<&str>) -> Result<()> {
+    match backend_type_for_path(path) {
+        BackendType::Yaml => serve_backend(YamlBackend::new(path), author),
+        BackendType::Sqlite => serve_backend(SqliteBackend::new(path)?, author),
+    }
+}
+
+fn serve_backend<B: DatabaseBackend>(backend: B, author: Option<&str>) -> Result<()> {
+    let mut server = McpServer::new(backend);
+    if let Some(author) = author {
+        server = server.with_author(author);
+    }
+    server.run_stdio()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn server(dir: &tempfile::TempDir) -> McpServer<YamlBackend> {
+        let backend = YamlBackend::new(dir.path().join("requirements.yaml"));
+        backend.save(&RequirementsStore::new()).unwrap();
+        McpServer::new(backend).with_author("agent")
+    }
+
+    fn call(server: &McpServer<YamlBackend>, tool: &str, arguments: Value) -> (Value, bool) {
+        let response = server
+            .handle_message(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": { "name": tool, "arguments": arguments },
+            }))
+            .unwrap();
+        let result = &response["result"];
+        let text = result["content"][0]["text"].as_str().unwrap();
+        let is_error = result["isError"].as_bool().unwrap_or(false);
+        (
+            serde_json::from_str(text).unwrap_or_else(|_| json!(text)),
+            is_error,
+        )
+    }
+
+    #[test]
+    fn test_protocol_messages() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+
+        let init = server
+            .handle_message(
+                &json!({ "jsonrpc": "2.0", "id": 0, "method": "initialize", "params": {} }),
+            )
+            .unwrap();
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(server
+            .handle_message(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .is_none());
+
+        let tools = server
+            .handle_message(&json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }))
+            .unwrap();
+        assert_eq!(tools["result"]["tools"].as_array().unwrap().len(), 8);
+
+        let unknown = server
+            .handle_message(&json!({ "jsonrpc": "2.0", "id": 2, "method": "bogus" }))
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_tools_create_link_and_record_implementation() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+
+        let (parent, _) = call(
+            &server,
+            "create_requirement",
+            json!({ "title": "Login", "type": "Functional" }),
+        );
+        let parent_spec = parent["spec_id"].as_str().unwrap().to_string();
+        let (child, is_error) = call(
+            &server,
+            "create_requirement",
+            json!({ "title": "Password reset", "parent": parent_spec.to_lowercase() }),
+        );
+        assert!(!is_error);
+        assert_eq!(child["created_by"], "agent");
+
+        let (children, _) = call(&server, "list_children", json!({ "id": parent_spec }));
+        assert_eq!(children.as_array().unwrap().len(), 1);
+
+        let (record, is_error) = call(
+            &server,
+            "record_implementation",
+            json!({ "id": parent_spec, "summary": "Added login form", "files_changed": ["src/login.rs"] }),
+        );
+        assert!(!is_error);
+        assert!(record["spec_id"].as_str().unwrap().starts_with("IMPL-"));
+        assert_eq!(record["custom_fields"]["files_changed"], "src/login.rs");
+
+        let (found, _) = call(&server, "get_requirement", json!({ "id": parent_spec }));
+        assert_eq!(found["children"].as_array().unwrap().len(), 2);
+
+        let (_, is_error) = call(
+            &server,
+            "update_requirement",
+            json!({ "id": parent_spec, "status": "Nope" }),
+        );
+        assert!(is_error);
+        let (message, is_error) = call(&server, "get_requirement", json!({ "id": "FR-9999" }));
+        assert!(is_error);
+        assert!(message.as_str().unwrap().contains("not found"));
+    }
+}
@@ -374,6 +374,20 @@ pub struct CustomTypeDefinition {
     /// They are excluded from status metrics and reports by default
     #[serde(default)]
     pub stateless: bool,
+
+    /// Description template used when creating a requirement of this type.
+    /// Supports placeholders such as `{{user}}`, `{{date}}` and `{{feature}}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_template: Option<String>,
+
+    /// Default custom field values (key = field name), placeholders allowed
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub field_defaults: std::collections::HashMap<String, String>,
+
+    /// Set once a built-in type has its default template and field defaults,
+    /// so templates or defaults a team removed later are not restored
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub defaults_applied: bool,
 }
 
 impl CustomTypeDefinition {
@@ -390,6 +404,9 @@ impl CustomTypeDefinition {
             built_in: false,
             color: None,
             stateless: false,
+            description_template: None,
+            field_defaults: std::collections::HashMap::new(),
+            defaults_applied: false,
         }
     }
 
@@ -406,6 +423,9 @@ impl CustomTypeDefinition {
             built_in: true,
             color: None,
             stateless: false,
+            description_template: None,
+            field_defaults: std::collections::HashMap::new(),
+            defaults_applied: true,
         }
     }
 
@@ -422,6 +442,9 @@ impl CustomTypeDefinition {
             built_in: true,
             color: None,
             stateless: true,
+            description_template: None,
+            field_defaults: std::collections::HashMap::new(),
+            defaults_applied: true,
         }
     }
 
@@ -467,6 +490,18 @@ impl CustomTypeDefinition {
         self
     }
 
+    /// Sets the description template
+    pub fn with_description_template(mut self, template: impl Into<String>) -> Self {
+        self.description_template = Some(template.into());
+        self
+    }
+
+    /// Sets a default value for a custom field
+    pub fn with_field_default(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.field_defaults.insert(field.into(), value.into());
+        self
+    }
+
     /// Gets the statuses for this type, falling back to defaults if none specified
     pub fn get_statuses(&self) -> Vec<String> {
         if self.statuses.is_empty() {
@@ -497,6 +532,33 @@ impl CustomTypeDefinition {
     }
 }
 
+/// Default description template for stories
+const STORY_DESCRIPTION_TEMPLATE: &str = "\
+As a <role>, I want <capability> so that <benefit>.
+
+## Acceptance Criteria
+
+- [ ] 
+
+## Notes
+";
+
+/// Default description template for bugs
+const BUG_DESCRIPTION_TEMPLATE: &str = "\
+## Steps to Reproduce
+
+1. 
+
+## Expected Behavior
+
+## Actual Behavior
+
+## Environment
+
+- Feature: {{feature}}
+- Reported by {{user}} on {{date}}
+";
+
 /// Returns the default type definitions
 pub fn default_type_definitions() -> Vec<CustomTypeDefinition> {
     vec![
@@ -511,7 +573,8 @@ pub fn default_type_definitions() -> Vec<CustomTypeDefinition> {
             .with_description("System-level requirements"),
         CustomTypeDefinition::built_in("User", "User Story")
             .with_prefix("US")
-            .with_description("User stories and user requirements"),
+            .with_description("User stories and user requirements")
+            .with_description_template(STORY_DESCRIPTION_TEMPLATE),
         CustomTypeDefinition::built_in("ChangeRequest", "Change Request")
             .with_prefix("CR")
             .with_description("Change requests for existing functionality")
@@ -546,6 +609,7 @@ pub fn default_type_definitions() -> Vec<CustomTypeDefinition> {
                     .with_description("User who requested this change")
                     .with_order(2),
             )
+            .with_field_default("requested_by", "{{user}}")
             .with_field(
                 CustomFieldDefinition::text("target_release", "Target Release")
                     .with_description("Target release version for this change")
@@ -571,6 +635,7 @@ pub fn default_type_definitions() -> Vec<CustomTypeDefinition> {
                 "Won't Fix",
             ])
             .with_color("#dc2626")
+            .with_description_template(BUG_DESCRIPTION_TEMPLATE)
             .with_field(
                 CustomFieldDefinition::select(
                     "severity",
@@ -626,6 +691,16 @@ pub fn default_type_definitions() -> Vec<CustomTypeDefinition> {
                     .with_description("Estimated story points")
                     .with_order(3),
             ),
+        CustomTypeDefinition::built_in("Story", "Story")
+            .with_prefix("STORY")
+            .with_description("Agile user story delivering value within a sprint")
+            .with_color("#16a34a")
+            .with_description_template(STORY_DESCRIPTION_TEMPLATE)
+            .with_field(
+                CustomFieldDefinition::number("story_points", "Story Points")
+                    .with_description("Estimated story points")
+                    .with_order(1),
+            ),
         CustomTypeDefinition::built_in("Task", "Task")
             .with_prefix("TASK")
             .with_description("Implementation task or work item")
@@ -1285,6 +1360,10 @@ pub struct RequirementSnapshot {
     /// Custom fields at snapshot time
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub custom_fields: std::collections::HashMap<String, String>,
+
+    /// Acceptance criteria at snapshot time
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
 }
 
 impl RequirementSnapshot {
@@ -1305,10 +1384,20 @@ impl RequirementSnapshot {
             custom_status: req.custom_status.clone(),
             custom_priority: req.custom_priority.clone(),
             custom_fields: req.custom_fields.clone(),
+            acceptance_criteria: req.acceptance_criteria.clone(),
         }
     }
 }
 
+/// Renders acceptance criteria as one checklist line per criterion (for diffs)
+fn acceptance_criteria_summary(criteria: &[AcceptanceCriterion]) -> String {
+    criteria
+        .iter()
+        .map(|ac| ac.checklist_line())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Represents a baseline - a named snapshot of requirements at a point in time
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Baseline {
@@ -1580,6 +1669,138 @@ impl UrlLink {
     }
 }
 
+/// Verification status of an acceptance criterion
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum CriterionStatus {
+    #[default]
+    Pending,
+    Passed,
+    Failed,
+    Waived,
+}
+
+impl fmt::Display for CriterionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CriterionStatus::Pending => write!(f, "Pending"),
+            CriterionStatus::Passed => write!(f, "Passed"),
+            CriterionStatus::Failed => write!(f, "Failed"),
+            CriterionStatus::Waived => write!(f, "Waived"),
+        }
+    }
+}
+
+impl CriterionStatus {
+    /// Parses a status name (case-insensitive, accepts common aliases)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "pending" | "open" | "todo" => Some(CriterionStatus::Pending),
+            "passed" | "pass" | "done" | "ok" => Some(CriterionStatus::Passed),
+            "failed" | "fail" => Some(CriterionStatus::Failed),
+            "waived" | "skip" | "skipped" | "n/a" => Some(CriterionStatus::Waived),
+            _ => None,
+        }
+    }
+
+    /// Markdown-style checkbox for this status
+    pub fn checkbox(&self) -> &'static str {
+        match self {
+            CriterionStatus::Pending => "[ ]",
+            CriterionStatus::Passed => "[x]",
+            CriterionStatus::Failed => "[!]",
+            CriterionStatus::Waived => "[-]",
+        }
+    }
+
+    /// Whether this status counts as satisfied
+    pub fn is_satisfied(&self) -> bool {
+        matches!(self, CriterionStatus::Passed | CriterionStatus::Waived)
+    }
+}
+
+/// Given/When/Then structure for an acceptance criterion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, Hash)]
+pub struct GivenWhenThen {
+    pub given: String,
+    pub when: String,
+    pub then: String,
+}
+
+impl fmt::Display for GivenWhenThen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Given {}, when {}, then {}", self.given, self.when, self.then)
+    }
+}
+
+/// A single acceptance criterion on a requirement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AcceptanceCriterion {
+    /// Identifier, unique within the requirement (e.g., "AC-1")
+    pub id: String,
+
+    /// Criterion text
+    pub text: String,
+
+    /// Optional Given/When/Then structure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<GivenWhenThen>,
+
+    /// Verification status
+    #[serde(default)]
+    pub status: CriterionStatus,
+
+    /// Linked test (test name, file path or test case ID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_test: Option<String>,
+
+    /// When the status was last set to Passed/Failed/Waived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<DateTime<Utc>>,
+
+    /// Who last set the status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_by: Option<String>,
+}
+
+impl AcceptanceCriterion {
+    /// Creates a new pending criterion
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            scenario: None,
+            status: CriterionStatus::Pending,
+            linked_test: None,
+            verified_at: None,
+            verified_by: None,
+        }
+    }
+
+    /// Sets the Given/When/Then structure
+    pub fn with_scenario(mut self, scenario: GivenWhenThen) -> Self {
+        self.scenario = Some(scenario);
+        self
+    }
+
+    /// Sets the linked test
+    pub fn with_linked_test(mut self, test: impl Into<String>) -> Self {
+        self.linked_test = Some(test.into());
+        self
+    }
+
+    /// One-line checklist rendering (e.g., "[x] AC-1: Login succeeds")
+    pub fn checklist_line(&self) -> String {
+        let mut line = format!("{} {}: {}", self.status.checkbox(), self.id, self.text);
+        if let Some(scenario) = &self.scenario {
+            line.push_str(&format!(" ({})", scenario));
+        }
+        if let Some(test) = &self.linked_test {
+            line.push_str(&format!(" [test: {}]", test));
+        }
+        line
+    }
+}
+
 /// Represents a comment on a requirement with threading support
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Comment {
@@ -1991,6 +2212,14 @@ pub struct Requirement {
     /// Automatically populated by background evaluator when requirement changes
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ai_evaluation: Option<StoredAiEvaluation>,
+
+    /// Structured acceptance criteria
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+
+    /// Git commits that implement this requirement (from `aida git sync`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<crate::git::CommitLink>,
 }
 
 impl Requirement {
@@ -2027,6 +2256,8 @@ impl Requirement {
             custom_fields: std::collections::HashMap::new(),
             urls: Vec::new(),
             ai_evaluation: None,
+            acceptance_criteria: Vec::new(),
+            commits: Vec::new(),
         }
     }
 
@@ -2224,9 +2455,104 @@ impl Requirement {
         self.title.hash(&mut hasher);
         self.description.hash(&mut hasher);
         self.req_type.to_string().hash(&mut hasher);
+        for ac in &self.acceptance_criteria {
+            ac.id.hash(&mut hasher);
+            ac.text.hash(&mut hasher);
+            ac.scenario.hash(&mut hasher);
+        }
         format!("{:016x}", hasher.finish())
     }
 
+    /// Adds an acceptance criterion with the next free "AC-N" id, returning the id
+    pub fn add_acceptance_criterion(
+        &mut self,
+        mut criterion: AcceptanceCriterion,
+        author: &str,
+    ) -> String {
+        let next = self
+            .acceptance_criteria
+            .iter()
+            .filter_map(|ac| ac.id.strip_prefix("AC-")?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        criterion.id = format!("AC-{}", next);
+        let id = criterion.id.clone();
+        let change = Self::field_change("acceptance_criteria", String::new(), criterion.checklist_line());
+        self.acceptance_criteria.push(criterion);
+        self.record_change(author.to_string(), vec![change]);
+        id
+    }
+
+    /// Finds an acceptance criterion by id (case-insensitive, "3" is accepted for "AC-3")
+    pub fn find_acceptance_criterion(&self, ac_id: &str) -> Option<&AcceptanceCriterion> {
+        let idx = self.acceptance_criterion_index(ac_id)?;
+        self.acceptance_criteria.get(idx)
+    }
+
+    fn acceptance_criterion_index(&self, ac_id: &str) -> Option<usize> {
+        let wanted = if ac_id.chars().all(|c| c.is_ascii_digit()) {
+            format!("AC-{}", ac_id)
+        } else {
+            ac_id.to_string()
+        };
+        self.acceptance_criteria
+            .iter()
+            .position(|ac| ac.id.eq_ignore_ascii_case(&wanted))
+    }
+
+    /// Sets the verification status of an acceptance criterion
+    pub fn set_acceptance_status(
+        &mut self,
+        ac_id: &str,
+        status: CriterionStatus,
+        author: &str,
+    ) -> anyhow::Result<()> {
+        let Some(idx) = self.acceptance_criterion_index(ac_id) else {
+            anyhow::bail!("Acceptance criterion '{}' not found", ac_id);
+        };
+        let ac = &mut self.acceptance_criteria[idx];
+        if ac.status == status {
+            return Ok(());
+        }
+        let change = Self::field_change(&ac.id, ac.status.to_string(), status.to_string());
+        ac.status = status;
+        if status == CriterionStatus::Pending {
+            ac.verified_at = None;
+            ac.verified_by = None;
+        } else {
+            ac.verified_at = Some(Utc::now());
+            ac.verified_by = Some(author.to_string());
+        }
+        self.record_change(author.to_string(), vec![change]);
+        Ok(())
+    }
+
+    /// Removes an acceptance criterion, returning it
+    pub fn remove_acceptance_criterion(
+        &mut self,
+        ac_id: &str,
+        author: &str,
+    ) -> anyhow::Result<AcceptanceCriterion> {
+        let Some(idx) = self.acceptance_criterion_index(ac_id) else {
+            anyhow::bail!("Acceptance criterion '{}' not found", ac_id);
+        };
+        let removed = self.acceptance_criteria.remove(idx);
+        let change = Self::field_change("acceptance_criteria", removed.checklist_line(), String::new());
+        self.record_change(author.to_string(), vec![change]);
+        Ok(removed)
+    }
+
+    /// Returns (satisfied, total) acceptance criteria counts
+    pub fn acceptance_progress(&self) -> (usize, usize) {
+        let satisfied = self
+            .acceptance_criteria
+            .iter()
+            .filter(|ac| ac.status.is_satisfied())
+            .count();
+        (satisfied, self.acceptance_criteria.len())
+    }
+
     /// Check if AI evaluation is needed (never evaluated or stale)
     pub fn needs_ai_evaluation(&self) -> bool {
         match &self.ai_evaluation {
@@ -2421,6 +2747,26 @@ pub struct RequirementsStore {
     /// Baselines - named snapshots of requirements at specific points in time
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub baselines: Vec<Baseline>,
+
+    /// Test cases verifying requirements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_cases: Vec<crate::test_management::TestCase>,
+
+    /// Recorded test runs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_runs: Vec<crate::test_management::TestRun>,
+
+    /// Progress of `aida git sync` through the repository history
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_sync: Option<crate::git::GitSyncState>,
+
+    /// Outbound hooks notified of store changes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<crate::events::HookConfig>,
+
+    /// Project scripts for validation, automation and computed columns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<crate::scripting::ScriptConfig>,
 }
 
 /// Helper function for skip_serializing_if on AiPromptConfig
@@ -2473,6 +2819,11 @@ impl RequirementsStore {
             restrict_prefixes: false,
             ai_prompts: AiPromptConfig::default(),
             baselines: Vec::new(),
+            test_cases: Vec::new(),
+            test_runs: Vec::new(),
+            git_sync: None,
+            hooks: Vec::new(),
+            scripts: Vec::new(),
         }
     }
 
@@ -2671,6 +3022,29 @@ impl RequirementsStore {
         self.users.iter_mut().find(|u| &u.id == id)
     }
 
+    /// Finds a user by spec_id, UUID, handle (with or without `@`) or name,
+    /// ignoring case
+    pub fn find_user(&self, key: &str) -> Option<&User> {
+        let key = key.trim();
+        if let Ok(id) = Uuid::parse_str(key) {
+            return self.find_user_by_id(&id);
+        }
+        let handle = key.strip_prefix('@').unwrap_or(key);
+        self.users
+            .iter()
+            .find(|u| {
+                u.spec_id
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(key))
+            })
+            .or_else(|| {
+                self.users
+                    .iter()
+                    .find(|u| !u.handle.is_empty() && u.handle.eq_ignore_ascii_case(handle))
+            })
+            .or_else(|| self.users.iter().find(|u| u.name.eq_ignore_ascii_case(key)))
+    }
+
     /// Removes a user by ID
     pub fn remove_user(&mut self, id: &Uuid) -> bool {
         if let Some(pos) = self.users.iter().position(|u| &u.id == id) {
@@ -2720,6 +3094,22 @@ impl RequirementsStore {
         self.teams.iter().find(|t| t.id == *id)
     }
 
+    /// Finds a team by spec_id, UUID or name, ignoring case
+    pub fn find_team(&self, key: &str) -> Option<&Team> {
+        let key = key.trim();
+        if let Ok(id) = Uuid::parse_str(key) {
+            return self.find_team_by_id(&id);
+        }
+        self.teams
+            .iter()
+            .find(|t| {
+                t.spec_id
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(key))
+            })
+            .or_else(|| self.teams.iter().find(|t| t.name.eq_ignore_ascii_case(key)))
+    }
+
     /// Gets a mutable reference to a team by ID
     pub fn get_team_by_id_mut(&mut self, id: &Uuid) -> Option<&mut Team> {
         self.teams.iter_mut().find(|t| &t.id == id)
@@ -2898,6 +3288,19 @@ impl RequirementsStore {
             .find(|r| r.spec_id.as_ref().map(|s| s.as_str()) == Some(spec_id))
     }
 
+    /// Resolves a UUID or SPEC-ID (case-insensitive) to a requirement ID
+    pub fn resolve_requirement_id(&self, id: &str) -> Option<Uuid> {
+        Uuid::parse_str(id)
+            .ok()
+            .filter(|uuid| self.get_requirement_by_id(uuid).is_some())
+            .or_else(|| {
+                self.requirements
+                    .iter()
+                    .find(|r| r.spec_id.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(id)))
+                    .map(|r| r.id)
+            })
+    }
+
     /// Gets a mutable reference to a requirement by SPEC-ID
     pub fn get_requirement_by_spec_id_mut(&mut self, spec_id: &str) -> Option<&mut Requirement> {
         self.requirements
@@ -2990,6 +3393,28 @@ impl RequirementsStore {
     }
 
     /// Extract the prefix from a spec_id (e.g., "FR-0042" -> "FR", "AUTH-REQ-001" -> "AUTH-REQ")
+    /// Advances the ID counters past an externally assigned SPEC-ID
+    ///
+    /// Used when importing requirements that keep their SPEC-IDs, so that
+    /// IDs generated afterwards do not collide with them.
+    pub fn reserve_spec_id(&mut self, spec_id: &str) {
+        let Some((prefix, number)) = spec_id.rsplit_once('-') else {
+            return;
+        };
+        let Ok(number) = number.parse::<u32>() else {
+            return;
+        };
+        self.next_spec_number = self.next_spec_number.max(number + 1);
+        let mut prefixes = vec![prefix.to_uppercase()];
+        if let Some((feature, _)) = prefix.split_once('-') {
+            prefixes.push(feature.to_uppercase());
+        }
+        for prefix in prefixes {
+            let counter = self.prefix_counters.entry(prefix).or_insert(1);
+            *counter = (*counter).max(number + 1);
+        }
+    }
+
     fn extract_prefix_from_spec_id(spec_id: &str) -> String {
         // Find the last '-' followed by digits
         if let Some(last_dash_pos) = spec_id.rfind('-') {
@@ -3025,10 +3450,28 @@ impl RequirementsStore {
         for default_type in defaults {
             // Only add built-in types that are missing
             if default_type.built_in {
-                let exists = self.type_definitions.iter().any(|t| t.name == default_type.name);
-                if !exists {
-                    self.type_definitions.push(default_type);
-                    added = true;
+                match self
+                    .type_definitions
+                    .iter_mut()
+                    .find(|t| t.name == default_type.name)
+                {
+                    None => {
+                        self.type_definitions.push(default_type);
+                        added = true;
+                    }
+                    // Backfill the template and field defaults once on built-in
+                    // types saved before they existed
+                    Some(existing) if existing.built_in && !existing.defaults_applied => {
+                        if existing.description_template.is_none() {
+                            existing.description_template = default_type.description_template;
+                        }
+                        for (field, value) in default_type.field_defaults {
+                            existing.field_defaults.entry(field).or_insert(value);
+                        }
+                        existing.defaults_applied = true;
+                        added = true;
+                    }
+                    Some(_) => {}
                 }
             }
         }
@@ -4327,6 +4770,14 @@ impl RequirementsStore {
             });
         }
 
+        if snapshot.acceptance_criteria != current.acceptance_criteria {
+            changes.push(FieldChange {
+                field_name: "acceptance_criteria".to_string(),
+                old_value: acceptance_criteria_summary(&snapshot.acceptance_criteria),
+                new_value: acceptance_criteria_summary(&current.acceptance_criteria),
+            });
+        }
+
         changes
     }
 
@@ -4387,6 +4838,14 @@ impl RequirementsStore {
             });
         }
 
+        if source.acceptance_criteria != target.acceptance_criteria {
+            changes.push(FieldChange {
+                field_name: "acceptance_criteria".to_string(),
+                old_value: acceptance_criteria_summary(&source.acceptance_criteria),
+                new_value: acceptance_criteria_summary(&target.acceptance_criteria),
+            });
+        }
+
         changes
     }
 }
@@ -4786,4 +5245,133 @@ mod tests {
         assert_eq!(RelationshipType::References.inverse(), None);
         assert_eq!(RelationshipType::Custom("test".to_string()).inverse(), None);
     }
+
+    #[test]
+    fn test_acceptance_criteria_lifecycle() {
+        let mut req = Requirement::new("Login".into(), "Users can log in".into());
+        let hash_before = req.content_hash();
+
+        let first = req.add_acceptance_criterion(AcceptanceCriterion::new("", "Valid login works"), "alice");
+        let second = req.add_acceptance_criterion(
+            AcceptanceCriterion::new("", "Lockout after failures").with_scenario(GivenWhenThen {
+                given: "three failed attempts".into(),
+                when: "the user logs in again".into(),
+                then: "the account is locked".into(),
+            }),
+            "alice",
+        );
+        assert_eq!((first.as_str(), second.as_str()), ("AC-1", "AC-2"));
+        assert_ne!(req.content_hash(), hash_before);
+
+        // Status changes are verification, not content
+        let hash_with_criteria = req.content_hash();
+        req.set_acceptance_status("2", CriterionStatus::Passed, "bob").unwrap();
+        assert_eq!(req.content_hash(), hash_with_criteria);
+        assert_eq!(req.acceptance_progress(), (1, 2));
+        assert_eq!(req.find_acceptance_criterion("ac-2").unwrap().verified_by.as_deref(), Some("bob"));
+        assert!(req.set_acceptance_status("AC-9", CriterionStatus::Passed, "bob").is_err());
+
+        req.remove_acceptance_criterion("AC-1", "alice").unwrap();
+        assert_eq!(req.history.len(), 4);
+        // Ids are not reused while higher ones exist
+        let third = req.add_acceptance_criterion(AcceptanceCriterion::new("", "Logout"), "alice");
+        assert_eq!(third, "AC-3");
+    }
+
+    #[test]
+    fn test_baseline_tracks_acceptance_criteria() {
+        let mut store = RequirementsStore::new();
+        let mut req = Requirement::new("Login".into(), "Users can log in".into());
+        req.add_acceptance_criterion(AcceptanceCriterion::new("", "Valid login works"), "alice");
+        store.requirements.push(req);
+
+        let baseline_id = store.create_baseline("v1".into(), None, "alice".into()).id;
+        store.requirements[0]
+            .set_acceptance_status("AC-1", CriterionStatus::Failed, "bob")
+            .unwrap();
+
+        let comparison = store.compare_with_baseline(&baseline_id).unwrap();
+        assert_eq!(comparison.modified.len(), 1);
+        let change = &comparison.modified[0].changes[0];
+        assert_eq!(change.field_name, "acceptance_criteria");
+        assert!(change.new_value.contains("[!] AC-1"));
+    }
+
+    #[test]
+    fn test_find_user_by_spec_id_handle_or_name() {
+        let mut store = RequirementsStore::new();
+        store.add_user_with_id("Alice Smith".into(), "alice@x.org".into(), "alice".into());
+        let bob = store.add_user_with_id("Bob".into(), String::new(), String::new());
+
+        let alice_id = store.users[0].id.to_string();
+        let keys = [
+            "$USER-001",
+            "$user-001",
+            "alice",
+            "@Alice",
+            "alice smith",
+            &alice_id,
+        ];
+        for key in keys {
+            let found = store.find_user(key).map(|u| u.id.to_string());
+            assert_eq!(found.as_deref(), Some(alice_id.as_str()), "{}", key);
+        }
+        // Users without a handle are found by name, not by an empty handle
+        let found = store.find_user("bob").and_then(|u| u.spec_id.clone());
+        assert_eq!(found, Some(bob));
+        assert!(store.find_user("@").is_none());
+        assert!(store.find_user("carol").is_none());
+    }
+
+    #[test]
+    fn test_find_team_and_cycles() {
+        let mut store = RequirementsStore::new();
+        store.add_team_with_id("Engineering".into(), String::new(), None);
+        let eng = store.find_team("$TEAM-001").unwrap().id;
+        store.add_team_with_id("Backend".into(), String::new(), Some(eng));
+        let backend = store.find_team("backend").unwrap().id;
+
+        let by_uuid = store.find_team(&eng.to_string()).unwrap();
+        assert_eq!(by_uuid.name, "Engineering");
+        assert!(store.would_create_team_cycle(&eng, &backend));
+        assert!(store.would_create_team_cycle(&eng, &eng));
+        assert!(!store.would_create_team_cycle(&backend, &eng));
+    }
+
+    #[test]
+    fn test_type_defaults_backfilled_once() {
+        let mut store = RequirementsStore::new();
+        // Built-in types as saved before templates and field defaults existed
+        store.type_definitions = default_type_definitions()
+            .into_iter()
+            .map(|mut t| {
+                t.description_template = None;
+                t.field_defaults.clear();
+                t.defaults_applied = false;
+                t
+            })
+            .collect();
+        let find = |store: &RequirementsStore, name: &str| {
+            store
+                .type_definitions
+                .iter()
+                .find(|t| t.name == name)
+                .cloned()
+                .unwrap()
+        };
+
+        assert!(store.migrate_type_definitions());
+        assert!(find(&store, "Story").description_template.is_some());
+        assert_eq!(
+            find(&store, "ChangeRequest").field_defaults["requested_by"],
+            "{{user}}"
+        );
+
+        // A template the team removed afterwards stays removed
+        for t in &mut store.type_definitions {
+            t.description_template = None;
+        }
+        assert!(!store.migrate_type_definitions());
+        assert!(find(&store, "Story").description_template.is_none());
+    }
 }
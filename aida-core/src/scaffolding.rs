@@ -30,6 +30,8 @@ pub struct ScaffoldConfig {
     pub include_aida_implement_skill: bool,
     /// Include aida-capture skill for session review
     pub include_aida_capture_skill: bool,
+    /// Register the `aida mcp` server in .mcp.json
+    pub register_mcp_server: bool,
     /// Custom project type for specialized scaffolding
     pub project_type: ProjectType,
     /// Tech stack hints for context generation
@@ -45,6 +47,7 @@ impl Default for ScaffoldConfig {
             include_aida_req_skill: true,
             include_aida_implement_skill: true,
             include_aida_capture_skill: true,
+            register_mcp_server: true,
             project_type: ProjectType::Generic,
             tech_stack: Vec::new(),
         }
@@ -254,6 +257,29 @@ impl Scaffolder {
             }
         }
 
+        // .mcp.json registering the aida MCP server (merged into an existing file;
+        // a file that isn't a JSON object is left alone)
+        if self.config.register_mcp_server {
+            let path = PathBuf::from(".mcp.json");
+            let existing = fs::read_to_string(self.project_root.join(&path)).ok();
+            let exists = existing.is_some();
+
+            if let Some(content) = self.generate_mcp_config(existing.as_deref()) {
+                if exists {
+                    overwrites.push(path.clone());
+                } else {
+                    new_files.push(path.clone());
+                }
+
+                artifacts.push(ScaffoldArtifact {
+                    path,
+                    content,
+                    description: "Registers the aida MCP server for coding agents".to_string(),
+                    exists,
+                });
+            }
+        }
+
         // Filter new_dirs to only include those that don't exist
         let new_dirs: Vec<PathBuf> = new_dirs
             .into_iter()
@@ -371,6 +397,10 @@ Confidence levels:
 - `high`: >80% AI-generated
 - `med`: 40-80% AI with modifications
 - `low`: <40% AI, mostly human
+
+Run `aida trace scan` to list traced code, unknown IDs and approved requirements without code.
+
+Mention the SPEC-ID in commit messages (e.g. `Implement FR-0042 login`); `aida git sync` links those commits to the requirement.
 "#;
 
         let requirements_section = r#"
@@ -398,6 +428,15 @@ aida comment add <ID> "..."            # Add implementation note
 
 ### Session Workflow
 If you work conversationally without explicit /aida-req calls, use `/aida-capture` at session end to review and capture any requirements that were discussed but not yet added to the database.
+"#;
+
+        let mcp_section = r#"
+## MCP Server
+
+The `aida` MCP server is registered in `.mcp.json` (`aida mcp`). Prefer its tools over shelling out to the CLI:
+- `search_requirements`, `get_requirement`, `list_children` to read requirements
+- `create_requirement`, `update_requirement`, `link_requirements`, `add_comment` to change them
+- `record_implementation` to add an IMPL record summarising the work done for a requirement
 "#;
 
         let skills_section = r#"
@@ -434,7 +473,7 @@ This file provides guidance to Claude Code (claude.ai/code) when working with co
 
 ## Project Overview
 
-{}{}{}{}{}{}{}{}
+{}{}{}{}{}{}{}{}{}
 "#,
             project_name,
             description,
@@ -443,6 +482,11 @@ This file provides guidance to Claude Code (claude.ai/code) when working with co
             type_section,
             requirements_section,
             traceability_section,
+            if self.config.register_mcp_server {
+                mcp_section
+            } else {
+                ""
+            },
             if self.config.generate_skills {
                 skills_section
             } else {
@@ -451,6 +495,34 @@ This file provides guidance to Claude Code (claude.ai/code) when working with co
         )
     }
 
+    /// Generate .mcp.json registering `aida mcp` as a stdio server
+    ///
+    /// Other entries in `existing` are kept; returns `None` if it isn't a JSON object.
+    fn generate_mcp_config(&self, existing: Option<&str>) -> Option<String> {
+        let mut config = match existing {
+            Some(content) => serde_json::from_str::<serde_json::Value>(content).ok()?,
+            None => serde_json::json!({}),
+        };
+        let root = config.as_object_mut()?;
+        let servers = root
+            .entry("mcpServers")
+            .or_insert_with(|| serde_json::json!({}));
+        if !servers.is_object() {
+            *servers = serde_json::json!({});
+        }
+        servers.as_object_mut()?.insert(
+            "aida".to_string(),
+            serde_json::json!({
+                "command": "aida",
+                "args": ["mcp"],
+            }),
+        );
+        Some(format!(
+            "{}\n",
+            serde_json::to_string_pretty(&config).unwrap_or_default()
+        ))
+    }
+
     /// Generate type-specific sections based on project type
     fn generate_type_specific_section(&self) -> String {
         match self.config.project_type {
@@ -1171,6 +1243,7 @@ mod tests {
         assert!(config.include_aida_req_skill);
         assert!(config.include_aida_implement_skill);
         assert!(config.include_aida_capture_skill);
+        assert!(config.register_mcp_server);
         assert_eq!(config.project_type, ProjectType::Generic);
     }
 
@@ -1193,6 +1266,44 @@ mod tests {
             .find(|a| a.path == PathBuf::from("CLAUDE.md"));
         assert!(claude_md.is_some());
         assert!(claude_md.unwrap().content.contains("Test Project"));
+
+        // Check that the MCP server is registered
+        let mcp = preview
+            .artifacts
+            .iter()
+            .find(|a| a.path.as_path() == std::path::Path::new(".mcp.json"))
+            .unwrap();
+        let config: serde_json::Value = serde_json::from_str(&mcp.content).unwrap();
+        assert_eq!(config["mcpServers"]["aida"]["args"][0], "mcp");
+    }
+
+    #[test]
+    fn test_mcp_config_merges_existing_servers() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(".mcp.json"),
+            r#"{"mcpServers": {"other": {"command": "other"}}, "extra": 1}"#,
+        )
+        .unwrap();
+        let scaffolder = Scaffolder::new(temp_dir.path().to_path_buf(), ScaffoldConfig::default());
+
+        let preview = scaffolder.preview(&create_test_store());
+        let mcp = preview
+            .artifacts
+            .iter()
+            .find(|a| a.path.as_path() == std::path::Path::new(".mcp.json"))
+            .unwrap();
+        let config: serde_json::Value = serde_json::from_str(&mcp.content).unwrap();
+        assert_eq!(config["mcpServers"]["other"]["command"], "other");
+        assert_eq!(config["mcpServers"]["aida"]["args"][0], "mcp");
+        assert_eq!(config["extra"], 1);
+
+        fs::write(temp_dir.path().join(".mcp.json"), "not json").unwrap();
+        let preview = scaffolder.preview(&create_test_store());
+        assert!(preview
+            .artifacts
+            .iter()
+            .all(|a| a.path.as_path() != std::path::Path::new(".mcp.json")));
     }
 
     #[test]
@@ -0,0 +1,514 @@
+//! Project scripts
+//!
+//! Projects can register small [Rhai](https://rhai.rs) scripts in the
+//! project file to enforce team rules and automate routine edits:
+//!
+//! ```yaml
+//! scripts:
+//!   - name: security-owner
+//!     kind: validate
+//!     source: |
+//!       if req.tags.contains("security") && !in_team(req.owner, "SEC") {
+//!         throw "security requirements must be owned by the SEC team";
+//!       }
+//!   - name: gdpr-tag
+//!     kind: automate
+//!     source: |
+//!       if req.description.to_lower().contains("personal data") && !req.tags.contains("gdpr") {
+//!         req.tags.push("gdpr");
+//!       }
+//!   - name: Age (days)
+//!     kind: column
+//!     file: scripts/age.rhai
+//! ```
+//!
+//! Every script sees the requirement as `req` and the whole store, read-only,
+//! as `store`. On each write, the requirements that are new or changed first
+//! go through the `validate` scripts, any of which can reject the write with
+//! `throw "message"`; the `automate` scripts then run and may edit `req`, and
+//! their edits are saved in the same write. `column` scripts return a value
+//! that is shown as an extra column in `aida list`, `aida show` and the GUI.
+//!
+//! Scripts run in a sandbox: they have no file, network or process access
+//! and are stopped once they exceed the operation and size limits below.
+
+use anyhow::{bail, Context, Result};
+use rhai::serde::{from_dynamic, to_dynamic};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::models::{Requirement, RequirementPriority, RequirementStatus, RequirementsStore};
+
+/// Maximum number of operations a single script run may perform
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Maximum function call nesting depth
+const MAX_CALL_LEVELS: usize = 32;
+
+/// Maximum length of any string, and size of any array or map, a script builds
+const MAX_DATA_SIZE: usize = 1_000_000;
+
+/// When a project script runs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptKind {
+    /// Checks new and changed requirements before they are saved; `throw` rejects the write
+    Validate,
+    /// Edits new and changed requirements as they are saved
+    Automate,
+    /// Computes a value shown as an extra column
+    Column,
+}
+
+/// A script registered in the project file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScriptConfig {
+    /// Script name, used in error messages, history entries and as the column header
+    pub name: String,
+
+    /// When the script runs
+    pub kind: ScriptKind,
+
+    /// Inline script source
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub source: String,
+
+    /// Script file, relative to the requirements file's directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+impl ScriptConfig {
+    /// Returns the script source, reading it from `file` when set
+    fn load_source(&self, base_dir: &Path) -> Result<String> {
+        match (&self.file, self.source.trim().is_empty()) {
+            (Some(file), true) => {
+                let path = base_dir.join(file);
+                std::fs::read_to_string(&path).with_context(|| {
+                    format!("Failed to read script '{}' from {:?}", self.name, path)
+                })
+            }
+            (None, false) => Ok(self.source.clone()),
+            (Some(_), false) => bail!("Script '{}' has both 'source' and 'file'", self.name),
+            (None, true) => bail!("Script '{}' has neither 'source' nor 'file'", self.name),
+        }
+    }
+}
+
+/// A write rejected by a `validate` script
+#[derive(Error, Debug)]
+#[error("{spec_id}: {message} (rejected by script '{script}')")]
+pub struct ScriptRejection {
+    pub script: String,
+    pub spec_id: String,
+    pub message: String,
+}
+
+/// Requirement fields a script may edit; everything else is read-only
+#[derive(Deserialize)]
+struct ScriptEdits {
+    title: String,
+    description: String,
+    status: RequirementStatus,
+    priority: RequirementPriority,
+    owner: String,
+    feature: String,
+    #[serde(default)]
+    tags: HashSet<String>,
+    #[serde(default)]
+    custom_fields: HashMap<String, String>,
+}
+
+/// The compiled scripts of a project
+pub struct ScriptEngine {
+    engine: Engine,
+    scripts: Vec<(ScriptConfig, AST)>,
+    store: Dynamic,
+}
+
+impl ScriptEngine {
+    /// Compiles the scripts configured in `store`
+    pub fn new(store: &RequirementsStore, base_dir: &Path) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_string_size(MAX_DATA_SIZE)
+            .set_max_array_size(MAX_DATA_SIZE)
+            .set_max_map_size(MAX_DATA_SIZE)
+            .disable_symbol("eval")
+            .on_print(|text| eprintln!("script: {}", text));
+
+        let teams = team_directory(store);
+        let members = teams.clone();
+        engine.register_fn("in_team", move |who: &str, team: &str| {
+            let who = who.trim().trim_start_matches('@').to_lowercase();
+            !who.is_empty()
+                && teams
+                    .get(&team.to_lowercase())
+                    .is_some_and(|names| names.contains(&who))
+        });
+        engine.register_fn("team_members", move |team: &str| -> Array {
+            members
+                .get(&team.to_lowercase())
+                .map(|names| names.iter().cloned().map(Dynamic::from).collect())
+                .unwrap_or_default()
+        });
+
+        let mut scripts = Vec::with_capacity(store.scripts.len());
+        for config in &store.scripts {
+            let source = config.load_source(base_dir)?;
+            let ast = engine
+                .compile(&source)
+                .map_err(|e| anyhow::anyhow!("Script '{}' does not compile: {}", config.name, e))?;
+            scripts.push((config.clone(), ast));
+        }
+
+        let store = to_dynamic(store)
+            .map_err(|e| anyhow::anyhow!("Failed to expose the store to scripts: {}", e))?;
+        Ok(Self {
+            engine,
+            scripts,
+            store,
+        })
+    }
+
+    /// Names of the `column` scripts, in configuration order
+    pub fn column_names(&self) -> Vec<String> {
+        self.scripts_of(ScriptKind::Column)
+            .map(|(config, _)| config.name.clone())
+            .collect()
+    }
+
+    /// Evaluates the `column` scripts for a requirement
+    ///
+    /// A script that fails shows its error in place of the value.
+    pub fn computed_columns(&self, req: &Requirement) -> Vec<(String, String)> {
+        self.scripts_of(ScriptKind::Column)
+            .map(|(config, ast)| {
+                let value = requirement_map(req).and_then(|map| {
+                    let mut scope = self.scope(map, true);
+                    self.engine
+                        .eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+                        .map_err(|e| anyhow::anyhow!("{}", e))
+                });
+                let text = match value {
+                    Ok(value) if value.is_unit() => String::new(),
+                    Ok(value) => value.to_string(),
+                    Err(e) => format!("error: {}", e),
+                };
+                (config.name.clone(), text)
+            })
+            .collect()
+    }
+
+    /// Runs the `validate` scripts, returning a [`ScriptRejection`] if one throws
+    pub fn validate(&self, req: &Requirement) -> Result<()> {
+        for (config, ast) in self.scripts_of(ScriptKind::Validate) {
+            let mut scope = self.scope(requirement_map(req)?, true);
+            match self.engine.run_ast_with_scope(&mut scope, ast) {
+                Ok(()) => {}
+                Err(e) => match *e {
+                    EvalAltResult::ErrorRuntime(value, _) => {
+                        return Err(ScriptRejection {
+                            script: config.name.clone(),
+                            spec_id: display_id(req),
+                            message: value.to_string(),
+                        }
+                        .into())
+                    }
+                    e => bail!(
+                        "Script '{}' failed on {}: {}",
+                        config.name,
+                        display_id(req),
+                        e
+                    ),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the `automate` scripts, applying their edits to `req`
+    ///
+    /// Each script's edits are recorded in the requirement history as
+    /// `script:<name>`. Returns whether anything changed.
+    pub fn automate(&self, req: &mut Requirement) -> Result<bool> {
+        let mut changed = false;
+        for (config, ast) in self.scripts_of(ScriptKind::Automate) {
+            let mut scope = self.scope(requirement_map(req)?, false);
+            self.engine
+                .run_ast_with_scope(&mut scope, ast)
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Script '{}' failed on {}: {}",
+                        config.name,
+                        display_id(req),
+                        e
+                    )
+                })?;
+            let value = scope.get_value::<Dynamic>("req").unwrap_or_default();
+            let edits: ScriptEdits = from_dynamic(&value).map_err(|e| {
+                anyhow::anyhow!(
+                    "Script '{}' left {} invalid: {}",
+                    config.name,
+                    display_id(req),
+                    e
+                )
+            })?;
+            changed |= apply_edits(req, edits, &config.name);
+        }
+        Ok(changed)
+    }
+
+    fn scripts_of(&self, kind: ScriptKind) -> impl Iterator<Item = &(ScriptConfig, AST)> {
+        self.scripts
+            .iter()
+            .filter(move |(config, _)| config.kind == kind)
+    }
+
+    fn scope(&self, req: Map, read_only: bool) -> Scope<'static> {
+        let mut scope = Scope::new();
+        scope.push_constant_dynamic("store", self.store.clone());
+        if read_only {
+            scope.push_constant("req", req);
+        } else {
+            scope.push("req", req);
+        }
+        scope
+    }
+}
+
+/// Runs the project scripts on the requirements that changed between
+/// `before` and `after`
+///
+/// Validation runs first and rejects the whole write; automation edits are
+/// applied to `after`. Without a previous version, every requirement counts
+/// as changed.
+pub fn apply_scripts(
+    before: Option<&RequirementsStore>,
+    after: &mut RequirementsStore,
+    base_dir: &Path,
+) -> Result<()> {
+    if after.scripts.is_empty() {
+        return Ok(());
+    }
+    let engine = ScriptEngine::new(after, base_dir)?;
+
+    let changed: Vec<usize> = after
+        .requirements
+        .iter()
+        .enumerate()
+        .filter(|(_, req)| {
+            let previous = before.and_then(|b| b.requirements.iter().find(|r| r.id == req.id));
+            previous.is_none_or(|previous| {
+                serde_json::to_value(previous).ok() != serde_json::to_value(req).ok()
+            })
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+
+    for &idx in &changed {
+        engine.validate(&after.requirements[idx])?;
+    }
+    for &idx in &changed {
+        engine.automate(&mut after.requirements[idx])?;
+    }
+    Ok(())
+}
+
+/// Maps lowercase team names to the lowercase names, handles and emails of their members
+fn team_directory(store: &RequirementsStore) -> HashMap<String, HashSet<String>> {
+    store
+        .teams
+        .iter()
+        .map(|team| {
+            let names = store
+                .users
+                .iter()
+                .filter(|user| team.member_ids.contains(&user.id))
+                .flat_map(|user| [&user.name, &user.handle, &user.email])
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_lowercase())
+                .collect();
+            (team.name.to_lowercase(), names)
+        })
+        .collect()
+}
+
+/// Converts a requirement to a script map, with the editable collections always present
+fn requirement_map(req: &Requirement) -> Result<Map> {
+    let mut map = to_dynamic(req)
+        .map_err(|e| anyhow::anyhow!("Failed to expose {} to scripts: {}", display_id(req), e))?
+        .try_cast::<Map>()
+        .unwrap_or_default();
+    map.entry("tags".into())
+        .or_insert_with(|| Array::new().into());
+    map.entry("custom_fields".into())
+        .or_insert_with(|| Map::new().into());
+    Ok(map)
+}
+
+fn display_id(req: &Requirement) -> String {
+    req.spec_id.clone().unwrap_or_else(|| req.id.to_string())
+}
+
+/// Copies script edits onto the requirement and records them in its history
+fn apply_edits(req: &mut Requirement, edits: ScriptEdits, script: &str) -> bool {
+    let mut changes = Vec::new();
+    let mut track = |field: &str, old: String, new: String| {
+        if old != new {
+            changes.push(Requirement::field_change(field, old, new));
+        }
+    };
+
+    track("title", req.title.clone(), edits.title.clone());
+    track(
+        "description",
+        req.description.clone(),
+        edits.description.clone(),
+    );
+    track("status", req.status.to_string(), edits.status.to_string());
+    track(
+        "priority",
+        req.priority.to_string(),
+        edits.priority.to_string(),
+    );
+    track("owner", req.owner.clone(), edits.owner.clone());
+    track("feature", req.feature.clone(), edits.feature.clone());
+    track("tags", sorted_join(&req.tags), sorted_join(&edits.tags));
+    let keys: BTreeSet<&String> = req
+        .custom_fields
+        .keys()
+        .chain(edits.custom_fields.keys())
+        .collect();
+    for key in keys {
+        let old = req.custom_fields.get(key).cloned().unwrap_or_default();
+        let new = edits.custom_fields.get(key).cloned().unwrap_or_default();
+        track(key, old, new);
+    }
+    if changes.is_empty() {
+        return false;
+    }
+
+    req.title = edits.title;
+    req.description = edits.description;
+    req.status = edits.status;
+    req.priority = edits.priority;
+    req.owner = edits.owner;
+    req.feature = edits.feature;
+    req.tags = edits.tags;
+    req.custom_fields = edits.custom_fields;
+    req.record_change(format!("script:{}", script), changes);
+    true
+}
+
+fn sorted_join(values: &HashSet<String>) -> String {
+    let mut values: Vec<&str> = values.iter().map(String::as_str).collect();
+    values.sort_unstable();
+    values.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Team, User};
+
+    fn script(name: &str, kind: ScriptKind, source: &str) -> ScriptConfig {
+        ScriptConfig {
+            name: name.to_string(),
+            kind,
+            source: source.to_string(),
+            file: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_security_requirement_outside_team() {
+        let mut store = RequirementsStore::new();
+        let alice = User::new("Alice".into(), "alice@example.com".into(), "alice".into());
+        let mut team = Team::new("SEC".into(), String::new(), None);
+        team.member_ids.push(alice.id);
+        store.users.push(alice);
+        store.teams.push(team);
+        store.scripts.push(script(
+            "security-owner",
+            ScriptKind::Validate,
+            r#"if req.tags.contains("security") && !in_team(req.owner, "SEC") {
+                throw "security requirements must be owned by the SEC team";
+            }"#,
+        ));
+
+        let mut req = Requirement::new("Encrypt backups".into(), String::new());
+        req.spec_id = Some("FR-001".into());
+        req.tags.insert("security".into());
+        req.owner = "bob".into();
+        store.requirements.push(req);
+
+        let err = apply_scripts(None, &mut store.clone(), Path::new(".")).unwrap_err();
+        let rejection = err.downcast_ref::<ScriptRejection>().unwrap();
+        assert_eq!(rejection.spec_id, "FR-001");
+        assert!(rejection.message.contains("SEC team"));
+
+        store.requirements[0].owner = "@alice".into();
+        apply_scripts(None, &mut store, Path::new(".")).unwrap();
+    }
+
+    #[test]
+    fn test_automate_edits_only_changed_requirements() {
+        let mut store = RequirementsStore::new();
+        store.scripts.push(script(
+            "gdpr-tag",
+            ScriptKind::Automate,
+            r#"if req.description.to_lower().contains("personal data") {
+                req.tags.push("gdpr");
+                req.custom_fields.reviewed = "no";
+                req.id = "ignored";
+            }"#,
+        ));
+        let old = Requirement::new("Old".into(), "Stores personal data".into());
+        let new = Requirement::new("New".into(), "Exports Personal Data".into());
+        store.requirements.push(old);
+        let before = store.clone();
+        store.requirements.push(new.clone());
+
+        apply_scripts(Some(&before), &mut store, Path::new(".")).unwrap();
+
+        assert!(store.requirements[0].tags.is_empty());
+        let edited = &store.requirements[1];
+        assert_eq!(edited.id, new.id);
+        assert!(edited.tags.contains("gdpr"));
+        assert_eq!(edited.custom_fields["reviewed"], "no");
+        let entry = edited.history.last().unwrap();
+        assert_eq!(entry.author, "script:gdpr-tag");
+        assert_eq!(entry.changes.len(), 2);
+    }
+
+    #[test]
+    fn test_computed_columns_and_sandbox_limits() {
+        let mut store = RequirementsStore::new();
+        store.scripts.push(script(
+            "Children",
+            ScriptKind::Column,
+            r#"store.requirements.filter(|r| r.title.starts_with(req.title)).len() - 1"#,
+        ));
+        store
+            .scripts
+            .push(script("Spin", ScriptKind::Column, "loop {}"));
+        store
+            .requirements
+            .push(Requirement::new("Login".into(), String::new()));
+        store
+            .requirements
+            .push(Requirement::new("Login with SSO".into(), String::new()));
+
+        let engine = ScriptEngine::new(&store, Path::new(".")).unwrap();
+        assert_eq!(engine.column_names(), vec!["Children", "Spin"]);
+        let columns = engine.computed_columns(&store.requirements[0]);
+        assert_eq!(columns[0], ("Children".to_string(), "1".to_string()));
+        assert!(columns[1].1.starts_with("error:"));
+    }
+}
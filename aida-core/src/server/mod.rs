@@ -0,0 +1,1498 @@
+//! Local HTTP REST API (`aida serve`)
+//!
+//! Exposes requirements, relationships, comments, users, teams and baselines
+//! as JSON over HTTP on top of any [`DatabaseBackend`]. Every write goes
+//! through `update_atomically`, so the server can run alongside the CLI and
+//! GUI on the same file.
+//!
+//! Requirements carry an `ETag` derived from `modified_at`. Sending it back in
+//! `If-Match` (or as `modified_at` in a PATCH body) makes the write fail with
+//! `412 Precondition Failed` if the requirement changed in the meantime. The
+//! OpenAPI description is served at `/api/openapi.json`.
+//!
+//! The API has no authentication, so it only listens on loopback addresses
+//! unless remote access is allowed explicitly. To keep web pages from driving
+//! it (CSRF, DNS rebinding), requests with a non-loopback `Host` or a foreign
+//! `Origin` are rejected, and request bodies must be sent as `application/json`.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::net::{IpAddr, ToSocketAddrs};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::bulk::{RequirementPatch, RequirementQuery};
+use crate::db::{backend_type_for_path, BackendType, DatabaseBackend, SqliteBackend, YamlBackend};
+use crate::models::{
+    Comment, RelationshipType, Requirement, RequirementType, RequirementsStore, Team, User,
+};
+use crate::scripting::ScriptRejection;
+use crate::storage::Storage;
+
+/// OpenAPI 3 description of the API
+pub const OPENAPI_SPEC: &str = include_str!("openapi.json");
+
+/// Default listen address (localhost only)
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+
+/// Header naming the user a write is attributed to
+const USER_HEADER: &str = "X-Aida-User";
+
+/// ETag for a requirement, derived from its `modified_at` timestamp
+pub fn requirement_etag(req: &Requirement) -> String {
+    format!("\"{}\"", req.modified_at.timestamp_micros())
+}
+
+/// A request as seen by the router (decoupled from the HTTP library)
+#[derive(Debug, Clone, Default)]
+pub struct ApiRequest {
+    pub method: String,
+    /// Path without the query string, e.g. `/api/requirements/FR-001`
+    pub path: String,
+    /// Raw query string without the leading `?`
+    pub query: String,
+    pub if_match: Option<String>,
+    pub if_none_match: Option<String>,
+    /// Value of the `X-Aida-User` header
+    pub user: Option<String>,
+    pub host: Option<String>,
+    pub origin: Option<String>,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl ApiRequest {
+    /// Creates a request for `method` and `url` (path plus optional query)
+    pub fn new(method: &str, url: &str) -> Self {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        Self {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets a JSON body (and the matching `Content-Type`)
+    pub fn with_body(mut self, body: &Value) -> Self {
+        self.body = body.to_string();
+        self.content_type = Some(String::from("application/json"));
+        self
+    }
+
+    /// Sets a header the router looks at; other headers are ignored
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.set_header(name, value.into());
+        self
+    }
+
+    /// Sets the `If-Match` precondition
+    pub fn with_if_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    fn from_http(request: &mut tiny_http::Request) -> Self {
+        let mut api_request = Self::new(request.method().as_str(), request.url());
+        for header in request.headers() {
+            api_request.set_header(header.field.as_str().as_str(), header.value.to_string());
+        }
+        let _ = request.as_reader().read_to_string(&mut api_request.body);
+        api_request
+    }
+
+    fn set_header(&mut self, name: &str, value: String) {
+        let slot = match name.to_ascii_lowercase().as_str() {
+            "if-match" => &mut self.if_match,
+            "if-none-match" => &mut self.if_none_match,
+            "host" => &mut self.host,
+            "origin" => &mut self.origin,
+            "content-type" => &mut self.content_type,
+            other if other.eq_ignore_ascii_case(USER_HEADER) => &mut self.user,
+            _ => return,
+        };
+        *slot = Some(value);
+    }
+
+    /// Decoded `key=value` pairs from the query string
+    fn query_pairs(&self) -> Vec<(String, String)> {
+        self.query
+            .split('&')
+            .filter(|p| !p.is_empty())
+            .map(|pair| {
+                let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(k), percent_decode(v))
+            })
+            .collect()
+    }
+
+    fn query_flag(&self, key: &str) -> bool {
+        self.query_pairs()
+            .iter()
+            .any(|(k, v)| k == key && (v.is_empty() || v.eq_ignore_ascii_case("true")))
+    }
+
+    fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, ApiError> {
+        serde_json::from_str(&self.body)
+            .map_err(|e| ApiError::bad_request(format!("Invalid request body: {}", e)))
+    }
+}
+
+/// Response produced by the router
+#[derive(Debug, Clone)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String,
+    pub etag: Option<String>,
+    pub content_type: &'static str,
+}
+
+impl ApiResponse {
+    /// A JSON response
+    pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        Self {
+            status,
+            body: serde_json::to_string_pretty(value).unwrap_or_default(),
+            etag: None,
+            content_type: "application/json",
+        }
+    }
+
+    /// A JSON response for a requirement, tagged with its ETag
+    fn requirement(status: u16, req: &Requirement) -> Self {
+        Self {
+            etag: Some(requirement_etag(req)),
+            ..Self::json(status, req)
+        }
+    }
+
+    fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    fn no_content() -> Self {
+        Self {
+            status: 204,
+            body: String::new(),
+            etag: None,
+            content_type: "application/json",
+        }
+    }
+
+    /// Parses the body as JSON
+    pub fn body_json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or(Value::Null)
+    }
+
+    fn into_http(self) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+        let mut response = tiny_http::Response::from_string(self.body)
+            .with_status_code(self.status)
+            .with_header(header("Content-Type", self.content_type));
+        if let Some(etag) = &self.etag {
+            response = response.with_header(header("ETag", etag));
+        }
+        response
+    }
+}
+
+fn header(name: &str, value: &str) -> tiny_http::Header {
+    tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes())
+        .expect("static header names and ASCII values are valid")
+}
+
+/// An error returned to the client as `{"error": ...}`
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: u16,
+    pub message: String,
+    /// Extra fields merged into the error body
+    pub details: Option<Value>,
+}
+
+impl ApiError {
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    fn bad_request(message: impl std::fmt::Display) -> Self {
+        Self::new(400, message.to_string())
+    }
+
+    fn not_found(kind: &str, id: &str) -> Self {
+        Self::new(404, format!("{} not found: {}", kind, id))
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        // Writes rejected by a project script are the client's to fix
+        let status = if e.is::<ScriptRejection>() { 422 } else { 500 };
+        Self::new(status, e.to_string())
+    }
+}
+
+impl From<ApiError> for ApiResponse {
+    fn from(e: ApiError) -> Self {
+        let mut body = json!({ "error": e.message });
+        if let (Some(Value::Object(details)), Value::Object(map)) = (e.details, &mut body) {
+            map.extend(details);
+        }
+        ApiResponse::json(e.status, &body)
+    }
+}
+
+type ApiResult = std::result::Result<ApiResponse, ApiError>;
+
+/// A requirement to create (body of `POST /api/requirements`)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NewRequirement {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, rename = "type")]
+    pub req_type: Option<RequirementType>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub feature: Option<String>,
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// ID prefix override (A-Z)
+    pub prefix: Option<String>,
+    /// Parent requirement (UUID or SPEC-ID); the new requirement becomes its child
+    pub parent: Option<String>,
+}
+
+impl NewRequirement {
+    /// Validates and adds the requirement, assigning a SPEC-ID; returns its UUID
+    pub fn insert(self, store: &mut RequirementsStore, author: &str) -> Result<Uuid> {
+        if self.title.trim().is_empty() {
+            anyhow::bail!("Title cannot be empty");
+        }
+        let parent = match &self.parent {
+            Some(parent) => Some(
+                store
+                    .resolve_requirement_id(parent)
+                    .ok_or_else(|| anyhow::anyhow!("Parent requirement not found: {}", parent))?,
+            ),
+            None => None,
+        };
+
+        let mut requirement = Requirement::new(self.title, self.description);
+        requirement.created_by = Some(author.to_string());
+        if let Some(req_type) = self.req_type {
+            requirement.req_type = req_type;
+        }
+        if let Some(feature) = self.feature {
+            requirement.feature = feature;
+        }
+        if let Some(owner) = self.owner {
+            requirement.owner = owner;
+        }
+        requirement
+            .tags
+            .extend(self.tags.into_iter().map(|t| t.trim().to_string()));
+
+        if let Some(status) = &self.status {
+            let statuses = store.get_statuses_for_type(&requirement.req_type);
+            let Some(canonical) = statuses.iter().find(|s| s.eq_ignore_ascii_case(status)) else {
+                anyhow::bail!(
+                    "Invalid status '{}' (valid: {})",
+                    status,
+                    statuses.join(", ")
+                );
+            };
+            requirement.set_status_from_str(canonical);
+        }
+        if let Some(priority) = &self.priority {
+            let priorities = store.get_priorities_for_type(&requirement.req_type);
+            let Some(canonical) = priorities.iter().find(|p| p.eq_ignore_ascii_case(priority))
+            else {
+                anyhow::bail!(
+                    "Invalid priority '{}' (valid: {})",
+                    priority,
+                    priorities.join(", ")
+                );
+            };
+            requirement.set_priority_from_str(canonical);
+        }
+        if let Some(prefix) = &self.prefix {
+            requirement
+                .set_prefix_override(prefix)
+                .map_err(anyhow::Error::msg)?;
+            if !store.is_prefix_allowed(prefix) {
+                anyhow::bail!("Prefix '{}' is not allowed", prefix);
+            }
+        }
+
+        let id = requirement.id;
+        let feature_prefix = store
+            .get_feature_by_name(&requirement.feature)
+            .map(|f| f.prefix.clone());
+        let type_prefix = store.get_type_prefix(&requirement.req_type);
+        store.add_requirement_with_id(
+            requirement,
+            feature_prefix.as_deref(),
+            type_prefix.as_deref(),
+        );
+
+        if let Some(parent_id) = parent {
+            store.add_relationship_with_creator(
+                &id,
+                RelationshipType::Parent,
+                &parent_id,
+                true,
+                Some(author.to_string()),
+            )?;
+        }
+        Ok(id)
+    }
+}
+
+/// Body of `PATCH /api/requirements/{id}`
+#[derive(Debug, Deserialize)]
+struct RequirementUpdate {
+    /// Optimistic concurrency alternative to `If-Match`
+    modified_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    patch: RequirementPatch,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewRelationship {
+    #[serde(rename = "type")]
+    rel_type: String,
+    target: String,
+    #[serde(default)]
+    bidirectional: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewComment {
+    content: String,
+    parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewUser {
+    name: String,
+    #[serde(default)]
+    email: String,
+    handle: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserUpdate {
+    name: Option<String>,
+    email: Option<String>,
+    handle: Option<String>,
+    archived: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewTeam {
+    name: String,
+    #[serde(default)]
+    description: String,
+    parent: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamUpdate {
+    name: Option<String>,
+    description: Option<String>,
+    /// Parent team; an empty string makes the team top-level
+    parent: Option<String>,
+    /// Replaces the member list (user UUIDs, spec IDs or handles)
+    members: Option<Vec<String>>,
+    archived: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewBaseline {
+    name: String,
+    description: Option<String>,
+}
+
+/// Serves the JSON API for one database backend
+pub struct ApiServer<B: DatabaseBackend> {
+    backend: B,
+    default_author: String,
+    allow_remote: bool,
+}
+
+impl<B: DatabaseBackend> ApiServer<B> {
+    /// Creates a server; writes without an `X-Aida-User` header are attributed to `$USER`
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            default_author: std::env::var("USER").unwrap_or_else(|_| String::from("api")),
+            allow_remote: false,
+        }
+    }
+
+    /// Sets the author used for writes without an `X-Aida-User` header
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.default_author = author.into();
+        self
+    }
+
+    /// Allows listening on non-loopback addresses and any `Host` header
+    pub fn with_allow_remote(mut self, allow_remote: bool) -> Self {
+        self.allow_remote = allow_remote;
+        self
+    }
+
+    /// Listens on `addr` and handles requests until the process exits
+    ///
+    /// Fails for addresses other machines can reach unless remote access is allowed.
+    pub fn serve(&self, addr: &str) -> Result<()> {
+        if !self.allow_remote && !is_loopback_addr(addr) {
+            anyhow::bail!(
+                "Refusing to listen on {}: the API has no authentication, so only loopback addresses are allowed by default",
+                addr
+            );
+        }
+        let server = tiny_http::Server::http(addr)
+            .map_err(|e| anyhow::anyhow!("Failed to listen on {}: {}", addr, e))?;
+        for mut request in server.incoming_requests() {
+            let response = self.handle(&ApiRequest::from_http(&mut request));
+            let _ = request.respond(response.into_http());
+        }
+        Ok(())
+    }
+
+    /// Routes one request
+    pub fn handle(&self, request: &ApiRequest) -> ApiResponse {
+        self.check_headers(request)
+            .and_then(|()| self.route(request))
+            .unwrap_or_else(ApiResponse::from)
+    }
+
+    /// Rejects requests a web page could forge: foreign hosts/origins and non-JSON writes
+    fn check_headers(&self, req: &ApiRequest) -> std::result::Result<(), ApiError> {
+        if let Some(host) = &req.host {
+            if !self.allow_remote && !is_loopback_host(host) {
+                return Err(ApiError::new(
+                    403,
+                    format!("Host '{}' is not allowed", host),
+                ));
+            }
+        }
+        if let Some(origin) = &req.origin {
+            let authority = origin
+                .split_once("://")
+                .map_or(origin.as_str(), |(_, rest)| rest)
+                .trim_end_matches('/');
+            let same_origin = match &req.host {
+                Some(host) => authority.eq_ignore_ascii_case(host),
+                None => is_loopback_host(authority),
+            };
+            if !same_origin {
+                return Err(ApiError::new(
+                    403,
+                    format!("Origin '{}' is not allowed", origin),
+                ));
+            }
+        }
+        // DELETE has no body, and cross-origin DELETE is already preflighted
+        if matches!(req.method.as_str(), "POST" | "PUT" | "PATCH") {
+            let is_json = req.content_type.as_deref().is_some_and(|ct| {
+                ct.split(';')
+                    .next()
+                    .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+            });
+            if !is_json {
+                return Err(ApiError::new(
+                    415,
+                    format!(
+                        "{} requests must use Content-Type: application/json",
+                        req.method
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn route(&self, req: &ApiRequest) -> ApiResult {
+        let segments: Vec<String> = req
+            .path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(percent_decode)
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let Some((&"api", rest)) = segments.split_first() else {
+            return Err(ApiError::new(404, format!("No route for {}", req.path)));
+        };
+
+        match (req.method.as_str(), rest) {
+            ("GET", ["openapi.json"]) => Ok(ApiResponse {
+                status: 200,
+                body: OPENAPI_SPEC.to_string(),
+                etag: None,
+                content_type: "application/json",
+            }),
+
+            ("GET", ["requirements"]) => self.list_requirements(req),
+            ("POST", ["requirements"]) => self.create_requirement(req),
+            ("GET", ["requirements", id]) => self.get_requirement(req, id),
+            ("PATCH", ["requirements", id]) => self.update_requirement(req, id),
+            ("DELETE", ["requirements", id]) => self.delete_requirement(req, id),
+
+            ("GET", ["requirements", id, "relationships"]) => {
+                let store = self.backend.load()?;
+                let req_id = find_requirement_id(&store, id)?;
+                Ok(ApiResponse::json(
+                    200,
+                    &store
+                        .get_requirement_by_id(&req_id)
+                        .map(|r| &r.relationships),
+                ))
+            }
+            ("POST", ["requirements", id, "relationships"]) => self.add_relationship(req, id),
+            ("DELETE", ["requirements", id, "relationships", rel_type, target]) => {
+                self.remove_relationship(req, id, rel_type, target)
+            }
+
+            ("GET", ["requirements", id, "comments"]) => {
+                let store = self.backend.load()?;
+                let req_id = find_requirement_id(&store, id)?;
+                Ok(ApiResponse::json(
+                    200,
+                    &store.get_requirement_by_id(&req_id).map(|r| &r.comments),
+                ))
+            }
+            ("POST", ["requirements", id, "comments"]) => self.add_comment(req, id),
+            ("DELETE", ["requirements", id, "comments", comment_id]) => {
+                self.delete_comment(id, comment_id)
+            }
+
+            ("GET", ["users"]) => {
+                let store = self.backend.load()?;
+                let archived = req.query_flag("archived");
+                let users: Vec<&User> = store
+                    .users
+                    .iter()
+                    .filter(|u| archived || !u.archived)
+                    .collect();
+                Ok(ApiResponse::json(200, &users))
+            }
+            ("POST", ["users"]) => self.create_user(req),
+            ("GET", ["users", id]) => {
+                let store = self.backend.load()?;
+                let user_id = find_user_id(&store, id)?;
+                Ok(ApiResponse::json(200, &store.find_user_by_id(&user_id)))
+            }
+            ("PATCH", ["users", id]) => self.update_user(req, id),
+            ("DELETE", ["users", id]) => self.write(|store| {
+                let user_id = find_user_id(store, id)?;
+                store.remove_user(&user_id);
+                Ok(ApiResponse::no_content())
+            }),
+
+            ("GET", ["teams"]) => {
+                let store = self.backend.load()?;
+                let archived = req.query_flag("archived");
+                let teams: Vec<&Team> = store
+                    .teams
+                    .iter()
+                    .filter(|t| archived || !t.archived)
+                    .collect();
+                Ok(ApiResponse::json(200, &teams))
+            }
+            ("POST", ["teams"]) => self.create_team(req),
+            ("GET", ["teams", id]) => {
+                let store = self.backend.load()?;
+                let team_id = find_team_id(&store, id)?;
+                Ok(ApiResponse::json(200, &store.find_team_by_id(&team_id)))
+            }
+            ("PATCH", ["teams", id]) => self.update_team(req, id),
+            ("DELETE", ["teams", id]) => self.write(|store| {
+                let team_id = find_team_id(store, id)?;
+                store.remove_team(&team_id);
+                Ok(ApiResponse::no_content())
+            }),
+
+            ("GET", ["baselines"]) => {
+                let store = self.backend.load()?;
+                let summaries: Vec<Value> = store
+                    .baselines
+                    .iter()
+                    .map(|b| {
+                        json!({
+                            "id": b.id,
+                            "name": b.name,
+                            "description": b.description,
+                            "created_at": b.created_at,
+                            "created_by": b.created_by,
+                            "git_tag": b.git_tag,
+                            "locked": b.locked,
+                            "requirement_count": b.requirements.len(),
+                        })
+                    })
+                    .collect();
+                Ok(ApiResponse::json(200, &summaries))
+            }
+            ("POST", ["baselines"]) => {
+                let body: NewBaseline = req.json()?;
+                let author = self.author(req);
+                self.write(|store| {
+                    if store.get_baseline_by_name(&body.name).is_some() {
+                        return Err(ApiError::new(
+                            409,
+                            format!("Baseline already exists: {}", body.name),
+                        ));
+                    }
+                    let baseline = store.create_baseline(body.name, body.description, author);
+                    Ok(ApiResponse::json(201, baseline))
+                })
+            }
+            ("GET", ["baselines", id]) => {
+                let store = self.backend.load()?;
+                let baseline_id = find_baseline_id(&store, id)?;
+                Ok(ApiResponse::json(200, &store.get_baseline(&baseline_id)))
+            }
+            ("GET", ["baselines", id, "compare"]) => {
+                let store = self.backend.load()?;
+                let baseline_id = find_baseline_id(&store, id)?;
+                Ok(ApiResponse::json(
+                    200,
+                    &store.compare_with_baseline(&baseline_id),
+                ))
+            }
+            ("DELETE", ["baselines", id]) => self.write(|store| {
+                let baseline_id = find_baseline_id(store, id)?;
+                if !store.delete_baseline(&baseline_id) {
+                    return Err(ApiError::new(409, format!("Baseline is locked: {}", id)));
+                }
+                Ok(ApiResponse::no_content())
+            }),
+
+            (method, _) => Err(ApiError::new(
+                404,
+                format!("No route for {} {}", method, req.path),
+            )),
+        }
+    }
+
+    /// Author for writes: the `X-Aida-User` header, else the server default
+    fn author(&self, req: &ApiRequest) -> String {
+        req.user
+            .clone()
+            .filter(|u| !u.trim().is_empty())
+            .unwrap_or_else(|| self.default_author.clone())
+    }
+
+    /// Applies `f` inside `update_atomically_if`; on error nothing is written
+    fn write<T>(
+        &self,
+        f: impl FnOnce(&mut RequirementsStore) -> std::result::Result<T, ApiError>,
+    ) -> std::result::Result<T, ApiError> {
+        self.write_with_store(f).map(|(value, _)| value)
+    }
+
+    /// Like [`Self::write`], also returning the store as saved (after automation scripts ran)
+    fn write_with_store<T>(
+        &self,
+        f: impl FnOnce(&mut RequirementsStore) -> std::result::Result<T, ApiError>,
+    ) -> std::result::Result<(T, RequirementsStore), ApiError> {
+        let mut outcome = None;
+        let saved = self.backend.update_atomically_if(|store| {
+            let result = f(store);
+            let ok = result.is_ok();
+            outcome = Some(result);
+            ok
+        })?;
+        let value = outcome.unwrap_or_else(|| Err(ApiError::new(500, "Update was not applied")))?;
+        let saved = saved.ok_or_else(|| ApiError::new(500, "Update was not applied"))?;
+        Ok((value, saved))
+    }
+
+    fn list_requirements(&self, req: &ApiRequest) -> ApiResult {
+        let mut query = RequirementQuery::default();
+        for (key, value) in req.query_pairs() {
+            query.set(&key, &value).map_err(ApiError::bad_request)?;
+        }
+        let store = self.backend.load()?;
+        let reqs: Vec<&Requirement> = store
+            .requirements
+            .iter()
+            .filter(|r| query.matches(r))
+            .collect();
+        Ok(ApiResponse::json(200, &reqs))
+    }
+
+    fn get_requirement(&self, req: &ApiRequest, id: &str) -> ApiResult {
+        let store = self.backend.load()?;
+        let req_id = find_requirement_id(&store, id)?;
+        let requirement = store
+            .get_requirement_by_id(&req_id)
+            .ok_or_else(|| ApiError::not_found("Requirement", id))?;
+
+        let etag = requirement_etag(requirement);
+        if req
+            .if_none_match
+            .as_deref()
+            .is_some_and(|tags| etag_matches(tags, &etag))
+        {
+            return Ok(ApiResponse {
+                etag: Some(etag),
+                ..ApiResponse::no_content()
+            }
+            .with_status(304));
+        }
+        Ok(ApiResponse::requirement(200, requirement))
+    }
+
+    fn create_requirement(&self, req: &ApiRequest) -> ApiResult {
+        let body: NewRequirement = req.json()?;
+        let author = self.author(req);
+
+        let (id, saved) = self
+            .write_with_store(|store| body.insert(store, &author).map_err(ApiError::bad_request))?;
+        let created = saved
+            .get_requirement_by_id(&id)
+            .ok_or_else(|| ApiError::new(500, "Requirement was not added"))?;
+        Ok(ApiResponse::requirement(201, created))
+    }
+
+    fn update_requirement(&self, req: &ApiRequest, id: &str) -> ApiResult {
+        let update: RequirementUpdate = req.json()?;
+        let author = self.author(req);
+
+        let (req_id, saved) = self.write_with_store(|store| {
+            let req_id = find_requirement_id(store, id)?;
+            let errors = store.validate_bulk_update(&[req_id], &update.patch);
+            if !errors.is_empty() {
+                return Err(ApiError::bad_request(errors.join("; ")));
+            }
+
+            let mut preview = store.clone();
+            preview.bulk_update(&[req_id], &update.patch, &author)?;
+            let proposed = preview.get_requirement_by_id(&req_id);
+            check_precondition(
+                store,
+                req_id,
+                req.if_match.as_deref(),
+                update.modified_at,
+                proposed,
+            )?;
+
+            store.bulk_update(&[req_id], &update.patch, &author)?;
+            Ok(req_id)
+        })?;
+        let updated = saved
+            .get_requirement_by_id(&req_id)
+            .ok_or_else(|| ApiError::not_found("Requirement", id))?;
+        Ok(ApiResponse::requirement(200, updated))
+    }
+
+    fn delete_requirement(&self, req: &ApiRequest, id: &str) -> ApiResult {
+        self.write(|store| {
+            let req_id = find_requirement_id(store, id)?;
+            check_precondition(store, req_id, req.if_match.as_deref(), None, None)?;
+            store.requirements.retain(|r| r.id != req_id);
+            // Drop links pointing at the deleted requirement (inverse and incoming)
+            for other in &mut store.requirements {
+                other.relationships.retain(|r| r.target_id != req_id);
+            }
+            Ok(ApiResponse::no_content())
+        })
+    }
+
+    fn add_relationship(&self, req: &ApiRequest, id: &str) -> ApiResult {
+        let body: NewRelationship = req.json()?;
+        let author = self.author(req);
+
+        self.write(|store| {
+            let source = find_requirement_id(store, id)?;
+            check_precondition(store, source, req.if_match.as_deref(), None, None)?;
+            let target = find_requirement_id(store, &body.target)?;
+            let rel_type = RelationshipType::from_str(&body.rel_type);
+            store
+                .add_relationship_with_creator(
+                    &source,
+                    rel_type,
+                    &target,
+                    body.bidirectional,
+                    Some(author),
+                )
+                .map_err(ApiError::bad_request)?;
+            let updated = store
+                .get_requirement_by_id(&source)
+                .ok_or_else(|| ApiError::not_found("Requirement", id))?;
+            Ok(ApiResponse {
+                etag: Some(requirement_etag(updated)),
+                ..ApiResponse::json(201, &updated.relationships)
+            })
+        })
+    }
+
+    fn remove_relationship(
+        &self,
+        req: &ApiRequest,
+        id: &str,
+        rel_type: &str,
+        target: &str,
+    ) -> ApiResult {
+        let bidirectional = req.query_flag("bidirectional");
+        self.write(|store| {
+            let source = find_requirement_id(store, id)?;
+            check_precondition(store, source, req.if_match.as_deref(), None, None)?;
+            let target = find_requirement_id(store, target)?;
+            store
+                .remove_relationship(
+                    &source,
+                    &RelationshipType::from_str(rel_type),
+                    &target,
+                    bidirectional,
+                )
+                .map_err(|e| ApiError::new(404, e.to_string()))?;
+            Ok(ApiResponse::no_content())
+        })
+    }
+
+    fn add_comment(&self, req: &ApiRequest, id: &str) -> ApiResult {
+        let body: NewComment = req.json()?;
+        if body.content.trim().is_empty() {
+            return Err(ApiError::bad_request("Comment cannot be empty"));
+        }
+        let author = self.author(req);
+
+        self.write(|store| {
+            let req_id = find_requirement_id(store, id)?;
+            let requirement = store
+                .get_requirement_by_id_mut(&req_id)
+                .ok_or_else(|| ApiError::not_found("Requirement", id))?;
+            let comment = match body.parent_id {
+                Some(parent_id) => {
+                    let reply = Comment::new_reply(author, body.content, parent_id);
+                    requirement
+                        .add_reply(parent_id, reply.clone())
+                        .map_err(|e| ApiError::new(404, e.to_string()))?;
+                    reply
+                }
+                None => {
+                    let comment = Comment::new(author, body.content);
+                    requirement.add_comment(comment.clone());
+                    comment
+                }
+            };
+            Ok(ApiResponse::json(201, &comment))
+        })
+    }
+
+    fn delete_comment(&self, id: &str, comment_id: &str) -> ApiResult {
+        let comment_id =
+            Uuid::parse_str(comment_id).map_err(|_| ApiError::not_found("Comment", comment_id))?;
+        self.write(|store| {
+            let req_id = find_requirement_id(store, id)?;
+            store
+                .get_requirement_by_id_mut(&req_id)
+                .ok_or_else(|| ApiError::not_found("Requirement", id))?
+                .delete_comment(&comment_id)
+                .map_err(|e| ApiError::new(404, e.to_string()))?;
+            Ok(ApiResponse::no_content())
+        })
+    }
+
+    fn create_user(&self, req: &ApiRequest) -> ApiResult {
+        let body: NewUser = req.json()?;
+        let handle = body.handle.trim().trim_start_matches('@').to_string();
+        if body.name.trim().is_empty() || handle.is_empty() {
+            return Err(ApiError::bad_request("Name and handle are required"));
+        }
+
+        self.write(|store| {
+            if store
+                .users
+                .iter()
+                .any(|u| u.handle.eq_ignore_ascii_case(&handle))
+            {
+                return Err(ApiError::new(
+                    409,
+                    format!("Handle already in use: {}", handle),
+                ));
+            }
+            let spec_id = store.add_user_with_id(body.name, body.email, handle);
+            let user = store
+                .find_user_by_spec_id(&spec_id)
+                .ok_or_else(|| ApiError::new(500, "User was not added"))?;
+            Ok(ApiResponse::json(201, user))
+        })
+    }
+
+    fn update_user(&self, req: &ApiRequest, id: &str) -> ApiResult {
+        let body: UserUpdate = req.json()?;
+        self.write(|store| {
+            let user_id = find_user_id(store, id)?;
+            if let Some(handle) = &body.handle {
+                if store
+                    .users
+                    .iter()
+                    .any(|u| u.id != user_id && u.handle.eq_ignore_ascii_case(handle))
+                {
+                    return Err(ApiError::new(
+                        409,
+                        format!("Handle already in use: {}", handle),
+                    ));
+                }
+            }
+            let user = store
+                .get_user_by_id_mut(&user_id)
+                .ok_or_else(|| ApiError::not_found("User", id))?;
+            if let Some(name) = body.name {
+                user.name = name;
+            }
+            if let Some(email) = body.email {
+                user.email = email;
+            }
+            if let Some(handle) = body.handle {
+                user.handle = handle.trim_start_matches('@').to_string();
+            }
+            if let Some(archived) = body.archived {
+                user.archived = archived;
+            }
+            Ok(ApiResponse::json(200, user))
+        })
+    }
+
+    fn create_team(&self, req: &ApiRequest) -> ApiResult {
+        let body: NewTeam = req.json()?;
+        if body.name.trim().is_empty() {
+            return Err(ApiError::bad_request("Team name is required"));
+        }
+
+        self.write(|store| {
+            let parent = body
+                .parent
+                .as_deref()
+                .map(|p| find_team_id(store, p))
+                .transpose()?;
+            let spec_id = store.add_team_with_id(body.name, body.description, parent);
+            let team = store
+                .find_team_by_spec_id(&spec_id)
+                .ok_or_else(|| ApiError::new(500, "Team was not added"))?;
+            Ok(ApiResponse::json(201, team))
+        })
+    }
+
+    fn update_team(&self, req: &ApiRequest, id: &str) -> ApiResult {
+        let body: TeamUpdate = req.json()?;
+        self.write(|store| {
+            let team_id = find_team_id(store, id)?;
+            let parent = match body.parent.as_deref().map(str::trim) {
+                None => None,
+                Some("") => Some(None),
+                Some(p) => {
+                    let parent_id = find_team_id(store, p)?;
+                    if parent_id == team_id || store.would_create_team_cycle(&team_id, &parent_id) {
+                        return Err(ApiError::bad_request(
+                            "Team hierarchy cannot contain cycles",
+                        ));
+                    }
+                    Some(Some(parent_id))
+                }
+            };
+            let members = body
+                .members
+                .as_ref()
+                .map(|ids| {
+                    ids.iter()
+                        .map(|m| find_user_id(store, m))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?;
+
+            let team = store
+                .get_team_by_id_mut(&team_id)
+                .ok_or_else(|| ApiError::not_found("Team", id))?;
+            if let Some(name) = body.name {
+                team.name = name;
+            }
+            if let Some(description) = body.description {
+                team.description = description;
+            }
+            if let Some(parent) = parent {
+                team.parent_team_id = parent;
+            }
+            if let Some(members) = members {
+                team.member_ids = members;
+            }
+            if let Some(archived) = body.archived {
+                team.archived = archived;
+            }
+            team.modified_at = Some(Utc::now());
+            Ok(ApiResponse::json(200, team))
+        })
+    }
+}
+
+/// Rejects a write when the client's `If-Match`/`modified_at` is stale.
+///
+/// The 412 body carries the current ETag and version, plus the fields the
+/// rejected update would overwrite (from the storage conflict detection).
+fn check_precondition(
+    store: &RequirementsStore,
+    req_id: Uuid,
+    if_match: Option<&str>,
+    modified_at: Option<DateTime<Utc>>,
+    proposed: Option<&Requirement>,
+) -> std::result::Result<(), ApiError> {
+    let Some(current) = store.get_requirement_by_id(&req_id) else {
+        return Ok(());
+    };
+    let etag = requirement_etag(current);
+    let stale = if_match.is_some_and(|tags| !etag_matches(tags, &etag))
+        || modified_at
+            .is_some_and(|ts| ts.timestamp_micros() != current.modified_at.timestamp_micros());
+    if !stale {
+        return Ok(());
+    }
+
+    let conflicts: Vec<Value> = proposed
+        .map(|p| Storage::detect_field_conflicts(p, current, &current.modified_at))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| json!({ "field": c.field_name, "current_value": c.disk_value, "requested_value": c.local_value }))
+        .collect();
+
+    let mut error = ApiError::new(
+        412,
+        format!(
+            "{} was modified since your copy; reload and retry",
+            current.spec_id.as_deref().unwrap_or("Requirement")
+        ),
+    );
+    error.details = Some(json!({
+        "etag": etag,
+        "modified_at": current.modified_at,
+        "conflicting_fields": conflicts,
+        "current": current,
+    }));
+    Err(error)
+}
+
+/// True if an `If-Match`/`If-None-Match` value matches `etag` (`*` matches anything)
+fn etag_matches(header_value: &str, etag: &str) -> bool {
+    header_value
+        .split(',')
+        .map(|t| t.trim().trim_start_matches("W/"))
+        .any(|t| t == "*" || t == etag)
+}
+
+fn find_requirement_id(store: &RequirementsStore, id: &str) -> std::result::Result<Uuid, ApiError> {
+    store
+        .resolve_requirement_id(id)
+        .ok_or_else(|| ApiError::not_found("Requirement", id))
+}
+
+/// Finds a user by UUID, spec ID or handle
+fn find_user_id(store: &RequirementsStore, id: &str) -> std::result::Result<Uuid, ApiError> {
+    let handle = id.trim_start_matches('@');
+    store
+        .users
+        .iter()
+        .find(|u| {
+            u.id.to_string() == id
+                || u.spec_id
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(id))
+                || u.handle.eq_ignore_ascii_case(handle)
+        })
+        .map(|u| u.id)
+        .ok_or_else(|| ApiError::not_found("User", id))
+}
+
+/// Finds a team by UUID, spec ID or name
+fn find_team_id(store: &RequirementsStore, id: &str) -> std::result::Result<Uuid, ApiError> {
+    store
+        .teams
+        .iter()
+        .find(|t| {
+            t.id.to_string() == id
+                || t.spec_id
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(id))
+                || t.name.eq_ignore_ascii_case(id)
+        })
+        .map(|t| t.id)
+        .ok_or_else(|| ApiError::not_found("Team", id))
+}
+
+/// Finds a baseline by UUID or name
+fn find_baseline_id(store: &RequirementsStore, id: &str) -> std::result::Result<Uuid, ApiError> {
+    store
+        .baselines
+        .iter()
+        .find(|b| b.id.to_string() == id || b.name == id)
+        .map(|b| b.id)
+        .ok_or_else(|| ApiError::not_found("Baseline", id))
+}
+
+/// Decodes `%XX` escapes and `+` (as space) in a URL component
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |b: u8| (b as char).to_digit(16);
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(hi), Some(lo)) => {
+                        out.push((hi * 16 + lo) as u8);
+                        i += 3;
+                        continue;
+                    }
+                    _ => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Whether `host` (a `Host` header value, optionally with port) names the local machine
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(rest),
+        None => host.split(':').next().unwrap_or(host),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Whether every address `addr` resolves to is a loopback address
+pub fn is_loopback_addr(addr: &str) -> bool {
+    addr.to_socket_addrs()
+        .map(|addrs| {
+            let addrs: Vec<_> = addrs.collect();
+            !addrs.is_empty() && addrs.iter().all(|a| a.ip().is_loopback())
+        })
+        .unwrap_or(false)
+}
+
+/// Serves the requirements database at `path` (YAML or SQLite, by extension)
+///
+/// Non-loopback `addr`s are refused unless `allow_remote` is set.
+pub fn serve(path: &Path, addr: &str, author: Option<&str>, allow_remote: bool) -> Result<()> {
+    match backend_type_for_path(path) {
+        BackendType::Yaml => serve_backend(YamlBackend::new(path), addr, author, allow_remote),
+        BackendType::Sqlite => serve_backend(SqliteBackend::new(path)?, addr, author, allow_remote),
+    }
+}
+
+fn serve_backend<B: DatabaseBackend>(
+    backend: B,
+    addr: &str,
+    author: Option<&str>,
+    allow_remote: bool,
+) -> Result<()> {
+    let mut server = ApiServer::new(backend).with_allow_remote(allow_remote);
+    if let Some(author) = author {
+        server = server.with_author(author);
+    }
+    server.serve(addr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn server(dir: &tempfile::TempDir) -> ApiServer<YamlBackend> {
+        let backend = YamlBackend::new(dir.path().join("requirements.yaml"));
+        backend.save(&RequirementsStore::new()).unwrap();
+        ApiServer::new(backend).with_author("tester")
+    }
+
+    #[test]
+    fn test_requirement_crud_and_query() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+
+        let created = server.handle(
+            &ApiRequest::new("POST", "/api/requirements")
+                .with_body(&json!({ "title": "Login", "status": "approved", "tags": ["auth"] })),
+        );
+        assert_eq!(created.status, 201, "{}", created.body);
+        let spec_id = created.body_json()["spec_id"].as_str().unwrap().to_string();
+        assert_eq!(created.body_json()["status"], "Approved");
+
+        let fetched = server.handle(&ApiRequest::new(
+            "GET",
+            &format!("/api/requirements/{}", spec_id),
+        ));
+        assert_eq!(fetched.status, 200);
+        assert_eq!(fetched.etag, created.etag);
+
+        let listed = server.handle(&ApiRequest::new(
+            "GET",
+            "/api/requirements?tag=auth&text=log%20",
+        ));
+        assert_eq!(listed.body_json().as_array().unwrap().len(), 1);
+
+        let bad = server.handle(&ApiRequest::new("GET", "/api/requirements?colour=red"));
+        assert_eq!(bad.status, 400);
+
+        let deleted = server.handle(&ApiRequest::new(
+            "DELETE",
+            &format!("/api/requirements/{}", spec_id),
+        ));
+        assert_eq!(deleted.status, 204);
+        let missing = server.handle(&ApiRequest::new(
+            "GET",
+            &format!("/api/requirements/{}", spec_id),
+        ));
+        assert_eq!(missing.status, 404);
+    }
+
+    #[test]
+    fn test_patch_uses_etag_precondition() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+
+        let created = server.handle(
+            &ApiRequest::new("POST", "/api/requirements").with_body(&json!({ "title": "Login" })),
+        );
+        let url = format!(
+            "/api/requirements/{}",
+            created.body_json()["id"].as_str().unwrap()
+        );
+        let stale_etag = created.etag.clone().unwrap();
+
+        let first = server.handle(
+            &ApiRequest::new("PATCH", &url)
+                .with_if_match(stale_etag.clone())
+                .with_body(&json!({ "title": "Sign in" })),
+        );
+        assert_eq!(first.status, 200, "{}", first.body);
+        assert_ne!(first.etag.as_deref(), Some(stale_etag.as_str()));
+        assert_eq!(first.body_json()["history"][0]["author"], "tester");
+
+        let conflict = server.handle(
+            &ApiRequest::new("PATCH", &url)
+                .with_if_match(stale_etag)
+                .with_body(&json!({ "title": "Log in" })),
+        );
+        assert_eq!(conflict.status, 412);
+        let body = conflict.body_json();
+        assert_eq!(body["conflicting_fields"][0]["field"], "title");
+        assert_eq!(body["current"]["title"], "Sign in");
+
+        let invalid = server
+            .handle(&ApiRequest::new("PATCH", &url).with_body(&json!({ "status": "Shipped" })));
+        assert_eq!(invalid.status, 400);
+    }
+
+    #[test]
+    fn test_comments_relationships_users_and_openapi() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+        for title in ["Parent", "Child"] {
+            server.handle(
+                &ApiRequest::new("POST", "/api/requirements").with_body(&json!({ "title": title })),
+            );
+        }
+        let all = server
+            .handle(&ApiRequest::new("GET", "/api/requirements"))
+            .body_json();
+        let (parent, child) = (
+            all[0]["spec_id"].as_str().unwrap(),
+            all[1]["spec_id"].as_str().unwrap(),
+        );
+
+        let rel = server.handle(
+            &ApiRequest::new(
+                "POST",
+                &format!("/api/requirements/{}/relationships", child),
+            )
+            .with_body(&json!({ "type": "parent", "target": parent })),
+        );
+        assert_eq!(rel.status, 201, "{}", rel.body);
+
+        let comment = server.handle(
+            &ApiRequest::new("POST", &format!("/api/requirements/{}/comments", parent))
+                .with_body(&json!({ "content": "Looks good" })),
+        );
+        assert_eq!(comment.status, 201);
+        assert_eq!(comment.body_json()["author"], "tester");
+
+        let user = server.handle(
+            &ApiRequest::new("POST", "/api/users")
+                .with_body(&json!({ "name": "Alice", "handle": "@alice" })),
+        );
+        assert_eq!(user.status, 201);
+        let by_handle = server.handle(&ApiRequest::new("GET", "/api/users/alice"));
+        assert_eq!(by_handle.body_json()["name"], "Alice");
+        let duplicate = server.handle(
+            &ApiRequest::new("POST", "/api/users")
+                .with_body(&json!({ "name": "Al", "handle": "alice" })),
+        );
+        assert_eq!(duplicate.status, 409);
+
+        let spec: Value = serde_json::from_str(OPENAPI_SPEC).unwrap();
+        assert!(spec["paths"]["/api/requirements/{id}"]["patch"].is_object());
+    }
+
+    #[test]
+    fn test_rejects_foreign_host_origin_and_non_json_writes() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+
+        let local =
+            ApiRequest::new("GET", "/api/requirements").with_header("Host", "localhost:8787");
+        assert_eq!(server.handle(&local).status, 200);
+        let same_origin = local.clone().with_header("Origin", "http://localhost:8787");
+        assert_eq!(server.handle(&same_origin).status, 200);
+
+        let rebound =
+            ApiRequest::new("GET", "/api/requirements").with_header("Host", "evil.example:8787");
+        assert_eq!(server.handle(&rebound).status, 403);
+        let foreign = local.with_header("Origin", "https://evil.example");
+        assert_eq!(server.handle(&foreign).status, 403);
+
+        let mut form_post =
+            ApiRequest::new("POST", "/api/requirements").with_body(&json!({ "title": "Forged" }));
+        form_post.content_type = Some(String::from("text/plain"));
+        assert_eq!(server.handle(&form_post).status, 415);
+        // DELETE has no body, so it needs no Content-Type
+        let delete = ApiRequest::new("DELETE", "/api/requirements/FR-001");
+        assert_eq!(server.handle(&delete).status, 404);
+
+        let remote = ApiServer::new(YamlBackend::new(dir.path().join("requirements.yaml")))
+            .with_allow_remote(true);
+        assert_eq!(remote.handle(&rebound).status, 200);
+        assert!(server.serve("0.0.0.0:0").is_err());
+        assert!(is_loopback_addr("127.0.0.1:0") && !is_loopback_addr("0.0.0.0:0"));
+    }
+
+    #[test]
+    fn test_delete_removes_incoming_relationships() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+        for title in ["Parent", "Child"] {
+            server.handle(
+                &ApiRequest::new("POST", "/api/requirements").with_body(&json!({ "title": title })),
+            );
+        }
+        let all = server
+            .handle(&ApiRequest::new("GET", "/api/requirements"))
+            .body_json();
+        let (parent, child) = (
+            all[0]["spec_id"].as_str().unwrap(),
+            all[1]["spec_id"].as_str().unwrap(),
+        );
+        server.handle(
+            &ApiRequest::new(
+                "POST",
+                &format!("/api/requirements/{}/relationships", child),
+            )
+            .with_body(&json!({ "type": "parent", "target": parent })),
+        );
+
+        let deleted = server.handle(&ApiRequest::new(
+            "DELETE",
+            &format!("/api/requirements/{}", parent),
+        ));
+        assert_eq!(deleted.status, 204);
+        let remaining = server.handle(&ApiRequest::new(
+            "GET",
+            &format!("/api/requirements/{}", child),
+        ));
+        assert_eq!(
+            remaining.body_json()["relationships"]
+                .as_array()
+                .map_or(0, Vec::len),
+            0
+        );
+    }
+
+    #[test]
+    fn test_responses_include_automation_edits() {
+        let dir = tempdir().unwrap();
+        let backend = YamlBackend::new(dir.path().join("requirements.yaml"));
+        let mut store = RequirementsStore::new();
+        store.scripts.push(crate::scripting::ScriptConfig {
+            name: "gdpr-tag".into(),
+            kind: crate::scripting::ScriptKind::Automate,
+            source: r#"if req.description.contains("personal data") && !req.tags.contains("gdpr") {
+                req.tags.push("gdpr");
+            }"#
+            .into(),
+            file: None,
+        });
+        backend.save(&store).unwrap();
+        let server = ApiServer::new(backend).with_author("tester");
+
+        let created = server.handle(
+            &ApiRequest::new("POST", "/api/requirements").with_body(&json!({ "title": "Export" })),
+        );
+        assert_eq!(created.status, 201, "{}", created.body);
+        let url = format!(
+            "/api/requirements/{}",
+            created.body_json()["id"].as_str().unwrap()
+        );
+
+        let updated = server.handle(
+            &ApiRequest::new("PATCH", &url)
+                .with_body(&json!({ "description": "Exports personal data" })),
+        );
+        assert_eq!(updated.status, 200, "{}", updated.body);
+        assert_eq!(updated.body_json()["tags"], json!(["gdpr"]));
+        let fetched = server.handle(&ApiRequest::new("GET", &url));
+        assert_eq!(updated.etag, fetched.etag);
+    }
+
+    #[test]
+    fn test_teams_and_baselines_persist_on_sqlite() {
+        let dir = tempdir().unwrap();
+        let backend = SqliteBackend::new(dir.path().join("requirements.db")).unwrap();
+        let server = ApiServer::new(backend).with_author("tester");
+
+        let team = server.handle(
+            &ApiRequest::new("POST", "/api/teams").with_body(&json!({ "name": "Platform" })),
+        );
+        assert_eq!(team.status, 201, "{}", team.body);
+        let baseline = server
+            .handle(&ApiRequest::new("POST", "/api/baselines").with_body(&json!({ "name": "v1" })));
+        assert_eq!(baseline.status, 201, "{}", baseline.body);
+
+        let teams = server
+            .handle(&ApiRequest::new("GET", "/api/teams"))
+            .body_json();
+        assert_eq!(teams[0]["name"], "Platform");
+        let fetched = server.handle(&ApiRequest::new("GET", "/api/baselines/v1"));
+        assert_eq!(fetched.status, 200, "{}", fetched.body);
+        assert_eq!(fetched.body_json()["name"], "v1");
+    }
+
+    #[test]
+    fn test_rejected_write_leaves_file_untouched() {
+        let dir = tempdir().unwrap();
+        let server = server(&dir);
+        let path = dir.path().join("requirements.yaml");
+        let content = format!("# hand-edited\n{}", std::fs::read_to_string(&path).unwrap());
+        std::fs::write(&path, &content).unwrap();
+
+        let missing = server.handle(
+            &ApiRequest::new("PATCH", "/api/requirements/FR-404")
+                .with_body(&json!({ "title": "Nope" })),
+        );
+        assert_eq!(missing.status, 404);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+    }
+}
@@ -6,6 +6,8 @@ use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -191,6 +193,82 @@ impl SessionInfo {
     }
 }
 
+/// Seconds between heartbeats of a registered session
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 5;
+
+enum HeartbeatCommand {
+    SetEditLock(Option<EditLock>),
+    Shutdown,
+}
+
+/// A session registered in the lock file, kept alive by a background thread
+///
+/// Dropping the handle unregisters the session.
+pub struct SessionHeartbeat {
+    storage: Storage,
+    session_id: String,
+    sender: mpsc::Sender<HeartbeatCommand>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl SessionHeartbeat {
+    /// Registers the session and starts sending heartbeats
+    pub fn start(storage: &Storage, session: SessionInfo) -> Result<(Self, LockFileInfo)> {
+        let session_id = session.session_id.clone();
+        let info = storage.register_session(session.clone())?;
+
+        let (sender, receiver) = mpsc::channel();
+        let heartbeat_storage = Storage::new(storage.path());
+        let thread = thread::spawn(move || {
+            let mut session = session;
+            loop {
+                match receiver.recv_timeout(Duration::from_secs(HEARTBEAT_INTERVAL_SECS)) {
+                    Ok(HeartbeatCommand::SetEditLock(lock)) => session.editing_requirement = lock,
+                    Ok(HeartbeatCommand::Shutdown) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                        break
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                }
+                // Registering again rather than updating restores the session
+                // after a save has rewritten the lock file
+                session.last_heartbeat = Utc::now();
+                let _ = heartbeat_storage.register_session(session.clone());
+            }
+        });
+
+        Ok((
+            Self {
+                storage: Storage::new(storage.path()),
+                session_id,
+                sender,
+                thread: Some(thread),
+            },
+            info,
+        ))
+    }
+
+    /// ID of the registered session
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Announces the requirement being edited, or `None` when editing stops
+    pub fn set_edit_lock(&self, lock: Option<EditLock>) {
+        let _ = self.sender.send(HeartbeatCommand::SetEditLock(lock));
+    }
+}
+
+impl Drop for SessionHeartbeat {
+    fn drop(&mut self) {
+        let _ = self.sender.send(HeartbeatCommand::Shutdown);
+        // Wait for an in-flight heartbeat so it can't re-register the session
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        let _ = self.storage.unregister_session(&self.session_id);
+    }
+}
+
 /// Handles saving and loading requirements from disk with file locking
 /// for rudimentary multi-user support
 pub struct Storage {
@@ -453,11 +531,34 @@ impl Storage {
             chrono::Utc::now().to_rfc3339()
         );
 
+        // Keep the previous version for hooks and scripts, if any are configured
+        let before: Option<RequirementsStore> = if store.hooks.is_empty() && store.scripts.is_empty() {
+            None
+        } else {
+            File::open(&self.file_path)
+                .ok()
+                .and_then(|file| serde_yaml::from_reader(BufReader::new(file)).ok())
+        };
+
+        // Scripts may edit requirements, so they work on a copy
+        let scripted;
+        let store = if store.scripts.is_empty() {
+            store
+        } else {
+            let mut copy = store.clone();
+            self.run_scripts(before.as_ref(), &mut copy)?;
+            scripted = copy;
+            &scripted
+        };
+
         // Serialize and write to file
         let yaml = serde_yaml::to_string(store)?;
         fs::write(&self.file_path, yaml)?;
 
-        // Lock is automatically released when lock_file is dropped
+        drop(lock_file);
+        if let Some(before) = before {
+            self.dispatch_events(&before, store);
+        }
         Ok(())
     }
 
@@ -483,6 +584,19 @@ impl Storage {
     pub fn update_atomically<F>(&self, update_fn: F) -> Result<RequirementsStore>
     where
         F: FnOnce(&mut RequirementsStore),
+    {
+        let saved = self.update_atomically_if(|store| {
+            update_fn(store);
+            true
+        })?;
+        Ok(saved.expect("update is always saved"))
+    }
+
+    /// Like [`Self::update_atomically`], but nothing is written if `update_fn`
+    /// returns false; returns the saved store, or `None` if the update was dropped
+    pub fn update_atomically_if<F>(&self, update_fn: F) -> Result<Option<RequirementsStore>>
+    where
+        F: FnOnce(&mut RequirementsStore) -> bool,
     {
         // Acquire exclusive lock
         let mut lock_file = self.acquire_write_lock()?;
@@ -503,14 +617,21 @@ impl Storage {
             .with_context(|| format!("Failed to parse YAML from {:?}", self.file_path))?;
 
         // Apply the update
-        update_fn(&mut store);
+        let before = (!store.hooks.is_empty() || !store.scripts.is_empty()).then(|| store.clone());
+        if !update_fn(&mut store) {
+            return Ok(None);
+        }
+        self.run_scripts(before.as_ref(), &mut store)?;
 
         // Save back
         let yaml = serde_yaml::to_string(&store)?;
         fs::write(&self.file_path, yaml)?;
 
-        // Lock is released when lock_file is dropped
-        Ok(store)
+        drop(lock_file);
+        if let Some(before) = before {
+            self.dispatch_events(&before, &store);
+        }
+        Ok(Some(store))
     }
 
     // trace:FR-0153 | ai:claude:high
@@ -640,11 +761,17 @@ impl Storage {
         final_store.ai_prompts = local_store.ai_prompts.clone();
         final_store.allowed_prefixes = local_store.allowed_prefixes.clone();
         final_store.restrict_prefixes = local_store.restrict_prefixes;
+        final_store.hooks = local_store.hooks.clone();
+        final_store.scripts = local_store.scripts.clone();
+        self.run_scripts(Some(&disk_store), &mut final_store)?;
 
         // Save the merged/updated store
         let yaml = serde_yaml::to_string(&final_store)?;
         fs::write(&self.file_path, yaml)?;
 
+        drop(lock_file);
+        self.dispatch_events(&disk_store, &final_store);
+
         if merged_count > 0 {
             Ok(SaveResult::Merged { merged_count })
         } else {
@@ -654,7 +781,7 @@ impl Storage {
 
     /// Detect field-level conflicts between local and disk versions
     /// Returns list of fields that were modified both locally and externally
-    fn detect_field_conflicts(
+    pub(crate) fn detect_field_conflicts(
         local: &Requirement,
         disk: &Requirement,
         _original_timestamp: &DateTime<Utc>,
@@ -743,6 +870,22 @@ impl Storage {
             });
         }
 
+        if local.acceptance_criteria != disk.acceptance_criteria {
+            let checklist = |req: &Requirement| {
+                req.acceptance_criteria
+                    .iter()
+                    .map(|ac| ac.checklist_line())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            conflicts.push(FieldConflict {
+                field_name: "acceptance_criteria".to_string(),
+                original_value: String::new(),
+                disk_value: checklist(disk),
+                local_value: checklist(local),
+            });
+        }
+
         conflicts
     }
 
@@ -796,6 +939,18 @@ impl Storage {
             }
         }
 
+        // Acceptance criteria are not merged: their "AC-N" IDs are assigned
+        // per session, so differing lists are reported as a conflict instead
+
+        // Merge linked commits: include all from both (dedupe by sha)
+        let existing_commits: std::collections::HashSet<_> =
+            merged.commits.iter().map(|c| c.sha.clone()).collect();
+        for commit in &disk.commits {
+            if !existing_commits.contains(&commit.sha) {
+                merged.commits.push(commit.clone());
+            }
+        }
+
         // Keep the later modified_at timestamp
         if disk.modified_at > merged.modified_at {
             merged.modified_at = disk.modified_at;
@@ -834,6 +989,8 @@ impl Storage {
         } else {
             RequirementsStore::new()
         };
+        let before = (!disk_store.hooks.is_empty() || !disk_store.scripts.is_empty())
+            .then(|| disk_store.clone());
 
         match resolution {
             ConflictResolution::ForceLocal => {
@@ -864,13 +1021,40 @@ impl Storage {
             }
         }
 
+        self.run_scripts(before.as_ref(), &mut disk_store)?;
+
         // Save the updated store
         let yaml = serde_yaml::to_string(&disk_store)?;
         fs::write(&self.file_path, yaml)?;
 
+        drop(lock_file);
+        if let Some(before) = before {
+            self.dispatch_events(&before, &disk_store);
+        }
         Ok(disk_store)
     }
 
+    /// Sends the changes between two versions of the store to the configured hooks
+    fn dispatch_events(&self, before: &RequirementsStore, after: &RequirementsStore) {
+        crate::events::dispatch(before, after, self.project_dir());
+    }
+
+    /// Runs the project scripts on the requirements changed by a write
+    fn run_scripts(&self, before: Option<&RequirementsStore>, after: &mut RequirementsStore) -> Result<()> {
+        crate::scripting::apply_scripts(before, after, self.project_dir())
+    }
+
+    /// Directory holding the requirements file, `.` for a bare file name
+    ///
+    /// Relative hook and script paths are resolved against it, and it is the
+    /// default root for source scans and git sync.
+    pub fn project_dir(&self) -> &Path {
+        self.file_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+    }
+
     /// Get a snapshot of requirement timestamps for conflict detection
     pub fn get_requirement_timestamps(store: &RequirementsStore) -> HashMap<Uuid, DateTime<Utc>> {
         store
@@ -931,6 +1115,9 @@ impl Storage {
             RequirementsStore::new()
         };
 
+        let before = (!local_store.hooks.is_empty() || !local_store.scripts.is_empty())
+            .then(|| disk_store.clone());
+
         // Count external requirement additions (requirements in disk but not in local)
         let local_req_ids: std::collections::HashSet<Uuid> = local_store
             .requirements
@@ -957,6 +1144,8 @@ impl Storage {
         disk_store.ai_prompts = local_store.ai_prompts.clone();
         disk_store.allowed_prefixes = local_store.allowed_prefixes.clone();
         disk_store.restrict_prefixes = local_store.restrict_prefixes;
+        disk_store.hooks = local_store.hooks.clone();
+        disk_store.scripts = local_store.scripts.clone();
 
         // Generate SPEC-ID using the fresh disk store state (which has all existing IDs)
         // This ensures we don't create duplicates
@@ -968,11 +1157,16 @@ impl Storage {
             .last()
             .and_then(|r| r.spec_id.clone())
             .unwrap_or_default();
+        self.run_scripts(before.as_ref(), &mut disk_store)?;
 
         // Save the updated store
         let yaml = serde_yaml::to_string(&disk_store)?;
         fs::write(&self.file_path, yaml)?;
 
+        drop(lock_file);
+        if let Some(before) = before {
+            self.dispatch_events(&before, &disk_store);
+        }
         Ok(AddResult {
             store: disk_store,
             external_changes_merged,
@@ -984,6 +1178,7 @@ impl Storage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::AcceptanceCriterion;
     use tempfile::TempDir;
 
     fn create_test_store() -> RequirementsStore {
@@ -996,6 +1191,15 @@ mod tests {
         Requirement::new(title.to_string(), format!("Description for {}", title))
     }
 
+    #[test]
+    fn test_project_dir_of_bare_file_name_is_current_dir() {
+        assert_eq!(Storage::new("requirements.yaml").project_dir(), Path::new("."));
+        assert_eq!(
+            Storage::new("docs/requirements.yaml").project_dir(),
+            Path::new("docs")
+        );
+    }
+
     #[test]
     fn test_save_and_load() {
         let temp_dir = TempDir::new().unwrap();
@@ -1092,6 +1296,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_acceptance_criteria_added_on_both_sides_conflict() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().join("test.yaml"));
+        let mut store = create_test_store();
+        let req = create_test_requirement("Test Req");
+        let req_id = req.id;
+        store.requirements.push(req);
+        storage.save(&store).unwrap();
+
+        let timestamps = Storage::get_requirement_timestamps(&store);
+        let mut local_store = store.clone();
+
+        // Both sessions add what becomes AC-1
+        store.requirements[0]
+            .add_acceptance_criterion(AcceptanceCriterion::new("", "External"), "bob");
+        store.requirements[0].modified_at = Utc::now();
+        storage.save(&store).unwrap();
+        local_store.requirements[0]
+            .add_acceptance_criterion(AcceptanceCriterion::new("", "Local"), "alice");
+        local_store.requirements[0].modified_at = Utc::now();
+
+        match storage
+            .save_with_conflict_detection(&local_store, &timestamps, &[req_id])
+            .unwrap()
+        {
+            SaveResult::Conflict(info) => {
+                let field = &info.conflicting_fields[0];
+                assert_eq!(field.field_name, "acceptance_criteria");
+                assert!(field.disk_value.contains("External"));
+                assert!(field.local_value.contains("Local"));
+            }
+            _ => panic!("Expected SaveResult::Conflict"),
+        }
+    }
+
     #[test]
     fn test_conflict_resolution_force_local() {
         let temp_dir = TempDir::new().unwrap();
@@ -1259,4 +1499,27 @@ mod tests {
         assert!(final_store.requirements.iter().any(|r| r.id == req1_id));
         assert!(!final_store.requirements.iter().any(|r| r.id == req2_id));
     }
+
+    #[test]
+    fn test_session_heartbeat_registers_and_unregisters() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().join("test.yaml"));
+        let session = SessionInfo {
+            session_id: "tui-1".to_string(),
+            user_name: "alice".to_string(),
+            hostname: "box".to_string(),
+            pid: std::process::id(),
+            started_at: Utc::now(),
+            last_heartbeat: Utc::now(),
+            editing_requirement: None,
+        };
+
+        let (heartbeat, info) = SessionHeartbeat::start(&storage, session).unwrap();
+        assert_eq!(heartbeat.session_id(), "tui-1");
+        assert!(info.sessions.contains_key("tui-1"));
+        assert_eq!(storage.get_active_sessions().unwrap().sessions.len(), 1);
+
+        drop(heartbeat);
+        assert!(storage.get_active_sessions().unwrap().sessions.is_empty());
+    }
 }
@@ -0,0 +1,300 @@
+//! Requirement templates per type
+//!
+//! Each `CustomTypeDefinition` may carry a description template and default
+//! custom field values. Templates may contain placeholders that are filled
+//! in when a requirement is created:
+//! - `{{user}}` - the current user
+//! - `{{date}}` / `{{datetime}}` - creation date (UTC)
+//! - `{{feature}}` - the selected feature
+//! - `{{type}}` - the requirement type
+//!
+//! Teams can share templates through a library of YAML files stored in
+//! `<templates dir>/types/`. Each file maps type names to templates; the
+//! project's own type definition takes precedence over the library.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{RequirementType, RequirementsStore};
+
+/// Values substituted into template placeholders
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    pub user: String,
+    pub feature: String,
+    pub type_name: String,
+    pub date: String,
+    pub datetime: String,
+}
+
+impl TemplateContext {
+    /// Creates a context stamped with the current date and time
+    pub fn new(
+        user: impl Into<String>,
+        feature: impl Into<String>,
+        req_type: &RequirementType,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            user: user.into(),
+            feature: feature.into(),
+            type_name: req_type.to_string(),
+            date: now.format("%Y-%m-%d").to_string(),
+            datetime: now.format("%Y-%m-%d %H:%M UTC").to_string(),
+        }
+    }
+
+    /// Replaces known placeholders in `template`; unknown ones are left as-is
+    pub fn render(&self, template: &str) -> String {
+        template
+            .replace("{{user}}", &self.user)
+            .replace("{{feature}}", &self.feature)
+            .replace("{{type}}", &self.type_name)
+            .replace("{{datetime}}", &self.datetime)
+            .replace("{{date}}", &self.date)
+    }
+}
+
+/// A template for one requirement type, as stored in a shared library
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeTemplate {
+    /// Description template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Default custom field values (key = field name)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub field_defaults: HashMap<String, String>,
+}
+
+/// Shared collection of type templates keyed by type name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TypeTemplateLibrary {
+    #[serde(default)]
+    pub templates: BTreeMap<String, TypeTemplate>,
+}
+
+impl TypeTemplateLibrary {
+    /// Directory holding shared type templates (`<templates dir>/types/`)
+    pub fn default_dir() -> Result<PathBuf> {
+        Ok(crate::registry::get_templates_dir()?.join("types"))
+    }
+
+    /// Loads the library from the default directory (empty if it doesn't exist)
+    pub fn load_default() -> Result<Self> {
+        Self::load_dir(&Self::default_dir()?)
+    }
+
+    /// Loads and merges every `*.yaml` file in `dir`, in file name order.
+    /// Later files override earlier ones for the same type.
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        let mut library = Self::default();
+        if !dir.is_dir() {
+            return Ok(library);
+        }
+
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext == "yaml" || ext == "yml")
+            })
+            .collect();
+        files.sort();
+
+        for file in files {
+            library.templates.extend(Self::load_file(&file)?.templates);
+        }
+        Ok(library)
+    }
+
+    /// Loads a single library file (empty if it doesn't exist)
+    pub fn load_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse template library {}", path.display()))
+    }
+
+    /// Saves the library to a YAML file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Gets the template for a type name (case-insensitive)
+    pub fn get(&self, type_name: &str) -> Option<&TypeTemplate> {
+        self.templates
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(type_name))
+            .map(|(_, t)| t)
+    }
+}
+
+/// A template rendered for a new requirement
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateInstance {
+    pub description: String,
+    pub custom_fields: HashMap<String, String>,
+}
+
+impl TemplateInstance {
+    /// Returns true if the template provides nothing
+    pub fn is_empty(&self) -> bool {
+        self.description.is_empty() && self.custom_fields.is_empty()
+    }
+}
+
+impl RequirementsStore {
+    /// Resolves the template for a type: the project definition first, then the library
+    pub fn get_type_template(
+        &self,
+        req_type: &RequirementType,
+        library: &TypeTemplateLibrary,
+    ) -> TypeTemplate {
+        let type_def = self.get_type_definition(req_type);
+        let shared = type_def
+            .and_then(|td| library.get(&td.name))
+            .or_else(|| library.get(&req_type.to_string()));
+
+        let mut template = shared.cloned().unwrap_or_default();
+        if let Some(td) = type_def {
+            if td.description_template.is_some() {
+                template.description = td.description_template.clone();
+            }
+            template.field_defaults.extend(
+                td.field_defaults
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone())),
+            );
+        }
+        template
+    }
+
+    /// Renders the template for a type, including custom field `default_value`s
+    pub fn instantiate_type_template(
+        &self,
+        req_type: &RequirementType,
+        ctx: &TemplateContext,
+        library: &TypeTemplateLibrary,
+    ) -> TemplateInstance {
+        let template = self.get_type_template(req_type, library);
+
+        let mut custom_fields = HashMap::new();
+        for field in self.get_custom_fields_for_type(req_type) {
+            if let Some(default) = &field.default_value {
+                custom_fields.insert(field.name.clone(), ctx.render(default));
+            }
+        }
+        for (name, value) in &template.field_defaults {
+            custom_fields.insert(name.clone(), ctx.render(value));
+        }
+
+        TemplateInstance {
+            description: template
+                .description
+                .map(|d| ctx.render(&d))
+                .unwrap_or_default(),
+            custom_fields,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn ctx() -> TemplateContext {
+        TemplateContext {
+            user: "alice".to_string(),
+            feature: "Auth".to_string(),
+            type_name: "Bug".to_string(),
+            date: "2024-01-02".to_string(),
+            datetime: "2024-01-02 10:00 UTC".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let rendered = ctx().render("{{user}} on {{date}} in {{feature}} ({{type}}) {{unknown}}");
+        assert_eq!(rendered, "alice on 2024-01-02 in Auth (Bug) {{unknown}}");
+    }
+
+    #[test]
+    fn test_instantiate_builtin_templates() {
+        let store = RequirementsStore::new();
+
+        let bug = store.instantiate_type_template(
+            &RequirementType::Bug,
+            &ctx(),
+            &TypeTemplateLibrary::default(),
+        );
+        assert!(bug.description.contains("## Steps to Reproduce"));
+        assert!(bug.description.contains("Reported by alice on 2024-01-02"));
+
+        let story = store.instantiate_type_template(
+            &RequirementType::Story,
+            &ctx(),
+            &TypeTemplateLibrary::default(),
+        );
+        assert!(story.description.starts_with("As a "));
+
+        let cr = store.instantiate_type_template(
+            &RequirementType::ChangeRequest,
+            &ctx(),
+            &TypeTemplateLibrary::default(),
+        );
+        assert_eq!(
+            cr.custom_fields.get("requested_by"),
+            Some(&"alice".to_string())
+        );
+    }
+
+    #[test]
+    fn test_library_fallback_and_precedence() -> Result<()> {
+        let dir = tempdir()?;
+        let mut library = TypeTemplateLibrary::default();
+        library.templates.insert(
+            "Task".to_string(),
+            TypeTemplate {
+                description: Some("Task for {{feature}}".to_string()),
+                field_defaults: HashMap::from([("assignee".to_string(), "{{user}}".to_string())]),
+            },
+        );
+        library.templates.insert(
+            "Bug".to_string(),
+            TypeTemplate {
+                description: Some("Shared bug template".to_string()),
+                field_defaults: HashMap::new(),
+            },
+        );
+        library.save(&dir.path().join("team.yaml"))?;
+
+        let loaded = TypeTemplateLibrary::load_dir(dir.path())?;
+        let store = RequirementsStore::new();
+
+        // Task has no project template, so the library is used
+        let task = store.instantiate_type_template(&RequirementType::Task, &ctx(), &loaded);
+        assert_eq!(task.description, "Task for Auth");
+        assert_eq!(
+            task.custom_fields.get("assignee"),
+            Some(&"alice".to_string())
+        );
+
+        // Bug has a project template, which wins over the library
+        let bug = store.instantiate_type_template(&RequirementType::Bug, &ctx(), &loaded);
+        assert!(bug.description.contains("## Steps to Reproduce"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,661 @@
+//! Test case and test run management
+//!
+//! Test cases describe how requirements are verified: preconditions, steps
+//! with expected results, and the requirements they verify. Test runs record
+//! the outcome of executing test cases against a build in an environment.
+//! The latest outcome of each test case rolls up onto the requirements it
+//! verifies, which feeds the coverage report.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::models::RequirementsStore;
+
+/// Meta-type prefix for test case IDs (e.g., "TC-001")
+pub const META_PREFIX_TEST_CASE: &str = "TC";
+
+/// Meta-type prefix for test run IDs (e.g., "RUN-001")
+pub const META_PREFIX_TEST_RUN: &str = "RUN";
+
+/// Outcome of executing a test case
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum TestOutcome {
+    #[default]
+    NotRun,
+    Passed,
+    Failed,
+    Blocked,
+    Skipped,
+}
+
+impl fmt::Display for TestOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestOutcome::NotRun => write!(f, "Not Run"),
+            TestOutcome::Passed => write!(f, "Passed"),
+            TestOutcome::Failed => write!(f, "Failed"),
+            TestOutcome::Blocked => write!(f, "Blocked"),
+            TestOutcome::Skipped => write!(f, "Skipped"),
+        }
+    }
+}
+
+impl TestOutcome {
+    /// Parses an outcome name (case-insensitive, accepts "pass", "fail", "skip")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s
+            .trim()
+            .to_lowercase()
+            .replace(['-', '_', ' '], "")
+            .as_str()
+        {
+            "notrun" | "pending" => Some(TestOutcome::NotRun),
+            "pass" | "passed" => Some(TestOutcome::Passed),
+            "fail" | "failed" => Some(TestOutcome::Failed),
+            "blocked" => Some(TestOutcome::Blocked),
+            "skip" | "skipped" => Some(TestOutcome::Skipped),
+            _ => None,
+        }
+    }
+
+    /// Short symbol for compact displays
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TestOutcome::NotRun => "○",
+            TestOutcome::Passed => "✓",
+            TestOutcome::Failed => "✗",
+            TestOutcome::Blocked => "⊘",
+            TestOutcome::Skipped => "-",
+        }
+    }
+}
+
+/// A single step of a manual test case
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TestStep {
+    /// Action to perform
+    pub action: String,
+
+    /// Expected result of the action
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub expected: String,
+}
+
+/// A test case verifying one or more requirements
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestCase {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Human-friendly ID (e.g., "TC-001")
+    pub spec_id: String,
+
+    /// Short title
+    pub title: String,
+
+    /// Longer description
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+
+    /// Preconditions that must hold before executing the steps
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub preconditions: String,
+
+    /// Ordered steps with expected results
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<TestStep>,
+
+    /// Overall expected result
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub expected_result: String,
+
+    /// Requirements verified by this test case
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verifies: Vec<Uuid>,
+
+    /// Name of the automated test implementing this case, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automated_test: Option<String>,
+
+    /// When the test case was created
+    pub created_at: DateTime<Utc>,
+
+    /// Who created the test case
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+
+    /// Whether the test case is archived
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+}
+
+impl TestCase {
+    /// Creates a new test case (spec_id is assigned by `RequirementsStore::add_test_case`)
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            spec_id: String::new(),
+            title: title.into(),
+            description: String::new(),
+            preconditions: String::new(),
+            steps: Vec::new(),
+            expected_result: String::new(),
+            verifies: Vec::new(),
+            automated_test: None,
+            created_at: Utc::now(),
+            created_by: None,
+            archived: false,
+        }
+    }
+
+    /// Sets the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the preconditions
+    pub fn with_preconditions(mut self, preconditions: impl Into<String>) -> Self {
+        self.preconditions = preconditions.into();
+        self
+    }
+
+    /// Appends a step
+    pub fn with_step(mut self, action: impl Into<String>, expected: impl Into<String>) -> Self {
+        self.steps.push(TestStep {
+            action: action.into(),
+            expected: expected.into(),
+        });
+        self
+    }
+
+    /// Sets the overall expected result
+    pub fn with_expected_result(mut self, expected: impl Into<String>) -> Self {
+        self.expected_result = expected.into();
+        self
+    }
+
+    /// Adds a verified requirement
+    pub fn with_verifies(mut self, requirement_id: Uuid) -> Self {
+        if !self.verifies.contains(&requirement_id) {
+            self.verifies.push(requirement_id);
+        }
+        self
+    }
+
+    /// Sets the automated test name
+    pub fn with_automated_test(mut self, name: impl Into<String>) -> Self {
+        self.automated_test = Some(name.into());
+        self
+    }
+
+    /// Sets the creator
+    pub fn with_created_by(mut self, created_by: impl Into<String>) -> Self {
+        self.created_by = Some(created_by.into());
+        self
+    }
+}
+
+/// Result of one test case within a run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestResult {
+    /// Test case this result belongs to
+    pub case_id: Uuid,
+
+    /// Outcome of the execution
+    pub outcome: TestOutcome,
+
+    /// Free-form notes (failure message, reason for blocking, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// Execution time in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+}
+
+/// An execution of a set of test cases
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestRun {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Human-friendly ID (e.g., "RUN-001")
+    pub spec_id: String,
+
+    /// Name of the run (e.g., "Nightly regression")
+    pub name: String,
+
+    /// Environment the tests ran in (e.g., "staging", "linux-x86_64")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+
+    /// Build or version under test
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<String>,
+
+    /// When the run was executed
+    pub executed_at: DateTime<Utc>,
+
+    /// Who executed the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executed_by: Option<String>,
+
+    /// Per-case results
+    #[serde(default)]
+    pub results: Vec<TestResult>,
+}
+
+impl TestRun {
+    /// Creates a new run executed now (spec_id is assigned by `RequirementsStore::add_test_run`)
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            spec_id: String::new(),
+            name: name.into(),
+            environment: None,
+            build: None,
+            executed_at: Utc::now(),
+            executed_by: None,
+            results: Vec::new(),
+        }
+    }
+
+    /// Sets the environment
+    pub fn with_environment(mut self, environment: impl Into<String>) -> Self {
+        self.environment = Some(environment.into());
+        self
+    }
+
+    /// Sets the build under test
+    pub fn with_build(mut self, build: impl Into<String>) -> Self {
+        self.build = Some(build.into());
+        self
+    }
+
+    /// Sets who executed the run
+    pub fn with_executed_by(mut self, executed_by: impl Into<String>) -> Self {
+        self.executed_by = Some(executed_by.into());
+        self
+    }
+
+    /// Records the outcome of a test case, replacing any earlier result for it in this run
+    pub fn record(&mut self, case_id: Uuid, outcome: TestOutcome) -> &mut TestResult {
+        let idx = match self.results.iter().position(|r| r.case_id == case_id) {
+            Some(idx) => {
+                self.results[idx].outcome = outcome;
+                idx
+            }
+            None => {
+                self.results.push(TestResult {
+                    case_id,
+                    outcome,
+                    notes: None,
+                    duration_secs: None,
+                });
+                self.results.len() - 1
+            }
+        };
+        &mut self.results[idx]
+    }
+
+    /// Gets the result for a test case
+    pub fn result_for(&self, case_id: &Uuid) -> Option<&TestResult> {
+        self.results.iter().find(|r| &r.case_id == case_id)
+    }
+
+    /// Counts the outcomes in this run
+    pub fn summary(&self) -> VerificationSummary {
+        let mut summary = VerificationSummary::default();
+        for result in &self.results {
+            summary.add(result.outcome);
+        }
+        summary
+    }
+}
+
+/// Rolled-up verification state of a requirement
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum VerificationStatus {
+    /// No test cases verify the requirement
+    #[default]
+    Unverified,
+    /// Test cases exist but none has been run
+    NotRun,
+    /// Some test cases passed, others have not been run or were skipped
+    Partial,
+    /// All test cases passed
+    Passing,
+    /// At least one test case is blocked (and none failed)
+    Blocked,
+    /// At least one test case failed
+    Failing,
+}
+
+impl fmt::Display for VerificationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationStatus::Unverified => write!(f, "Unverified"),
+            VerificationStatus::NotRun => write!(f, "Not Run"),
+            VerificationStatus::Partial => write!(f, "Partial"),
+            VerificationStatus::Passing => write!(f, "Passing"),
+            VerificationStatus::Blocked => write!(f, "Blocked"),
+            VerificationStatus::Failing => write!(f, "Failing"),
+        }
+    }
+}
+
+/// Outcome counts for a set of test cases
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct VerificationSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub blocked: usize,
+    pub skipped: usize,
+    pub not_run: usize,
+}
+
+impl VerificationSummary {
+    /// Counts one outcome
+    pub fn add(&mut self, outcome: TestOutcome) {
+        self.total += 1;
+        match outcome {
+            TestOutcome::NotRun => self.not_run += 1,
+            TestOutcome::Passed => self.passed += 1,
+            TestOutcome::Failed => self.failed += 1,
+            TestOutcome::Blocked => self.blocked += 1,
+            TestOutcome::Skipped => self.skipped += 1,
+        }
+    }
+
+    /// Derives the rolled-up status from the counts
+    pub fn status(&self) -> VerificationStatus {
+        if self.total == 0 {
+            VerificationStatus::Unverified
+        } else if self.failed > 0 {
+            VerificationStatus::Failing
+        } else if self.blocked > 0 {
+            VerificationStatus::Blocked
+        } else if self.passed == self.total {
+            VerificationStatus::Passing
+        } else if self.passed > 0 {
+            VerificationStatus::Partial
+        } else {
+            VerificationStatus::NotRun
+        }
+    }
+}
+
+/// One requirement's line in the coverage report
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageEntry {
+    pub requirement_id: Uuid,
+    pub spec_id: Option<String>,
+    pub title: String,
+    pub feature: String,
+    pub summary: VerificationSummary,
+}
+
+/// Test coverage of all active requirements
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoverageReport {
+    pub entries: Vec<CoverageEntry>,
+}
+
+impl CoverageReport {
+    /// Number of requirements with at least one test case
+    pub fn covered(&self) -> usize {
+        self.entries.iter().filter(|e| e.summary.total > 0).count()
+    }
+
+    /// Number of requirements whose test cases all passed
+    pub fn passing(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.summary.status() == VerificationStatus::Passing)
+            .count()
+    }
+
+    /// Number of requirements with a given status
+    pub fn count(&self, status: VerificationStatus) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.summary.status() == status)
+            .count()
+    }
+
+    /// Percentage of requirements covered by test cases
+    pub fn coverage_percent(&self) -> f64 {
+        if self.entries.is_empty() {
+            return 0.0;
+        }
+        self.covered() as f64 * 100.0 / self.entries.len() as f64
+    }
+
+    /// Renders the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Test Coverage Report\n\n");
+        out.push_str(&format!(
+            "- Requirements: {}\n- Covered by tests: {} ({:.1}%)\n- Passing: {}\n- Failing: {}\n- Blocked: {}\n\n",
+            self.entries.len(),
+            self.covered(),
+            self.coverage_percent(),
+            self.passing(),
+            self.count(VerificationStatus::Failing),
+            self.count(VerificationStatus::Blocked),
+        ));
+
+        out.push_str("| ID | Title | Status | Tests | Passed | Failed | Blocked | Not Run |\n");
+        out.push_str("|----|-------|--------|-------|--------|--------|---------|---------|\n");
+        for entry in &self.entries {
+            let s = &entry.summary;
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                entry.spec_id.as_deref().unwrap_or("N/A"),
+                entry.title.replace('|', "\\|"),
+                s.status(),
+                s.total,
+                s.passed,
+                s.failed,
+                s.blocked,
+                s.not_run + s.skipped,
+            ));
+        }
+        out
+    }
+}
+
+impl RequirementsStore {
+    /// Adds a test case with an auto-generated "TC-NNN" spec_id, returning the spec_id
+    pub fn add_test_case(&mut self, mut case: TestCase) -> String {
+        case.spec_id = self.next_meta_id(META_PREFIX_TEST_CASE);
+        let spec_id = case.spec_id.clone();
+        self.test_cases.push(case);
+        spec_id
+    }
+
+    /// Finds a test case by spec_id (case-insensitive) or UUID
+    pub fn find_test_case(&self, id: &str) -> Option<&TestCase> {
+        let uuid = Uuid::parse_str(id).ok();
+        self.test_cases
+            .iter()
+            .find(|tc| tc.spec_id.eq_ignore_ascii_case(id) || Some(tc.id) == uuid)
+    }
+
+    /// Finds a test case by spec_id or UUID (mutable)
+    pub fn find_test_case_mut(&mut self, id: &str) -> Option<&mut TestCase> {
+        let uuid = Uuid::parse_str(id).ok();
+        self.test_cases
+            .iter_mut()
+            .find(|tc| tc.spec_id.eq_ignore_ascii_case(id) || Some(tc.id) == uuid)
+    }
+
+    /// Finds a test case by automated test name
+    pub fn find_test_case_by_automated_test(&self, name: &str) -> Option<&TestCase> {
+        self.test_cases
+            .iter()
+            .find(|tc| tc.automated_test.as_deref() == Some(name))
+    }
+
+    /// Removes a test case by UUID; recorded results are kept in their runs
+    pub fn remove_test_case(&mut self, id: &Uuid) -> bool {
+        let original_len = self.test_cases.len();
+        self.test_cases.retain(|tc| &tc.id != id);
+        self.test_cases.len() != original_len
+    }
+
+    /// Active test cases verifying a requirement
+    pub fn test_cases_for_requirement(&self, requirement_id: &Uuid) -> Vec<&TestCase> {
+        self.test_cases
+            .iter()
+            .filter(|tc| !tc.archived && tc.verifies.contains(requirement_id))
+            .collect()
+    }
+
+    /// Adds a test run with an auto-generated "RUN-NNN" spec_id, returning the spec_id
+    pub fn add_test_run(&mut self, mut run: TestRun) -> String {
+        run.spec_id = self.next_meta_id(META_PREFIX_TEST_RUN);
+        let spec_id = run.spec_id.clone();
+        self.test_runs.push(run);
+        spec_id
+    }
+
+    /// Finds a test run by spec_id (case-insensitive) or UUID
+    pub fn find_test_run(&self, id: &str) -> Option<&TestRun> {
+        let uuid = Uuid::parse_str(id).ok();
+        self.test_runs
+            .iter()
+            .find(|run| run.spec_id.eq_ignore_ascii_case(id) || Some(run.id) == uuid)
+    }
+
+    /// The most recent result recorded for a test case, with its run
+    pub fn latest_test_result(&self, case_id: &Uuid) -> Option<(&TestRun, &TestResult)> {
+        self.test_runs
+            .iter()
+            .filter_map(|run| run.result_for(case_id).map(|result| (run, result)))
+            .max_by_key(|(run, _)| run.executed_at)
+    }
+
+    /// Rolls up the latest results of every test case verifying a requirement
+    pub fn verification_summary(&self, requirement_id: &Uuid) -> VerificationSummary {
+        let mut summary = VerificationSummary::default();
+        for case in self.test_cases_for_requirement(requirement_id) {
+            let outcome = self
+                .latest_test_result(&case.id)
+                .map(|(_, result)| result.outcome)
+                .unwrap_or_default();
+            summary.add(outcome);
+        }
+        summary
+    }
+
+    /// Builds the coverage report over all non-archived requirements
+    pub fn coverage_report(&self) -> CoverageReport {
+        let entries = self
+            .requirements
+            .iter()
+            .filter(|req| !req.archived)
+            .map(|req| CoverageEntry {
+                requirement_id: req.id,
+                spec_id: req.spec_id.clone(),
+                title: req.title.clone(),
+                feature: req.feature.clone(),
+                summary: self.verification_summary(&req.id),
+            })
+            .collect();
+        CoverageReport { entries }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Requirement, RequirementsStore};
+    use chrono::Duration;
+
+    fn store_with_requirement() -> (RequirementsStore, Uuid) {
+        let mut store = RequirementsStore::new();
+        let req = Requirement::new("Login".to_string(), "Users can log in".to_string());
+        let id = req.id;
+        store.add_requirement(req);
+        (store, id)
+    }
+
+    #[test]
+    fn test_outcome_parse() {
+        assert_eq!(TestOutcome::parse("pass"), Some(TestOutcome::Passed));
+        assert_eq!(TestOutcome::parse("Not-Run"), Some(TestOutcome::NotRun));
+        assert_eq!(TestOutcome::parse("SKIPPED"), Some(TestOutcome::Skipped));
+        assert_eq!(TestOutcome::parse("bogus"), None);
+    }
+
+    #[test]
+    fn test_results_roll_up_onto_requirement() {
+        let (mut store, req_id) = store_with_requirement();
+        assert_eq!(
+            store.verification_summary(&req_id).status(),
+            VerificationStatus::Unverified
+        );
+
+        let tc1 = store.add_test_case(
+            TestCase::new("Valid login")
+                .with_step("Enter valid credentials", "Dashboard is shown")
+                .with_verifies(req_id),
+        );
+        let tc2 = store.add_test_case(TestCase::new("Invalid login").with_verifies(req_id));
+        assert_eq!(tc1, "TC-001");
+        assert_eq!(tc2, "TC-002");
+        assert_eq!(
+            store.verification_summary(&req_id).status(),
+            VerificationStatus::NotRun
+        );
+
+        let case1 = store.find_test_case("tc-001").unwrap().id;
+        let case2 = store.find_test_case("TC-002").unwrap().id;
+
+        // First run: one pass, one failure
+        let mut run = TestRun::new("Nightly").with_build("1.0.0");
+        run.executed_at = Utc::now() - Duration::hours(1);
+        run.record(case1, TestOutcome::Passed);
+        run.record(case2, TestOutcome::Failed).notes = Some("Wrong error message".to_string());
+        assert_eq!(store.add_test_run(run), "RUN-001");
+        assert_eq!(
+            store.verification_summary(&req_id).status(),
+            VerificationStatus::Failing
+        );
+
+        // A later run fixes the failure; only the latest result counts
+        let mut run = TestRun::new("Nightly").with_build("1.0.1");
+        run.record(case2, TestOutcome::Passed);
+        store.add_test_run(run);
+        let summary = store.verification_summary(&req_id);
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.status(), VerificationStatus::Passing);
+
+        let (latest_run, _) = store.latest_test_result(&case2).unwrap();
+        assert_eq!(latest_run.build.as_deref(), Some("1.0.1"));
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        let (mut store, req_id) = store_with_requirement();
+        store.add_requirement(Requirement::new("Logout".to_string(), String::new()));
+        store.add_test_case(TestCase::new("Valid login").with_verifies(req_id));
+        let case_id = store.test_cases[0].id;
+        let mut run = TestRun::new("Manual");
+        run.record(case_id, TestOutcome::Blocked);
+        store.add_test_run(run);
+
+        let report = store.coverage_report();
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.covered(), 1);
+        assert_eq!(report.count(VerificationStatus::Blocked), 1);
+        assert_eq!(report.coverage_percent(), 50.0);
+        assert!(report
+            .to_markdown()
+            .contains("| Login | Blocked | 1 | 0 | 0 | 1 | 0 |"));
+    }
+}
@@ -0,0 +1,606 @@
+//! Source-code trace scanner
+//!
+//! Reads back the inline markers recommended by the scaffolding:
+//!
+//! ```text
+//! // trace:FR-0042 | ai:claude:high
+//! # trace:FR-0042, FR-0043
+//! ```
+//!
+//! Markers are recognised in any line comment or block comment (`//`, `#`,
+//! `--`, `;`, `%`, `/* */`, `<!-- -->`) when `trace:` directly follows the
+//! comment opener; occurrences in string literals are ignored. The optional `ai:<tool>:<confidence>`
+//! part records AI involvement; markers without it count as human-written.
+//! The walk respects `.gitignore` and skips hidden and binary files.
+
+use anyhow::Result;
+use ignore::WalkBuilder;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::models::{RequirementStatus, RequirementsStore};
+
+/// Tokens that open a comment; a marker must follow one of them on its line
+const COMMENT_OPENERS: &[&str] = &["//", "/*", "<!--", "#", "--", ";", "%", "*"];
+
+/// Files larger than this are not scanned
+const MAX_FILE_SIZE: u64 = 2 * 1024 * 1024;
+
+/// How much of the traced code was AI-generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AiConfidence {
+    /// More than 80% AI-generated
+    High,
+    /// 40-80% AI with modifications
+    Medium,
+    /// Less than 40% AI, mostly human
+    Low,
+}
+
+impl AiConfidence {
+    /// Parses "high", "med"/"medium" or "low" (case-insensitive)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "high" => Some(AiConfidence::High),
+            "med" | "medium" => Some(AiConfidence::Medium),
+            "low" => Some(AiConfidence::Low),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AiConfidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AiConfidence::High => write!(f, "high"),
+            AiConfidence::Medium => write!(f, "med"),
+            AiConfidence::Low => write!(f, "low"),
+        }
+    }
+}
+
+/// A trace marker found in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceMarker {
+    /// Referenced requirement SPEC-ID
+    pub spec_id: String,
+    /// File path relative to the scanned root
+    pub file: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    /// AI tool named in the marker (e.g., "claude")
+    pub ai_tool: Option<String>,
+    /// AI confidence named in the marker
+    pub confidence: Option<AiConfidence>,
+}
+
+impl TraceMarker {
+    /// Returns true if the marker declares AI involvement
+    pub fn is_ai(&self) -> bool {
+        self.ai_tool.is_some()
+    }
+
+    /// "file:line" location string
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file.display(), self.line)
+    }
+}
+
+/// Parsed contents of one marker: SPEC-IDs, AI tool and confidence
+pub type ParsedMarker = (Vec<String>, Option<String>, Option<AiConfidence>);
+
+/// Returns true if `s` looks like a SPEC-ID (e.g., "FR-0042", "AUTH-FR-001")
+fn is_spec_id(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    parts.len() >= 2
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_alphanumeric()))
+        && parts[0].starts_with(|c: char| c.is_ascii_alphabetic())
+        && parts[parts.len() - 1].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Returns true if byte offset `pos` is inside a `"` or `` ` `` string
+fn in_string_literal(line: &str, pos: usize) -> bool {
+    let mut quote = None;
+    let mut escaped = false;
+    for c in line[..pos].chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '`' => quote = Some(c),
+            None => {}
+        }
+    }
+    quote.is_some()
+}
+
+/// Returns true if a `trace:` at byte offset `pos` directly follows a comment
+/// opener (allowing whitespace and the `!` of doc comments) outside a string
+fn is_marker_start(line: &str, pos: usize) -> bool {
+    let prefix = line[..pos].trim_end().trim_end_matches('!');
+    COMMENT_OPENERS.iter().any(|opener| {
+        prefix.ends_with(opener) && !in_string_literal(line, prefix.len() - opener.len())
+    })
+}
+
+/// Byte offset and text of each marker body (after `trace:`) on a line
+fn marker_bodies(line: &str) -> Vec<(usize, &str)> {
+    line.match_indices("trace:")
+        .filter(|(pos, _)| is_marker_start(line, *pos))
+        .map(|(pos, _)| {
+            // The marker runs until the next marker or the end of the comment
+            let start = pos + "trace:".len();
+            let body = &line[start..];
+            let body = body.split("trace:").next().unwrap_or_default();
+            let body = body.split("*/").next().unwrap_or_default();
+            let body = body.split("-->").next().unwrap_or_default();
+            (start, body)
+        })
+        .collect()
+}
+
+/// Reads the comma-separated ID list at the start of a marker body
+///
+/// The list ends at the first token that is not followed by a comma, so
+/// trailing prose or an `| ai:` section is not part of it.
+fn scan_id_list(body: &str) -> Vec<&str> {
+    let mut ids = Vec::new();
+    let mut rest = body;
+    loop {
+        rest = rest.trim_start();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            break;
+        }
+        ids.push(&rest[..len]);
+        match rest[len..].trim_start().strip_prefix(',') {
+            Some(next) => rest = next,
+            None => break,
+        }
+    }
+    ids
+}
+
+/// Extracts the IDs listed after `trace:` markers in text
+///
+/// By default a marker must follow a comment opener, as in source files.
+/// With `anywhere` set, `trace:` counts wherever it appears, e.g. in a test
+/// name or captured test output. IDs are returned as written, without
+/// checking that they look like SPEC-IDs.
+pub fn trace_ids(text: &str, anywhere: bool) -> Vec<String> {
+    let mut ids = Vec::new();
+    for line in text.lines() {
+        let bodies: Vec<&str> = if anywhere {
+            line.match_indices("trace:")
+                .map(|(pos, _)| &line[pos + "trace:".len()..])
+                .collect()
+        } else {
+            marker_bodies(line)
+                .into_iter()
+                .map(|(_, body)| body)
+                .collect()
+        };
+        for body in bodies {
+            ids.extend(scan_id_list(body).into_iter().map(str::to_string));
+        }
+    }
+    ids
+}
+
+/// Parses the trace markers on one line of source
+pub fn parse_trace_line(line: &str) -> Vec<ParsedMarker> {
+    let mut markers = Vec::new();
+    for (_, body) in marker_bodies(line) {
+        let mut sections = body.split('|');
+        let ids: Vec<String> = scan_id_list(sections.next().unwrap_or_default())
+            .into_iter()
+            .filter(|id| is_spec_id(id))
+            .map(str::to_string)
+            .collect();
+        if ids.is_empty() {
+            continue;
+        }
+
+        let mut ai_tool = None;
+        let mut confidence = None;
+        for section in sections {
+            if let Some(ai) = section.trim().strip_prefix("ai:") {
+                let mut parts = ai.split(':');
+                ai_tool = parts
+                    .next()
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty());
+                confidence = parts.next().and_then(AiConfidence::parse);
+            }
+        }
+        markers.push((ids, ai_tool, confidence));
+    }
+    markers
+}
+
+/// A SPEC-ID-shaped token on a line of text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecIdMention {
+    /// The token as written (e.g., "FR-0042")
+    pub spec_id: String,
+    /// Byte range within the line
+    pub range: std::ops::Range<usize>,
+    /// True if the token is one of the IDs of a `trace:` marker
+    pub in_marker: bool,
+}
+
+/// Finds SPEC-ID-shaped tokens anywhere on a line (code, comments or prose)
+pub fn find_spec_ids(line: &str) -> Vec<SpecIdMention> {
+    let marker_ids: Vec<std::ops::Range<usize>> = marker_bodies(line)
+        .into_iter()
+        .map(|(start, body)| start..start + body.find('|').unwrap_or(body.len()))
+        .collect();
+
+    let mut mentions = Vec::new();
+    let mut token_start = None;
+    for (i, c) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), ' ')))
+    {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            token_start.get_or_insert(i);
+            continue;
+        }
+        let Some(start) = token_start.take() else {
+            continue;
+        };
+        let token = &line[start..i];
+        let spec_id = token.trim_matches('-');
+        if !is_spec_id(spec_id) {
+            continue;
+        }
+        let offset = start + (token.len() - token.trim_start_matches('-').len());
+        let range = offset..offset + spec_id.len();
+        mentions.push(SpecIdMention {
+            spec_id: spec_id.to_string(),
+            in_marker: marker_ids
+                .iter()
+                .any(|m| m.start <= range.start && range.end <= m.end),
+            range,
+        });
+    }
+    mentions
+}
+
+/// If byte offset `col` is in the ID list of a trace marker, returns the
+/// partial SPEC-ID typed before it (possibly empty)
+pub fn marker_completion_prefix(line: &str, col: usize) -> Option<&str> {
+    marker_bodies(line).into_iter().find_map(|(start, body)| {
+        let ids_end = start + body.find('|').unwrap_or(body.len());
+        if col < start || col > ids_end {
+            return None;
+        }
+        let typed = line.get(start..col)?;
+        Some(typed.rsplit(',').next().unwrap_or_default().trim_start())
+    })
+}
+
+/// Counts of markers by AI involvement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfidenceBreakdown {
+    pub ai_high: usize,
+    pub ai_medium: usize,
+    pub ai_low: usize,
+    /// AI markers without a confidence level
+    pub ai_unspecified: usize,
+    pub human: usize,
+}
+
+impl ConfidenceBreakdown {
+    /// Total number of markers counted
+    pub fn total(&self) -> usize {
+        self.ai() + self.human
+    }
+
+    /// Number of markers declaring AI involvement
+    pub fn ai(&self) -> usize {
+        self.ai_high + self.ai_medium + self.ai_low + self.ai_unspecified
+    }
+}
+
+/// Code → requirement index built from trace markers
+#[derive(Debug, Clone, Default)]
+pub struct TraceIndex {
+    /// Root directory that was scanned
+    pub root: PathBuf,
+    /// All markers, ordered by file and line
+    pub markers: Vec<TraceMarker>,
+    /// Number of files read
+    pub files_scanned: usize,
+    /// Files and directories that could not be read
+    pub warnings: Vec<String>,
+}
+
+impl TraceIndex {
+    /// Scans a source tree, respecting .gitignore; unreadable entries are
+    /// skipped and listed in `warnings`
+    pub fn scan(root: &Path) -> Result<Self> {
+        let mut index = Self {
+            root: root.to_path_buf(),
+            ..Default::default()
+        };
+
+        let walker = WalkBuilder::new(root).require_git(false).build();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    index.warnings.push(e.to_string());
+                    continue;
+                }
+            };
+            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                continue;
+            }
+            if entry
+                .metadata()
+                .map(|m| m.len() > MAX_FILE_SIZE)
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            // Skip binary and non-UTF-8 files
+            let content = match fs::read_to_string(entry.path()) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => continue,
+                Err(e) => {
+                    index
+                        .warnings
+                        .push(format!("{}: {}", entry.path().display(), e));
+                    continue;
+                }
+            };
+            index.files_scanned += 1;
+
+            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            index.add_source(relative, &content);
+        }
+
+        index
+            .markers
+            .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        Ok(index)
+    }
+
+    /// Adds the markers found in one file's content
+    pub fn add_source(&mut self, file: &Path, content: &str) {
+        for (line_idx, line) in content.lines().enumerate() {
+            for (ids, ai_tool, confidence) in parse_trace_line(line) {
+                for spec_id in ids {
+                    self.markers.push(TraceMarker {
+                        spec_id,
+                        file: file.to_path_buf(),
+                        line: line_idx + 1,
+                        ai_tool: ai_tool.clone(),
+                        confidence,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Markers referencing a SPEC-ID (case-insensitive)
+    pub fn markers_for(&self, spec_id: &str) -> Vec<&TraceMarker> {
+        self.markers
+            .iter()
+            .filter(|m| m.spec_id.eq_ignore_ascii_case(spec_id))
+            .collect()
+    }
+
+    /// Markers grouped by SPEC-ID
+    pub fn by_requirement(&self) -> BTreeMap<String, Vec<&TraceMarker>> {
+        let mut grouped: BTreeMap<String, Vec<&TraceMarker>> = BTreeMap::new();
+        for marker in &self.markers {
+            grouped
+                .entry(marker.spec_id.to_uppercase())
+                .or_default()
+                .push(marker);
+        }
+        grouped
+    }
+
+    /// Counts markers by AI involvement
+    pub fn confidence_breakdown(&self) -> ConfidenceBreakdown {
+        let mut breakdown = ConfidenceBreakdown::default();
+        for marker in &self.markers {
+            match (marker.is_ai(), marker.confidence) {
+                (false, _) => breakdown.human += 1,
+                (true, Some(AiConfidence::High)) => breakdown.ai_high += 1,
+                (true, Some(AiConfidence::Medium)) => breakdown.ai_medium += 1,
+                (true, Some(AiConfidence::Low)) => breakdown.ai_low += 1,
+                (true, None) => breakdown.ai_unspecified += 1,
+            }
+        }
+        breakdown
+    }
+
+    /// Checks the index against the requirements database
+    pub fn report(&self, store: &RequirementsStore) -> TraceReport {
+        let known: HashSet<String> = store
+            .requirements
+            .iter()
+            .filter_map(|r| r.spec_id.as_deref().map(str::to_uppercase))
+            .collect();
+        let traced: HashSet<String> = self
+            .markers
+            .iter()
+            .map(|m| m.spec_id.to_uppercase())
+            .collect();
+
+        TraceReport {
+            unknown: self
+                .markers
+                .iter()
+                .filter(|m| !known.contains(&m.spec_id.to_uppercase()))
+                .cloned()
+                .collect(),
+            unimplemented: store
+                .requirements
+                .iter()
+                .filter(|r| !r.archived && r.status == RequirementStatus::Approved)
+                .filter(|r| {
+                    !r.spec_id
+                        .as_deref()
+                        .is_some_and(|s| traced.contains(&s.to_uppercase()))
+                })
+                .map(|r| r.id)
+                .collect(),
+            implemented: known.intersection(&traced).count(),
+            confidence: self.confidence_breakdown(),
+        }
+    }
+}
+
+/// Findings from comparing a trace index with the requirements database
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraceReport {
+    /// Markers referencing SPEC-IDs that don't exist
+    pub unknown: Vec<TraceMarker>,
+    /// Approved requirements with no trace marker
+    pub unimplemented: Vec<Uuid>,
+    /// Number of existing requirements referenced by at least one marker
+    pub implemented: usize,
+    /// AI-vs-human breakdown of all markers
+    pub confidence: ConfidenceBreakdown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Requirement;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_trace_line_comment_syntaxes() {
+        let (ids, tool, confidence) =
+            parse_trace_line("    // trace:FR-0042 | ai:claude:high").remove(0);
+        assert_eq!(ids, vec!["FR-0042"]);
+        assert_eq!(tool.as_deref(), Some("claude"));
+        assert_eq!(confidence, Some(AiConfidence::High));
+
+        let (ids, tool, _) = parse_trace_line("# trace:FR-1, AUTH-FR-002").remove(0);
+        assert_eq!(ids, vec!["FR-1", "AUTH-FR-002"]);
+        assert!(tool.is_none());
+
+        assert_eq!(
+            parse_trace_line("/* trace:NFR-7 | ai:copilot:med */ int x;")[0].2,
+            Some(AiConfidence::Medium)
+        );
+        assert_eq!(parse_trace_line("<!-- trace:UI-3 -->")[0].0, vec!["UI-3"]);
+        assert_eq!(
+            parse_trace_line("-- trace:DB-10 | ai:claude")[0]
+                .1
+                .as_deref(),
+            Some("claude")
+        );
+
+        assert_eq!(parse_trace_line("//! trace:LIB-1")[0].0, vec!["LIB-1"]);
+        assert_eq!(parse_trace_line(" * trace:FR-5")[0].0, vec!["FR-5"]);
+
+        // Not directly after a comment opener, in a string, or not a SPEC-ID
+        assert!(parse_trace_line("let s = \"trace:FR-1\";").is_empty());
+        assert!(parse_trace_line("let p = *x; let s = \"trace:FR-1\";").is_empty());
+        assert!(parse_trace_line("s = \"# trace:FR-1\"").is_empty());
+        assert!(parse_trace_line("// see trace:FR-1").is_empty());
+        assert!(parse_trace_line("// trace:<SPEC-ID> | ai:<tool>:<confidence>").is_empty());
+
+        // Trailing prose ends the ID list
+        assert_eq!(
+            parse_trace_line("// trace:FR-1, FR-2 login flow")[0].0,
+            vec!["FR-1", "FR-2"]
+        );
+    }
+
+    #[test]
+    fn test_trace_ids_anywhere() {
+        let name = "auth::test_login trace:FR-001,FR-002";
+        assert!(trace_ids(name, false).is_empty());
+        assert_eq!(trace_ids(name, true), vec!["FR-001", "FR-002"]);
+        assert_eq!(
+            trace_ids("x = 1 # trace:FR-3 | ai:claude", false),
+            vec!["FR-3"]
+        );
+    }
+
+    #[test]
+    fn test_find_spec_ids_and_completion_prefix() {
+        let line = "// trace:FR-0042, AUTH-FR-2 | ai:claude:high -- see NFR-7 (UTF-8)";
+        let mentions = find_spec_ids(line);
+        let ids: Vec<&str> = mentions.iter().map(|m| m.spec_id.as_str()).collect();
+        assert_eq!(ids, vec!["FR-0042", "AUTH-FR-2", "NFR-7", "UTF-8"]);
+        assert_eq!(&line[mentions[0].range.clone()], "FR-0042");
+        assert!(mentions[0].in_marker && mentions[1].in_marker);
+        assert!(!mentions[2].in_marker);
+
+        assert_eq!(marker_completion_prefix("// trace:", 9), Some(""));
+        assert_eq!(
+            marker_completion_prefix("# trace:FR-1, FR-0", 18),
+            Some("FR-0")
+        );
+        assert_eq!(
+            marker_completion_prefix("// trace:FR-1 | ai:claude", 25),
+            None
+        );
+        assert_eq!(marker_completion_prefix("let trace: u8", 10), None);
+    }
+
+    #[test]
+    fn test_scan_respects_gitignore_and_reports() -> Result<()> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("src"))?;
+        fs::create_dir_all(dir.path().join("target"))?;
+        fs::write(dir.path().join(".gitignore"), "target/\n")?;
+        fs::write(
+            dir.path().join("src/lib.rs"),
+            "// trace:FR-001 | ai:claude:high\nfn a() {}\n\n// trace:FR-404\nfn b() {}\n",
+        )?;
+        fs::write(
+            dir.path().join("src/app.py"),
+            "# trace:FR-001 | ai:claude:low\n",
+        )?;
+        fs::write(dir.path().join("target/gen.rs"), "// trace:FR-002\n")?;
+
+        let index = TraceIndex::scan(dir.path())?;
+        assert_eq!(index.markers.len(), 3);
+        assert!(index.markers.iter().all(|m| !m.file.starts_with("target")));
+        assert_eq!(index.markers_for("fr-001").len(), 2);
+        assert_eq!(
+            index.markers_for("FR-404")[0].location(),
+            format!("{}:4", Path::new("src/lib.rs").display())
+        );
+
+        let mut store = RequirementsStore::new();
+        for (spec_id, status) in [
+            ("FR-001", RequirementStatus::Approved),
+            ("FR-002", RequirementStatus::Approved),
+        ] {
+            let mut req = Requirement::new(spec_id.to_string(), String::new());
+            req.spec_id = Some(spec_id.to_string());
+            req.status = status;
+            store.add_requirement(req);
+        }
+
+        let report = index.report(&store);
+        assert_eq!(report.unknown.len(), 1);
+        assert_eq!(report.unknown[0].spec_id, "FR-404");
+        assert_eq!(report.unimplemented, vec![store.requirements[1].id]);
+        assert_eq!(report.implemented, 1);
+        assert_eq!(report.confidence.ai_high, 1);
+        assert_eq!(report.confidence.ai_low, 1);
+        assert_eq!(report.confidence.human, 1);
+        Ok(())
+    }
+}
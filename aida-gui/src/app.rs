@@ -1,10 +1,11 @@
 use aida_core::{
     ai::AiClient,
-    determine_requirements_path, AddResult, Cardinality, Comment, ConflictInfo, ConflictResolution,
-    CustomFieldDefinition, CustomFieldType, EditLock, EvaluationResponse, FieldChange, IdFormat,
-    LockFileInfo, NumberingStrategy, RelationshipDefinition, RelationshipType, Requirement,
-    RequirementPriority, RequirementStatus, RequirementType, RequirementsStore, SaveResult,
-    SessionInfo, Storage, StoredAiEvaluation, UrlLink,
+    determine_requirements_path, AcceptanceCriterion, AddResult, Cardinality, Comment,
+    ConflictInfo, ConflictResolution, CriterionStatus, CustomFieldDefinition, CustomFieldType,
+    EditLock, EvaluationResponse, FieldChange, GivenWhenThen, IdFormat, LockFileInfo, NumberingStrategy, RelationshipDefinition, RelationshipType, Requirement,
+    RequirementPatch, RequirementPriority, RequirementStatus, RequirementType, RequirementsStore,
+    SaveResult, ScriptEngine, ScriptKind, SessionInfo, Storage, StoredAiEvaluation, TemplateContext, TestOutcome,
+    TypeTemplateLibrary, UrlLink, VerificationStatus,
 };
 use eframe::egui;
 use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
@@ -164,7 +165,7 @@ fn show_text_context_menu(
                 // Remove the selected text
                 let before: String = text.chars().take(sel.start).collect();
                 let after: String = text.chars().skip(sel.end).collect();
-                *text = before + &after;
+                *text = format!("{before}{after}");
                 *stored_selection = None;
             }
             ui.close_menu();
@@ -195,7 +196,7 @@ fn show_text_context_menu(
                     // Replace selection with pasted text
                     let before: String = text.chars().take(sel.start).collect();
                     let after: String = text.chars().skip(sel.end).collect();
-                    *text = before + &paste_text + &after;
+                    *text = format!("{before}{paste_text}{after}");
                 } else {
                     // No selection - get cursor position from state
                     if let Some(state) = egui::TextEdit::load_state(ui.ctx(), id) {
@@ -203,7 +204,7 @@ fn show_text_context_menu(
                             let pos = range.primary.index;
                             let before: String = text.chars().take(pos).collect();
                             let after: String = text.chars().skip(pos).collect();
-                            *text = before + &paste_text + &after;
+                            *text = format!("{before}{paste_text}{after}");
                         } else {
                             text.push_str(&paste_text);
                         }
@@ -269,6 +270,24 @@ fn get_clipboard_text() -> Option<String> {
         .and_then(|mut c| c.get_text().ok())
 }
 
+/// Tab label for acceptance criteria, showing progress when any exist
+fn acceptance_tab_label(req: &Requirement) -> String {
+    let (satisfied, total) = req.acceptance_progress();
+    if total == 0 {
+        "☑ Acceptance".to_string()
+    } else {
+        format!("☑ Acceptance ({}/{})", satisfied, total)
+    }
+}
+
+/// Tab label for code traceability, showing the marker count once sources were scanned
+fn code_tab_label(index: Option<&aida_core::TraceIndex>, req: &Requirement) -> String {
+    match (index, req.spec_id.as_deref()) {
+        (Some(index), Some(spec_id)) => format!("💻 Code ({})", index.markers_for(spec_id).len()),
+        _ => "💻 Code".to_string(),
+    }
+}
+
 /// Format a requirement as a prompt for Claude Code implementation
 fn format_requirement_for_claude_code(req: &Requirement) -> String {
     let mut prompt = String::new();
@@ -299,6 +318,15 @@ fn format_requirement_for_claude_code(req: &Requirement) -> String {
         prompt.push_str("\n");
     }
 
+    // Acceptance criteria if present
+    if !req.acceptance_criteria.is_empty() {
+        prompt.push_str("## Acceptance Criteria\n\n");
+        for ac in &req.acceptance_criteria {
+            prompt.push_str(&format!("- {}\n", ac.checklist_line()));
+        }
+        prompt.push_str("\n");
+    }
+
     // Implementation instructions
     prompt.push_str("## Implementation Task\n\n");
     prompt.push_str("Please implement this requirement in the codebase. ");
@@ -1707,6 +1735,9 @@ enum DetailTab {
     #[default]
     Description,
     Comments,
+    Acceptance,
+    Tests,
+    Code,
     Links,
     History,
 }
@@ -2187,6 +2218,8 @@ pub struct RequirementsApp {
     form_status: RequirementStatus,
     form_status_string: String, // Status as string (for custom type statuses)
     form_custom_fields: HashMap<String, String>, // Custom field values
+    form_applied_template: String,                 // Description last filled in from a type template
+    type_template_library: TypeTemplateLibrary,    // Shared type templates from the templates dir
     form_priority: RequirementPriority,
     form_priority_string: String, // Priority as string (for custom type priorities)
     form_type: RequirementType,
@@ -2211,6 +2244,16 @@ pub struct RequirementsApp {
     #[allow(dead_code)]
     edit_comment_id: Option<Uuid>,
 
+    // Acceptance criteria form state
+    ac_form_text: String,
+    ac_form_given: String,
+    ac_form_when: String,
+    ac_form_then: String,
+    ac_form_test: String,
+
+    // Code traceability (trace markers scanned from the project sources)
+    trace_index: Option<aida_core::TraceIndex>,
+
     // Pending operations (to avoid borrow checker issues)
     pending_delete: Option<usize>,
     pending_view_change: Option<View>,
@@ -2338,6 +2381,7 @@ pub struct RequirementsApp {
 
     // Markdown rendering
     markdown_cache: CommonMarkCache,
+    computed_columns_cache: Option<(Uuid, DateTime<Utc>, Vec<(String, String)>)>, // Script column values of the selected requirement
     show_description_preview: bool, // Toggle preview mode in edit form
 
     // Layout state
@@ -2413,6 +2457,7 @@ pub struct RequirementsApp {
     type_def_form_name: String,
     type_def_form_display_name: String,
     type_def_form_description: String,
+    type_def_form_description_template: String, // Template for new requirement descriptions
     type_def_form_prefix: String,
     type_def_form_statuses: Vec<String>, // Editable list of statuses
     type_def_form_priorities: Vec<String>, // Editable list of priorities
@@ -2529,6 +2574,18 @@ pub struct RequirementsApp {
     clone_include_history: bool,
     clone_include_urls: bool,
     clone_include_custom_fields: bool,
+
+    // Multi-select and bulk edit state
+    multi_selected: HashSet<Uuid>,                      // Requirements selected with Ctrl/Shift+click
+    multi_select_anchor: Option<usize>,                 // Last plain/Ctrl-clicked index for Shift ranges
+    list_render_order: Vec<usize>,                      // Indices in the order rendered this frame
+    list_render_order_prev: Vec<usize>,                 // Render order from the previous frame
+    bulk_form_status: String,
+    bulk_form_priority: String,
+    bulk_form_owner: String,
+    bulk_form_feature: String,
+    bulk_form_add_tags: String,
+    bulk_form_remove_tags: String,
 }
 
 /// Result from background AI evaluation thread
@@ -2720,6 +2777,8 @@ impl RequirementsApp {
             form_status: RequirementStatus::Draft,
             form_status_string: String::from("Draft"),
             form_custom_fields: HashMap::new(),
+            form_applied_template: String::new(),
+            type_template_library: TypeTemplateLibrary::load_default().unwrap_or_default(),
             form_priority: RequirementPriority::Medium,
             form_priority_string: String::from("Medium"),
             form_type: RequirementType::Functional,
@@ -2738,6 +2797,12 @@ impl RequirementsApp {
             reply_to_comment: None,
             collapsed_comments: HashMap::new(),
             edit_comment_id: None,
+            ac_form_text: String::new(),
+            ac_form_given: String::new(),
+            ac_form_when: String::new(),
+            ac_form_then: String::new(),
+            ac_form_test: String::new(),
+            trace_index: None,
             pending_delete: None,
             pending_view_change: None,
             pending_save: false,
@@ -2836,6 +2901,7 @@ impl RequirementsApp {
             pending_apply_ai_description: None,
             drag_scroll_delta: 0.0,
             markdown_cache: CommonMarkCache::default(),
+            computed_columns_cache: None,
             show_description_preview: false,
             left_panel_collapsed: false,
             layout_mode: LayoutMode::ListDetailsSide,
@@ -2892,6 +2958,7 @@ impl RequirementsApp {
             type_def_form_name: String::new(),
             type_def_form_display_name: String::new(),
             type_def_form_description: String::new(),
+            type_def_form_description_template: String::new(),
             type_def_form_prefix: String::new(),
             type_def_form_statuses: Vec::new(),
             type_def_form_priorities: Vec::new(),
@@ -3001,6 +3068,18 @@ impl RequirementsApp {
             clone_include_history: false,
             clone_include_urls: true,
             clone_include_custom_fields: true,
+
+            // Multi-select and bulk edit state
+            multi_selected: HashSet::new(),
+            multi_select_anchor: None,
+            list_render_order: Vec::new(),
+            list_render_order_prev: Vec::new(),
+            bulk_form_status: String::new(),
+            bulk_form_priority: String::new(),
+            bulk_form_owner: String::new(),
+            bulk_form_feature: String::new(),
+            bulk_form_add_tags: String::new(),
+            bulk_form_remove_tags: String::new(),
         }
     }
 
@@ -3713,6 +3792,31 @@ impl RequirementsApp {
         self.show_description_preview = false;
         self.form_title_auto_synced = true;
         self.form_last_description.clear();
+        self.form_applied_template.clear();
+        self.apply_type_template();
+    }
+
+    /// Fill the add form from the selected type's template.
+    /// A description the user has already edited is left untouched.
+    fn apply_type_template(&mut self) {
+        let ctx = TemplateContext::new(
+            self.user_settings.display_name(),
+            &self.form_feature,
+            &self.form_type,
+        );
+        let template =
+            self.store
+                .instantiate_type_template(&self.form_type, &ctx, &self.type_template_library);
+
+        if self.form_description.is_empty() || self.form_description == self.form_applied_template {
+            self.form_description = template.description.clone();
+            self.form_applied_template = template.description;
+            // Don't let the template drive the auto-synced title
+            self.form_last_description = self.form_description.clone();
+        }
+        for (name, value) in template.custom_fields {
+            self.form_custom_fields.entry(name).or_insert(value);
+        }
     }
 
     fn load_form_from_requirement(&mut self, idx: usize) {
@@ -4687,6 +4791,8 @@ impl RequirementsApp {
                     .on_hover_text("Slash commands directory for project-specific commands");
                 ui.checkbox(&mut self.scaffold_config.generate_skills, ".claude/skills/")
                     .on_hover_text("Skills directory for requirements-driven development");
+                ui.checkbox(&mut self.scaffold_config.register_mcp_server, ".mcp.json")
+                    .on_hover_text("Register the aida MCP server so coding agents can use the requirements store");
 
                 if self.scaffold_config.generate_skills {
                     ui.indent("skills_indent", |ui| {
@@ -6892,6 +6998,7 @@ impl RequirementsApp {
                 self.type_def_form_name.clear();
                 self.type_def_form_display_name.clear();
                 self.type_def_form_description.clear();
+                self.type_def_form_description_template.clear();
                 self.type_def_form_prefix.clear();
                 self.type_def_form_statuses = vec![
                     "Draft".to_string(),
@@ -6954,6 +7061,8 @@ impl RequirementsApp {
                         self.type_def_form_display_name = type_def.display_name.clone();
                         self.type_def_form_description =
                             type_def.description.clone().unwrap_or_default();
+                        self.type_def_form_description_template =
+                            type_def.description_template.clone().unwrap_or_default();
                         self.type_def_form_prefix = type_def.prefix.clone().unwrap_or_default();
                         self.type_def_form_statuses = type_def.statuses.clone();
                         self.type_def_form_priorities = type_def.priorities.clone();
@@ -7216,6 +7325,17 @@ impl RequirementsApp {
                         .desired_width(100.0),
                 );
                 ui.end_row();
+
+                // Description template
+                ui.label("Template:")
+                    .on_hover_text("Pre-fills new requirements of this type.\nPlaceholders: {{user}}, {{date}}, {{datetime}}, {{feature}}, {{type}}");
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.type_def_form_description_template)
+                        .hint_text("e.g., As a <role>, I want <capability> so that <benefit>.")
+                        .desired_rows(4)
+                        .desired_width(300.0),
+                );
+                ui.end_row();
             });
 
         ui.add_space(15.0);
@@ -7673,6 +7793,26 @@ impl RequirementsApp {
             } else {
                 false
             },
+            description_template: if self.type_def_form_description_template.is_empty() {
+                None
+            } else {
+                Some(self.type_def_form_description_template.clone())
+            },
+            field_defaults: self
+                .editing_type_def
+                .as_ref()
+                .and_then(|editing_name| {
+                    self.store.type_definitions.iter().find(|t| &t.name == editing_name)
+                })
+                .map(|t| t.field_defaults.clone())
+                .unwrap_or_default(),
+            defaults_applied: self
+                .editing_type_def
+                .as_ref()
+                .and_then(|editing_name| {
+                    self.store.type_definitions.iter().find(|t| &t.name == editing_name)
+                })
+                .is_some_and(|t| t.defaults_applied),
         };
 
         if let Some(ref editing_name) = self.editing_type_def {
@@ -12561,7 +12701,7 @@ impl RequirementsApp {
         let status_string = req.effective_status();
         let is_inactive = Self::is_inactive_status(&status_string);
         let icon = self.get_requirement_icon(req); // Get icon before req ref is dropped
-        let selected = self.selected_idx == Some(idx);
+        let selected = self.selected_idx == Some(idx) || self.multi_selected.contains(&req_id);
         let is_drag_source = self.drag_source == Some(idx);
         let is_drop_target = self.drop_target == Some(idx);
         let can_drag = self.perspective != Perspective::Flat; // Only allow drag in tree views
@@ -12569,6 +12709,7 @@ impl RequirementsApp {
         let show_status_icons = self.user_settings.show_status_icons;
 
         let indent_space = indent as f32 * 20.0;
+        self.list_render_order.push(idx);
 
         ui.horizontal(|ui| {
             ui.add_space(indent_space);
@@ -12647,7 +12788,9 @@ impl RequirementsApp {
             ui.painter().galley(text_pos, galley, text_color);
 
             // Handle interactions
-            if response.clicked() {
+            if response.clicked() && self.handle_multi_select_click(ui, idx, req_id) {
+                // Ctrl/Shift+click only changes the multi-selection
+            } else if response.clicked() {
                 self.selected_idx = Some(idx);
                 self.focused_list = FocusedList::List1;
                 self.pending_view_change = Some(View::Detail);
@@ -13081,11 +13224,12 @@ impl RequirementsApp {
         let status_string = req.effective_status();
         let is_inactive = Self::is_inactive_status(&status_string);
         let icon = self.get_requirement_icon(req); // Get icon before req ref is dropped
-        let selected = self.selected_idx == Some(idx);
+        let selected = self.selected_idx == Some(idx) || self.multi_selected.contains(&req_id);
         let is_drag_source = self.drag_source == Some(idx);
         let is_drop_target = self.drop_target == Some(idx);
         let should_scroll_to = self.scroll_to_requirement == Some(req_id);
         let show_status_icons = self.user_settings.show_status_icons;
+        self.list_render_order.push(idx);
 
         // Build the label with optional status icon
         let label = if show_status_icons {
@@ -13161,6 +13305,8 @@ impl RequirementsApp {
                 self.focused_list = FocusedList::List1;
                 self.load_form_from_requirement(idx);
                 self.pending_view_change = Some(View::Edit);
+            } else if response.clicked() && self.handle_multi_select_click(ui, idx, req_id) {
+                // Ctrl/Shift+click only changes the multi-selection
             } else if response.clicked() {
                 self.selected_idx = Some(idx);
                 self.focused_list = FocusedList::List1;
@@ -13198,6 +13344,203 @@ impl RequirementsApp {
         }
     }
 
+    /// Handle Ctrl/Shift+click on a list item.
+    /// Returns true when the click only changed the multi-selection.
+    fn handle_multi_select_click(&mut self, ui: &egui::Ui, idx: usize, req_id: Uuid) -> bool {
+        let modifiers = ui.input(|i| i.modifiers);
+
+        if modifiers.shift {
+            let anchor = self.multi_select_anchor.or(self.selected_idx).unwrap_or(idx);
+            let order = &self.list_render_order_prev;
+            let (Some(a), Some(b)) = (
+                order.iter().position(|&i| i == anchor),
+                order.iter().position(|&i| i == idx),
+            ) else {
+                self.multi_selected.insert(req_id);
+                return true;
+            };
+            let (start, end) = if a <= b { (a, b) } else { (b, a) };
+            for &i in &order[start..=end] {
+                if let Some(req) = self.store.requirements.get(i) {
+                    self.multi_selected.insert(req.id);
+                }
+            }
+            return true;
+        }
+
+        if modifiers.command {
+            // Seed the selection with the current single selection
+            if self.multi_selected.is_empty() {
+                if let Some(req) = self.selected_idx.and_then(|i| self.store.requirements.get(i)) {
+                    self.multi_selected.insert(req.id);
+                }
+            }
+            if !self.multi_selected.remove(&req_id) {
+                self.multi_selected.insert(req_id);
+            }
+            self.multi_select_anchor = Some(idx);
+            return true;
+        }
+
+        // Plain click resets the multi-selection
+        self.multi_selected.clear();
+        self.multi_select_anchor = Some(idx);
+        false
+    }
+
+    /// Show the bulk edit window for multi-selected requirements
+    fn show_bulk_edit_window(&mut self, ctx: &egui::Context) {
+        if self.multi_selected.len() < 2 {
+            return;
+        }
+
+        // Offer the statuses/priorities valid for every selected type
+        let selected_types: Vec<RequirementType> = self
+            .store
+            .requirements
+            .iter()
+            .filter(|r| self.multi_selected.contains(&r.id))
+            .map(|r| r.req_type.clone())
+            .collect();
+        let common = |lists: Vec<Vec<String>>| -> Vec<String> {
+            let mut iter = lists.into_iter();
+            let first = iter.next().unwrap_or_default();
+            let rest: Vec<Vec<String>> = iter.collect();
+            first
+                .into_iter()
+                .filter(|v| rest.iter().all(|l| l.iter().any(|x| x.eq_ignore_ascii_case(v))))
+                .collect()
+        };
+        let statuses = common(selected_types.iter().map(|t| self.store.get_statuses_for_type(t)).collect());
+        let priorities = common(selected_types.iter().map(|t| self.store.get_priorities_for_type(t)).collect());
+
+        let mut apply = false;
+        let mut clear = false;
+
+        egui::Window::new(format!("Bulk Edit ({} selected)", self.multi_selected.len()))
+            .collapsible(true)
+            .resizable(false)
+            .default_width(320.0)
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new("Leave a field empty to keep existing values.").small().italics());
+                ui.add_space(5.0);
+
+                egui::Grid::new("bulk_edit_grid").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                    ui.label("Status:");
+                    egui::ComboBox::from_id_salt("bulk_status")
+                        .selected_text(if self.bulk_form_status.is_empty() { "(unchanged)" } else { self.bulk_form_status.as_str() })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.bulk_form_status, String::new(), "(unchanged)");
+                            for status in &statuses {
+                                ui.selectable_value(&mut self.bulk_form_status, status.clone(), status);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Priority:");
+                    egui::ComboBox::from_id_salt("bulk_priority")
+                        .selected_text(if self.bulk_form_priority.is_empty() { "(unchanged)" } else { self.bulk_form_priority.as_str() })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.bulk_form_priority, String::new(), "(unchanged)");
+                            for priority in &priorities {
+                                ui.selectable_value(&mut self.bulk_form_priority, priority.clone(), priority);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Feature:");
+                    ui.text_edit_singleline(&mut self.bulk_form_feature);
+                    ui.end_row();
+
+                    ui.label("Owner:");
+                    ui.text_edit_singleline(&mut self.bulk_form_owner);
+                    ui.end_row();
+
+                    ui.label("Add tags:");
+                    ui.text_edit_singleline(&mut self.bulk_form_add_tags)
+                        .on_hover_text("Comma-separated");
+                    ui.end_row();
+
+                    ui.label("Remove tags:");
+                    ui.text_edit_singleline(&mut self.bulk_form_remove_tags)
+                        .on_hover_text("Comma-separated");
+                    ui.end_row();
+                });
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Clear Selection").clicked() {
+                        clear = true;
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("✔ Apply").clicked() {
+                            apply = true;
+                        }
+                    });
+                });
+            });
+
+        if apply {
+            let split_tags = |s: &str| -> Vec<String> {
+                s.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect()
+            };
+            let non_empty = |s: &str| -> Option<String> {
+                let s = s.trim();
+                (!s.is_empty()).then(|| s.to_string())
+            };
+            let patch = RequirementPatch {
+                status: non_empty(&self.bulk_form_status),
+                priority: non_empty(&self.bulk_form_priority),
+                owner: non_empty(&self.bulk_form_owner),
+                feature: non_empty(&self.bulk_form_feature),
+                add_tags: split_tags(&self.bulk_form_add_tags),
+                remove_tags: split_tags(&self.bulk_form_remove_tags),
+                custom_fields: HashMap::new(),
+                ..Default::default()
+            };
+            let ids: Vec<Uuid> = self
+                .store
+                .requirements
+                .iter()
+                .filter(|r| self.multi_selected.contains(&r.id))
+                .map(|r| r.id)
+                .collect();
+            let author = self.user_settings.display_name();
+
+            match self.store.bulk_update(&ids, &patch, &author) {
+                Ok(result) => {
+                    for id in &result.updated {
+                        self.mark_requirement_modified(*id);
+                    }
+                    self.save();
+                    self.message = Some((
+                        format!(
+                            "Bulk update: {} updated, {} unchanged",
+                            result.updated.len(),
+                            result.unchanged.len()
+                        ),
+                        false,
+                    ));
+                    self.bulk_form_status.clear();
+                    self.bulk_form_priority.clear();
+                    self.bulk_form_owner.clear();
+                    self.bulk_form_feature.clear();
+                    self.bulk_form_add_tags.clear();
+                    self.bulk_form_remove_tags.clear();
+                }
+                Err(e) => {
+                    self.message = Some((e.to_string(), true));
+                }
+            }
+        }
+
+        if clear {
+            self.multi_selected.clear();
+            self.multi_select_anchor = None;
+        }
+    }
+
     fn show_tree_node_bottom_up(
         &mut self,
         ui: &mut egui::Ui,
@@ -13949,6 +14292,24 @@ impl RequirementsApp {
         self.show_detail_view_internal(ui, false, false);
     }
 
+    /// Values of the project's column scripts for a requirement, recomputed when it changes
+    fn computed_columns(&mut self, req: &Requirement) -> Vec<(String, String)> {
+        if !self.store.scripts.iter().any(|s| s.kind == ScriptKind::Column) {
+            return Vec::new();
+        }
+        if let Some((id, modified_at, columns)) = &self.computed_columns_cache {
+            if *id == req.id && *modified_at == req.modified_at {
+                return columns.clone();
+            }
+        }
+        let columns = match ScriptEngine::new(&self.store, self.storage.project_dir()) {
+            Ok(engine) => engine.computed_columns(req),
+            Err(e) => vec![("Scripts".to_string(), format!("error: {}", e))],
+        };
+        self.computed_columns_cache = Some((req.id, req.modified_at, columns.clone()));
+        columns
+    }
+
     fn show_detail_view_internal(&mut self, ui: &mut egui::Ui, show_close: bool, stacked: bool) {
         // Track if close was clicked
         let mut close_details = false;
@@ -13960,6 +14321,7 @@ impl RequirementsApp {
                 let mut delete_req = false;
                 let mut toggle_archive = false;
                 let is_archived = req.archived;
+                let computed_columns = self.computed_columns(&req);
 
                 // Track actions from Quick Actions menu
                 let mut new_priority: Option<RequirementPriority> = None;
@@ -14510,6 +14872,12 @@ impl RequirementsApp {
                                             ui.label("Modified:");
                                             ui.label(req.modified_at.format("%Y-%m-%d %H:%M").to_string());
                                             ui.end_row();
+
+                                            for (name, value) in &computed_columns {
+                                                ui.label(format!("{}:", name));
+                                                ui.label(value);
+                                                ui.end_row();
+                                            }
                                         });
                                 });
                         });
@@ -14545,6 +14913,24 @@ impl RequirementsApp {
                                     DetailTab::Comments,
                                     format!("💬 Comments ({})", req.comments.len()),
                                 );
+                                ui.selectable_value(
+                                    &mut self.active_tab,
+                                    DetailTab::Acceptance,
+                                    acceptance_tab_label(&req),
+                                );
+                                ui.selectable_value(
+                                    &mut self.active_tab,
+                                    DetailTab::Code,
+                                    code_tab_label(self.trace_index.as_ref(), &req),
+                                );
+                                ui.selectable_value(
+                                    &mut self.active_tab,
+                                    DetailTab::Tests,
+                                    format!(
+                                        "🧪 Tests ({})",
+                                        self.store.test_cases_for_requirement(&req.id).len()
+                                    ),
+                                );
                                 ui.selectable_value(
                                     &mut self.active_tab,
                                     DetailTab::Links,
@@ -14573,6 +14959,15 @@ impl RequirementsApp {
                                     DetailTab::Comments => {
                                         self.show_comments_tab(ui, &req, idx);
                                     }
+                                    DetailTab::Acceptance => {
+                                        self.show_acceptance_tab(ui, &req, idx);
+                                    }
+                                    DetailTab::Tests => {
+                                        self.show_tests_tab(ui, &req);
+                                    }
+                                    DetailTab::Code => {
+                                        self.show_code_tab(ui, &req);
+                                    }
                                     DetailTab::Links => {
                                         self.show_links_tab(ui, &req, req_id);
                                     }
@@ -14637,6 +15032,12 @@ impl RequirementsApp {
                             ui.label("Modified:");
                             ui.label(req.modified_at.format("%Y-%m-%d %H:%M").to_string());
                             ui.end_row();
+
+                            for (name, value) in &computed_columns {
+                                ui.label(format!("{}:", name));
+                                ui.label(value);
+                                ui.end_row();
+                            }
                         });
 
                     ui.separator();
@@ -14664,6 +15065,24 @@ impl RequirementsApp {
                             DetailTab::Comments,
                             format!("💬 Comments ({})", req.comments.len()),
                         );
+                        ui.selectable_value(
+                            &mut self.active_tab,
+                            DetailTab::Acceptance,
+                            acceptance_tab_label(&req),
+                        );
+                        ui.selectable_value(
+                            &mut self.active_tab,
+                            DetailTab::Code,
+                            code_tab_label(self.trace_index.as_ref(), &req),
+                        );
+                        ui.selectable_value(
+                            &mut self.active_tab,
+                            DetailTab::Tests,
+                            format!(
+                                "🧪 Tests ({})",
+                                self.store.test_cases_for_requirement(&req.id).len()
+                            ),
+                        );
                         ui.selectable_value(
                             &mut self.active_tab,
                             DetailTab::Links,
@@ -14690,6 +15109,15 @@ impl RequirementsApp {
                         DetailTab::Comments => {
                             self.show_comments_tab(ui, &req, idx);
                         }
+                        DetailTab::Acceptance => {
+                            self.show_acceptance_tab(ui, &req, idx);
+                        }
+                        DetailTab::Tests => {
+                            self.show_tests_tab(ui, &req);
+                        }
+                        DetailTab::Code => {
+                            self.show_code_tab(ui, &req);
+                        }
                         DetailTab::Links => {
                             self.show_links_tab(ui, &req, req_id);
                         }
@@ -14952,6 +15380,390 @@ impl RequirementsApp {
         }
     }
 
+    fn show_acceptance_tab(&mut self, ui: &mut egui::Ui, req: &Requirement, idx: usize) {
+        let (satisfied, total) = req.acceptance_progress();
+        ui.horizontal(|ui| {
+            ui.heading("Acceptance Criteria");
+            if total > 0 {
+                ui.label(format!("{}/{} satisfied", satisfied, total));
+            }
+        });
+        if total > 0 {
+            ui.add(
+                egui::ProgressBar::new(satisfied as f32 / total as f32)
+                    .desired_width(200.0),
+            );
+        }
+        ui.add_space(10.0);
+
+        let mut status_change: Option<(String, CriterionStatus)> = None;
+        let mut to_remove: Option<String> = None;
+
+        if req.acceptance_criteria.is_empty() {
+            ui.label("No acceptance criteria yet");
+        }
+        for ac in &req.acceptance_criteria {
+            ui.horizontal(|ui| {
+                let mut checked = ac.status.is_satisfied();
+                if ui
+                    .checkbox(&mut checked, "")
+                    .on_hover_text("Mark as passed / pending")
+                    .changed()
+                {
+                    let status = if checked {
+                        CriterionStatus::Passed
+                    } else {
+                        CriterionStatus::Pending
+                    };
+                    status_change = Some((ac.id.clone(), status));
+                }
+
+                let mut status = ac.status;
+                egui::ComboBox::from_id_salt(format!("ac_status_{}", ac.id))
+                    .selected_text(status.to_string())
+                    .width(80.0)
+                    .show_ui(ui, |ui| {
+                        for s in [
+                            CriterionStatus::Pending,
+                            CriterionStatus::Passed,
+                            CriterionStatus::Failed,
+                            CriterionStatus::Waived,
+                        ] {
+                            ui.selectable_value(&mut status, s, s.to_string());
+                        }
+                    });
+                if status != ac.status {
+                    status_change = Some((ac.id.clone(), status));
+                }
+
+                let text = egui::RichText::new(format!("{}: {}", ac.id, ac.text));
+                let text = match ac.status {
+                    CriterionStatus::Failed => text.color(egui::Color32::from_rgb(220, 60, 60)),
+                    CriterionStatus::Waived => text.weak(),
+                    _ => text,
+                };
+                let response = ui.label(text);
+                if let (Some(by), Some(at)) = (&ac.verified_by, ac.verified_at) {
+                    response.on_hover_text(format!(
+                        "{} by {} at {}",
+                        ac.status,
+                        by,
+                        at.format("%Y-%m-%d %H:%M")
+                    ));
+                }
+
+                if ui.small_button("x").on_hover_text("Remove criterion").clicked() {
+                    to_remove = Some(ac.id.clone());
+                }
+            });
+            ui.indent(format!("ac_details_{}", ac.id), |ui| {
+                if let Some(scenario) = &ac.scenario {
+                    ui.label(format!("Given {}", scenario.given));
+                    ui.label(format!("When {}", scenario.when));
+                    ui.label(format!("Then {}", scenario.then));
+                }
+                if let Some(test) = &ac.linked_test {
+                    ui.label(egui::RichText::new(format!("Test: {}", test)).weak());
+                }
+            });
+        }
+
+        ui.add_space(10.0);
+        ui.separator();
+        ui.label("Add criterion:");
+        egui::Grid::new("ac_add_form")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Text:");
+                ui.text_edit_singleline(&mut self.ac_form_text);
+                ui.end_row();
+                ui.label("Given:");
+                ui.text_edit_singleline(&mut self.ac_form_given);
+                ui.end_row();
+                ui.label("When:");
+                ui.text_edit_singleline(&mut self.ac_form_when);
+                ui.end_row();
+                ui.label("Then:");
+                ui.text_edit_singleline(&mut self.ac_form_then);
+                ui.end_row();
+                ui.label("Test:");
+                ui.text_edit_singleline(&mut self.ac_form_test);
+                ui.end_row();
+            });
+        let add_clicked = ui
+            .add_enabled(
+                !self.ac_form_text.trim().is_empty(),
+                egui::Button::new("➕ Add Criterion"),
+            )
+            .clicked();
+
+        let author = self.user_settings.display_name();
+        let req_id = req.id;
+        let mut modified = false;
+
+        if add_clicked {
+            let scenario = [&self.ac_form_given, &self.ac_form_when, &self.ac_form_then]
+                .map(|s| s.trim().to_string());
+            let filled = scenario.iter().filter(|s| !s.is_empty()).count();
+            if filled != 0 && filled != 3 {
+                self.message = Some((
+                    "Given, When and Then must all be filled in (or all left empty)".to_string(),
+                    true,
+                ));
+            } else if let Some(req) = self.store.requirements.get_mut(idx) {
+                let mut criterion = AcceptanceCriterion::new("", self.ac_form_text.trim());
+                if filled == 3 {
+                    let [given, when, then] = scenario;
+                    criterion = criterion.with_scenario(GivenWhenThen { given, when, then });
+                }
+                if !self.ac_form_test.trim().is_empty() {
+                    criterion = criterion.with_linked_test(self.ac_form_test.trim());
+                }
+                let ac_id = req.add_acceptance_criterion(criterion, &author);
+                self.ac_form_text.clear();
+                self.ac_form_given.clear();
+                self.ac_form_when.clear();
+                self.ac_form_then.clear();
+                self.ac_form_test.clear();
+                self.message = Some((format!("Added {}", ac_id), false));
+                modified = true;
+            }
+        }
+
+        if let Some((ac_id, status)) = status_change {
+            if let Some(req) = self.store.requirements.get_mut(idx) {
+                match req.set_acceptance_status(&ac_id, status, &author) {
+                    Ok(()) => modified = true,
+                    Err(e) => self.message = Some((e.to_string(), true)),
+                }
+            }
+        }
+
+        if let Some(ac_id) = to_remove {
+            if let Some(req) = self.store.requirements.get_mut(idx) {
+                match req.remove_acceptance_criterion(&ac_id, &author) {
+                    Ok(_) => {
+                        self.message = Some((format!("Removed {}", ac_id), false));
+                        modified = true;
+                    }
+                    Err(e) => self.message = Some((e.to_string(), true)),
+                }
+            }
+        }
+
+        if modified {
+            self.mark_requirement_modified(req_id);
+            self.save();
+        }
+    }
+
+    /// Scans the project sources for trace markers
+    fn scan_trace_markers(&mut self) {
+        match aida_core::TraceIndex::scan(self.storage.project_dir()) {
+            Ok(index) => {
+                self.message = Some((
+                    format!(
+                        "Scanned {} files: {} trace markers{}",
+                        index.files_scanned,
+                        index.markers.len(),
+                        if index.warnings.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" ({} unreadable entries skipped)", index.warnings.len())
+                        }
+                    ),
+                    false,
+                ));
+                self.trace_index = Some(index);
+            }
+            Err(e) => {
+                self.message = Some((format!("Trace scan failed: {}", e), true));
+            }
+        }
+    }
+
+    fn sync_git_commits(&mut self) {
+        match self.store.sync_git_commits(self.storage.project_dir(), false) {
+            Ok(result) => {
+                self.save();
+                self.message = Some((
+                    format!(
+                        "Scanned {} commits: {} new links",
+                        result.commits_scanned,
+                        result.linked.len()
+                    ),
+                    false,
+                ));
+            }
+            Err(e) => {
+                self.message = Some((format!("Git sync failed: {}", e), true));
+            }
+        }
+    }
+
+    fn show_code_tab(&mut self, ui: &mut egui::Ui, req: &Requirement) {
+        ui.horizontal(|ui| {
+            ui.heading("Code");
+            let label = if self.trace_index.is_some() { "🔄 Rescan" } else { "🔍 Scan Sources" };
+            if ui
+                .button(label)
+                .on_hover_text("Scan the project for // trace:<SPEC-ID> markers")
+                .clicked()
+            {
+                self.scan_trace_markers();
+            }
+        });
+        ui.add_space(10.0);
+
+        let Some(index) = &self.trace_index else {
+            ui.label("Scan the project sources to find code implementing this requirement.");
+            return;
+        };
+        let Some(spec_id) = req.spec_id.as_deref() else {
+            ui.label("This requirement has no SPEC-ID to trace");
+            return;
+        };
+
+        let markers = index.markers_for(spec_id);
+        if markers.is_empty() {
+            ui.label(format!("No trace markers for {} in {}", spec_id, index.root.display()));
+            return;
+        }
+
+        let ai_count = markers.iter().filter(|m| m.is_ai()).count();
+        ui.label(format!(
+            "{} locations ({} AI-assisted, {} human)",
+            markers.len(),
+            ai_count,
+            markers.len() - ai_count
+        ));
+        ui.add_space(5.0);
+
+        let root = index.root.clone();
+        let mut to_open: Option<std::path::PathBuf> = None;
+        egui::Grid::new("code_trace_grid")
+            .num_columns(2)
+            .spacing([20.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for marker in markers {
+                    if ui
+                        .link(marker.location())
+                        .on_hover_text("Open file")
+                        .clicked()
+                    {
+                        to_open = Some(root.join(&marker.file));
+                    }
+                    let origin = match (&marker.ai_tool, marker.confidence) {
+                        (Some(tool), Some(confidence)) => format!("🤖 {} ({})", tool, confidence),
+                        (Some(tool), None) => format!("🤖 {}", tool),
+                        (None, _) => "👤 human".to_string(),
+                    };
+                    ui.label(origin);
+                    ui.end_row();
+                }
+            });
+
+        if let Some(path) = to_open {
+            if let Err(e) = open::that(&path) {
+                self.message = Some((format!("Failed to open {}: {}", path.display(), e), true));
+            }
+        }
+    }
+
+    fn show_tests_tab(&self, ui: &mut egui::Ui, req: &Requirement) {
+        let cases = self.store.test_cases_for_requirement(&req.id);
+        let summary = self.store.verification_summary(&req.id);
+
+        ui.horizontal(|ui| {
+            ui.heading("Verification");
+            let color = match summary.status() {
+                VerificationStatus::Passing => egui::Color32::from_rgb(60, 170, 80),
+                VerificationStatus::Failing => egui::Color32::from_rgb(220, 60, 60),
+                VerificationStatus::Blocked | VerificationStatus::Partial => {
+                    egui::Color32::from_rgb(220, 160, 40)
+                }
+                VerificationStatus::NotRun | VerificationStatus::Unverified => {
+                    ui.visuals().weak_text_color()
+                }
+            };
+            ui.label(egui::RichText::new(summary.status().to_string()).color(color).strong());
+            if summary.total > 0 {
+                ui.label(format!("{}/{} passed", summary.passed, summary.total));
+            }
+        });
+        ui.add_space(10.0);
+
+        if cases.is_empty() {
+            ui.label("No test cases verify this requirement");
+            ui.label(
+                egui::RichText::new("Add one with: aida test add <TITLE> --verifies <SPEC-ID>")
+                    .weak(),
+            );
+            return;
+        }
+
+        for case in cases {
+            let latest = self.store.latest_test_result(&case.id);
+            let outcome = latest.map(|(_, result)| result.outcome).unwrap_or_default();
+            let outcome_color = match outcome {
+                TestOutcome::Passed => egui::Color32::from_rgb(60, 170, 80),
+                TestOutcome::Failed => egui::Color32::from_rgb(220, 60, 60),
+                TestOutcome::Blocked => egui::Color32::from_rgb(220, 160, 40),
+                TestOutcome::Skipped | TestOutcome::NotRun => ui.visuals().weak_text_color(),
+            };
+
+            egui::CollapsingHeader::new(format!("{} - {}", case.spec_id, case.title))
+                .id_salt(("test_case", case.id))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!("{} {}", outcome.symbol(), outcome))
+                                .color(outcome_color),
+                        );
+                        if let Some((run, result)) = latest {
+                            let mut context = format!(
+                                "{} ({})",
+                                run.spec_id,
+                                run.executed_at.format("%Y-%m-%d %H:%M")
+                            );
+                            if let Some(build) = &run.build {
+                                context.push_str(&format!(", build {}", build));
+                            }
+                            if let Some(env) = &run.environment {
+                                context.push_str(&format!(", {}", env));
+                            }
+                            ui.label(egui::RichText::new(context).weak());
+                            if let Some(notes) = &result.notes {
+                                ui.label(notes);
+                            }
+                        }
+                    });
+
+                    if !case.description.is_empty() {
+                        ui.label(&case.description);
+                    }
+                    if !case.preconditions.is_empty() {
+                        ui.label(format!("Preconditions: {}", case.preconditions));
+                    }
+                    for (i, step) in case.steps.iter().enumerate() {
+                        if step.expected.is_empty() {
+                            ui.label(format!("{}. {}", i + 1, step.action));
+                        } else {
+                            ui.label(format!("{}. {} → {}", i + 1, step.action, step.expected));
+                        }
+                    }
+                    if !case.expected_result.is_empty() {
+                        ui.label(format!("Expected: {}", case.expected_result));
+                    }
+                    if let Some(automated) = &case.automated_test {
+                        ui.label(egui::RichText::new(format!("Automated: {}", automated)).weak());
+                    }
+                });
+        }
+    }
+
     fn show_links_tab(&mut self, ui: &mut egui::Ui, req: &Requirement, req_id: Uuid) {
         // Show URL form modal if active
         if self.show_url_form {
@@ -15063,6 +15875,46 @@ impl RequirementsApp {
         ui.separator();
         ui.add_space(10.0);
 
+        // Commits section
+        ui.horizontal(|ui| {
+            ui.heading("Commits");
+            if ui
+                .button("🔄 Sync")
+                .on_hover_text("Link new git commits that mention this project's spec IDs")
+                .clicked()
+            {
+                self.sync_git_commits();
+            }
+        });
+        ui.add_space(5.0);
+
+        if req.commits.is_empty() {
+            if req.status == RequirementStatus::Completed {
+                ui.colored_label(egui::Color32::from_rgb(255, 180, 100), "⚠ Completed, but no linked commits");
+            } else {
+                ui.label("No linked commits");
+            }
+        } else {
+            for commit in &req.commits {
+                ui.horizontal(|ui| {
+                    ui.monospace(commit.short_sha()).on_hover_text(&commit.sha);
+                    ui.label(&commit.subject);
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{}, {}",
+                            commit.author,
+                            commit.date.format("%Y-%m-%d")
+                        ))
+                        .weak(),
+                    );
+                });
+            }
+        }
+
+        ui.add_space(15.0);
+        ui.separator();
+        ui.add_space(10.0);
+
         // Relationships section
         ui.horizontal(|ui| {
             ui.heading("Relationships");
@@ -16267,6 +17119,9 @@ fn main() {
             }
             // Clear custom fields when type changes (they may not be relevant)
             self.form_custom_fields.clear();
+            if !is_edit {
+                self.apply_type_template();
+            }
         }
         ui.add_space(4.0);
 
@@ -17045,6 +17900,9 @@ fn main() {
                 self.form_status_string = statuses.first().cloned().unwrap_or_else(|| "Draft".to_string());
             }
             self.form_custom_fields.clear();
+            if !is_edit {
+                self.apply_type_template();
+            }
         }
 
         // Show cancel confirmation dialog if there are unsaved changes
@@ -17324,6 +18182,9 @@ fn main() {
                 self.form_status_string = statuses.first().cloned().unwrap_or_else(|| "Draft".to_string());
             }
             self.form_custom_fields.clear();
+            if !is_edit {
+                self.apply_type_template();
+            }
         }
 
         // === DESCRIPTION EDITOR ===
@@ -18799,6 +19660,7 @@ impl eframe::App for RequirementsApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Reset per-frame flags at the start of each frame
         self.quick_change_consumed_action = false;
+        self.list_render_order_prev = std::mem::take(&mut self.list_render_order);
 
         // Periodically check for other concurrent users (every ~60 frames = ~1 second)
         static CONCURRENT_CHECK_COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
@@ -19899,6 +20761,9 @@ impl eframe::App for RequirementsApp {
         // Show clone requirement dialog
         self.show_clone_requirement_dialog(ctx);
 
+        // Show bulk edit window when several requirements are multi-selected
+        self.show_bulk_edit_window(ctx);
+
         // Show filter dialogs
         self.show_filter_dialog_list1(ctx);
         self.show_filter_dialog_list2(ctx);
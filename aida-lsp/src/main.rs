@@ -0,0 +1,83 @@
+//! `aida-lsp`: language server for requirement SPEC-IDs
+//!
+//! Shows a requirement's title and status when hovering a SPEC-ID such as
+//! `FR-0042` in source files or docs, completes SPEC-IDs inside `trace:`
+//! markers, jumps to the requirement in the requirements file, and flags
+//! unknown or Rejected IDs. Speaks LSP over stdio.
+
+mod server;
+
+use anyhow::Result;
+use clap::Parser;
+use lsp_server::Connection;
+use lsp_types::{
+    CompletionOptions, HoverProviderCapability, InitializeParams, OneOf, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind,
+};
+use std::path::PathBuf;
+
+use aida_core::determine_requirements_path;
+
+use crate::server::Server;
+
+#[derive(Parser, Debug)]
+#[clap(
+    author,
+    version,
+    about = "Language server for AIDA requirement SPEC-IDs"
+)]
+struct Args {
+    /// Path to the requirements file (defaults to requirements.yaml in the workspace root)
+    #[clap(long)]
+    file: Option<PathBuf>,
+
+    /// Project name to use from central registry
+    #[clap(long, short = 'p')]
+    project: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string(), ",".to_string(), "-".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let params: InitializeParams =
+        serde_json::from_value(connection.initialize(serde_json::to_value(capabilities)?)?)?;
+
+    let path = requirements_path(&args, &params)?;
+    eprintln!("aida-lsp: using {}", path.display());
+    Server::new(connection, path).run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Picks the requirements file: `--file`, `--project`, the workspace root,
+/// then the usual CLI lookup
+fn requirements_path(args: &Args, params: &InitializeParams) -> Result<PathBuf> {
+    if let Some(file) = &args.file {
+        return Ok(file.clone());
+    }
+    if args.project.is_none() {
+        let workspace_file = params
+            .workspace_folders
+            .iter()
+            .flatten()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .map(|root| root.join("requirements.yaml"))
+            .find(|path| path.exists());
+        if let Some(path) = workspace_file {
+            return Ok(path);
+        }
+    }
+    determine_requirements_path(args.project.as_deref())
+}
@@ -0,0 +1,486 @@
+//! Request handling and requirements file watching
+
+use anyhow::Result;
+use crossbeam_channel::{select, tick};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse, CompletionTextEdit,
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, Documentation, GotoDefinitionParams, GotoDefinitionResponse, Hover,
+    HoverContents, HoverParams, Location, MarkupContent, MarkupKind, Position,
+    PublishDiagnosticsParams, Range, TextEdit, Url,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use aida_core::db::{backend_type_for_path, create_backend, BackendType};
+use aida_core::trace::{find_spec_ids, marker_completion_prefix};
+use aida_core::{Requirement, RequirementsStore, SpecIdMention};
+
+/// How often the requirements file is checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Language server state
+pub struct Server {
+    connection: Connection,
+    requirements_path: PathBuf,
+    store: RequirementsStore,
+    /// Modification time of the requirements file when it was last loaded
+    loaded_mtime: Option<SystemTime>,
+    /// Open documents by URI
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    pub fn new(connection: Connection, requirements_path: PathBuf) -> Self {
+        let mut server = Self {
+            connection,
+            requirements_path,
+            store: RequirementsStore::new(),
+            loaded_mtime: None,
+            documents: HashMap::new(),
+        };
+        server.reload_if_changed();
+        server
+    }
+
+    /// Handles messages until the client shuts down, reloading the
+    /// requirements file whenever it changes on disk
+    pub fn run(mut self) -> Result<()> {
+        let ticker = tick(WATCH_INTERVAL);
+        loop {
+            select! {
+                recv(self.connection.receiver) -> message => {
+                    let Ok(message) = message else {
+                        return Ok(());
+                    };
+                    match message {
+                        Message::Request(request) => {
+                            if self.connection.handle_shutdown(&request)? {
+                                return Ok(());
+                            }
+                            self.handle_request(request)?;
+                        }
+                        Message::Notification(notification) => self.handle_notification(notification)?,
+                        Message::Response(_) => {}
+                    }
+                }
+                recv(ticker) -> _ => {
+                    if self.reload_if_changed() {
+                        let uris: Vec<Url> = self.documents.keys().cloned().collect();
+                        for uri in uris {
+                            self.publish_diagnostics(uri)?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reloads the store if the file's modification time changed; returns true if reloaded
+    fn reload_if_changed(&mut self) -> bool {
+        let mtime = std::fs::metadata(&self.requirements_path)
+            .and_then(|m| m.modified())
+            .ok();
+        if mtime.is_some() && mtime == self.loaded_mtime {
+            return false;
+        }
+
+        match create_backend(&self.requirements_path, None).and_then(|backend| backend.load()) {
+            Ok(store) => {
+                self.store = store;
+                self.loaded_mtime = mtime;
+                true
+            }
+            Err(e) => {
+                eprintln!(
+                    "aida-lsp: failed to load {}: {}",
+                    self.requirements_path.display(),
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let Request { id, method, params } = request;
+        let response = match method.as_str() {
+            HoverRequest::METHOD => respond(id, params, |p| self.hover(p)),
+            GotoDefinition::METHOD => respond(id, params, |p| self.definition(p)),
+            Completion::METHOD => respond(id, params, |p| self.completion(p)),
+            other => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request: {}", other),
+            ),
+        };
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    /// Applies a document notification; malformed ones are logged and skipped
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        let Notification { method, params } = notification;
+        match method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Some(params) =
+                    notification_params::<DidOpenTextDocumentParams>(&method, params)
+                else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                self.documents
+                    .insert(uri.clone(), params.text_document.text);
+                self.publish_diagnostics(uri)?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let Some(params) =
+                    notification_params::<DidChangeTextDocumentParams>(&method, params)
+                else {
+                    return Ok(());
+                };
+                // Full sync: the last change holds the whole document
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    let uri = params.text_document.uri;
+                    self.documents.insert(uri.clone(), change.text);
+                    self.publish_diagnostics(uri)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let Some(params) =
+                    notification_params::<DidCloseTextDocumentParams>(&method, params)
+                else {
+                    return Ok(());
+                };
+                self.documents.remove(&params.text_document.uri);
+                self.send_diagnostics(params.text_document.uri, Vec::new())?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn find(&self, spec_id: &str) -> Option<&Requirement> {
+        self.store
+            .resolve_requirement_id(spec_id)
+            .and_then(|id| self.store.get_requirement_by_id(&id))
+    }
+
+    /// Line text and SPEC-ID mention under the cursor
+    fn mention_at(&self, uri: &Url, position: Position) -> Option<(u32, &str, SpecIdMention)> {
+        let line = self
+            .documents
+            .get(uri)?
+            .lines()
+            .nth(position.line as usize)?;
+        let col = byte_offset(line, position.character);
+        let mention = find_spec_ids(line)
+            .into_iter()
+            .find(|m| m.range.start <= col && col <= m.range.end)?;
+        Some((position.line, line, mention))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let (line_no, line, mention) =
+            self.mention_at(&position.text_document.uri, position.position)?;
+        let req = self.find(&mention.spec_id)?;
+
+        let mut value = format!(
+            "**{}** {}\n\nStatus: {} · Priority: {} · Type: {}",
+            req.spec_id.as_deref().unwrap_or(&mention.spec_id),
+            req.title,
+            req.effective_status(),
+            req.effective_priority(),
+            req.req_type,
+        );
+        if !req.feature.is_empty() {
+            value.push_str(&format!(" · Feature: {}", req.feature));
+        }
+        if req.archived {
+            value.push_str(" · archived");
+        }
+        if !req.description.trim().is_empty() {
+            value.push_str(&format!("\n\n{}", req.description.trim()));
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(mention_range(line_no, line, &mention)),
+        })
+    }
+
+    /// Locates the requirement's `spec_id:` line in a YAML requirements file
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let (_, _, mention) = self.mention_at(&position.text_document.uri, position.position)?;
+        let spec_id = self.find(&mention.spec_id)?.spec_id.clone()?;
+        if backend_type_for_path(&self.requirements_path) != BackendType::Yaml {
+            return None;
+        }
+
+        let path = std::fs::canonicalize(&self.requirements_path).ok()?;
+        let content = std::fs::read_to_string(&path).ok()?;
+        let line = content.lines().position(|line| {
+            line.trim_start()
+                .trim_start_matches("- ")
+                .strip_prefix("spec_id:")
+                .is_some_and(|value| {
+                    value.trim().trim_matches(|c| c == '"' || c == '\'') == spec_id
+                })
+        })?;
+
+        let position = Position::new(line as u32, 0);
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            Url::from_file_path(path).ok()?,
+            Range::new(position, position),
+        )))
+    }
+
+    /// Completes SPEC-IDs inside the ID list of a `trace:` marker
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position.position;
+        let text = self
+            .documents
+            .get(&params.text_document_position.text_document.uri)?;
+        let line = text.lines().nth(position.line as usize).unwrap_or_default();
+        let col = byte_offset(line, position.character);
+        let typed = marker_completion_prefix(line, col)?;
+
+        let start = Position::new(position.line, utf16_len(&line[..col - typed.len()]));
+        let typed = typed.to_uppercase();
+        let mut items: Vec<CompletionItem> = self
+            .store
+            .requirements
+            .iter()
+            .filter(|r| !r.archived)
+            .filter_map(|r| Some((r.spec_id.as_deref()?, r)))
+            .filter(|(spec_id, _)| spec_id.to_uppercase().starts_with(&typed))
+            .map(|(spec_id, r)| CompletionItem {
+                label: spec_id.to_string(),
+                kind: Some(CompletionItemKind::REFERENCE),
+                detail: Some(r.title.clone()),
+                documentation: Some(Documentation::String(format!(
+                    "{} · {}",
+                    r.effective_status(),
+                    r.effective_priority()
+                ))),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+                    Range::new(start, position),
+                    spec_id.to_string(),
+                ))),
+                ..Default::default()
+            })
+            .collect();
+        items.sort_by(|a, b| a.label.cmp(&b.label));
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn publish_diagnostics(&self, uri: Url) -> Result<()> {
+        let diagnostics = self
+            .documents
+            .get(&uri)
+            .map(|text| self.diagnostics(text))
+            .unwrap_or_default();
+        self.send_diagnostics(uri, diagnostics)
+    }
+
+    /// Flags Rejected requirements, and unknown IDs that are in a `trace:`
+    /// marker or use a prefix the store already has (so `UTF-8` is left alone)
+    fn diagnostics(&self, text: &str) -> Vec<Diagnostic> {
+        let known_prefixes: HashSet<String> = self
+            .store
+            .requirements
+            .iter()
+            .filter_map(|r| r.spec_id.as_deref())
+            .filter_map(|id| id.rsplit_once('-'))
+            .map(|(prefix, _)| prefix.to_uppercase())
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            for mention in find_spec_ids(line) {
+                let message = match self.find(&mention.spec_id) {
+                    Some(req) if req.effective_status().eq_ignore_ascii_case("Rejected") => {
+                        format!("{} is Rejected: {}", mention.spec_id, req.title)
+                    }
+                    Some(_) => continue,
+                    None => {
+                        let prefix = mention
+                            .spec_id
+                            .rsplit_once('-')
+                            .map(|(prefix, _)| prefix.to_uppercase())
+                            .unwrap_or_default();
+                        if !mention.in_marker && !known_prefixes.contains(&prefix) {
+                            continue;
+                        }
+                        format!("Unknown requirement {}", mention.spec_id)
+                    }
+                };
+                diagnostics.push(Diagnostic {
+                    range: mention_range(line_no as u32, line, &mention),
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    source: Some("aida".to_string()),
+                    message,
+                    ..Default::default()
+                });
+            }
+        }
+        diagnostics
+    }
+
+    fn send_diagnostics(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+}
+
+/// Answers a request with `handler`'s result, or `InvalidParams` if `params` don't parse
+fn respond<P: DeserializeOwned, R: Serialize>(
+    id: RequestId,
+    params: Value,
+    handler: impl FnOnce(P) -> R,
+) -> Response {
+    match serde_json::from_value(params) {
+        Ok(params) => Response::new_ok(id, handler(params)),
+        Err(e) => Response::new_err(
+            id,
+            ErrorCode::InvalidParams as i32,
+            format!("Invalid params: {}", e),
+        ),
+    }
+}
+
+/// Parses notification params, logging and returning `None` if they are malformed
+fn notification_params<P: DeserializeOwned>(method: &str, params: Value) -> Option<P> {
+    serde_json::from_value(params)
+        .map_err(|e| eprintln!("aida-lsp: ignoring malformed {}: {}", method, e))
+        .ok()
+}
+
+/// Length of `s` in UTF-16 code units (LSP's default position encoding)
+fn utf16_len(s: &str) -> u32 {
+    s.encode_utf16().count() as u32
+}
+
+/// Byte offset in `line` of a UTF-16 column, clamped to the line
+fn byte_offset(line: &str, character: u32) -> usize {
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= character {
+            return i;
+        }
+        units += c.len_utf16() as u32;
+    }
+    line.len()
+}
+
+fn mention_range(line_no: u32, line: &str, mention: &SpecIdMention) -> Range {
+    Range::new(
+        Position::new(line_no, utf16_len(&line[..mention.range.start])),
+        Position::new(line_no, utf16_len(&line[..mention.range.end])),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aida_core::RequirementStatus;
+    use serde_json::json;
+
+    fn server() -> (Server, Connection) {
+        let mut store = RequirementsStore::new();
+        for (spec_id, status) in [
+            ("FR-001", RequirementStatus::Approved),
+            ("FR-002", RequirementStatus::Rejected),
+        ] {
+            let mut req = Requirement::new(format!("Title {}", spec_id), String::new());
+            req.spec_id = Some(spec_id.to_string());
+            req.status = status;
+            store.requirements.push(req);
+        }
+        let (connection, client) = Connection::memory();
+        let server = Server {
+            connection,
+            requirements_path: PathBuf::from("requirements.yaml"),
+            store,
+            loaded_mtime: None,
+            documents: HashMap::new(),
+        };
+        (server, client)
+    }
+
+    #[test]
+    fn test_utf16_positions() {
+        let line = "é😀 FR-001";
+        assert_eq!(utf16_len(line), 10);
+        assert_eq!(byte_offset(line, 3), 6);
+        assert_eq!(byte_offset(line, 99), line.len());
+
+        let mention = find_spec_ids(line).remove(0);
+        let range = mention_range(4, line, &mention);
+        assert_eq!(range.start, Position::new(4, 4));
+        assert_eq!(range.end, Position::new(4, 10));
+    }
+
+    #[test]
+    fn test_diagnostics_flag_rejected_and_unknown_ids() {
+        let (server, _client) = server();
+        let text = "// trace:FR-001, FR-002\nSee FR-404 and UTF-8\n// trace:NEW-9";
+        let messages: Vec<(u32, String)> = server
+            .diagnostics(text)
+            .into_iter()
+            .map(|d| (d.range.start.line, d.message))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (0, "FR-002 is Rejected: Title FR-002".to_string()),
+                (1, "Unknown requirement FR-404".to_string()),
+                (2, "Unknown requirement NEW-9".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_messages_do_not_stop_the_server() {
+        let (mut server, client) = server();
+        server
+            .handle_request(Request::new(
+                RequestId::from(1),
+                HoverRequest::METHOD.to_string(),
+                json!({ "textDocument": 42 }),
+            ))
+            .unwrap();
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(
+            response.error.unwrap().code,
+            ErrorCode::InvalidParams as i32
+        );
+
+        server
+            .handle_notification(Notification::new(
+                DidOpenTextDocument::METHOD.to_string(),
+                json!({ "textDocument": null }),
+            ))
+            .unwrap();
+        assert!(server.documents.is_empty());
+    }
+}